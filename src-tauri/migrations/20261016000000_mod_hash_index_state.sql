@@ -0,0 +1,20 @@
+-- `mod_hash_index` was created with the init schema but never written: the
+-- keyviewer harvester and the conflict scanner re-read every enabled mod's
+-- INIs on each pass. Disk reconcile now maintains it, re-harvesting a mod only
+-- when the fingerprint of its INI files (relative path, size, mtime) moves.
+CREATE TABLE IF NOT EXISTS mod_hash_index_state (
+    mod_id TEXT PRIMARY KEY REFERENCES mods(id) ON DELETE CASCADE,
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    ini_fingerprint TEXT NOT NULL,
+    indexed_at TEXT DEFAULT CURRENT_TIMESTAMP
+) STRICT;
+
+-- Conflict detection reads the index too, so rows carry the kind of section
+-- they came from: 8-digit `TextureOverride` hashes and shader hashes alike.
+ALTER TABLE mod_hash_index ADD COLUMN hash_kind TEXT NOT NULL DEFAULT 'resource_hash';
+
+-- Re-indexing replaces one mod's rows at a time.
+CREATE INDEX IF NOT EXISTS idx_mod_hash_index_mod
+    ON mod_hash_index(mod_id);
+CREATE INDEX IF NOT EXISTS idx_mod_hash_index_game_hash
+    ON mod_hash_index(game_id, hash);
@@ -0,0 +1,14 @@
+-- Winner decisions for hash conflicts. `ignored_object_conflicts` only hides a
+-- conflict; a resolution records which mod won and how the losers were
+-- handled. Mod ids carry no foreign key: the decision must survive a rescan
+-- that prunes and recreates the rows.
+CREATE TABLE IF NOT EXISTS conflict_resolutions (
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    hash TEXT NOT NULL COLLATE NOCASE,
+    kind TEXT NOT NULL CHECK(kind IN ('resource_hash', 'shader_hash', 'shader_replacement')),
+    strategy TEXT NOT NULL CHECK(strategy IN ('disable_losers', 'match_priority')),
+    winner_mod_id TEXT NOT NULL,
+    loser_mod_ids TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (game_id, hash, kind)
+) STRICT;
@@ -0,0 +1,16 @@
+-- Content-addressed index of the heavy mod payload files (`.dds`, `.buf`,
+-- `.ib`) across every configured game. A file is re-hashed only when its size
+-- or mtime moves, so every pass after the first is mostly `stat` calls.
+CREATE TABLE IF NOT EXISTS file_blobs (
+    file_path TEXT PRIMARY KEY,
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    size_bytes INTEGER NOT NULL,
+    mtime_ns INTEGER NOT NULL,
+    content_hash TEXT NOT NULL,
+    hashed_at TEXT DEFAULT CURRENT_TIMESTAMP
+) STRICT;
+
+CREATE INDEX IF NOT EXISTS idx_file_blobs_hash
+    ON file_blobs(content_hash);
+CREATE INDEX IF NOT EXISTS idx_file_blobs_game
+    ON file_blobs(game_id);
@@ -0,0 +1,17 @@
+-- Folder names the user reassigned to a different MasterDB entry, in the scan
+-- review or the object match dialog. The matcher folds them back into its
+-- indexes so the same folder-name pattern is not misclassified again. They are
+-- per game: a folder name re-pointed in one game must not steer a same-named
+-- folder in another.
+CREATE TABLE IF NOT EXISTS matcher_corrections (
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    -- `normalize_for_matching_default` of the folder name.
+    folder_pattern TEXT NOT NULL,
+    entry_key TEXT NOT NULL,
+    -- The folder name as the user saw it, for review and export.
+    folder_name TEXT NOT NULL,
+    hits INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (game_id, folder_pattern)
+) STRICT;
@@ -0,0 +1,17 @@
+-- Matcher signals (`FolderSignals`) persisted across scans. A rescan reuses a
+-- row while the folder fingerprint (names found by the walk, size and mtime of
+-- each INI, token filters) is unchanged, so unchanged folders skip reading and
+-- tokenizing their INIs. Disk reconcile drops the rows of folders it touched.
+CREATE TABLE IF NOT EXISTS folder_signal_cache (
+    folder_path TEXT NOT NULL,
+    -- `MatchMode as u8`: Quick and Full read different amounts of a folder.
+    mode INTEGER NOT NULL,
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    fingerprint TEXT NOT NULL,
+    signals_json TEXT NOT NULL,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (folder_path, mode)
+) STRICT;
+
+CREATE INDEX IF NOT EXISTS idx_folder_signal_cache_game
+    ON folder_signal_cache(game_id);
@@ -0,0 +1,14 @@
+-- The user's own MasterDB additions, per game type: entries the bundled
+-- database lacks, and extra tags, skins, aliases, and hashes for entries it
+-- has. Merged over the bundled database whenever it is loaded.
+CREATE TABLE IF NOT EXISTS master_db_overlay (
+    -- `normalize_game_type`, as the bundled database file is named.
+    game_type TEXT NOT NULL,
+    -- `canonical_entry_key` of the entry name.
+    entry_key TEXT NOT NULL,
+    -- The overlay record: a `DbEntry` as JSON.
+    entry_json TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (game_type, entry_key)
+) STRICT;
@@ -0,0 +1,18 @@
+-- Resource hashes learned from the user's own library: for an object whose
+-- MasterDB match is confident, the hashes most of its mods override and no
+-- other object's mods do. Written only by the opt-in learning pass and kept
+-- apart from the bundled `objects.hash_db`, so a reset drops exactly these.
+CREATE TABLE IF NOT EXISTS learned_object_hashes (
+    object_id TEXT NOT NULL REFERENCES objects(id) ON DELETE CASCADE,
+    game_id TEXT NOT NULL REFERENCES games(id) ON DELETE CASCADE,
+    hash TEXT NOT NULL,
+    -- Mods of the object whose INIs carry the hash.
+    mod_count INTEGER NOT NULL,
+    -- Mods of the object with any indexed hash, when the pass ran.
+    object_mod_count INTEGER NOT NULL,
+    learned_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (object_id, hash)
+) STRICT;
+
+CREATE INDEX IF NOT EXISTS idx_learned_object_hashes_game
+    ON learned_object_hashes(game_id);
@@ -0,0 +1,19 @@
+-- Layered collections: a collection may build on a parent and record only
+-- what it changes. Its member tables still hold the resolved set, so apply,
+-- previews and signature matching read it like any other collection.
+ALTER TABLE collections ADD COLUMN parent_collection_id TEXT
+    REFERENCES collections(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_collections_parent
+    ON collections(parent_collection_id);
+
+-- One enable/disable per object (`target_key` = object id) or mod
+-- (`target_key` = mod id), applied over the parent's members.
+CREATE TABLE IF NOT EXISTS collection_overlays (
+    collection_id TEXT NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    target_kind TEXT NOT NULL CHECK(target_kind IN ('object', 'mod')),
+    target_key TEXT NOT NULL,
+    is_enabled INTEGER NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (collection_id, target_kind, target_key)
+) STRICT;
@@ -0,0 +1,11 @@
+-- 3DMigoto `persist` variable values saved with a collection: the
+-- `d3dx_user.ini` lines of its member mods, written back on apply so toggles
+-- like outfit swaps come back with the mods.
+CREATE TABLE IF NOT EXISTS collection_user_vars (
+    collection_id TEXT NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+    -- Full variable key as 3DMigoto writes it, e.g.
+    -- `$\mods\raiden\blue\mod.ini\swapvar`, lowercased.
+    var_key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (collection_id, var_key)
+) STRICT;
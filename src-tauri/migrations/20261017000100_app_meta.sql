@@ -0,0 +1,7 @@
+-- Key/value store read by `settings_repo::get_app_meta`. Metadata sync (ETag,
+-- version), the unicode key migration and the re-match baselines all write
+-- here, and every write failed silently without the table.
+CREATE TABLE IF NOT EXISTS app_meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+) STRICT;
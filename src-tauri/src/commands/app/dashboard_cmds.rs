@@ -1,5 +1,7 @@
 use crate::domain::errors::AppError;
 use crate::services::app::dashboard::{self, ActiveKeyBinding, DashboardPayload};
+use crate::services::app::keybind_collisions;
+use crate::services::keyviewer::collisions::KeybindCollision;
 
 /// Fetch all dashboard data in a single command for minimal IPC overhead.
 ///
@@ -27,6 +29,20 @@ pub async fn get_active_keybindings(
     dashboard::get_active_keybindings_service(pool.inner(), &game_id).await
 }
 
+/// Keys that fire in more than one enabled mod, or in a mod and one of the
+/// app's global hotkeys. Reads every enabled mod's INI files, like
+/// `get_active_keybindings`.
+#[specta::specta]
+#[tauri::command]
+pub async fn get_keybind_collisions(
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    config: tauri::State<'_, crate::services::config::ConfigService>,
+    game_id: String,
+) -> Result<Vec<KeybindCollision>, AppError> {
+    let hotkeys = config.with_settings(|settings| settings.hotkeys.clone());
+    keybind_collisions::get_keybind_collisions_service(pool.inner(), &game_id, &hotkeys).await
+}
+
 #[cfg(test)]
 #[path = "tests/dashboard_cmds_tests.rs"]
 mod tests;
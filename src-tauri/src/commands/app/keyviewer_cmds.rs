@@ -0,0 +1,53 @@
+use crate::domain::errors::AppError;
+use crate::services::config::ConfigService;
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::keyviewer::learned_hashes::{self, LearnedHashSummary};
+use tauri::State;
+
+/// Learns resource hashes for confidently matched objects from the mods under
+/// them, for the KeyViewer to use where the bundled `hash_db` falls short.
+/// Opt-in: nothing is learned until this runs, and each run replaces the last.
+#[specta::specta]
+#[tauri::command]
+pub async fn learn_object_hashes_cmd(
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+    game_id: String,
+) -> Result<LearnedHashSummary, AppError> {
+    let mods_root = config
+        .mods_root_for(&game_id)
+        .ok_or_else(|| AppError::NotFound(format!("Game {game_id} not found")))?;
+    let op_guard = op_lock.acquire().await?;
+    let summary =
+        learned_hashes::learn_object_hashes(&op_guard, pool.inner(), &game_id, &mods_root).await?;
+    refresh_overlay(pool.inner(), &config, &game_id).await;
+    Ok(summary)
+}
+
+/// Forgets every hash learned for a game. Returns how many there were.
+#[specta::specta]
+#[tauri::command]
+pub async fn reset_learned_hashes_cmd(
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+    game_id: String,
+) -> Result<u32, AppError> {
+    let op_guard = op_lock.acquire().await?;
+    let removed = learned_hashes::reset_learned_hashes(&op_guard, pool.inner(), &game_id).await?;
+    if removed > 0 {
+        refresh_overlay(pool.inner(), &config, &game_id).await;
+    }
+    Ok(removed)
+}
+
+/// Regenerates the KeyViewer artifacts so a learning change takes effect.
+async fn refresh_overlay(pool: &sqlx::SqlitePool, config: &ConfigService, game_id: &str) {
+    if let Err(error) =
+        crate::services::app::post_apply::trigger_overlay_refresh_for_game(pool, config, game_id)
+            .await
+    {
+        log::warn!("Learned hashes changed but overlay refresh failed: {error}");
+    }
+}
@@ -2,6 +2,7 @@ pub mod app_cmds;
 pub mod dashboard_cmds;
 pub mod game_cmds;
 pub mod hotkey_cmds;
+pub mod keyviewer_cmds;
 pub mod settings_cmds;
 pub mod theme_cmds;
 pub mod update_cmds;
@@ -9,9 +9,11 @@ use tauri::{AppHandle, Manager};
 #[specta::specta]
 #[tauri::command]
 pub async fn check_metadata_update(
+    app: AppHandle,
     pool: tauri::State<'_, SqlitePool>,
 ) -> Result<metadata_sync::MetadataSyncResult, AppError> {
-    let result = metadata_sync::check_and_sync_metadata(&pool).await;
+    let resource_dir = app.path().resource_dir().ok();
+    let result = metadata_sync::check_and_sync_metadata(&pool, resource_dir.as_deref()).await;
     Ok(result)
 }
 
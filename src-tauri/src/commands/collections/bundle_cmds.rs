@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::domain::collection::{BundleImportResult, BundleResolution};
+use crate::domain::errors::AppError;
+use crate::services::collection_service;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_collection_bundle(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    collection_id: String,
+    path: String,
+) -> Result<u32, AppError> {
+    let written = collection_service::export_collection_bundle(
+        pool.inner(),
+        &game_id,
+        &collection_id,
+        Path::new(&path),
+    )
+    .await?;
+    Ok(u32::try_from(written).unwrap_or(u32::MAX))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_collection_bundle(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    path: String,
+) -> Result<BundleResolution, AppError> {
+    let result =
+        collection_service::resolve_collection_bundle(pool.inner(), &game_id, Path::new(&path))
+            .await?;
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_collection_bundle(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    path: String,
+    name: Option<String>,
+) -> Result<BundleImportResult, AppError> {
+    let result = collection_service::import_collection_bundle(
+        pool.inner(),
+        &game_id,
+        Path::new(&path),
+        name,
+    )
+    .await?;
+    Ok(result)
+}
@@ -2,8 +2,8 @@ use sqlx::SqlitePool;
 use tauri::State;
 
 use crate::domain::collection::{
-    ApplyPreview, ApplyProgressSnapshot, ApplyResult, CollectionPreview, CollectionSummary,
-    CreateCollectionInput, CreateCollectionMode, UpdateCollectionInput,
+    ApplyPreview, ApplyProgressSnapshot, ApplyResult, ApplyScope, CollectionPreview,
+    CollectionSummary, CreateCollectionInput, CreateCollectionMode, UpdateCollectionInput,
 };
 use crate::domain::corridor::CorridorSnapshot;
 use crate::domain::errors::AppError;
@@ -65,12 +65,14 @@ pub async fn create_collection(
     name: String,
     save_mode: Option<CreateCollectionMode>,
     source_collection_id: Option<String>,
+    mod_ids: Option<Vec<String>>,
 ) -> Result<CollectionSummary, AppError> {
     let input = CreateCollectionInput {
         game_id,
         name,
         save_mode,
         source_collection_id,
+        mod_ids,
     };
 
     let result = collection_service::create_collection(pool.inner(), input).await?;
@@ -89,8 +91,9 @@ pub async fn apply_collection(
     game_id: String,
     collection_id: String,
     ignore_missing: Option<bool>,
+    scope: Option<ApplyScope>,
 ) -> Result<ApplyResult, AppError> {
-    let _guard = op_lock.inner().acquire().await?;
+    let op_guard = op_lock.inner().acquire().await?;
     let settings = config.get_settings();
     let is_safe = settings.safe_mode.enabled;
     let game = settings
@@ -105,6 +108,7 @@ pub async fn apply_collection(
     let mods_path = game.mod_path.clone();
 
     let result = collection_service::apply_collection(collection_service::ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: pool.inner(),
         game_id: &game_id,
         collection_id: &collection_id,
@@ -114,6 +118,7 @@ pub async fn apply_collection(
         ignore_missing: ignore_missing.unwrap_or(false),
         settings,
         reconcile_lock: Some(disk_reconcile.game_lock(&game_id)),
+        scope: scope.unwrap_or_default(),
     })
     .await?;
 
@@ -149,6 +154,19 @@ pub async fn replace_collection_with_current_state(
     Ok(result)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_collection_user_vars(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    collection_id: String,
+) -> Result<u32, AppError> {
+    let saved =
+        collection_service::capture_collection_user_vars(pool.inner(), &game_id, &collection_id)
+            .await?;
+    Ok(u32::try_from(saved).unwrap_or(u32::MAX))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_collection(
@@ -193,6 +211,7 @@ pub async fn preview_apply_collection(
     config: State<'_, crate::services::config::ConfigService>,
     game_id: String,
     collection_id: String,
+    scope: Option<ApplyScope>,
 ) -> Result<ApplyPreview, AppError> {
     let settings = config.get_settings();
     let mods_path = settings
@@ -207,6 +226,7 @@ pub async fn preview_apply_collection(
         &collection_id,
         config.current_corridor(),
         mods_path.as_deref(),
+        &scope.unwrap_or_default(),
     )
     .await?;
     Ok(result)
@@ -232,9 +252,10 @@ pub async fn resolve_recovery_task(
 ) -> Result<(), AppError> {
     // Resuming an interrupted apply mutates the filesystem, so it must be
     // mutually excluded from concurrent runtime ops just like a normal apply.
-    let _guard = op_lock.inner().acquire().await?;
+    let op_guard = op_lock.inner().acquire().await?;
 
     crate::services::recovery_service::resolve_recovery_task(
+        &op_guard,
         pool.inner(),
         config.inner(),
         watcher_state.inner(),
@@ -0,0 +1,56 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::domain::collection::{CollectionDiff, CollectionDiffSource, ThreeWayCollectionDiff};
+use crate::domain::errors::AppError;
+use crate::services::collection_service;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_collections(
+    pool: State<'_, SqlitePool>,
+    config: State<'_, crate::services::config::ConfigService>,
+    game_id: String,
+    base: CollectionDiffSource,
+    target: CollectionDiffSource,
+) -> Result<CollectionDiff, AppError> {
+    let mods_path = config
+        .mods_root_for(&game_id)
+        .map(|path| path.to_string_lossy().to_string());
+
+    let result = collection_service::diff_collections(
+        pool.inner(),
+        &game_id,
+        &base,
+        &target,
+        mods_path.as_deref(),
+    )
+    .await?;
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_collections_three_way(
+    pool: State<'_, SqlitePool>,
+    config: State<'_, crate::services::config::ConfigService>,
+    game_id: String,
+    base: CollectionDiffSource,
+    ours: CollectionDiffSource,
+    theirs: CollectionDiffSource,
+) -> Result<ThreeWayCollectionDiff, AppError> {
+    let mods_path = config
+        .mods_root_for(&game_id)
+        .map(|path| path.to_string_lossy().to_string());
+
+    let result = collection_service::compare_collections_three_way(
+        pool.inner(),
+        &game_id,
+        &base,
+        &ours,
+        &theirs,
+        mods_path.as_deref(),
+    )
+    .await?;
+    Ok(result)
+}
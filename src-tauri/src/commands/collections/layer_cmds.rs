@@ -0,0 +1,39 @@
+use sqlx::SqlitePool;
+use tauri::State;
+
+use crate::domain::collection::{
+    CollectionLayers, CollectionOverlay, CollectionSummary, SetCollectionLayersInput,
+};
+use crate::domain::errors::AppError;
+use crate::services::collection_service;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_collection_layers(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    collection_id: String,
+) -> Result<CollectionLayers, AppError> {
+    let result =
+        collection_service::get_collection_layers(pool.inner(), &game_id, &collection_id).await?;
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_collection_layers(
+    pool: State<'_, SqlitePool>,
+    game_id: String,
+    id: String,
+    parent_collection_id: Option<String>,
+    overlays: Vec<CollectionOverlay>,
+) -> Result<CollectionSummary, AppError> {
+    let input = SetCollectionLayersInput {
+        id,
+        game_id,
+        parent_collection_id,
+        overlays,
+    };
+    let result = collection_service::set_collection_layers(pool.inner(), input).await?;
+    Ok(result)
+}
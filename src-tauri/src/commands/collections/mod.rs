@@ -1 +1,4 @@
+pub mod bundle_cmds;
 pub mod cmds;
+pub mod diff_cmds;
+pub mod layer_cmds;
@@ -0,0 +1,35 @@
+use crate::domain::errors::AppError;
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::scanner::dedup::blob_hardlink::{HardlinkAllSummary, HardlinkProgress};
+use crate::services::scanner::dedup::blob_store::BlobDedupReport;
+use crate::services::scanner::watcher::WatcherState;
+use tauri::{AppHandle, Emitter, State};
+
+/// Identical payload files across every configured game and the bytes
+/// hardlinking them would free.
+#[tauri::command]
+#[specta::specta]
+pub async fn dup_blob_report(db: State<'_, sqlx::SqlitePool>) -> Result<BlobDedupReport, AppError> {
+    crate::services::scanner::dedup::blob_store::blob_dedup_report(db.inner()).await
+}
+
+/// Collapses every identical payload file, across games, into hardlinks.
+#[tauri::command]
+#[specta::specta]
+pub async fn dup_hardlink_all(
+    app: AppHandle,
+    watcher_state: State<'_, WatcherState>,
+    op_lock: State<'_, OperationLock>,
+    db: State<'_, sqlx::SqlitePool>,
+) -> Result<HardlinkAllSummary, AppError> {
+    let op_guard = op_lock.acquire().await?;
+    crate::services::scanner::dedup::blob_hardlink::hardlink_all_duplicates(
+        db.inner(),
+        &op_guard,
+        &watcher_state.suppressor,
+        |progress: HardlinkProgress| {
+            let _ = app.emit("dup-hardlink-progress", &progress);
+        },
+    )
+    .await
+}
@@ -1,3 +1,4 @@
+pub mod dup_blob_cmds;
 pub mod dup_ignore_cmds;
 pub mod dup_resolve_cmds;
 pub mod dup_scan_cmds;
@@ -4,8 +4,12 @@ use std::path::Path;
 use tauri::State;
 
 use crate::services::fs_utils::operation_lock::OperationLock;
-use crate::services::scanner::watcher::WatcherState;
+use crate::services::scanner::conflict::resolution::{
+    resolve_hash_conflict_service, ResolveHashConflictRequest,
+};
+use crate::services::scanner::watcher::{SuppressionGuard, WatcherState};
 
+use crate::domain::conflicts::{ConflictKind, ConflictResolution, ConflictResolutionStrategy};
 use crate::domain::errors::AppError;
 use crate::services::config::ConfigService;
 use crate::services::fs_utils::guard::validate_path;
@@ -222,3 +226,64 @@ pub async fn list_ignored_object_conflicts(
     let list = crate::repo::conflict_repo::list_ignored_object_conflicts(&pool, &game_id).await?;
     Ok(list)
 }
+
+/// Settles the hash conflict on `hash` in favour of `winner_path`, disabling
+/// the other mods or demoting their override sections, and stores the choice.
+#[allow(clippy::too_many_arguments)] // Tauri command boundary: states plus the IPC payload.
+#[specta::specta]
+#[tauri::command]
+pub async fn resolve_hash_conflict(
+    app: tauri::AppHandle,
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    watcher: State<'_, WatcherState>,
+    op_lock: State<'_, OperationLock>,
+    game_id: String,
+    hash: String,
+    kind: ConflictKind,
+    winner_path: String,
+    strategy: ConflictResolutionStrategy,
+) -> Result<ConflictResolution, AppError> {
+    let winner = validate_path(&config, &game_id, &winner_path)?;
+    let _guard = SuppressionGuard::new(&watcher.suppressor);
+    let op_guard = op_lock.acquire().await?;
+
+    let outcome = resolve_hash_conflict_service(ResolveHashConflictRequest {
+        pool: pool.inner(),
+        state: &watcher,
+        op_guard: &op_guard,
+        game_id: &game_id,
+        hash: &hash,
+        kind,
+        winner: &winner,
+        strategy,
+    })
+    .await?;
+
+    if let Err(error) = crate::services::disk_reconcile::emit::emit_internal_disk_reconcile(
+        &app,
+        pool.inner(),
+        &game_id,
+        outcome.changed_paths,
+    )
+    .await
+    {
+        log::warn!("Post-hash-conflict-resolution disk reconcile failed: {error}");
+    }
+
+    Ok(outcome.resolution)
+}
+
+/// Forgets the stored winner for `hash`. Disabled mods and demoted sections
+/// are left as they are.
+#[specta::specta]
+#[tauri::command]
+pub async fn clear_hash_conflict_resolution(
+    pool: State<'_, sqlx::SqlitePool>,
+    game_id: String,
+    hash: String,
+    kind: ConflictKind,
+) -> Result<(), AppError> {
+    crate::repo::conflict_repo::delete_conflict_resolution(&pool, &game_id, &hash, kind).await?;
+    Ok(())
+}
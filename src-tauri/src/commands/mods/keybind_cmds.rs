@@ -0,0 +1,55 @@
+use crate::domain::errors::AppError;
+use crate::services::config::ConfigService;
+use crate::services::disk_reconcile::emit::emit_internal_disk_reconcile;
+use crate::services::fs_utils::guard::validate_path;
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::mods::keybind_remap::{
+    remap_keybinds_service, revert_keybind_remap_service, KeybindRemapReport,
+};
+use crate::services::scanner::watcher::{SuppressionGuard, WatcherState};
+use tauri::State;
+
+/// Moves the mod's keybinds that collide with other enabled mods or app
+/// hotkeys to free keys from the KeyViewer remap pool.
+#[specta::specta]
+#[tauri::command]
+pub async fn remap_mod_keybinds(
+    app: tauri::AppHandle,
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+    watcher: State<'_, WatcherState>,
+    game_id: String,
+    folder_path: String,
+) -> Result<KeybindRemapReport, AppError> {
+    let mod_root = validate_path(&config, &game_id, &folder_path)?;
+    let changed_path = mod_root.to_string_lossy().to_string();
+    let _guard = SuppressionGuard::new(&watcher.suppressor);
+    let op_guard = op_lock.acquire().await?;
+    let report =
+        remap_keybinds_service(&op_guard, pool.inner(), &config, &game_id, &mod_root).await?;
+    emit_internal_disk_reconcile(&app, pool.inner(), &game_id, vec![changed_path]).await?;
+    Ok(report)
+}
+
+/// Restores the bindings a previous `remap_mod_keybinds` changed.
+#[specta::specta]
+#[tauri::command]
+pub async fn revert_mod_keybind_remap(
+    app: tauri::AppHandle,
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+    watcher: State<'_, WatcherState>,
+    game_id: String,
+    folder_path: String,
+) -> Result<KeybindRemapReport, AppError> {
+    let mod_root = validate_path(&config, &game_id, &folder_path)?;
+    let changed_path = mod_root.to_string_lossy().to_string();
+    let _guard = SuppressionGuard::new(&watcher.suppressor);
+    let op_guard = op_lock.acquire().await?;
+    let report =
+        revert_keybind_remap_service(&op_guard, pool.inner(), &config, &game_id, &mod_root).await?;
+    emit_internal_disk_reconcile(&app, pool.inner(), &game_id, vec![changed_path]).await?;
+    Ok(report)
+}
@@ -1,4 +1,5 @@
 pub mod conflict_cmds;
+pub mod keybind_cmds;
 pub mod mod_bulk_cmds;
 pub mod mod_core_cmds;
 pub mod mod_import_cmds;
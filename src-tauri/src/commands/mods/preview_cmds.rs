@@ -4,10 +4,14 @@ use crate::services::disk_reconcile::emit::emit_internal_disk_reconcile;
 use crate::services::fs_utils::guard::validate_path;
 use crate::services::fs_utils::operation_lock::OperationLock;
 use crate::services::ini::document::IniDocument;
+use crate::services::ini::edit::IniEdit;
+use crate::services::ini::lint::IniDiagnostic;
+use crate::services::mods::ini_lint::lint_mod_ini_service;
 use crate::services::mods::preview_ops::{
-    clear_mod_preview_images_inner, ensure_image_size, list_mod_ini_files_inner,
-    list_mod_preview_images_inner, read_mod_ini_inner, remove_mod_preview_image_inner,
-    resolve_image_path, save_mod_preview_image_inner, write_mod_ini_locked_inner,
+    clear_mod_preview_images_inner, edit_mod_ini_locked_inner, ensure_image_size,
+    list_mod_ini_files_inner, list_mod_preview_images_inner, read_mod_ini_inner,
+    remove_mod_preview_image_inner, resolve_image_path, save_mod_preview_image_inner,
+    write_mod_ini_locked_inner,
 };
 use crate::services::scanner::watcher::{SuppressionGuard, WatcherState};
 use tauri::State;
@@ -67,6 +71,57 @@ pub async fn write_mod_ini(
     emit_internal_disk_reconcile(&app, pool.inner(), &game_id, vec![changed_path]).await
 }
 
+/// Structural edits to one INI of a mod, checked against the hash it was read
+/// with.
+#[derive(serde::Deserialize, specta::Type)]
+pub struct EditModIniInput {
+    pub game_id: String,
+    pub folder_path: String,
+    pub file_name: String,
+    pub expected_source_hash: String,
+    pub edits: Vec<IniEdit>,
+}
+
+#[specta::specta]
+#[tauri::command]
+pub async fn edit_mod_ini(
+    app: tauri::AppHandle,
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+    watcher: State<'_, WatcherState>,
+    input: EditModIniInput,
+) -> Result<(), AppError> {
+    let mod_root = validate_path(&config, &input.game_id, &input.folder_path)?;
+    let changed_path = mod_root
+        .join(&input.file_name)
+        .to_string_lossy()
+        .to_string();
+    let _guard = SuppressionGuard::new(&watcher.suppressor);
+    let op_guard = op_lock.acquire().await?;
+    edit_mod_ini_locked_inner(
+        &op_guard,
+        &mod_root,
+        &input.file_name,
+        &input.expected_source_hash,
+        input.edits,
+    )
+    .await?;
+    emit_internal_disk_reconcile(&app, pool.inner(), &input.game_id, vec![changed_path]).await
+}
+
+#[specta::specta]
+#[tauri::command]
+pub async fn lint_mod_ini(
+    config: State<'_, ConfigService>,
+    pool: State<'_, sqlx::SqlitePool>,
+    game_id: String,
+    folder_path: String,
+) -> Result<Vec<IniDiagnostic>, AppError> {
+    let mod_root = validate_path(&config, &game_id, &folder_path)?;
+    lint_mod_ini_service(pool.inner(), &game_id, &mod_root).await
+}
+
 #[specta::specta]
 #[tauri::command]
 pub async fn list_mod_preview_images(
@@ -1,5 +1,6 @@
 use crate::domain::errors::AppError;
 use crate::services::game::schema_loader;
+use crate::services::scanner::master_db::{overlay, MasterDbCache};
 use tauri::Manager;
 
 /// The bundled-resources directory, or a typed error.
@@ -45,13 +46,20 @@ pub async fn get_object(
 /// Loads from `resources/databases/{game_type}.json`.
 /// Returns array JSON for frontend compatibility (even if file uses new object format).
 /// When hash_db is present in source, merges hashes into matching entries.
+/// The user's MasterDB overlay is merged in too.
 #[tauri::command]
 #[specta::specta]
-pub async fn get_master_db(app: tauri::AppHandle, game_type: i32) -> Result<String, AppError> {
+pub async fn get_master_db(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+) -> Result<String, AppError> {
     let resource_dir = resource_dir(&app)?;
+    let overlay = overlay::load_overlay(pool.inner(), game_type).await;
     Ok(crate::services::scanner::master_db::load_master_db_json(
         &resource_dir,
         game_type,
+        &overlay,
     )?)
 }
 
@@ -77,14 +85,17 @@ pub async fn match_object_with_db(
     game_type: i32,
     object_name: String,
 ) -> Result<Option<crate::services::scanner::master_db::MatchedDbEntry>, AppError> {
+    let Some(db) = crate::services::scanner::master_db::get_cached(&app, game_type).await? else {
+        return Ok(None);
+    };
     let resource_dir = resource_dir(&app)?;
 
     Ok(
         crate::services::scanner::master_db::match_object_with_db_service(
             &resource_dir,
-            game_type,
+            &db,
             &object_name,
-        )?,
+        ),
     )
 }
 
@@ -114,6 +125,132 @@ pub async fn search_master_db(
     )
 }
 
+/// The loaded MasterDB for `game_type`, or a typed not-found error.
+async fn require_master_db(
+    app: &tauri::AppHandle,
+    game_type: i32,
+    game_id: &str,
+) -> Result<std::sync::Arc<crate::services::scanner::deep_matcher::MasterDb>, AppError> {
+    crate::services::scanner::master_db::get_cached_for_game(app, game_type, game_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::Scanner(crate::domain::errors::ScannerError::PathNotFound {
+                path: format!("MasterDB for game type {}", game_type),
+            })
+        })
+}
+
+/// Re-match a game's objects against the current MasterDB.
+///
+/// Only objects whose entry changed since the last reviewed run, plus
+/// unmatched and low-confidence ones, are tried. Nothing is written; accepted
+/// changes go through `apply_object_match_cmd`.
+#[tauri::command]
+#[specta::specta]
+pub async fn plan_master_db_rematch_cmd(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_id: String,
+    game_type: i32,
+) -> Result<crate::services::scanner::master_db::rematch::RematchPlan, AppError> {
+    let db = require_master_db(&app, game_type, &game_id).await?;
+    let resource_dir = app.path().resource_dir().ok();
+    let ini_filters =
+        crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), game_type);
+    crate::services::scanner::master_db::rematch::plan_rematch(
+        pool.inner(),
+        &game_id,
+        db,
+        ini_filters,
+    )
+    .await
+}
+
+/// Mark the current MasterDB as reviewed, so the next re-match only diffs
+/// against later updates.
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_master_db_rematch_reviewed_cmd(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_id: String,
+    game_type: i32,
+) -> Result<(), AppError> {
+    let db = require_master_db(&app, game_type, &game_id).await?;
+    crate::services::scanner::master_db::rematch::store_baseline(pool.inner(), &game_id, &db).await
+}
+
+/// The user's MasterDB overlay for a game type.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_master_db_overlay_cmd(
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+) -> Result<Vec<crate::services::scanner::deep_matcher::DbEntry>, AppError> {
+    overlay::list_overlay(pool.inner(), game_type).await
+}
+
+/// Adds or replaces one overlay entry. An entry named like a bundled one adds
+/// its tags, skins, aliases, and hashes to it; any other is a new entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_master_db_overlay_entry_cmd(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+    entry: crate::services::scanner::deep_matcher::DbEntry,
+) -> Result<(), AppError> {
+    overlay::save_overlay_entry(pool.inner(), game_type, entry).await?;
+    MasterDbCache::invalidate(&app).await;
+    Ok(())
+}
+
+/// Drops one overlay entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_master_db_overlay_entry_cmd(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+    entry_name: String,
+) -> Result<(), AppError> {
+    overlay::delete_overlay_entry(pool.inner(), game_type, &entry_name).await?;
+    MasterDbCache::invalidate(&app).await;
+    Ok(())
+}
+
+/// Writes the overlay to `path` in the MasterDB file layout. Returns how many
+/// entries were written.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_master_db_overlay_cmd(
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+    path: String,
+) -> Result<u32, AppError> {
+    let written =
+        overlay::export_overlay(pool.inner(), game_type, std::path::Path::new(&path)).await?;
+    Ok(u32::try_from(written).unwrap_or(u32::MAX))
+}
+
+/// Merges a MasterDB-layout file into the overlay. Returns how many entries it
+/// carried.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_master_db_overlay_cmd(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+    game_type: i32,
+    path: String,
+) -> Result<u32, AppError> {
+    let imported =
+        overlay::import_overlay(pool.inner(), game_type, std::path::Path::new(&path)).await?;
+    if imported > 0 {
+        MasterDbCache::invalidate(&app).await;
+    }
+    Ok(u32::try_from(imported).unwrap_or(u32::MAX))
+}
+
 #[cfg(test)]
 #[path = "tests/master_db_cmds_tests.rs"]
 mod tests;
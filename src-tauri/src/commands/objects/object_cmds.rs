@@ -98,14 +98,20 @@ pub async fn update_object_cmd(
 pub async fn apply_object_match_cmd(
     input: ApplyObjectMatchInput,
     pool: State<'_, sqlx::SqlitePool>,
+    app: tauri::AppHandle,
 ) -> Result<(), AppError> {
-    apply_object_match_cmd_inner(&input, &pool).await
+    // A learned correction lives in the cached MasterDB's indexes.
+    if apply_object_match_cmd_inner(&input, &pool).await? {
+        crate::services::scanner::master_db::MasterDbCache::invalidate(&app).await;
+    }
+    Ok(())
 }
 
+/// Returns whether the match taught the matcher a correction.
 pub async fn apply_object_match_cmd_inner(
     input: &ApplyObjectMatchInput,
     pool: &sqlx::SqlitePool,
-) -> Result<(), AppError> {
+) -> Result<bool, AppError> {
     crate::services::objects::matching::apply_object_match(
         pool,
         &input.game_id,
@@ -147,3 +153,7 @@ pub async fn delete_object_cmd(
 #[cfg(test)]
 #[path = "tests/object_cmds_tests.rs"]
 mod tests;
+
+#[cfg(test)]
+#[path = "tests/object_match_learning_tests.rs"]
+mod object_match_learning_tests;
@@ -0,0 +1,67 @@
+use super::*;
+use crate::test_utils::{insert_test_object, TestObjectFixture};
+type CommandResult<T> = Result<T, crate::domain::errors::AppError>;
+
+#[tokio::test]
+async fn test_apply_object_match_cmd_learns_manual_reassignment() -> CommandResult<()> {
+    let pool = crate::test_utils::init_test_db().await.pool;
+    let game_id = "test_game_1".to_string();
+    crate::test_utils::insert_test_game(
+        &pool,
+        &crate::test_utils::TestGameFixture {
+            id: &game_id,
+            name: "Test Game",
+            game_type: crate::domain::models::GameType::GIMI,
+            path: "/",
+            mods_path: Some("/Mods"),
+        },
+    )
+    .await
+    .unwrap();
+
+    insert_test_object(
+        &pool,
+        &TestObjectFixture {
+            id: "relearn_obj",
+            game_id: &game_id,
+            name: "Purple Thunder Lady",
+            folder_path: "Purple Thunder Lady",
+            object_type: "Character",
+        },
+    )
+    .await
+    .unwrap();
+
+    let input = |entry_key: &str, source: &str| ApplyObjectMatchInput {
+        game_id: game_id.clone(),
+        object_id: Some("relearn_obj".to_string()),
+        folder_path: None,
+        matched_entry_key: Some(entry_key.to_string()),
+        matched_alias_name: None,
+        matched_confidence: None,
+        matched_reason: None,
+        matched_source: Some(source.to_string()),
+    };
+
+    // Automatic matches are the matcher's own output, not a correction.
+    assert!(!apply_object_match_cmd_inner(&input("yae miko", "auto_recognize"), &pool).await?);
+    assert!(apply_object_match_cmd_inner(&input("raiden shogun", "manual_match"), &pool).await?);
+    // Re-applying the same entry teaches nothing new.
+    assert!(!apply_object_match_cmd_inner(&input("raiden shogun", "manual_match"), &pool).await?);
+
+    let learned = sqlx::query_as::<_, (String, String)>(
+        "SELECT folder_pattern, entry_key FROM matcher_corrections",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        learned,
+        vec![(
+            "purple thunder lady".to_string(),
+            "raiden shogun".to_string()
+        )]
+    );
+
+    Ok(())
+}
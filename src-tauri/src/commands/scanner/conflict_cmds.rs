@@ -4,6 +4,7 @@
 //! Separated from mod_cmds.rs to keep file sizes manageable.
 
 use crate::domain::errors::AppError;
+use crate::services::fs_utils::operation_lock::OpGuard;
 use crate::services::scanner::conflict::ConflictInfo;
 use std::path::PathBuf;
 
@@ -42,6 +43,51 @@ pub async fn detect_conflicts_in_folder_cmd(
     Ok(crate::services::scanner::conflict::detect::detect_conflicts_in_folder_service(&path)?)
 }
 
+/// Mods whose INIs carry a resource hash, from the persisted hash index.
+#[specta::specta]
+#[tauri::command]
+pub async fn find_mods_by_hash(
+    game_id: String,
+    hash: String,
+    pool: tauri::State<'_, sqlx::SqlitePool>,
+) -> Result<Vec<crate::domain::conflicts::HashIndexHit>, AppError> {
+    crate::services::keyviewer::hash_index::find_mods_by_hash_service(pool.inner(), &game_id, &hash)
+        .await
+}
+
+/// Carry out the stored conflict decisions again and reconcile what that
+/// changed. Failures are logged: the caller's own work already succeeded.
+pub(crate) async fn reapply_conflict_decisions(
+    app: &tauri::AppHandle,
+    op_guard: &OpGuard,
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    mods_path: &str,
+) {
+    let changed = match crate::services::scanner::conflict::enforce::enforce_resolutions(
+        op_guard,
+        pool,
+        game_id,
+        std::path::Path::new(mods_path),
+    )
+    .await
+    {
+        Ok(changed) if changed.is_empty() => return,
+        Ok(changed) => changed,
+        Err(error) => {
+            log::warn!("[conflict] stored decisions were not re-applied: {error}");
+            return;
+        }
+    };
+    if let Err(error) = crate::services::disk_reconcile::emit::emit_internal_disk_reconcile(
+        app, pool, game_id, changed,
+    )
+    .await
+    {
+        log::warn!("[conflict] reconcile after re-applying decisions failed: {error}");
+    }
+}
+
 #[cfg(test)]
 #[path = "tests/conflict_cmds_tests.rs"]
 mod tests;
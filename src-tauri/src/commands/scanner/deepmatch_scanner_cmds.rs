@@ -55,12 +55,14 @@ pub async fn deepmatch_scanner_cmd(
     let mods = require_mods_dir(&mods_path)?;
 
     let Some(master_db) =
-        crate::services::scanner::master_db::get_cached(&app, master_db_type).await?
+        crate::services::scanner::master_db::get_cached_for_game(&app, master_db_type, &game_id)
+            .await?
     else {
         return Err(AppError::Scanner(ScannerError::PathNotFound {
             path: format!("MasterDB for game type {}", master_db_type),
         }));
     };
+    let ai = crate::services::scanner::master_db::ai_rerank_context(&app, &master_db).await;
     let resource_dir = app.path().resource_dir().ok();
     let ini_filters =
         crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), master_db_type);
@@ -75,6 +77,7 @@ pub async fn deepmatch_scanner_cmd(
         resource_dir: resource_dir.as_deref(),
         on_progress: Some(on_progress),
         specific_paths: None,
+        ai,
     })
     .await?;
 
@@ -97,6 +100,8 @@ pub async fn deepmatch_scanner_cmd(
             db_thumbnail: item.db_thumbnail,
             skip: false,
             move_from_temp: false,
+            // Packs are only split from the review modal, never unattended.
+            pack_parts: Vec::new(),
         })
         .collect();
 
@@ -143,12 +148,14 @@ pub async fn deepmatch_preview_cmd(
 
     let mods = require_mods_dir(&mods_path)?;
 
-    let Some(master_db) = crate::services::scanner::master_db::get_cached(&app, game_type).await?
+    let Some(master_db) =
+        crate::services::scanner::master_db::get_cached_for_game(&app, game_type, &game_id).await?
     else {
         return Err(AppError::Scanner(ScannerError::PathNotFound {
             path: format!("MasterDB for game type {}", game_type),
         }));
     };
+    let ai = crate::services::scanner::master_db::ai_rerank_context(&app, &master_db).await;
     let resource_dir = app.path().resource_dir().ok();
     let ini_filters =
         crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), game_type);
@@ -169,6 +176,7 @@ pub async fn deepmatch_preview_cmd(
         resource_dir: resource_dir.as_deref(),
         on_progress: Some(on_progress),
         specific_paths: optional_paths,
+        ai,
     })
     .await?)
 }
@@ -193,13 +201,18 @@ pub async fn deepmatch_preview_for_objects_cmd(
         return Ok(Vec::new());
     }
 
-    let Some(master_db) =
-        crate::services::scanner::master_db::get_cached(&app, input.game_type).await?
+    let Some(master_db) = crate::services::scanner::master_db::get_cached_for_game(
+        &app,
+        input.game_type,
+        &input.game_id,
+    )
+    .await?
     else {
         return Err(AppError::Scanner(ScannerError::PathNotFound {
             path: format!("MasterDB for game type {}", input.game_type),
         }));
     };
+    let ai = crate::services::scanner::master_db::ai_rerank_context(&app, &master_db).await;
     let resource_dir = app.path().resource_dir().ok();
     let ini_filters =
         crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), input.game_type);
@@ -213,6 +226,7 @@ pub async fn deepmatch_preview_for_objects_cmd(
         resource_dir: resource_dir.as_deref(),
         on_progress: Some(on_progress),
         specific_paths: Some(object_paths),
+        ai,
     })
     .await?)
 }
@@ -297,84 +311,6 @@ async fn resolve_object_preview_paths(
     Ok(resolved_paths)
 }
 
-/// Phase 2: Commit user-confirmed scan results to DB.
-/// Called after the user reviews and confirms/overrides matches in the review modal.
-///
-/// # Covers: US-2.3 (Review & Organize UI — Confirm)
-#[tauri::command]
-#[specta::specta]
-#[allow(clippy::too_many_arguments)] // Tauri command boundary keeps the existing IPC payload stable.
-pub async fn commit_scan_cmd(
-    app: tauri::AppHandle,
-    state: State<'_, WatcherState>,
-    game_id: String,
-    game_name: String,
-    game_type: String,
-    mods_path: String,
-    items: Vec<crate::services::scanner::sync::ConfirmedScanItem>,
-    pool: State<'_, sqlx::SqlitePool>,
-) -> Result<crate::services::scanner::sync::SyncResult, AppError> {
-    use crate::services::scanner::sync;
-
-    let _guard = SuppressionGuard::new(&state.suppressor);
-
-    let resource_dir = app.path().resource_dir().ok();
-
-    let keywords = app
-        .state::<crate::services::config::ConfigService>()
-        .get_settings()
-        .safe_mode
-        .keywords;
-
-    let result = sync::commit_scan_results(sync::CommitScanRequest {
-        pool: &pool,
-        game_id: &game_id,
-        game_name: &game_name,
-        game_type: &game_type,
-        mods_path: &mods_path,
-        items,
-        resource_dir: resource_dir.as_deref(),
-        safe_mode_keywords: &keywords,
-        preserve_existing_mappings: false,
-    })
-    .await?;
-
-    Ok(result)
-}
-
-/// Compute percentage scores for a specific batch of candidates against a folder.
-/// Used by the Scan Review Modal to lazy-load accurate matching percentages.
-///
-/// # Covers: US-2.3 (Review & Organize UI — Lazy Scoring)
-#[tauri::command]
-#[specta::specta]
-pub async fn score_candidates_batch_cmd(
-    folder_path: String,
-    candidate_names: Vec<String>,
-    game_type: i32,
-    app: tauri::AppHandle,
-) -> Result<std::collections::HashMap<String, u8>, AppError> {
-    use crate::services::scanner::sync;
-
-    let Some(master_db) = crate::services::scanner::master_db::get_cached(&app, game_type).await?
-    else {
-        return Err(AppError::Scanner(ScannerError::PathNotFound {
-            path: format!("MasterDB for game type {}", game_type),
-        }));
-    };
-    let resource_dir = app.path().resource_dir().ok();
-    let ini_filters =
-        crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), game_type);
-    // Move the Arc, not the 5 MB behind it.
-    let res = tauri::async_runtime::spawn_blocking(move || {
-        sync::score_candidates_batch(&folder_path, &master_db, candidate_names, &ini_filters)
-    })
-    .await
-    .map_err(|e| AppError::Internal(format!("batch scoring task panicked: {e}")))?;
-
-    Ok(res)
-}
-
 #[tauri::command]
 #[specta::specta]
 pub async fn list_folder_entries_cmd(
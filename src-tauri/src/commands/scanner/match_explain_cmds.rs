@@ -0,0 +1,38 @@
+//! The matcher's reasoning for one folder, for the match explanation panel.
+
+use crate::domain::errors::{AppError, ScannerError};
+use crate::services::scanner::master_db::{self, MatchExplanation};
+use tauri::{Manager, State};
+
+/// Explain how the matcher scores one folder: extracted tokens, each stage's
+/// contribution, the acceptance decisions, and mechanical rerank points.
+///
+/// Uses the same per-game MasterDB as scans, corrections included, so the
+/// explanation describes the match a scan would make.
+#[tauri::command]
+#[specta::specta]
+pub async fn explain_match_cmd(
+    folder_path: String,
+    game_id: String,
+    game_type: i32,
+    app: tauri::AppHandle,
+    config: State<'_, crate::services::config::ConfigService>,
+) -> Result<MatchExplanation, AppError> {
+    let path =
+        crate::services::fs_utils::guard::validate_dir_in_configured_roots(&config, &folder_path)?;
+    let folder = path.to_path_buf();
+
+    let Some(master_db) = master_db::get_cached_for_game(&app, game_type, &game_id).await? else {
+        return Err(AppError::Scanner(ScannerError::PathNotFound {
+            path: format!("MasterDB for game type {}", game_type),
+        }));
+    };
+    let resource_dir = app.path().resource_dir().ok();
+    let ini_filters = master_db::ini_filters(resource_dir.as_deref(), game_type);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        master_db::explain_folder_match(&folder, &master_db, &ini_filters)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("match explain task panicked: {e}")))
+}
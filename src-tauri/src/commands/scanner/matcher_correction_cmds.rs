@@ -0,0 +1,60 @@
+//! Matcher corrections: the folder-name reassignments the matcher learned
+//! from, listed, forgotten, and shared as a file.
+
+use crate::domain::errors::AppError;
+use crate::services::scanner::master_db::corrections::{self, MatcherCorrection};
+use crate::services::scanner::master_db::MasterDbCache;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// Every correction the matcher has learned for a game.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_matcher_corrections_cmd(
+    game_id: String,
+    pool: State<'_, sqlx::SqlitePool>,
+) -> Result<Vec<MatcherCorrection>, AppError> {
+    corrections::list_corrections(pool.inner(), &game_id).await
+}
+
+/// Forgets one correction so the folder pattern is matched from scratch.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_matcher_correction_cmd(
+    game_id: String,
+    folder_pattern: String,
+    pool: State<'_, sqlx::SqlitePool>,
+    app: AppHandle,
+) -> Result<(), AppError> {
+    corrections::delete_correction(pool.inner(), &game_id, &folder_pattern).await?;
+    MasterDbCache::invalidate(&app).await;
+    Ok(())
+}
+
+/// Writes a game's corrections to `path`. Returns how many were written.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_matcher_corrections_cmd(
+    game_id: String,
+    path: String,
+    pool: State<'_, sqlx::SqlitePool>,
+) -> Result<u32, AppError> {
+    let written = corrections::export_corrections(pool.inner(), &game_id, Path::new(&path)).await?;
+    Ok(u32::try_from(written).unwrap_or(u32::MAX))
+}
+
+/// Merges a shared corrections file into a game's. Returns how many were kept.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_matcher_corrections_cmd(
+    game_id: String,
+    path: String,
+    pool: State<'_, sqlx::SqlitePool>,
+    app: AppHandle,
+) -> Result<u32, AppError> {
+    let kept = corrections::import_corrections(pool.inner(), &game_id, Path::new(&path)).await?;
+    if kept > 0 {
+        MasterDbCache::invalidate(&app).await;
+    }
+    Ok(u32::try_from(kept).unwrap_or(u32::MAX))
+}
@@ -2,5 +2,8 @@ pub mod archive_cmds;
 pub mod conflict_cmds;
 pub mod deepmatch_scanner_cmds;
 pub mod disk_reconcile_cmds;
+pub mod match_explain_cmds;
+pub mod matcher_correction_cmds;
 pub mod scan_control_cmds;
+pub mod scan_review_cmds;
 pub mod watcher_cmds;
@@ -0,0 +1,104 @@
+//! The scan review modal: lazy candidate scores while the user reviews, and
+//! the commit of what they confirmed.
+
+use super::conflict_cmds::reapply_conflict_decisions;
+use crate::domain::errors::{AppError, ScannerError};
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::scanner::watcher::{SuppressionGuard, WatcherState};
+use tauri::{Manager, State};
+
+/// Phase 2: Commit user-confirmed scan results to DB.
+/// Called after the user reviews and confirms/overrides matches in the review modal.
+///
+/// # Covers: US-2.3 (Review & Organize UI — Confirm)
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)] // Tauri command boundary keeps the existing IPC payload stable.
+pub async fn commit_scan_cmd(
+    app: tauri::AppHandle,
+    state: State<'_, WatcherState>,
+    game_id: String,
+    game_name: String,
+    game_type: String,
+    mods_path: String,
+    items: Vec<crate::services::scanner::sync::ConfirmedScanItem>,
+    pool: State<'_, sqlx::SqlitePool>,
+    op_lock: State<'_, OperationLock>,
+) -> Result<crate::services::scanner::sync::SyncResult, AppError> {
+    use crate::services::scanner::sync;
+
+    let op_guard = op_lock.acquire().await?;
+    let _guard = SuppressionGuard::new(&state.suppressor);
+
+    let resource_dir = app.path().resource_dir().ok();
+
+    let keywords = app
+        .state::<crate::services::config::ConfigService>()
+        .get_settings()
+        .safe_mode
+        .keywords;
+
+    // Read before `items` moves into the request; recorded only once the
+    // commit has gone through.
+    let overrides = crate::services::scanner::master_db::corrections::scan_overrides(&items);
+
+    let result = sync::commit_scan_results(sync::CommitScanRequest {
+        pool: &pool,
+        game_id: &game_id,
+        game_name: &game_name,
+        game_type: &game_type,
+        mods_path: &mods_path,
+        items,
+        resource_dir: resource_dir.as_deref(),
+        safe_mode_keywords: &keywords,
+        preserve_existing_mappings: false,
+    })
+    .await?;
+
+    if crate::services::scanner::master_db::corrections::record_corrections(
+        &pool, &game_id, &overrides,
+    )
+    .await?
+        > 0
+    {
+        crate::services::scanner::master_db::MasterDbCache::invalidate(&app).await;
+    }
+
+    // A rescan can pick up a reinstalled loser; settled conflicts stay settled.
+    reapply_conflict_decisions(&app, &op_guard, &pool, &game_id, &mods_path).await;
+
+    Ok(result)
+}
+
+/// Compute percentage scores for a specific batch of candidates against a folder.
+/// Used by the Scan Review Modal to lazy-load accurate matching percentages.
+///
+/// # Covers: US-2.3 (Review & Organize UI — Lazy Scoring)
+#[tauri::command]
+#[specta::specta]
+pub async fn score_candidates_batch_cmd(
+    folder_path: String,
+    candidate_names: Vec<String>,
+    game_type: i32,
+    app: tauri::AppHandle,
+) -> Result<std::collections::HashMap<String, u8>, AppError> {
+    use crate::services::scanner::sync;
+
+    let Some(master_db) = crate::services::scanner::master_db::get_cached(&app, game_type).await?
+    else {
+        return Err(AppError::Scanner(ScannerError::PathNotFound {
+            path: format!("MasterDB for game type {}", game_type),
+        }));
+    };
+    let resource_dir = app.path().resource_dir().ok();
+    let ini_filters =
+        crate::services::scanner::master_db::ini_filters(resource_dir.as_deref(), game_type);
+    // Move the Arc, not the 5 MB behind it.
+    let res = tauri::async_runtime::spawn_blocking(move || {
+        sync::score_candidates_batch(&folder_path, &master_db, candidate_names, &ini_filters)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("batch scoring task panicked: {e}")))?;
+
+    Ok(res)
+}
@@ -11,7 +11,8 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::common::path_key::{canonical_name_key, names_equal_by_key, path_file_name_lossy};
+use crate::common::path_key::{names_equal_by_key, path_file_name_lossy};
+use crate::services::ini::ast::{parse_ini_text, SectionKind};
 
 /// The classification result for a folder.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,8 +39,12 @@ impl NodeType {
 /// File extensions that indicate 3DMigoto mod assets.
 const MOD_ASSET_EXTENSIONS: &[&str] = &["buf", "ib", "dds", "hlsl", "vb"];
 
-/// Section prefixes that indicate a valid 3DMigoto mod ini.
-const MOD_SECTION_PREFIXES: &[&str] = &["textureoverride", "shaderoverride", "resource"];
+/// Section kinds that indicate a valid 3DMigoto mod ini.
+const MOD_SECTION_KINDS: &[SectionKind] = &[
+    SectionKind::TextureOverride,
+    SectionKind::ShaderOverride,
+    SectionKind::Resource,
+];
 
 /// A folder with a root mod ini becomes a `VariantContainer` at this many
 /// ini-bearing children — or at the lower bar when its ini names subfolders.
@@ -243,41 +248,29 @@ fn has_any_mod_ini(path: &Path) -> bool {
 ///
 /// Returns: (has_mod_section, referenced_subfolder_names)
 fn scan_ini_content(content: &str) -> (bool, Vec<String>) {
-    let mut has_mod_section = false;
-    let mut referenced_subs: Vec<String> = Vec::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
+    let ast = parse_ini_text(content);
+    let has_mod_section = ast
+        .sections
+        .iter()
+        .any(|section| MOD_SECTION_KINDS.contains(&section.kind));
 
-        // Check section headers
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            let section = &trimmed[1..trimmed.len() - 1];
-            let lower = canonical_name_key(section);
-            if MOD_SECTION_PREFIXES.iter().any(|p| lower.starts_with(p)) {
-                has_mod_section = true;
-            }
+    let mut referenced_subs: Vec<String> = Vec::new();
+    for assignment in ast.assignments() {
+        if !assignment.key_is("filename") {
             continue;
         }
 
-        // Check filename= references for subfolder detection
-        if let Some((key, value)) = trimmed.split_once('=') {
-            if !key.trim().eq_ignore_ascii_case("filename") {
-                continue;
-            }
-
-            let val = value.trim();
-            // Extract first path component (subfolder name)
-            if let Some(sub) = val.split(['/', '\\']).next() {
-                let sub = sub.trim();
-                if !sub.is_empty()
-                    && !sub.contains('.')
-                    && !sub.starts_with('$')
-                    && !referenced_subs
-                        .iter()
-                        .any(|item| names_equal_by_key(item, sub))
-                {
-                    referenced_subs.push(sub.to_string());
-                }
+        // Extract first path component (subfolder name)
+        if let Some(sub) = assignment.value.split(['/', '\\']).next() {
+            let sub = sub.trim();
+            if !sub.is_empty()
+                && !sub.contains('.')
+                && !sub.starts_with('$')
+                && !referenced_subs
+                    .iter()
+                    .any(|item| names_equal_by_key(item, sub))
+            {
+                referenced_subs.push(sub.to_string());
             }
         }
     }
@@ -0,0 +1,68 @@
+//! Collection bundles: portable loadouts shared between machines.
+
+use serde::{Deserialize, Serialize};
+
+use super::CollectionSummary;
+
+/// Where a bundled mod was published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct BundleSource {
+    /// GameBanana item type, e.g. `Mod`.
+    pub item_type: String,
+    pub item_id: u32,
+}
+
+/// One mod of a collection bundle, described without machine-specific paths.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BundleMember {
+    /// MasterDB entry key of the mod's object, when it was matched.
+    #[serde(default)]
+    pub object_entry_key: Option<String>,
+    #[serde(default)]
+    pub object_name: Option<String>,
+    pub display_name: String,
+    /// Path under the mods root, `/`-separated, without `DISABLED` prefixes.
+    pub relative_path: String,
+    /// Hash over the resource hashes the mod's INIs override. Survives a
+    /// rename or a move to another object folder.
+    #[serde(default)]
+    pub content_fingerprint: Option<String>,
+    #[serde(default)]
+    pub source: Option<BundleSource>,
+}
+
+/// How a bundle member was found in the local library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleMatch {
+    Path,
+    Fingerprint,
+    /// Same object and display name.
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ResolvedBundleMember {
+    pub member: BundleMember,
+    pub mod_id: String,
+    pub matched_by: BundleMatch,
+}
+
+/// A bundle's members resolved against the local library.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct BundleResolution {
+    pub name: String,
+    pub resolved: Vec<ResolvedBundleMember>,
+    /// Members no local mod stands in for.
+    pub missing: Vec<BundleMember>,
+    /// Members whose content matches several local mods, left out rather
+    /// than guessed.
+    pub ambiguous: Vec<BundleMember>,
+}
+
+/// A collection created from a bundle, and what of it could not be found.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct BundleImportResult {
+    pub collection: CollectionSummary,
+    pub resolution: BundleResolution,
+}
@@ -0,0 +1,80 @@
+//! Collection diff: what one loadout changes relative to another.
+
+use serde::{Deserialize, Serialize};
+
+use super::{PreviewTreeNode, ProjectedActiveRoot};
+
+/// One side of a collection comparison.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum CollectionDiffSource {
+    /// The live runtime state, as `CorridorSnapshot.current_mods` shows it.
+    Current,
+    Collection {
+        collection_id: String,
+    },
+}
+
+/// An object whose active root changed from one mod to another.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct RootVariantChange {
+    pub from: ProjectedActiveRoot,
+    pub to: ProjectedActiveRoot,
+}
+
+/// How one object differs between two projected states.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ObjectDiff {
+    pub object_id: String,
+    pub display_name: String,
+    /// `None` when the object is not part of that side.
+    pub base_enabled: Option<bool>,
+    pub target_enabled: Option<bool>,
+    pub added: Vec<ProjectedActiveRoot>,
+    pub removed: Vec<ProjectedActiveRoot>,
+    pub changed: Vec<RootVariantChange>,
+}
+
+/// What switching from `base` to `target` would change, per object.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CollectionDiff {
+    pub base_name: String,
+    pub target_name: String,
+    /// Only objects that differ, ordered by name.
+    pub objects: Vec<ObjectDiff>,
+    pub base_tree_nodes: Vec<PreviewTreeNode>,
+    pub target_tree_nodes: Vec<PreviewTreeNode>,
+}
+
+/// Which side of a three-way compare changed an object relative to the base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreeWayStatus {
+    Ours,
+    Theirs,
+    /// Both sides made the same change.
+    Both,
+    /// Both sides changed the object, differently.
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ThreeWayObjectDiff {
+    pub object_id: String,
+    pub display_name: String,
+    pub status: ThreeWayStatus,
+    /// Base → ours, when ours changed the object.
+    pub ours: Option<ObjectDiff>,
+    /// Base → theirs, when theirs changed the object.
+    pub theirs: Option<ObjectDiff>,
+}
+
+/// Two loadouts compared against the one they both started from.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ThreeWayCollectionDiff {
+    pub base_name: String,
+    pub ours_name: String,
+    pub theirs_name: String,
+    /// Only objects either side changed, ordered by name.
+    pub objects: Vec<ThreeWayObjectDiff>,
+}
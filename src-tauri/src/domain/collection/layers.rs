@@ -0,0 +1,37 @@
+//! Layered collections: a parent loadout plus per-collection overlays.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, specta::Type)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayTargetKind {
+    Object,
+    Mod,
+}
+
+/// One change a layered collection makes to its parent's members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow, specta::Type)]
+pub struct CollectionOverlay {
+    pub target_kind: OverlayTargetKind,
+    /// Object id or mod id, per `target_kind`.
+    pub target_key: String,
+    pub is_enabled: bool,
+}
+
+/// A collection's parent and overlays. No parent means a plain collection.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct CollectionLayers {
+    pub parent_collection_id: Option<String>,
+    pub overlays: Vec<CollectionOverlay>,
+}
+
+/// Input for making a collection layered, or plain again.
+#[derive(Debug, Clone, Deserialize, specta::Type)]
+pub struct SetCollectionLayersInput {
+    pub id: String,
+    pub game_id: String,
+    pub parent_collection_id: Option<String>,
+    #[serde(default)]
+    pub overlays: Vec<CollectionOverlay>,
+}
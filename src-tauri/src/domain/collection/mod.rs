@@ -2,6 +2,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::domain::workspace::WorkspacePathRewrite;
 
+mod bundle;
+mod diff;
+mod layers;
+
+pub use bundle::*;
+pub use diff::*;
+pub use layers::*;
+
 // ---------------------------------------------------------------------------
 // Collection — A named loadout snapshot
 // ---------------------------------------------------------------------------
@@ -42,6 +50,9 @@ pub struct Collection {
     pub display_mod_count: i32,
     pub created_at: String,
     pub updated_at: String,
+    /// Set on a layered collection: its members are this parent's with its
+    /// overlays applied.
+    pub parent_collection_id: Option<String>,
 }
 
 /// Summary returned in list views.
@@ -55,6 +66,7 @@ pub struct CollectionSummary {
     pub signature: Option<String>,
     pub updated_at: String,
     pub mod_count: i32,
+    pub parent_collection_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -172,6 +184,23 @@ pub struct CollectionPreview {
     pub projected_state: ProjectedCollectionState,
 }
 
+/// Which part of the runtime an apply converges to the collection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum ApplyScope {
+    /// The whole corridor: mods the collection does not have are disabled.
+    #[default]
+    Full,
+    /// Only the listed objects and every object of the listed types, e.g.
+    /// `Weapon`. Mods of other objects are left as they are.
+    Objects {
+        #[serde(default)]
+        object_ids: Vec<String>,
+        #[serde(default)]
+        object_types: Vec<String>,
+    },
+}
+
 /// Result of applying a collection.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ApplyResult {
@@ -229,6 +258,8 @@ impl CollectionReferenceImpact {
 pub enum CreateCollectionMode {
     SaveCurrentState,
     CloneSnapshot,
+    /// The mods named by `mod_ids`, whatever their current state.
+    SelectedMods,
 }
 
 #[derive(Debug, Clone, Deserialize, specta::Type)]
@@ -237,6 +268,8 @@ pub struct CreateCollectionInput {
     pub name: String,
     pub save_mode: Option<CreateCollectionMode>,
     pub source_collection_id: Option<String>,
+    #[serde(default)]
+    pub mod_ids: Option<Vec<String>>,
 }
 
 /// Input for updating an existing collection.
@@ -274,3 +307,11 @@ pub struct ApplyProgressSnapshot {
     pub final_mode: Option<String>,
     pub success: bool,
 }
+
+/// A 3DMigoto `persist` variable value a collection restores on apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CollectionUserVar {
+    /// Lowercased `d3dx_user.ini` key, e.g. `$\mods\raiden\mod.ini\swapvar`.
+    pub var_key: String,
+    pub value: String,
+}
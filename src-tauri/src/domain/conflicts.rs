@@ -2,6 +2,56 @@
 
 use serde::{Deserialize, Serialize};
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, specta::Type,
+)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    ResourceHash,
+    ShaderHash,
+    ShaderReplacement,
+}
+
+/// What a piece of conflict evidence belongs to.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, specta::Type,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictOwner {
+    /// A mod folder, which the user can disable or demote.
+    #[default]
+    Mod,
+    /// A loose file in the game's own `ShaderFixes/`, which 3DMigoto always
+    /// loads and no resolution touches.
+    GameShaderFixes,
+}
+
+/// How a hash conflict was settled in favour of one mod.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, specta::Type)]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolutionStrategy {
+    /// The losing mods were disabled.
+    DisableLosers,
+    /// The losing override sections got a lower `match_priority`.
+    MatchPriority,
+}
+
+/// A stored winner decision for one conflicting hash. Mods are referenced by
+/// their stable ids, so the decision outlives rescans and toggles.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, sqlx::FromRow)]
+pub struct ConflictResolution {
+    pub game_id: String,
+    pub hash: String,
+    pub kind: ConflictKind,
+    pub strategy: ConflictResolutionStrategy,
+    pub winner_mod_id: String,
+    #[sqlx(json)]
+    pub loser_mod_ids: Vec<String>,
+    pub created_at: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, sqlx::FromRow)]
 pub struct IgnoredConflict {
     pub id: String,
@@ -26,3 +76,17 @@ pub struct WhitelistEntry {
     pub reason: String,
     pub ignored_at: String,
 }
+
+/// One mod INI section carrying a resource hash, read from `mod_hash_index`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, sqlx::FromRow)]
+pub struct HashIndexHit {
+    pub mod_id: String,
+    pub actual_name: String,
+    pub folder_path: String,
+    pub enabled: bool,
+    pub section_name: String,
+    /// INI path relative to the mod folder, `/`-separated.
+    pub file_path: String,
+    /// Other mods in the game whose INIs carry the same hash.
+    pub collision_count: i64,
+}
@@ -98,6 +98,12 @@ impl From<std::io::Error> for CollectionError {
     }
 }
 
+impl From<tokio::task::JoinError> for CollectionError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        Self::Io(format!("background collection task failed: {error}"))
+    }
+}
+
 /// Errors specific to Metadata operations.
 #[derive(Debug, Clone, Error, Serialize, Deserialize, specta::Type)]
 pub enum MetadataError {
@@ -41,6 +41,8 @@ pub struct PipelineTask {
     pub task_type: String, // 'apply_collection' (legacy rows may contain 'switch_corridor')
     pub status: TaskStatus,
     pub target_id: Option<String>,
+    /// JSON the task is resumed with, e.g. an apply's `ApplyScope`.
+    pub payload: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
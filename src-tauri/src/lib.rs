@@ -22,6 +22,7 @@ macro_rules! emmm_collect_commands {
             commands::app::app_cmds::check_config_status,
             commands::app::dashboard_cmds::get_dashboard_stats,
             commands::app::dashboard_cmds::get_active_keybindings,
+            commands::app::dashboard_cmds::get_keybind_collisions,
             commands::app::workspace_cmds::get_workspace_view_model,
             commands::app::workspace_cmds::execute_workspace_switch,
             commands::app::app_cmds::get_logs,
@@ -41,6 +42,13 @@ macro_rules! emmm_collect_commands {
             commands::objects::master_db_cmds::search_master_db,
             commands::objects::master_db_cmds::match_object_with_db,
             commands::objects::master_db_cmds::pin_object,
+            commands::objects::master_db_cmds::plan_master_db_rematch_cmd,
+            commands::objects::master_db_cmds::mark_master_db_rematch_reviewed_cmd,
+            commands::objects::master_db_cmds::list_master_db_overlay_cmd,
+            commands::objects::master_db_cmds::save_master_db_overlay_entry_cmd,
+            commands::objects::master_db_cmds::delete_master_db_overlay_entry_cmd,
+            commands::objects::master_db_cmds::export_master_db_overlay_cmd,
+            commands::objects::master_db_cmds::import_master_db_overlay_cmd,
             commands::scanner::scan_control_cmds::cancel_scan_cmd,
             commands::scanner::archive_cmds::detect_archives_cmd,
             commands::scanner::archive_cmds::extract_archive_cmd,
@@ -49,6 +57,7 @@ macro_rules! emmm_collect_commands {
             commands::scanner::archive_cmds::abort_extraction_cmd,
             commands::scanner::conflict_cmds::detect_conflicts_cmd,
             commands::scanner::conflict_cmds::detect_conflicts_in_folder_cmd,
+            commands::scanner::conflict_cmds::find_mods_by_hash,
             commands::scanner::watcher_cmds::set_watcher_suppression,
             commands::folder_grid::get_mod_thumbnail,
             commands::mods::mod_core_cmds::open_in_explorer,
@@ -58,6 +67,8 @@ macro_rules! emmm_collect_commands {
             commands::mods::conflict_cmds::ignore_object_conflict,
             commands::mods::conflict_cmds::revoke_object_conflict,
             commands::mods::conflict_cmds::list_ignored_object_conflicts,
+            commands::mods::conflict_cmds::resolve_hash_conflict,
+            commands::mods::conflict_cmds::clear_hash_conflict_resolution,
             commands::mods::mod_core_cmds::rename_mod_folder,
             commands::mods::mod_import_cmds::import_mods_from_paths,
             commands::mods::mod_import_cmds::ingest_dropped_folders,
@@ -86,6 +97,12 @@ macro_rules! emmm_collect_commands {
             commands::mods::preview_cmds::list_mod_ini_files,
             commands::mods::preview_cmds::read_mod_ini,
             commands::mods::preview_cmds::write_mod_ini,
+            commands::mods::preview_cmds::edit_mod_ini,
+            commands::mods::preview_cmds::lint_mod_ini,
+            commands::mods::keybind_cmds::remap_mod_keybinds,
+            commands::mods::keybind_cmds::revert_mod_keybind_remap,
+            commands::app::keyviewer_cmds::learn_object_hashes_cmd,
+            commands::app::keyviewer_cmds::reset_learned_hashes_cmd,
             commands::mods::preview_cmds::list_mod_preview_images,
             commands::mods::preview_cmds::save_mod_preview_image,
             commands::mods::preview_cmds::remove_mod_preview_image,
@@ -114,11 +131,19 @@ macro_rules! emmm_collect_commands {
             commands::collections::cmds::apply_collection,
             commands::collections::cmds::update_collection,
             commands::collections::cmds::replace_collection_with_current_state,
+            commands::collections::cmds::capture_collection_user_vars,
+            commands::collections::layer_cmds::get_collection_layers,
+            commands::collections::layer_cmds::set_collection_layers,
             commands::collections::cmds::delete_collection,
             commands::collections::cmds::app_startup_check,
             commands::collections::cmds::resolve_recovery_task,
             commands::collections::cmds::get_collection_preview,
             commands::collections::cmds::preview_apply_collection,
+            commands::collections::diff_cmds::diff_collections,
+            commands::collections::diff_cmds::compare_collections_three_way,
+            commands::collections::bundle_cmds::export_collection_bundle,
+            commands::collections::bundle_cmds::resolve_collection_bundle,
+            commands::collections::bundle_cmds::import_collection_bundle,
             commands::collections::cmds::set_pin,
             commands::collections::cmds::verify_pin,
             commands::collections::cmds::get_pin_status,
@@ -126,9 +151,14 @@ macro_rules! emmm_collect_commands {
             commands::scanner::deepmatch_scanner_cmds::deepmatch_preview_cmd,
             commands::scanner::deepmatch_scanner_cmds::deepmatch_preview_for_objects_cmd,
             commands::scanner::deepmatch_scanner_cmds::rename_staged_folder_cmd,
-            commands::scanner::deepmatch_scanner_cmds::commit_scan_cmd,
-            commands::scanner::deepmatch_scanner_cmds::score_candidates_batch_cmd,
+            commands::scanner::scan_review_cmds::commit_scan_cmd,
+            commands::scanner::scan_review_cmds::score_candidates_batch_cmd,
+            commands::scanner::match_explain_cmds::explain_match_cmd,
             commands::scanner::deepmatch_scanner_cmds::list_folder_entries_cmd,
+            commands::scanner::matcher_correction_cmds::list_matcher_corrections_cmd,
+            commands::scanner::matcher_correction_cmds::delete_matcher_correction_cmd,
+            commands::scanner::matcher_correction_cmds::export_matcher_corrections_cmd,
+            commands::scanner::matcher_correction_cmds::import_matcher_corrections_cmd,
             commands::scanner::disk_reconcile_cmds::reconcile_disk_state_cmd,
             commands::scanner::watcher_cmds::start_watcher,
             commands::scanner::watcher_cmds::stop_watcher,
@@ -136,6 +166,8 @@ macro_rules! emmm_collect_commands {
             commands::duplicates::dup_scan_cmds::dup_scan_cancel,
             commands::duplicates::dup_scan_cmds::dup_scan_get_report,
             commands::duplicates::dup_resolve_cmds::dup_resolve_batch,
+            commands::duplicates::dup_blob_cmds::dup_blob_report,
+            commands::duplicates::dup_blob_cmds::dup_hardlink_all,
             commands::duplicates::dup_ignore_cmds::get_ignored_pairs,
             commands::duplicates::dup_ignore_cmds::remove_ignored_pair,
             commands::app::update_cmds::check_metadata_update,
@@ -220,6 +252,11 @@ pub fn run() {
 
             if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
                 services::images::thumbnail_cache::ThumbnailCache::init(&app_data_dir);
+                app.manage(std::sync::Arc::new(
+                    services::scanner::deep_matcher::analysis::ai_rerank::AiRerankCache::open(
+                        app_data_dir.join("ai_rerank_cache.jsonl"),
+                    ),
+                ));
 
                 #[cfg(desktop)]
                 app.manage(services::bootstrap::init_pool(&app_data_dir));
@@ -4,12 +4,15 @@ use std::path::PathBuf;
 use sqlx::SqlitePool;
 
 use crate::common::path_key::folder_path_key;
-use crate::domain::collection::{ApplyResult, Collection, CollectionMod, CollectionObject};
+use crate::domain::collection::{
+    ApplyResult, ApplyScope, Collection, CollectionMod, CollectionObject,
+};
 use crate::domain::errors::CollectionError;
 use crate::domain::workspace::WorkspacePathRewrite;
 use crate::services::app::post_apply::PostApplyContext;
 use crate::services::collection_service::ApplyCollectionRequest;
 use crate::services::config::AppSettings;
+use crate::services::fs_utils::operation_lock::OpGuard;
 use crate::services::scanner::watcher::WatcherSuppressor;
 
 // ---------------------------------------------------------------------------
@@ -28,8 +31,12 @@ pub struct ApplyContext {
     pub settings: AppSettings,
     /// See `ApplyCollectionRequest::reconcile_lock`.
     pub reconcile_lock: Option<std::sync::Arc<tokio::sync::Mutex<()>>>,
+    pub scope: ApplyScope,
 
     pub collection: Option<Collection>,
+    /// The objects a scoped apply may touch, resolved by `validate_corridor`.
+    /// `None` for a full apply.
+    pub scoped_object_ids: Option<HashSet<String>>,
     pub target_mods: Vec<CollectionMod>,
     pub target_objects: Vec<CollectionObject>,
     pub currently_enabled_path_keys: HashSet<String>,
@@ -63,6 +70,13 @@ impl ApplyContext {
             })
     }
 
+    /// Whether the apply converges `object_id`'s mods.
+    pub fn in_scope(&self, object_id: &str) -> bool {
+        self.scoped_object_ids
+            .as_ref()
+            .is_none_or(|object_ids| object_ids.contains(object_id))
+    }
+
     /// Seed a context from the caller's request. Takes the borrowed request
     /// type directly — an owned intermediate struct would just re-declare the
     /// same eight fields a third time.
@@ -77,7 +91,9 @@ impl ApplyContext {
             ignore_missing: request.ignore_missing,
             settings: request.settings,
             reconcile_lock: request.reconcile_lock,
+            scope: request.scope,
             collection: None,
+            scoped_object_ids: None,
             target_mods: Vec::new(),
             target_objects: Vec::new(),
             currently_enabled_path_keys: HashSet::new(),
@@ -95,20 +111,29 @@ impl ApplyContext {
 
 /// Disk Reconcile must not perform these physical collection renames during a
 /// passive startup or watcher refresh.
-pub async fn execute(ctx: &mut ApplyContext) -> Result<ApplyResult, CollectionError> {
+pub async fn execute(
+    ctx: &mut ApplyContext,
+    op_guard: &OpGuard,
+) -> Result<ApplyResult, CollectionError> {
     crate::services::apply_progress_service::start(&ctx.game_id, ctx.is_safe);
     let task_id = uuid::Uuid::new_v4().to_string();
+    // Recovery re-runs the apply with the same scope; a full apply needs none.
+    let payload = match &ctx.scope {
+        ApplyScope::Full => None,
+        scope => serde_json::to_string(scope).ok(),
+    };
     crate::repo::task_repo::create_task(
         &ctx.pool,
         &task_id,
         &ctx.game_id,
         "apply_collection",
         Some(&ctx.collection_id),
+        payload.as_deref(),
     )
     .await
     .map_err(|e| CollectionError::Db(e.to_string()))?;
 
-    let apply_outcome = execute_inner(ctx).await;
+    let apply_outcome = execute_inner(ctx, op_guard).await;
     update_recovery_task(ctx, &task_id, apply_outcome.is_ok()).await?;
     if apply_outcome.is_err() {
         finish_failed_apply(ctx);
@@ -153,7 +178,10 @@ fn finish_failed_apply(ctx: &ApplyContext) {
     );
 }
 
-async fn execute_inner(ctx: &mut ApplyContext) -> Result<ApplyResult, CollectionError> {
+async fn execute_inner(
+    ctx: &mut ApplyContext,
+    op_guard: &OpGuard,
+) -> Result<ApplyResult, CollectionError> {
     crate::services::apply_progress_service::update(
         &ctx.game_id,
         ctx.is_safe,
@@ -201,6 +229,8 @@ async fn execute_inner(ctx: &mut ApplyContext) -> Result<ApplyResult, Collection
         None,
     );
     super::steps::batch_rename::rename(ctx).await?;
+    super::steps::restore_user_vars::restore(ctx).await;
+    super::steps::enforce_conflict_resolutions::enforce(ctx, op_guard).await;
 
     crate::services::apply_progress_service::update(
         &ctx.game_id,
@@ -219,13 +249,13 @@ async fn execute_inner(ctx: &mut ApplyContext) -> Result<ApplyResult, Collection
         is_safe: ctx.is_safe,
         mods_path: ctx.mods_path.clone(),
         hotkeys: ctx.settings.hotkeys.clone(),
-        status_fields: ctx.skipped_missing_paths.is_empty().then(|| {
-            crate::services::keyviewer::generator::StatusFields {
+        // A partial runtime is not the preset the status overlay would name.
+        status_fields: (ctx.skipped_missing_paths.is_empty() && ctx.scoped_object_ids.is_none())
+            .then(|| crate::services::keyviewer::generator::StatusFields {
                 safe_mode: ctx.is_safe,
                 preset_name: ctx.final_state_name.clone(),
                 ..Default::default()
-            }
-        }),
+            }),
     };
     if let Err(error) = crate::services::app::post_apply::run_post_apply_tasks(post_ctx).await {
         log::warn!("apply_pipeline[post_apply]: {error}");
@@ -287,7 +317,10 @@ mod tests {
         let test_db = crate::test_utils::init_test_db().await;
         let pool = test_db.pool.clone();
         pool.close().await;
+        let op_lock = crate::services::fs_utils::operation_lock::OperationLock::new();
+        let op_guard = op_lock.acquire().await.unwrap();
         let mut apply_context = ApplyContext::new(ApplyCollectionRequest {
+            op_guard: &op_guard,
             pool: &pool,
             game_id: "game-1",
             collection_id: "collection-1",
@@ -297,6 +330,7 @@ mod tests {
             ignore_missing: false,
             settings: AppSettings::default(),
             reconcile_lock: None,
+            scope: ApplyScope::Full,
         });
         apply_context.final_state_name = Some("Preset".to_string());
 
@@ -0,0 +1,52 @@
+use crate::pipeline::apply_pipeline::ApplyContext;
+use crate::services::fs_utils::operation_lock::OpGuard;
+use crate::services::scanner::conflict::enforce::enforce_resolutions;
+
+/// Post-Rename Step: carry out the stored conflict decisions again, since the
+/// collection may have re-enabled a mod the user disabled to settle one.
+///
+/// A failure is a warning: the collection itself is in place either way.
+pub async fn enforce(ctx: &mut ApplyContext, op_guard: &OpGuard) {
+    let _guard = crate::services::scanner::watcher::SuppressionGuard::new(&ctx.suppressor);
+    let changed_paths =
+        match enforce_resolutions(op_guard, &ctx.pool, &ctx.game_id, &ctx.mods_path).await {
+            Ok(changed_paths) => changed_paths,
+            Err(error) => {
+                log::warn!("apply_pipeline[enforce_conflict_resolutions]: {error}");
+                ctx.warnings.push(format!(
+                    "Stored conflict decisions were not re-applied: {error}"
+                ));
+                return;
+            }
+        };
+    if changed_paths.is_empty() {
+        return;
+    }
+
+    log::info!(
+        "apply_pipeline[enforce_conflict_resolutions]: re-applied on {} path(s)",
+        changed_paths.len()
+    );
+    let _reconcile_lock = match ctx.reconcile_lock.as_ref() {
+        Some(lock) => Some(lock.clone().lock_owned().await),
+        None => None,
+    };
+    if let Err(error) = crate::services::disk_reconcile::reconcile::reconcile_disk_projection(
+        crate::services::disk_reconcile::reconcile::ReconcileDiskProjectionRequest {
+            pool: &ctx.pool,
+            game_id: &ctx.game_id,
+            mods_path: &ctx.mods_path,
+            safe_mode_keywords: &ctx.settings.safe_mode.keywords,
+            reason: &crate::services::disk_reconcile::types::DiskReconcileReason::InternalMutation,
+            changed_paths: &changed_paths,
+            force_full: false,
+            watcher_events: None,
+        },
+    )
+    .await
+    {
+        ctx.warnings.push(format!(
+            "Re-applied conflict decisions were not reconciled: {error}"
+        ));
+    }
+}
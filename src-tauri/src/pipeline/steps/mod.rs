@@ -1,5 +1,7 @@
 pub mod batch_rename;
+pub mod enforce_conflict_resolutions;
 pub mod resolve_current_state;
 pub mod resolve_target;
+pub mod restore_user_vars;
 pub mod validate_corridor;
 pub mod validate_paths;
@@ -1,7 +1,8 @@
 use crate::domain::errors::CollectionError;
 use crate::pipeline::apply_pipeline::ApplyContext;
 
-/// Resolve currently-enabled mod path keys for the whole runtime.
+/// Resolve currently-enabled mod path keys for the whole runtime, or for the
+/// in-scope objects of a scoped apply so the rest is never disabled.
 pub async fn resolve(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
     let (mods, objects) =
         crate::services::collection_service::load_live_runtime_state(&ctx.pool, &ctx.game_id)
@@ -11,6 +12,7 @@ pub async fn resolve(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
     ctx.currently_enabled_path_keys = projected_state
         .active_roots
         .into_iter()
+        .filter(|root| ctx.in_scope(&root.object_id))
         .map(|root| root.root_key)
         .collect();
 
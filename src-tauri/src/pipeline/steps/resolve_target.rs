@@ -1,7 +1,8 @@
 use crate::domain::errors::CollectionError;
 use crate::pipeline::apply_pipeline::ApplyContext;
 
-/// Load the target collection's members.
+/// Load the target collection's members, only those of in-scope objects for a
+/// scoped apply.
 pub async fn resolve(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
     let collection = ctx.collection()?.clone();
     let mods_path = ctx.mods_path.to_string_lossy().to_string();
@@ -17,8 +18,14 @@ pub async fn resolve(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
             collection.is_safe,
             &snapshot,
         );
-    ctx.target_mods = mods;
-    ctx.target_objects = objects;
+    ctx.target_mods = mods
+        .into_iter()
+        .filter(|member| ctx.in_scope(&member.object_id))
+        .collect();
+    ctx.target_objects = objects
+        .into_iter()
+        .filter(|object| ctx.in_scope(&object.object_id))
+        .collect();
 
     log::info!(
         "apply_pipeline[resolve_target]: loaded {} mods and {} objects for collection '{}'",
@@ -0,0 +1,25 @@
+use crate::pipeline::apply_pipeline::ApplyContext;
+use crate::services::collection_service::restore_user_vars;
+
+/// Post-Rename Step: write the collection's saved `d3dx_user.ini` values back
+/// for the mods it enabled, so swap toggles return with the folders.
+///
+/// Only `target_mods` are restored, which a scoped apply has already narrowed
+/// to its objects. A failure is a warning: the mods are in place either way.
+pub async fn restore(ctx: &mut ApplyContext) {
+    let Ok(collection) = ctx.collection() else {
+        return;
+    };
+    let restored = restore_user_vars(&ctx.pool, collection, &ctx.mods_path, &ctx.target_mods).await;
+    match restored {
+        Ok(0) => {}
+        Ok(changed) => log::info!(
+            "apply_pipeline[restore_user_vars]: restored {changed} d3dx_user.ini value(s)"
+        ),
+        Err(error) => {
+            log::warn!("apply_pipeline[restore_user_vars]: {error}");
+            ctx.warnings
+                .push(format!("Mod toggle values were not restored: {error}"));
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use crate::domain::errors::CollectionError;
 use crate::pipeline::apply_pipeline::{corridor_label, ApplyContext};
-use crate::services::collection_service::{require_collection, require_game_match};
+use crate::services::collection_service::{
+    require_collection, require_game_match, resolve_apply_scope,
+};
 
-/// Load the collection once and validate it belongs to the requested game.
+/// Load the collection once, validate it belongs to the requested game, and
+/// resolve a scoped apply to the objects it may touch.
 pub async fn validate(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
     let collection = require_collection(&ctx.pool, &ctx.collection_id).await?;
     require_game_match(&collection, &ctx.game_id)?;
@@ -19,10 +22,16 @@ pub async fn validate(ctx: &mut ApplyContext) -> Result<(), CollectionError> {
         )));
     }
 
+    ctx.scoped_object_ids = resolve_apply_scope(&ctx.pool, &ctx.game_id, &ctx.scope).await?;
+
     log::info!(
-        "apply_pipeline[validate]: collection '{}' validated for game '{}'",
+        "apply_pipeline[validate]: collection '{}' validated for game '{}'{}",
         collection.name,
-        ctx.game_id
+        ctx.game_id,
+        ctx.scoped_object_ids
+            .as_ref()
+            .map(|object_ids| format!(", scoped to {} object(s)", object_ids.len()))
+            .unwrap_or_default()
     );
 
     ctx.collection = Some(collection);
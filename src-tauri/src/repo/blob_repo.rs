@@ -0,0 +1,87 @@
+use sqlx::SqlitePool;
+
+/// One hashed payload file, as stored in `file_blobs`.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct BlobRow {
+    pub file_path: String,
+    pub game_id: String,
+    pub size_bytes: i64,
+    pub mtime_ns: i64,
+    pub content_hash: String,
+}
+
+/// Every stored blob of one game.
+pub async fn list_game_blobs(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<BlobRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT file_path, game_id, size_bytes, mtime_ns, content_hash
+         FROM file_blobs WHERE game_id = ?",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Every blob whose content is stored at least twice, across all games,
+/// grouped by hash and ordered by path inside each group.
+pub async fn list_duplicate_blobs(pool: &SqlitePool) -> Result<Vec<BlobRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT file_path, game_id, size_bytes, mtime_ns, content_hash
+         FROM file_blobs
+         WHERE content_hash IN (
+             SELECT content_hash FROM file_blobs
+             GROUP BY content_hash HAVING COUNT(*) > 1
+         )
+         ORDER BY content_hash, file_path",
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Inserts or refreshes blobs, atomically.
+pub async fn upsert_blobs(pool: &SqlitePool, rows: &[BlobRow]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO file_blobs (file_path, game_id, size_bytes, mtime_ns, content_hash, hashed_at)
+             VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+             ON CONFLICT(file_path) DO UPDATE SET
+                 game_id = excluded.game_id,
+                 size_bytes = excluded.size_bytes,
+                 mtime_ns = excluded.mtime_ns,
+                 content_hash = excluded.content_hash,
+                 hashed_at = excluded.hashed_at",
+        )
+        .bind(&row.file_path)
+        .bind(&row.game_id)
+        .bind(row.size_bytes)
+        .bind(row.mtime_ns)
+        .bind(&row.content_hash)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Drops blobs whose files are gone, atomically.
+pub async fn delete_blobs(pool: &SqlitePool, file_paths: &[String]) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for file_path in file_paths {
+        sqlx::query("DELETE FROM file_blobs WHERE file_path = ?")
+            .bind(file_path)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await
+}
+
+/// Drops every blob of a game, for a game whose mods folder is gone.
+pub async fn clear_game_blobs(pool: &SqlitePool, game_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM file_blobs WHERE game_id = ?")
+        .bind(game_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -26,7 +26,8 @@ pub async fn list_for_game(
 ) -> Result<Vec<Collection>, CollectionError> {
     let rows = sqlx::query(
         r#"SELECT id, game_id, name, name_key, is_safe, is_unsaved, is_last_unsaved,
-                  last_active, signature, root_count, display_mod_count, created_at, updated_at
+                  last_active, signature, root_count, display_mod_count, created_at, updated_at,
+                  parent_collection_id
         FROM collections
         WHERE game_id = ?
         ORDER BY is_unsaved DESC, name ASC"#,
@@ -46,7 +47,7 @@ pub async fn list_named_for_corridor(
     let rows = sqlx::query(
         r#"SELECT c.id, c.game_id, c.name, c.name_key, c.is_safe, c.is_unsaved, c.is_last_unsaved,
                   c.last_active, c.signature, c.root_count, c.display_mod_count,
-                  c.created_at, c.updated_at
+                  c.created_at, c.updated_at, c.parent_collection_id
         FROM collections c
         WHERE c.game_id = ? AND c.is_safe = ? AND c.is_unsaved = 0
         ORDER BY c.name ASC"#,
@@ -63,7 +64,8 @@ pub async fn list_named_for_corridor(
 pub async fn get_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Collection>, CollectionError> {
     let row = sqlx::query(
         r#"SELECT id, game_id, name, name_key, is_safe, is_unsaved, is_last_unsaved,
-                  last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at
+                  last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at,
+                  parent_collection_id
         FROM collections
         WHERE id = ?"#,
     )
@@ -159,7 +161,8 @@ pub async fn find_unsaved_for_corridor(
     let row = if let Some(excluded_id) = exclude_id {
         sqlx::query(
             r#"SELECT id, game_id, name, name_key, is_safe, is_unsaved, is_last_unsaved,
-                      last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at
+                      last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at,
+                      parent_collection_id
                FROM collections
                WHERE game_id = ? AND is_safe = ? AND is_unsaved = 1 AND id != ?
                ORDER BY updated_at DESC
@@ -173,7 +176,8 @@ pub async fn find_unsaved_for_corridor(
     } else {
         sqlx::query(
             r#"SELECT id, game_id, name, name_key, is_safe, is_unsaved, is_last_unsaved,
-                      last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at
+                      last_active, snapshot_json, signature, root_count, display_mod_count, created_at, updated_at,
+                      parent_collection_id
                FROM collections
                WHERE game_id = ? AND is_safe = ? AND is_unsaved = 1
                ORDER BY updated_at DESC
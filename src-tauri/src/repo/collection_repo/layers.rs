@@ -0,0 +1,91 @@
+//! Layered-collection rows: a collection's parent pointer and its overlays.
+
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::domain::collection::CollectionOverlay;
+use crate::domain::errors::CollectionError;
+
+pub async fn get_overlays(
+    pool: &SqlitePool,
+    collection_id: &str,
+) -> Result<Vec<CollectionOverlay>, CollectionError> {
+    Ok(sqlx::query_as(
+        r#"SELECT target_kind, target_key, is_enabled
+           FROM collection_overlays
+           WHERE collection_id = ?
+           ORDER BY target_kind, target_key"#,
+    )
+    .bind(collection_id)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Point `collection_id` at `parent_collection_id` and replace its overlays.
+pub async fn set_layers_tx(
+    conn: &mut SqliteConnection,
+    collection_id: &str,
+    parent_collection_id: Option<&str>,
+    overlays: &[CollectionOverlay],
+) -> Result<(), CollectionError> {
+    sqlx::query("UPDATE collections SET parent_collection_id = ? WHERE id = ?")
+        .bind(parent_collection_id)
+        .bind(collection_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("DELETE FROM collection_overlays WHERE collection_id = ?")
+        .bind(collection_id)
+        .execute(&mut *conn)
+        .await?;
+    for overlay in overlays {
+        sqlx::query(
+            r#"INSERT INTO collection_overlays (collection_id, target_kind, target_key, is_enabled)
+               VALUES (?, ?, ?, ?)
+               ON CONFLICT(collection_id, target_kind, target_key)
+               DO UPDATE SET is_enabled = excluded.is_enabled"#,
+        )
+        .bind(collection_id)
+        .bind(overlay.target_kind)
+        .bind(&overlay.target_key)
+        .bind(overlay.is_enabled)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Ids of the collections layered directly on `parent_collection_id`.
+pub async fn list_child_ids(
+    pool: &SqlitePool,
+    parent_collection_id: &str,
+) -> Result<Vec<String>, CollectionError> {
+    Ok(sqlx::query_scalar(
+        "SELECT id FROM collections WHERE parent_collection_id = ? ORDER BY name ASC, id ASC",
+    )
+    .bind(parent_collection_id)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Turn the collections layered on `parent_collection_id` into plain ones,
+/// keeping the members they last resolved to.
+pub async fn detach_children_tx(
+    conn: &mut SqliteConnection,
+    parent_collection_id: &str,
+) -> Result<(), CollectionError> {
+    sqlx::query(
+        r#"DELETE FROM collection_overlays
+           WHERE collection_id IN (
+               SELECT id FROM collections WHERE parent_collection_id = ?
+           )"#,
+    )
+    .bind(parent_collection_id)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "UPDATE collections SET parent_collection_id = NULL WHERE parent_collection_id = ?",
+    )
+    .bind(parent_collection_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
@@ -64,3 +64,104 @@ pub async fn get_live_active_mod_rows(
     };
     Ok(rows)
 }
+
+/// A mod picked by id for a new collection, with its corridor.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SelectedModRow {
+    #[sqlx(flatten)]
+    pub row: LiveActiveModRow,
+    pub is_safe: bool,
+}
+
+/// The mods of a game among `mod_ids`, enabled or not. Unknown ids and mods
+/// without an object are left out.
+pub async fn get_mod_rows_by_ids(
+    pool: &SqlitePool,
+    game_id: &str,
+    mod_ids: &[String],
+) -> Result<Vec<SelectedModRow>, CollectionError> {
+    let ids_json = serde_json::to_string(mod_ids).unwrap_or_else(|_| "[]".to_string());
+    Ok(sqlx::query_as(
+        r#"
+        SELECT
+            id as mod_id,
+            folder_path as mod_path,
+            folder_path_key as mod_path_key,
+            object_id,
+            actual_name as display_name,
+            is_safe
+        FROM mods
+        WHERE game_id = ? AND object_id IS NOT NULL
+          AND id IN (SELECT value FROM json_each(?))
+        "#,
+    )
+    .bind(game_id)
+    .bind(ids_json)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// A library mod with the MasterDB identity of its object.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LibraryModRow {
+    pub mod_id: String,
+    pub mod_path: String,
+    pub mod_path_key: String,
+    pub display_name: String,
+    pub object_id: Option<String>,
+    pub object_name: Option<String>,
+    pub object_entry_key: Option<String>,
+}
+
+/// Every mod of a game, enabled or not, ordered by path key.
+pub async fn get_library_mod_rows(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<LibraryModRow>, CollectionError> {
+    Ok(sqlx::query_as(
+        r#"
+        SELECT
+            m.id as mod_id,
+            m.folder_path as mod_path,
+            m.folder_path_key as mod_path_key,
+            m.actual_name as display_name,
+            m.object_id,
+            o.name as object_name,
+            o.matched_entry_key as object_entry_key
+        FROM mods m
+        LEFT JOIN objects o ON o.id = m.object_id
+        WHERE m.game_id = ?
+        ORDER BY m.folder_path_key
+        "#,
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Ids of a game's objects that are among `object_ids` or of one of
+/// `object_types`.
+pub async fn get_scoped_object_ids(
+    pool: &SqlitePool,
+    game_id: &str,
+    object_ids: &[String],
+    object_types: &[String],
+) -> Result<Vec<String>, CollectionError> {
+    let ids_json = serde_json::to_string(object_ids).unwrap_or_else(|_| "[]".to_string());
+    let types_json = serde_json::to_string(object_types).unwrap_or_else(|_| "[]".to_string());
+    Ok(sqlx::query_scalar(
+        r#"
+        SELECT id
+        FROM objects
+        WHERE game_id = ?
+          AND (id IN (SELECT value FROM json_each(?))
+               OR object_type IN (SELECT value FROM json_each(?)))
+        ORDER BY id
+        "#,
+    )
+    .bind(game_id)
+    .bind(ids_json)
+    .bind(types_json)
+    .fetch_all(pool)
+    .await?)
+}
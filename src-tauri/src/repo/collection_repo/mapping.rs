@@ -20,6 +20,7 @@ pub(super) fn row_to_collection(r: &sqlx::sqlite::SqliteRow) -> Collection {
         display_mod_count: r.try_get("display_mod_count").unwrap_or(0),
         created_at: r.get("created_at"),
         updated_at: r.get("updated_at"),
+        parent_collection_id: r.try_get("parent_collection_id").ok().flatten(),
     }
 }
 
@@ -51,5 +52,6 @@ pub fn to_summary(c: &Collection, active_collection_id: Option<&str>) -> Collect
         is_active: active_collection_id == Some(c.id.as_str()),
         updated_at: c.updated_at.clone(),
         mod_count: c.display_mod_count,
+        parent_collection_id: c.parent_collection_id.clone(),
     }
 }
@@ -3,15 +3,19 @@
 //! re-exported here.
 
 mod crud;
+mod layers;
 mod live;
 mod mapping;
 mod members;
 mod references;
 mod state;
+mod user_vars;
 
 pub use crud::*;
+pub use layers::*;
 pub use live::*;
 pub use mapping::*;
 pub use members::*;
 pub use references::*;
 pub use state::*;
+pub use user_vars::*;
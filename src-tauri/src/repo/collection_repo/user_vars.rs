@@ -0,0 +1,46 @@
+//! `d3dx_user.ini` variable values saved with a collection.
+
+use sqlx::{SqliteConnection, SqlitePool};
+
+use crate::domain::collection::CollectionUserVar;
+use crate::domain::errors::CollectionError;
+
+pub async fn get_user_vars(
+    pool: &SqlitePool,
+    collection_id: &str,
+) -> Result<Vec<CollectionUserVar>, CollectionError> {
+    Ok(sqlx::query_as(
+        r#"SELECT var_key, value
+           FROM collection_user_vars
+           WHERE collection_id = ?
+           ORDER BY var_key"#,
+    )
+    .bind(collection_id)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Replace every saved value of `collection_id` with `vars`.
+pub async fn replace_user_vars_tx(
+    conn: &mut SqliteConnection,
+    collection_id: &str,
+    vars: &[CollectionUserVar],
+) -> Result<(), CollectionError> {
+    sqlx::query("DELETE FROM collection_user_vars WHERE collection_id = ?")
+        .bind(collection_id)
+        .execute(&mut *conn)
+        .await?;
+    for var in vars {
+        sqlx::query(
+            r#"INSERT INTO collection_user_vars (collection_id, var_key, value)
+               VALUES (?, ?, ?)
+               ON CONFLICT(collection_id, var_key) DO UPDATE SET value = excluded.value"#,
+        )
+        .bind(collection_id)
+        .bind(&var.var_key)
+        .bind(&var.value)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
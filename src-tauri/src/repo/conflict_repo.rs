@@ -1,4 +1,6 @@
-use crate::domain::conflicts::IgnoredConflict;
+use crate::domain::conflicts::{
+    ConflictKind, ConflictResolution, ConflictResolutionStrategy, IgnoredConflict,
+};
 use sqlx::SqlitePool;
 use std::collections::HashMap;
 
@@ -114,3 +116,68 @@ pub async fn revoke_object_conflict(
         .await?;
     Ok(())
 }
+
+/// Stores (or replaces) the winner decision for one conflicting hash and
+/// returns the stored row.
+pub async fn upsert_conflict_resolution(
+    pool: &SqlitePool,
+    game_id: &str,
+    hash: &str,
+    kind: ConflictKind,
+    strategy: ConflictResolutionStrategy,
+    winner_mod_id: &str,
+    loser_mod_ids: &[String],
+) -> Result<ConflictResolution, sqlx::Error> {
+    let mut sorted_ids = loser_mod_ids.to_vec();
+    sorted_ids.sort();
+    let loser_ids_json = serde_json::to_string(&sorted_ids).unwrap_or_else(|_| "[]".to_string());
+
+    sqlx::query_as(
+        "INSERT INTO conflict_resolutions
+             (game_id, hash, kind, strategy, winner_mod_id, loser_mod_ids, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(game_id, hash, kind) DO UPDATE SET
+             strategy = excluded.strategy,
+             winner_mod_id = excluded.winner_mod_id,
+             loser_mod_ids = excluded.loser_mod_ids,
+             created_at = excluded.created_at
+         RETURNING game_id, hash, kind, strategy, winner_mod_id, loser_mod_ids, created_at",
+    )
+    .bind(game_id)
+    .bind(hash.to_ascii_lowercase())
+    .bind(kind)
+    .bind(strategy)
+    .bind(winner_mod_id)
+    .bind(loser_ids_json)
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn list_conflict_resolutions(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<ConflictResolution>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT game_id, hash, kind, strategy, winner_mod_id, loser_mod_ids, created_at
+         FROM conflict_resolutions WHERE game_id = ?",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Forgets a winner decision. Whatever it changed on disk stays as it is.
+pub async fn delete_conflict_resolution(
+    pool: &SqlitePool,
+    game_id: &str,
+    hash: &str,
+    kind: ConflictKind,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM conflict_resolutions WHERE game_id = ? AND hash = ? AND kind = ?")
+        .bind(game_id)
+        .bind(hash)
+        .bind(kind)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use sqlx::SqlitePool;
+
+/// One learned folder-name correction, as stored in `matcher_corrections`.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct CorrectionRow {
+    pub folder_pattern: String,
+    pub entry_key: String,
+    pub folder_name: String,
+    pub hits: i64,
+}
+
+/// Every correction stored for `game_id`, ordered by pattern.
+pub async fn list_corrections(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<CorrectionRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT folder_pattern, entry_key, folder_name, hits
+         FROM matcher_corrections WHERE game_id = ? ORDER BY folder_pattern",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Records corrections for `game_id`, atomically.
+///
+/// A pattern already stored is re-pointed at the new entry: the latest
+/// correction is the one the user means. Re-recording the same entry only
+/// bumps `hits`.
+pub async fn upsert_corrections(
+    pool: &SqlitePool,
+    game_id: &str,
+    rows: &[CorrectionRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO matcher_corrections (game_id, folder_pattern, entry_key, folder_name, hits)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(game_id, folder_pattern) DO UPDATE SET
+                 hits = CASE WHEN entry_key = excluded.entry_key
+                             THEN hits + excluded.hits ELSE excluded.hits END,
+                 entry_key = excluded.entry_key,
+                 folder_name = excluded.folder_name,
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(game_id)
+        .bind(&row.folder_pattern)
+        .bind(&row.entry_key)
+        .bind(&row.folder_name)
+        .bind(row.hits)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Forgets one of `game_id`'s corrections. Returns whether it existed.
+pub async fn delete_correction(
+    pool: &SqlitePool,
+    game_id: &str,
+    folder_pattern: &str,
+) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("DELETE FROM matcher_corrections WHERE game_id = ? AND folder_pattern = ?")
+            .bind(game_id)
+            .bind(folder_pattern)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
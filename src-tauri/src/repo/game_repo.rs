@@ -122,6 +122,15 @@ pub async fn get_mod_path(pool: &SqlitePool, game_id: &str) -> Result<Option<Str
     }
 }
 
+pub async fn get_game_exe(pool: &SqlitePool, game_id: &str) -> Result<Option<String>, sqlx::Error> {
+    let exe: Option<Option<String>> =
+        sqlx::query_scalar("SELECT NULLIF(game_exe, '') FROM games WHERE id = ?")
+            .bind(game_id)
+            .fetch_optional(pool)
+            .await?;
+    Ok(exe.flatten())
+}
+
 pub async fn ensure_game_exists(
     conn: &mut sqlx::SqliteConnection,
     game_id: &str,
@@ -0,0 +1,146 @@
+use crate::domain::conflicts::{ConflictKind, HashIndexHit};
+use crate::domain::mod_path::ModFolderPath;
+use sqlx::SqlitePool;
+
+/// One harvested hash occurrence, as written to `mod_hash_index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashIndexRow {
+    pub kind: ConflictKind,
+    pub hash: String,
+    pub section_name: String,
+    pub file_path: String,
+}
+
+/// Every mod of a game with the INI fingerprint it was last indexed at, or
+/// `None` when it has never been indexed.
+pub async fn list_mod_fingerprints(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<(String, ModFolderPath, Option<String>)>, sqlx::Error> {
+    let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        "SELECT m.id, m.folder_path, s.ini_fingerprint FROM mods m
+         LEFT JOIN mod_hash_index_state s ON s.mod_id = m.id
+         WHERE m.game_id = ?",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, path, fingerprint)| (id, ModFolderPath::from_stored(path), fingerprint))
+        .collect())
+}
+
+/// Replaces one mod's indexed hashes and records the fingerprint they were
+/// harvested at, atomically.
+pub async fn replace_mod_hashes(
+    pool: &SqlitePool,
+    game_id: &str,
+    mod_id: &str,
+    fingerprint: &str,
+    rows: &[HashIndexRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM mod_hash_index WHERE mod_id = ?")
+        .bind(mod_id)
+        .execute(&mut *tx)
+        .await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO mod_hash_index (game_id, mod_id, hash_kind, hash, section_name, file_path)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(game_id)
+        .bind(mod_id)
+        .bind(row.kind)
+        .bind(&row.hash)
+        .bind(&row.section_name)
+        .bind(&row.file_path)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "INSERT INTO mod_hash_index_state (mod_id, game_id, ini_fingerprint, indexed_at)
+         VALUES (?, ?, ?, CURRENT_TIMESTAMP)
+         ON CONFLICT(mod_id) DO UPDATE SET
+             ini_fingerprint = excluded.ini_fingerprint,
+             indexed_at = excluded.indexed_at",
+    )
+    .bind(mod_id)
+    .bind(game_id)
+    .bind(fingerprint)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// Recomputes `collision_count` — how many *other* mods carry the same hash of
+/// the same kind — for every indexed row of a game.
+pub async fn refresh_collision_counts(pool: &SqlitePool, game_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE mod_hash_index SET collision_count = (
+             SELECT COUNT(DISTINCT other.mod_id) FROM mod_hash_index other
+             WHERE other.game_id = mod_hash_index.game_id
+               AND other.hash_kind = mod_hash_index.hash_kind
+               AND other.hash = mod_hash_index.hash
+               AND other.mod_id != mod_hash_index.mod_id
+         )
+         WHERE game_id = ?",
+    )
+    .bind(game_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Every indexed section, across all mods of a game, that carries `hash`.
+pub async fn find_mods_by_hash(
+    pool: &SqlitePool,
+    game_id: &str,
+    hash: &str,
+) -> Result<Vec<HashIndexHit>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT m.id AS mod_id, m.actual_name, m.folder_path, m.status = 1 AS enabled,
+                h.section_name, h.file_path, h.collision_count
+         FROM mod_hash_index h
+         JOIN mods m ON m.id = h.mod_id
+         WHERE h.game_id = ? AND h.hash = ?
+         ORDER BY m.actual_name, h.file_path, h.section_name",
+    )
+    .bind(game_id)
+    .bind(hash)
+    .fetch_all(pool)
+    .await
+}
+
+/// One indexed hash of a mod whose row is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct EnabledHashRow {
+    /// `mods.folder_path` as stored.
+    pub folder_path: String,
+    pub hash_kind: ConflictKind,
+    pub hash: String,
+    pub section_name: String,
+    pub file_path: String,
+}
+
+/// Every indexed hash of a game's enabled mods.
+pub async fn enabled_mod_hashes(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<EnabledHashRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT m.folder_path, h.hash_kind, h.hash, h.section_name, h.file_path
+         FROM mod_hash_index h
+         JOIN mods m ON m.id = h.mod_id
+         WHERE h.game_id = ? AND m.status = 1
+         ORDER BY m.folder_path, h.file_path, h.section_name",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+#[path = "tests/hash_index_repo_test.rs"]
+mod tests;
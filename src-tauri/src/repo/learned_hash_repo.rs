@@ -0,0 +1,111 @@
+use sqlx::SqlitePool;
+
+/// One learned hash, as stored in `learned_object_hashes`.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct LearnedHashRow {
+    pub object_id: String,
+    pub hash: String,
+    pub mod_count: i64,
+    pub object_mod_count: i64,
+}
+
+/// For every object matched at `min_confidence` or better, how many of its
+/// mods carry each indexed hash, as `(object_id, hash, mod_count)`.
+pub async fn object_hash_frequencies(
+    pool: &SqlitePool,
+    game_id: &str,
+    min_confidence: f64,
+) -> Result<Vec<(String, String, i64)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT m.object_id, lower(h.hash), COUNT(DISTINCT m.id)
+         FROM mod_hash_index h
+         JOIN mods m ON m.id = h.mod_id
+         JOIN objects o ON o.id = m.object_id
+         WHERE h.game_id = ?
+           AND h.hash_kind = 'resource_hash' AND length(h.hash) = 8
+           AND o.matched_entry_key IS NOT NULL
+           AND o.matched_confidence >= ?
+         GROUP BY m.object_id, lower(h.hash)
+         ORDER BY m.object_id, lower(h.hash)",
+    )
+    .bind(game_id)
+    .bind(min_confidence)
+    .fetch_all(pool)
+    .await
+}
+
+/// For the same objects, how many of their mods have any indexed hash.
+pub async fn object_indexed_mod_counts(
+    pool: &SqlitePool,
+    game_id: &str,
+    min_confidence: f64,
+) -> Result<Vec<(String, i64)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT m.object_id, COUNT(DISTINCT m.id)
+         FROM mod_hash_index h
+         JOIN mods m ON m.id = h.mod_id
+         JOIN objects o ON o.id = m.object_id
+         WHERE h.game_id = ?
+           AND h.hash_kind = 'resource_hash' AND length(h.hash) = 8
+           AND o.matched_entry_key IS NOT NULL
+           AND o.matched_confidence >= ?
+         GROUP BY m.object_id",
+    )
+    .bind(game_id)
+    .bind(min_confidence)
+    .fetch_all(pool)
+    .await
+}
+
+/// Replaces a game's learned hashes with `rows`, atomically.
+pub async fn replace_learned_hashes(
+    pool: &SqlitePool,
+    game_id: &str,
+    rows: &[LearnedHashRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM learned_object_hashes WHERE game_id = ?")
+        .bind(game_id)
+        .execute(&mut *tx)
+        .await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO learned_object_hashes
+                 (object_id, game_id, hash, mod_count, object_mod_count)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&row.object_id)
+        .bind(game_id)
+        .bind(&row.hash)
+        .bind(row.mod_count)
+        .bind(row.object_mod_count)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Drops every learned hash of a game. Returns how many there were.
+pub async fn delete_learned_hashes(pool: &SqlitePool, game_id: &str) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM learned_object_hashes WHERE game_id = ?")
+        .bind(game_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Every learned hash of a game as `(object name, hash)`, ordered by both.
+pub async fn list_learned_hashes_by_name(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT o.name, l.hash FROM learned_object_hashes l
+         JOIN objects o ON o.id = l.object_id
+         WHERE l.game_id = ?
+         ORDER BY o.name, l.hash",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
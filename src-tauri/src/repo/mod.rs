@@ -1,15 +1,21 @@
+pub mod blob_repo;
 pub mod browser_repo;
 pub mod collection_repo;
 pub mod conflict_repo;
+pub mod correction_repo;
 pub mod corridor_repo;
 pub mod dashboard_repo;
 pub mod dedup_repo;
 pub mod game_repo;
+pub mod hash_index_repo;
+pub mod learned_hash_repo;
 pub mod mod_repo;
 pub mod object_repo;
+pub mod overlay_repo;
 pub mod pin_repo;
 pub mod runtime_projection_repo;
 pub mod settings_repo;
+pub mod signal_cache_repo;
 pub mod stable_ids;
 pub mod task_repo;
 pub mod unicode_keys;
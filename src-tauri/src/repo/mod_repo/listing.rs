@@ -5,7 +5,7 @@ use crate::domain::mod_path::ModFolderPath;
 use crate::domain::models::ItemStatus;
 use sqlx::SqlitePool;
 
-fn is_effectively_enabled_path(folder_path: &str) -> bool {
+pub fn is_effectively_enabled_path(folder_path: &str) -> bool {
     !folder_path
         .split(['/', '\\'])
         .filter(|component| !component.is_empty())
@@ -119,6 +119,26 @@ pub async fn get_enabled_mods_names_and_paths(
         .collect())
 }
 
+/// Enabled mods with the name of the object each is assigned to, if any.
+pub async fn get_enabled_mods_with_object_names(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<(String, ModFolderPath, Option<String>)>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String, Option<String>)>(
+        "SELECT m.actual_name, m.folder_path, o.name FROM mods m
+         LEFT JOIN objects o ON o.id = m.object_id
+         WHERE m.game_id = ? AND m.status = 1",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter(|(_, path, _)| is_effectively_enabled_path(path))
+        .map(|(name, path, object)| (name, ModFolderPath::from_stored(path), object))
+        .collect())
+}
+
 #[cfg(test)]
 #[path = "tests/listing_tests.rs"]
 mod tests;
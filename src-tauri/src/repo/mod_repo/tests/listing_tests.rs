@@ -80,3 +80,64 @@ async fn enabled_queries_exclude_rows_under_disabled_ancestors() {
     assert_eq!(names.len(), 1);
     assert_eq!(names[0].0, "Active");
 }
+
+#[tokio::test]
+async fn enabled_mods_carry_their_object_name_when_assigned() {
+    let context = crate::test_utils::init_test_db().await;
+    let pool = context.pool;
+    crate::test_utils::insert_test_game(
+        &pool,
+        &crate::test_utils::TestGameFixture {
+            id: "g_objects",
+            name: "Objects",
+            game_type: GameType::GIMI,
+            path: "C:/Game",
+            mods_path: Some("C:/Mods"),
+        },
+    )
+    .await
+    .unwrap();
+    crate::test_utils::insert_test_object(
+        &pool,
+        &crate::test_utils::TestObjectFixture {
+            id: "obj_amber",
+            game_id: "g_objects",
+            name: "Amber",
+            folder_path: "Amber",
+            object_type: "Character",
+        },
+    )
+    .await
+    .unwrap();
+    for (id, object_id, path) in [
+        ("assigned", Some("obj_amber"), "Amber/BlueDress"),
+        ("loose", None, "Loose"),
+    ] {
+        crate::test_utils::insert_test_mod(
+            &pool,
+            &crate::test_utils::TestModFixture {
+                id,
+                game_id: "g_objects",
+                object_id,
+                actual_name: id,
+                folder_path: path,
+                status: ItemStatus::Enabled,
+                is_safe: true,
+                object_type: None,
+                mods_path: Some("C:/Mods"),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut rows = get_enabled_mods_with_object_names(&pool, "g_objects")
+        .await
+        .unwrap();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+    let objects: Vec<_> = rows
+        .iter()
+        .map(|(name, _, object)| (name.as_str(), object.as_deref()))
+        .collect();
+    assert_eq!(objects, vec![("assigned", Some("Amber")), ("loose", None)]);
+}
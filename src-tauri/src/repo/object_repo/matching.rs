@@ -11,6 +11,18 @@ pub async fn get_matched_entry_key_by_id(
         .await
 }
 
+/// The object's folder and current match, read before a manual match
+/// overwrites it so the matcher can learn from the change.
+pub async fn get_folder_and_matched_entry_key(
+    pool: &SqlitePool,
+    id: &str,
+) -> Result<Option<(String, Option<String>)>, sqlx::Error> {
+    sqlx::query_as("SELECT folder_path, matched_entry_key FROM objects WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
 pub async fn get_matched_entry_keys_by_game(
     pool: &SqlitePool,
     game_id: &str,
@@ -26,6 +38,23 @@ pub async fn get_matched_entry_keys_by_game(
     Ok(pairs.into_iter().collect())
 }
 
+/// The current match of every object in a game, by name.
+pub async fn get_match_states_by_game(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<super::types::ObjectMatchState>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT id, name, folder_path, matched_entry_key, matched_alias_name,
+                matched_confidence, matched_source
+         FROM objects
+         WHERE game_id = ?
+         ORDER BY name",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
 /// Every object's `(matched_entry_key, custom_skins)` pair that carries both.
 ///
 /// Feeds the MasterDB loader, which folds the user's own aliases into the
@@ -38,6 +38,18 @@ pub struct ReconcileObjectRow {
     pub object_type: String,
 }
 
+/// An object's current MasterDB match, as the re-match job reads it.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ObjectMatchState {
+    pub id: String,
+    pub name: String,
+    pub folder_path: String,
+    pub matched_entry_key: Option<String>,
+    pub matched_alias_name: Option<String>,
+    pub matched_confidence: Option<f64>,
+    pub matched_source: Option<String>,
+}
+
 /// A page of objects plus the ids whose runtime projection is cold.
 ///
 /// The repo cannot resolve those itself — filling them in reads the disk.
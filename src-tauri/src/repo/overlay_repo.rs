@@ -0,0 +1,61 @@
+use sqlx::SqlitePool;
+
+/// One user MasterDB overlay record, as stored in `master_db_overlay`.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct OverlayRow {
+    pub entry_key: String,
+    pub entry_json: String,
+}
+
+/// Every overlay record for a game type, ordered by entry key.
+pub async fn list_overlay(
+    pool: &SqlitePool,
+    game_type: &str,
+) -> Result<Vec<OverlayRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT entry_key, entry_json FROM master_db_overlay
+         WHERE game_type = ? ORDER BY entry_key",
+    )
+    .bind(game_type)
+    .fetch_all(pool)
+    .await
+}
+
+/// Stores overlay records, atomically. A record already stored for the same
+/// entry is replaced.
+pub async fn upsert_overlay(
+    pool: &SqlitePool,
+    game_type: &str,
+    rows: &[OverlayRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO master_db_overlay (game_type, entry_key, entry_json)
+             VALUES (?, ?, ?)
+             ON CONFLICT(game_type, entry_key) DO UPDATE SET
+                 entry_json = excluded.entry_json,
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(game_type)
+        .bind(&row.entry_key)
+        .bind(&row.entry_json)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Drops one overlay record. Returns whether it existed.
+pub async fn delete_overlay(
+    pool: &SqlitePool,
+    game_type: &str,
+    entry_key: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM master_db_overlay WHERE game_type = ? AND entry_key = ?")
+        .bind(game_type)
+        .bind(entry_key)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
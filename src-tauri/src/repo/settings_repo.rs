@@ -121,11 +121,21 @@ pub async fn get_app_meta(pool: &SqlitePool, key: &str) -> Option<String> {
 }
 
 pub async fn set_app_meta(pool: &SqlitePool, key: &str, value: &str) {
-    let _ = sqlx::query("INSERT OR REPLACE INTO app_meta (key, value) VALUES (?, ?)")
+    let _ = try_set_app_meta(pool, key, value).await;
+}
+
+/// Like [`set_app_meta`], for callers that must know the value was saved.
+pub async fn try_set_app_meta(
+    pool: &SqlitePool,
+    key: &str,
+    value: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR REPLACE INTO app_meta (key, value) VALUES (?, ?)")
         .bind(key)
         .bind(value)
         .execute(pool)
-        .await;
+        .await?;
+    Ok(())
 }
 
 #[cfg(test)]
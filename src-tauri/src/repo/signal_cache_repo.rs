@@ -0,0 +1,85 @@
+use sqlx::SqlitePool;
+
+/// One persisted `FolderSignals`, as stored in `folder_signal_cache`.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct SignalCacheRow {
+    pub folder_path: String,
+    pub mode: i64,
+    pub fingerprint: String,
+    pub signals_json: String,
+}
+
+/// Every persisted row of a game.
+pub async fn list_for_game(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<SignalCacheRow>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT folder_path, mode, fingerprint, signals_json
+         FROM folder_signal_cache WHERE game_id = ?",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Every folder of a game with at least one persisted row.
+pub async fn list_folder_paths(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT folder_path FROM folder_signal_cache
+         WHERE game_id = ? ORDER BY folder_path",
+    )
+    .bind(game_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Stores rows, atomically, replacing any row for the same folder and mode.
+pub async fn upsert_rows(
+    pool: &SqlitePool,
+    game_id: &str,
+    rows: &[SignalCacheRow],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for row in rows {
+        sqlx::query(
+            "INSERT INTO folder_signal_cache
+                 (folder_path, mode, game_id, fingerprint, signals_json)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(folder_path, mode) DO UPDATE SET
+                 game_id = excluded.game_id,
+                 fingerprint = excluded.fingerprint,
+                 signals_json = excluded.signals_json,
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(&row.folder_path)
+        .bind(row.mode)
+        .bind(game_id)
+        .bind(&row.fingerprint)
+        .bind(&row.signals_json)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Drops every row of the given folders. Returns how many rows went.
+pub async fn delete_folders(
+    pool: &SqlitePool,
+    folder_paths: &[String],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut deleted = 0;
+    for folder_path in folder_paths {
+        deleted += sqlx::query("DELETE FROM folder_signal_cache WHERE folder_path = ?")
+            .bind(folder_path)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    }
+    tx.commit().await?;
+    Ok(deleted)
+}
@@ -3,7 +3,8 @@ use crate::domain::task::{PipelineTask, TaskStatus};
 use sqlx::{Row, SqlitePool};
 
 /// Columns every `PipelineTask` read selects, in the order `row_to_task` expects.
-const TASK_COLUMNS: &str = "id, game_id, task_type, status, target_id, created_at, updated_at";
+const TASK_COLUMNS: &str =
+    "id, game_id, task_type, status, target_id, payload, created_at, updated_at";
 
 fn row_to_task(r: &sqlx::sqlite::SqliteRow) -> PipelineTask {
     PipelineTask {
@@ -12,23 +13,26 @@ fn row_to_task(r: &sqlx::sqlite::SqliteRow) -> PipelineTask {
         task_type: r.get("task_type"),
         status: TaskStatus::from_db_value(r.get::<&str, _>("status")),
         target_id: r.try_get("target_id").ok().flatten(),
+        payload: r.try_get("payload").ok().flatten(),
         created_at: r.get("created_at"),
         updated_at: r.get("updated_at"),
     }
 }
 
-/// Create a new pending task in the database and return its ID.
+/// Create a new pending task in the database and return its ID. `payload` is
+/// JSON recovery needs to resume the task beyond its target.
 pub async fn create_task(
     pool: &SqlitePool,
     id: &str,
     game_id: &str,
     task_type: &str,
     target_id: Option<&str>,
+    payload: Option<&str>,
 ) -> Result<String, AppError> {
     sqlx::query(
         r#"
-        INSERT INTO tasks (id, game_id, task_type, status, target_id)
-        VALUES (?, ?, ?, ?, ?)
+        INSERT INTO tasks (id, game_id, task_type, status, target_id, payload)
+        VALUES (?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(id)
@@ -36,6 +40,7 @@ pub async fn create_task(
     .bind(task_type)
     .bind(TaskStatus::Pending.as_str())
     .bind(target_id)
+    .bind(payload)
     .execute(pool)
     .await
     .map_err(|e| AppError::Db(e.to_string()))?;
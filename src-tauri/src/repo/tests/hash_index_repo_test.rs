@@ -0,0 +1,93 @@
+use super::*;
+use crate::domain::models::{GameType, ItemStatus};
+use crate::test_utils::{
+    init_test_db, insert_test_game, insert_test_mod, TestGameFixture, TestModFixture,
+};
+
+async fn seed(pool: &SqlitePool) {
+    insert_test_game(
+        pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some("/mods"),
+        },
+    )
+    .await
+    .unwrap();
+    for (id, status) in [("a", ItemStatus::Enabled), ("b", ItemStatus::Disabled)] {
+        insert_test_mod(
+            pool,
+            &TestModFixture {
+                id,
+                game_id: "g1",
+                object_id: None,
+                actual_name: id,
+                folder_path: id,
+                status,
+                is_safe: true,
+                object_type: None,
+                mods_path: Some("/mods"),
+            },
+        )
+        .await
+        .unwrap();
+    }
+}
+
+fn row(hash: &str, section: &str) -> HashIndexRow {
+    HashIndexRow {
+        kind: ConflictKind::ResourceHash,
+        hash: hash.to_string(),
+        section_name: section.to_string(),
+        file_path: "mod.ini".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn replacing_a_mods_rows_keeps_collision_counts_current() {
+    let pool = init_test_db().await.pool;
+    seed(&pool).await;
+
+    replace_mod_hashes(
+        &pool,
+        "g1",
+        "a",
+        "fp-a",
+        &[row("aabbccdd", "TextureOverrideBody")],
+    )
+    .await
+    .unwrap();
+    replace_mod_hashes(
+        &pool,
+        "g1",
+        "b",
+        "fp-b",
+        &[row("AABBCCDD", "TextureOverrideHead")],
+    )
+    .await
+    .unwrap();
+    refresh_collision_counts(&pool, "g1").await.unwrap();
+
+    let hits = find_mods_by_hash(&pool, "g1", "aabbccdd").await.unwrap();
+    let summary: Vec<_> = hits
+        .iter()
+        .map(|hit| (hit.mod_id.as_str(), hit.enabled, hit.collision_count))
+        .collect();
+    assert_eq!(summary, vec![("a", true, 1), ("b", false, 1)]);
+
+    replace_mod_hashes(&pool, "g1", "b", "fp-b2", &[])
+        .await
+        .unwrap();
+    refresh_collision_counts(&pool, "g1").await.unwrap();
+
+    let hits = find_mods_by_hash(&pool, "g1", "aabbccdd").await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].collision_count, 0);
+
+    let fingerprints = list_mod_fingerprints(&pool, "g1").await.unwrap();
+    let b = fingerprints.iter().find(|(id, _, _)| id == "b").unwrap();
+    assert_eq!(b.2.as_deref(), Some("fp-b2"));
+}
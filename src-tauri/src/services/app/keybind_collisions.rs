@@ -0,0 +1,51 @@
+//! Keybind collisions across the enabled mods of one game.
+
+use crate::domain::errors::AppError;
+use crate::services::hotkeys::HotkeyConfig;
+use crate::services::keyviewer::collisions::{self, KeybindCollision, ModKeybind};
+use crate::services::keyviewer::harvester;
+use std::path::Path;
+
+/// Every `key =`/`back =` value of the enabled mods, skipping the mod whose
+/// canonical folder is `exclude`.
+pub async fn enabled_mod_keybinds(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    exclude: Option<&Path>,
+) -> Result<Vec<ModKeybind>, AppError> {
+    let mods_root = crate::repo::game_repo::get_mod_path(pool, game_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Game {game_id} has no mods path")))?;
+    let mods_root = Path::new(&mods_root);
+    let rows = crate::repo::mod_repo::get_enabled_mods_with_object_names(pool, game_id).await?;
+
+    let mut keybinds = Vec::new();
+    for (mod_name, folder_path, object_name) in &rows {
+        let resolved = folder_path.resolve(mods_root);
+        if exclude.is_some_and(|exclude| {
+            std::fs::canonicalize(&resolved).is_ok_and(|canonical| canonical == exclude)
+        }) {
+            continue;
+        }
+        // Same skip-on-error policy as the dashboard keybind list.
+        let Ok(harvested) = harvester::harvest_keybinds_from_mod(&resolved) else {
+            continue;
+        };
+        keybinds.extend(ModKeybind::expand(
+            mod_name,
+            folder_path.as_stored(),
+            object_name.as_deref(),
+            &harvested,
+        ));
+    }
+    Ok(keybinds)
+}
+
+pub async fn get_keybind_collisions_service(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    hotkeys: &HotkeyConfig,
+) -> Result<Vec<KeybindCollision>, AppError> {
+    let keybinds = enabled_mod_keybinds(pool, game_id, None).await?;
+    Ok(collisions::find_collisions(&keybinds, hotkeys))
+}
@@ -1,5 +1,6 @@
 pub mod app_service;
 pub mod dashboard;
+pub mod keybind_collisions;
 pub mod log_service;
 pub mod maintenance_service;
 pub mod post_apply;
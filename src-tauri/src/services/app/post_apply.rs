@@ -2,10 +2,14 @@ use crate::domain::errors::AppError;
 use crate::repo;
 use crate::services::corridor_service;
 use crate::services::hotkeys::HotkeyConfig;
+use crate::services::keyviewer::collisions;
 use crate::services::keyviewer::generator;
 use crate::services::keyviewer::harvester;
+use crate::services::keyviewer::hash_index;
+use crate::services::keyviewer::learned_hashes;
 use crate::services::keyviewer::matcher;
 use crate::services::mods::metadata;
+use crate::services::scanner::conflict;
 use sqlx::SqlitePool;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
@@ -70,35 +74,59 @@ pub async fn run_post_apply_tasks(ctx: PostApplyContext) -> Result<(), AppError>
     // One query feeds both the conflict scan and the harvest below.
     let enabled_mods = crate::repo::mod_repo::get_enabled_mods_paths(pool, game_id).await?;
 
-    // 2. Refresh conflict cache
-    let conflicts = metadata::conflicts_for_enabled_paths(mods_path, &enabled_mods);
+    // 2. Refresh conflict cache; this also brings the hash index up to date
+    // for the harvest below.
+    let mut conflicts =
+        metadata::indexed_conflicts(pool, game_id, mods_path, &enabled_mods).await?;
+    conflict::resolution::annotate_resolutions(pool, game_id, &mut conflicts).await?;
+    // A conflict the user settled with `match_priority` is still detected;
+    // the banner counts only the ones still waiting for a decision.
+    let unresolved_conflicts = conflicts
+        .iter()
+        .filter(|conflict| conflict.resolution.is_none())
+        .count();
 
     // 3. KeyViewer Pipeline (Req-43)
     let emmm_data_dir = mods_path.join(".emmm_data");
     let keybinds_dir = emmm_data_dir.join("keybinds").join("active");
     let status_dir = emmm_data_dir.join("status");
 
-    // Harvest: one pass per mod for both hashes and keybinds.
+    // Harvest: hashes come from the index, keybinds from each mod's INIs.
+    let mut indexed_hashes = hash_index::keyviewer_hashes(pool, game_id).await?;
     let mut occurrence_counts = HashMap::new();
     let mut hash_to_mod_path = HashMap::new();
     let mut mod_keybinds = HashMap::new();
 
     for stored_path in enabled_mods {
         let abs_path = stored_path.resolve(mods_path);
-        let harvest = harvester::harvest_mod(&abs_path)?;
-
-        for (hash, occurrences) in harvest.hashes {
-            *occurrence_counts.entry(hash.clone()).or_insert(0) += occurrences.len();
+        for (hash, occurrences) in indexed_hashes.remove(&stored_path).unwrap_or_default() {
+            *occurrence_counts.entry(hash.clone()).or_insert(0) += occurrences;
             hash_to_mod_path
                 .entry(hash)
                 .or_insert_with(Vec::new)
                 .push(stored_path.clone());
         }
-        mod_keybinds.insert(stored_path, harvest.keybinds);
+        mod_keybinds.insert(
+            stored_path,
+            harvester::harvest_keybinds_from_mod(&abs_path)?,
+        );
+    }
+
+    let mut all_keybinds = Vec::new();
+    for (stored_path, keybinds) in &mod_keybinds {
+        all_keybinds.extend(collisions::ModKeybind::expand(
+            stored_path.folder_name(),
+            stored_path.as_stored(),
+            None,
+            keybinds,
+        ));
     }
+    let keybind_collisions = collisions::find_collisions(&all_keybinds, &ctx.hotkeys);
 
-    // Load character entries from DB
+    // Load character entries from DB, with whatever the opt-in learning pass
+    // attributed to them.
     let db_objects = repo::object_repo::get_kv_matching_objects(pool, game_id).await?;
+    let mut learned = learned_hashes::load_learned_hashes(pool, game_id).await?;
 
     let entries: Vec<matcher::KvObjectEntry> = db_objects
         .into_iter()
@@ -129,12 +157,19 @@ pub async fn run_post_apply_tasks(ctx: PostApplyContext) -> Result<(), AppError>
                 .collect::<BTreeSet<_>>()
                 .into_iter()
                 .collect();
+            let learned_hashes: Vec<String> = learned
+                .remove(&name)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|hash| code_hashes.binary_search(hash).is_err())
+                .collect();
 
             matcher::KvObjectEntry {
                 name,
                 object_type: "Character".to_string(),
                 code_hashes,
                 skin_hashes,
+                learned_hashes,
                 tags: Vec::new(),
                 thumbnail_path: None,
             }
@@ -216,7 +251,8 @@ pub async fn run_post_apply_tasks(ctx: PostApplyContext) -> Result<(), AppError>
         preset_name,
         folder_name: None,
         scope_name: None,
-        conflict_count: Some(conflicts.len()),
+        conflict_count: Some(unresolved_conflicts),
+        keybind_collision_count: Some(keybind_collisions.len()),
     };
 
     // Override with fields from the mutation source if provided
@@ -0,0 +1,93 @@
+use crate::domain::models::{GameType, ItemStatus};
+use crate::services::app::keybind_collisions::get_keybind_collisions_service;
+use crate::services::hotkeys::HotkeyConfig;
+use crate::services::keyviewer::collisions::KeybindOwner;
+use std::fs;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn collisions_span_enabled_mods_and_carry_object_names() {
+    let pool = crate::test_utils::init_test_db().await.pool;
+    let temp_dir = TempDir::new().unwrap();
+    let mods_root = temp_dir.path().to_str().unwrap();
+
+    crate::test_utils::insert_test_game(
+        &pool,
+        &crate::test_utils::TestGameFixture {
+            id: "g1",
+            name: "G1",
+            game_type: GameType::GIMI,
+            path: "/g1",
+            mods_path: Some(mods_root),
+        },
+    )
+    .await
+    .unwrap();
+    crate::test_utils::insert_test_object(
+        &pool,
+        &crate::test_utils::TestObjectFixture {
+            id: "obj_alice",
+            game_id: "g1",
+            name: "Alice",
+            folder_path: "Alice",
+            object_type: "Character",
+        },
+    )
+    .await
+    .unwrap();
+
+    for (id, object_id, folder, ini) in [
+        (
+            "body",
+            Some("obj_alice"),
+            "Body",
+            "[KeySwap]\nkey = VK_F5\n",
+        ),
+        (
+            "hair",
+            None,
+            "Hair",
+            "[KeyHair]\nkey = no_alt f5\nback = 9\n",
+        ),
+        ("cape", None, "DISABLED Cape", "[KeyCape]\nkey = F5\n"),
+    ] {
+        fs::create_dir_all(temp_dir.path().join(folder)).unwrap();
+        fs::write(temp_dir.path().join(folder).join("mod.ini"), ini).unwrap();
+        crate::test_utils::insert_test_mod(
+            &pool,
+            &crate::test_utils::TestModFixture {
+                id,
+                game_id: "g1",
+                object_id,
+                actual_name: id,
+                folder_path: folder,
+                status: ItemStatus::Enabled,
+                is_safe: true,
+                object_type: None,
+                mods_path: Some(mods_root),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let hotkeys = HotkeyConfig {
+        enabled: false,
+        ..HotkeyConfig::default()
+    };
+    let collisions = get_keybind_collisions_service(&pool, "g1", &hotkeys)
+        .await
+        .unwrap();
+
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].key, "F5");
+    let owners: Vec<_> = collisions[0]
+        .bindings
+        .iter()
+        .map(|bound| match &bound.owner {
+            KeybindOwner::Mod { mod_name, .. } => (mod_name.as_str(), bound.object_name.as_deref()),
+            KeybindOwner::Hotkey { action } => (action.as_str(), None),
+        })
+        .collect();
+    assert_eq!(owners, vec![("hair", None), ("body", Some("Alice"))]);
+}
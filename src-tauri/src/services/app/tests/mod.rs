@@ -1,4 +1,5 @@
 pub mod app_service_tests;
 pub mod dashboard_tests;
+pub mod keybind_collisions_tests;
 pub mod log_service_tests;
 pub mod maintenance_service_tests;
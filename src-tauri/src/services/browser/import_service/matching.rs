@@ -6,7 +6,7 @@ use tauri::{AppHandle, Manager};
 use crate::repo::browser_repo::ImportJobMatch as MatchResult;
 use crate::services::scanner::core::walker::ModCandidate;
 use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
-use crate::services::scanner::deep_matcher::{match_folder_phased, MasterDb};
+use crate::services::scanner::deep_matcher::match_folder_phased;
 
 /// Attempt deep match. If the scanner service has a `quick_folder_match` function, call it.
 /// Falls back to confidence 0.0 (needs_review) if the scanner is unavailable.
@@ -35,9 +35,14 @@ pub(super) async fn try_deep_match(
     let game_type: i32 = game_type_res?.parse().ok()?;
     let resource_dir = app.path().resource_dir().ok()?;
 
-    let json_content =
-        match crate::services::scanner::master_db::load_master_db_json(&resource_dir, game_type) {
-            Ok(content) => content,
+    // The cached database carries the user's overlay, aliases, and
+    // corrections, which a fresh parse of the bundled file would miss.
+    let master_db =
+        match crate::services::scanner::master_db::get_cached_for_game(app, game_type, game_id_str)
+            .await
+        {
+            Ok(Some(db)) => db,
+            Ok(None) => return None,
             Err(error) => {
                 return Some(MatchResult {
                     category: None,
@@ -48,7 +53,6 @@ pub(super) async fn try_deep_match(
                 })
             }
         };
-    let master_db = MasterDb::from_json(&json_content).ok()?;
 
     // Build candidate from extract_dir
     let raw_name = extract_dir
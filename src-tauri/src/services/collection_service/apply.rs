@@ -1,10 +1,16 @@
 //! Entry point that hands an apply request to the apply pipeline.
 
-use crate::domain::collection::ApplyResult;
+use std::collections::HashSet;
+
+use crate::domain::collection::{ApplyResult, ApplyScope};
 use crate::domain::errors::CollectionError;
+use crate::repo::collection_repo;
+use crate::services::fs_utils::operation_lock::OpGuard;
 use sqlx::SqlitePool;
 
 pub struct ApplyCollectionRequest<'a> {
+    /// The apply renames folders and rewrites INIs.
+    pub op_guard: &'a OpGuard,
     pub pool: &'a SqlitePool,
     pub game_id: &'a str,
     pub collection_id: &'a str,
@@ -19,6 +25,9 @@ pub struct ApplyCollectionRequest<'a> {
     /// path (no app handle there; recovery holds the op lock and blanket
     /// suppression, and the inline reconcile stays idempotent regardless).
     pub reconcile_lock: Option<std::sync::Arc<tokio::sync::Mutex<()>>>,
+    /// Which objects the apply converges; the rest of the runtime is left
+    /// alone unless this is `ApplyScope::Full`.
+    pub scope: ApplyScope,
 }
 
 pub async fn apply_collection(
@@ -27,7 +36,36 @@ pub async fn apply_collection(
     // Drive the pipeline with the caller's current corridor (request.is_safe),
     // NOT the collection's own is_safe — validate_corridor then rejects a
     // cross-corridor apply before any filesystem mutation.
+    let op_guard = request.op_guard;
     let mut ctx = crate::pipeline::apply_pipeline::ApplyContext::new(request);
 
-    crate::pipeline::apply_pipeline::execute(&mut ctx).await
+    crate::pipeline::apply_pipeline::execute(&mut ctx, op_guard).await
+}
+
+/// The object ids `scope` limits an apply to; `None` for a full apply.
+pub(crate) async fn resolve_apply_scope(
+    pool: &SqlitePool,
+    game_id: &str,
+    scope: &ApplyScope,
+) -> Result<Option<HashSet<String>>, CollectionError> {
+    let ApplyScope::Objects {
+        object_ids,
+        object_types,
+    } = scope
+    else {
+        return Ok(None);
+    };
+    let scoped =
+        collection_repo::get_scoped_object_ids(pool, game_id, object_ids, object_types).await?;
+    if let Some(unknown) = object_ids.iter().find(|id| !scoped.contains(id)) {
+        return Err(CollectionError::Validation(format!(
+            "Object '{unknown}' does not belong to game '{game_id}'"
+        )));
+    }
+    if scoped.is_empty() {
+        return Err(CollectionError::Validation(
+            "The apply scope selects no objects".to_string(),
+        ));
+    }
+    Ok(Some(scoped.into_iter().collect()))
 }
@@ -0,0 +1,297 @@
+//! Portable collection bundles (`.emmmcol`).
+//!
+//! A collection's members are rows keyed by absolute mod paths, which mean
+//! nothing on another machine. A bundle describes each member by what travels
+//! instead: its path under the mods root, its object's MasterDB entry key, a
+//! fingerprint of its INI and resource files, and where it was published.
+//! Importing resolves those against the local library and creates an ordinary
+//! collection from the mods it found.
+
+use std::path::{Component, Path};
+
+use super::bundle_resolve::resolve_members;
+use super::create::insert_collection;
+use super::live_state::load_game_mods_path;
+use super::projection::{require_collection, require_game_match};
+use super::user_vars::NewCollectionVars;
+use crate::common::normalizer::normalize_display_name;
+use crate::common::path_key;
+use crate::domain::collection::{
+    BundleImportResult, BundleMember, BundleResolution, BundleSource, CreateCollectionInput,
+    CreateCollectionMode,
+};
+use crate::domain::errors::CollectionError;
+use crate::repo::collection_repo;
+use crate::repo::game_repo;
+use crate::services::keyviewer::generator::atomic_write;
+use crate::services::mods::info_json;
+use crate::services::scanner::deep_matcher::analysis::gamebanana;
+use sqlx::SqlitePool;
+
+/// File extension the frontend offers for bundles.
+pub const BUNDLE_EXTENSION: &str = "emmmcol";
+
+const BUNDLE_FORMAT: &str = "emmm-collection";
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(super) struct CollectionBundle {
+    format: String,
+    version: u32,
+    /// Resource code of the game the bundle was made for, e.g. `GIMI`.
+    game_type: String,
+    pub(super) name: String,
+    pub(super) members: Vec<BundleMember>,
+}
+
+/// Extensions of the files that make up a mod's content: its INIs and the
+/// buffers and textures they load.
+const CONTENT_EXTENSIONS: &[&str] = &["ini", "dds", "buf", "ib", "vb"];
+
+/// Hash over the relative path and bytes of every INI and resource file under
+/// `mod_root`. Recolors override the same resource hashes, so only the files
+/// themselves tell them apart. `None` when the folder is unreadable or holds
+/// no such file.
+pub(crate) fn content_fingerprint(mod_root: &Path) -> Option<String> {
+    let mut files: Vec<(String, blake3::Hash)> = Vec::new();
+    for entry in walkdir::WalkDir::new(mod_root) {
+        let entry = entry.ok()?;
+        let is_content = entry.path().extension().is_some_and(|extension| {
+            CONTENT_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        });
+        if !entry.file_type().is_file() || !is_content {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(mod_root).ok()?;
+        let relative = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy().to_lowercase())
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut hasher = blake3::Hasher::new();
+        hasher
+            .update_reader(std::fs::File::open(entry.path()).ok()?)
+            .ok()?;
+        files.push((relative, hasher.finalize()));
+    }
+    if files.is_empty() {
+        return None;
+    }
+    files.sort_unstable_by(|left, right| left.0.cmp(&right.0));
+
+    let mut hasher = blake3::Hasher::new();
+    for (relative, hash) in files {
+        hasher.update(relative.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(hash.as_bytes());
+    }
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+/// The first GameBanana page the mod's `info.json` links to.
+fn gamebanana_source(mod_root: &Path) -> Option<BundleSource> {
+    let info = info_json::read_info_json(mod_root).ok().flatten()?;
+    let mut metadata: Vec<(&String, &String)> = info.metadata.iter().collect();
+    metadata.sort_unstable();
+    let texts = metadata
+        .into_iter()
+        .map(|(_, value)| value.as_str())
+        .chain(std::iter::once(info.description.as_str()));
+    let reference = gamebanana::detect_gamebanana_ids_in_text(texts)
+        .into_iter()
+        .next()?;
+    Some(BundleSource {
+        item_type: reference.item_type,
+        item_id: u32::try_from(reference.item_id).ok()?,
+    })
+}
+
+/// `mod_path` under the mods root, `/`-separated and without `DISABLED`
+/// prefixes, so it names the same folder whatever its state.
+pub(super) fn relative_member_path(mod_path: &str, mods_path: Option<&str>) -> String {
+    let path = Path::new(mod_path);
+    let relative = mods_path
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => {
+                Some(normalize_display_name(&part.to_string_lossy()).into_owned())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+async fn load_game_type_code(pool: &SqlitePool, game_id: &str) -> Result<String, CollectionError> {
+    game_repo::get_game_type(pool, game_id)
+        .await?
+        .map(|game_type| game_type.resource_code().to_string())
+        .ok_or_else(|| CollectionError::Validation(format!("Game '{game_id}' not found")))
+}
+
+/// Writes `collection_id` to `path` as a bundle. Returns how many members
+/// were written.
+pub async fn export_collection_bundle(
+    pool: &SqlitePool,
+    game_id: &str,
+    collection_id: &str,
+    path: &Path,
+) -> Result<usize, CollectionError> {
+    let collection = require_collection(pool, collection_id).await?;
+    require_game_match(&collection, game_id)?;
+    let game_type = load_game_type_code(pool, game_id).await?;
+    let mods_path = load_game_mods_path(pool, game_id).await?;
+    let library = collection_repo::get_library_mod_rows(pool, game_id).await?;
+
+    let collection_mods = collection_repo::get_mods(pool, collection_id).await?;
+
+    // Fingerprinting and `info.json` reads touch every member's files.
+    let mut members: Vec<BundleMember> = tokio::task::spawn_blocking(move || {
+        collection_mods
+            .into_iter()
+            .map(|member| {
+                let row = member
+                    .mod_id
+                    .as_deref()
+                    .and_then(|id| library.iter().find(|row| row.mod_id == id))
+                    .or_else(|| {
+                        let key = member.mod_path_key.as_deref()?;
+                        library.iter().find(|row| row.mod_path_key == key)
+                    });
+                let relative_path = relative_member_path(&member.mod_path, mods_path.as_deref());
+                let mod_root =
+                    path_key::resolve_collection_path(&member.mod_path, mods_path.as_deref());
+                let display_name = member
+                    .display_name
+                    .or_else(|| row.map(|row| row.display_name.clone()))
+                    .unwrap_or_else(|| {
+                        relative_path
+                            .rsplit('/')
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                    });
+                BundleMember {
+                    object_entry_key: row.and_then(|row| row.object_entry_key.clone()),
+                    object_name: row.and_then(|row| row.object_name.clone()),
+                    display_name,
+                    relative_path,
+                    content_fingerprint: mod_root.as_deref().and_then(content_fingerprint),
+                    source: mod_root.as_deref().and_then(gamebanana_source),
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+    members.sort_by(|left, right| left.relative_path.cmp(&right.relative_path));
+
+    let bundle = CollectionBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        game_type,
+        name: collection.name,
+        members,
+    };
+    let json = serde_json::to_string_pretty(&bundle)
+        .map_err(|error| CollectionError::Validation(format!("Cannot write bundle: {error}")))?;
+    atomic_write(path, &json).map_err(|error| CollectionError::Io(error.to_string()))?;
+    Ok(bundle.members.len())
+}
+
+async fn read_bundle(path: &Path) -> Result<CollectionBundle, CollectionError> {
+    let json = tokio::fs::read_to_string(path).await?;
+    let bundle: CollectionBundle = serde_json::from_str(&json).map_err(|error| {
+        CollectionError::Validation(format!("Not a collection bundle: {error}"))
+    })?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(CollectionError::Validation(format!(
+            "Not a collection bundle: format '{}'",
+            bundle.format
+        )));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(CollectionError::Validation(format!(
+            "Collection bundle version {} is newer than this app supports",
+            bundle.version
+        )));
+    }
+    Ok(bundle)
+}
+
+/// Reads the bundle at `path` and resolves its members against `game_id`'s
+/// library, without creating anything.
+pub async fn resolve_collection_bundle(
+    pool: &SqlitePool,
+    game_id: &str,
+    path: &Path,
+) -> Result<BundleResolution, CollectionError> {
+    let bundle = read_bundle(path).await?;
+    let game_type = load_game_type_code(pool, game_id).await?;
+    if !bundle.game_type.eq_ignore_ascii_case(&game_type) {
+        return Err(CollectionError::Validation(format!(
+            "Collection bundle is for {}, not {game_type}",
+            bundle.game_type
+        )));
+    }
+    let mods_path = load_game_mods_path(pool, game_id).await?;
+    let library = collection_repo::get_library_mod_rows(pool, game_id).await?;
+    // The fingerprint fallback can read every mod's files.
+    let resolution = tokio::task::spawn_blocking(move || {
+        resolve_members(bundle, &library, mods_path.as_deref())
+    })
+    .await?;
+    Ok(resolution)
+}
+
+/// Creates a collection from the bundle at `path` out of the members found
+/// locally. `name` overrides the bundle's own. Missing and ambiguous members
+/// are reported, not fatal, unless none were found.
+pub async fn import_collection_bundle(
+    pool: &SqlitePool,
+    game_id: &str,
+    path: &Path,
+    name: Option<String>,
+) -> Result<BundleImportResult, CollectionError> {
+    let resolution = resolve_collection_bundle(pool, game_id, path).await?;
+    if resolution.resolved.is_empty() {
+        return Err(CollectionError::Validation(format!(
+            "None of the bundle's {} mod(s) are in this library",
+            resolution.missing.len() + resolution.ambiguous.len()
+        )));
+    }
+
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| resolution.name.clone());
+    // The live d3dx_user.ini values belong to whatever is enabled now, not to
+    // the bundle's members.
+    let collection = insert_collection(
+        pool,
+        CreateCollectionInput {
+            game_id: game_id.to_string(),
+            name,
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(
+                resolution
+                    .resolved
+                    .iter()
+                    .map(|member| member.mod_id.clone())
+                    .collect(),
+            ),
+        },
+        NewCollectionVars::Skip,
+    )
+    .await?;
+
+    Ok(BundleImportResult {
+        collection,
+        resolution,
+    })
+}
@@ -0,0 +1,104 @@
+//! Matching a bundle's members to mods in the local library.
+
+use std::collections::{HashMap, HashSet};
+
+use super::bundle::{content_fingerprint, CollectionBundle};
+use crate::common::path_key;
+use crate::domain::collection::{
+    BundleMatch, BundleMember, BundleResolution, ResolvedBundleMember,
+};
+use crate::repo::collection_repo::LibraryModRow;
+
+/// Whether `row` sits under the object `member` was bundled from.
+fn same_object(member: &BundleMember, row: &LibraryModRow) -> bool {
+    if let (Some(expected), Some(actual)) = (&member.object_entry_key, &row.object_entry_key) {
+        return expected.eq_ignore_ascii_case(actual);
+    }
+    matches!(
+        (&member.object_name, &row.object_name),
+        (Some(expected), Some(actual)) if path_key::names_equal_by_key(expected, actual)
+    )
+}
+
+/// Finds a local mod for each member: by path, then by content fingerprint,
+/// then by display name under the same object. Each mod stands in for one
+/// member at most. A fingerprint several mods share leaves the member
+/// ambiguous rather than picking one.
+pub(super) fn resolve_members(
+    bundle: CollectionBundle,
+    library: &[LibraryModRow],
+    mods_path: Option<&str>,
+) -> BundleResolution {
+    let mut claimed: HashSet<&str> = HashSet::new();
+    let mut fingerprints: HashMap<&str, Option<String>> = HashMap::new();
+    let mut resolved = Vec::new();
+    let mut missing = Vec::new();
+    let mut ambiguous = Vec::new();
+
+    for member in bundle.members {
+        let path_key = path_key::folder_path_key(&member.relative_path, mods_path);
+        let mut found = library
+            .iter()
+            .find(|row| !claimed.contains(row.mod_id.as_str()) && row.mod_path_key == path_key)
+            .map(|row| (row, BundleMatch::Path));
+
+        if found.is_none() {
+            if let Some(expected) = member.content_fingerprint.as_deref() {
+                let matches: Vec<&LibraryModRow> = library
+                    .iter()
+                    .filter(|row| !claimed.contains(row.mod_id.as_str()))
+                    .filter(|row| {
+                        fingerprints
+                            .entry(row.mod_id.as_str())
+                            .or_insert_with(|| {
+                                path_key::resolve_collection_path(&row.mod_path, mods_path)
+                                    .as_deref()
+                                    .and_then(content_fingerprint)
+                            })
+                            .as_deref()
+                            == Some(expected)
+                    })
+                    .collect();
+                match matches.as_slice() {
+                    [row] => found = Some((row, BundleMatch::Fingerprint)),
+                    [] => {}
+                    // Identical copies: any pick could be the wrong folder.
+                    _ => {
+                        ambiguous.push(member);
+                        continue;
+                    }
+                }
+            }
+        }
+
+        if found.is_none() {
+            found = library
+                .iter()
+                .find(|row| {
+                    !claimed.contains(row.mod_id.as_str())
+                        && same_object(&member, row)
+                        && path_key::names_equal_by_key(&row.display_name, &member.display_name)
+                })
+                .map(|row| (row, BundleMatch::Name));
+        }
+
+        match found {
+            Some((row, matched_by)) => {
+                claimed.insert(row.mod_id.as_str());
+                resolved.push(ResolvedBundleMember {
+                    member,
+                    mod_id: row.mod_id.clone(),
+                    matched_by,
+                });
+            }
+            None => missing.push(member),
+        }
+    }
+
+    BundleResolution {
+        name: bundle.name,
+        resolved,
+        missing,
+        ambiguous,
+    }
+}
@@ -0,0 +1,193 @@
+//! Creating a collection from the running setup, a selection of mods, or a
+//! snapshot of another collection.
+
+use super::live_state::{
+    live_runtime_is_safe, load_game_mods_path, load_live_runtime_state, load_selected_mods_state,
+};
+use super::projection::{
+    collection_members_from_projected_state, load_projected_collection_state,
+    persist_projected_state, require_collection,
+};
+use super::user_vars::{initial_user_vars, NewCollectionVars};
+use crate::domain::collection::{
+    CollectionMod, CollectionObject, CollectionSummary, CreateCollectionInput,
+    CreateCollectionMode, ProjectedCollectionState,
+};
+use crate::domain::errors::CollectionError;
+use crate::repo::{collection_repo, corridor_repo};
+use crate::services::projected_state_service;
+use sqlx::SqlitePool;
+
+pub async fn create_collection(
+    pool: &SqlitePool,
+    input: CreateCollectionInput,
+) -> Result<CollectionSummary, CollectionError> {
+    insert_collection(pool, input, NewCollectionVars::Capture).await
+}
+
+/// `create_collection`, with `vars` choosing whether the new collection
+/// starts with captured `d3dx_user.ini` values.
+pub(super) async fn insert_collection(
+    pool: &SqlitePool,
+    input: CreateCollectionInput,
+    vars: NewCollectionVars,
+) -> Result<CollectionSummary, CollectionError> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let mods_path = load_game_mods_path(pool, &input.game_id).await?;
+    let save_mode = input.save_mode.unwrap_or({
+        if input.source_collection_id.is_some() {
+            CreateCollectionMode::CloneSnapshot
+        } else if input.mod_ids.is_some() {
+            CreateCollectionMode::SelectedMods
+        } else {
+            CreateCollectionMode::SaveCurrentState
+        }
+    });
+    let (persisted_mods, persisted_objects, projected_state, collection_is_safe) = match save_mode {
+        CreateCollectionMode::CloneSnapshot => {
+            let Some(source_collection_id) = input.source_collection_id.as_deref() else {
+                return Err(CollectionError::Validation(
+                    "Clone snapshot requires a source collection".to_string(),
+                ));
+            };
+            let source = require_collection(pool, source_collection_id).await?;
+            if source.game_id != input.game_id {
+                return Err(CollectionError::Validation(
+                    "Snapshot source does not belong to the active game".to_string(),
+                ));
+            }
+
+            let snapshot =
+                load_projected_collection_state(pool, &source, mods_path.as_deref()).await?;
+            let (mods, objects, _roots) =
+                collection_members_from_projected_state(&id, source.is_safe, &snapshot);
+            (mods, objects, snapshot, source.is_safe)
+        }
+        CreateCollectionMode::SaveCurrentState => {
+            if input.source_collection_id.is_some() {
+                return Err(CollectionError::Validation(
+                    "Save current state cannot use a source collection".to_string(),
+                ));
+            }
+
+            let (mods, objects) = load_live_runtime_state(pool, &input.game_id).await?;
+            if mods.is_empty() {
+                return Err(CollectionError::Validation(
+                    "A collection must contain at least 1 active mod".to_string(),
+                ));
+            }
+            let collection_is_safe = live_runtime_is_safe(pool, &input.game_id).await?;
+            let (persisted_mods, persisted_objects, projected_state) =
+                project_new_members(&id, &mods, &objects, mods_path.as_deref());
+            (
+                persisted_mods,
+                persisted_objects,
+                projected_state,
+                collection_is_safe,
+            )
+        }
+        CreateCollectionMode::SelectedMods => {
+            if input.source_collection_id.is_some() {
+                return Err(CollectionError::Validation(
+                    "Selected mods cannot use a source collection".to_string(),
+                ));
+            }
+            let mod_ids = input.mod_ids.as_deref().unwrap_or_default();
+            let (mods, objects, collection_is_safe) =
+                load_selected_mods_state(pool, &input.game_id, mod_ids).await?;
+            if mods.is_empty() {
+                return Err(CollectionError::Validation(
+                    "A collection must contain at least 1 mod of this game".to_string(),
+                ));
+            }
+            let (persisted_mods, persisted_objects, projected_state) =
+                project_new_members(&id, &mods, &objects, mods_path.as_deref());
+            (
+                persisted_mods,
+                persisted_objects,
+                projected_state,
+                collection_is_safe,
+            )
+        }
+    };
+
+    let user_vars = initial_user_vars(
+        pool,
+        &input,
+        save_mode,
+        mods_path.as_deref(),
+        &persisted_mods,
+        vars,
+    )
+    .await?;
+
+    let mut tx = pool.begin().await?;
+    collection_repo::create_tx(
+        &mut tx,
+        collection_repo::CreateCollectionRow {
+            id: &id,
+            game_id: &input.game_id,
+            name: &input.name,
+            is_safe: collection_is_safe,
+            is_unsaved: false,
+        },
+    )
+    .await?;
+    persist_projected_state(
+        &mut *tx,
+        &id,
+        collection_is_safe,
+        &persisted_mods,
+        &persisted_objects,
+        &projected_state,
+    )
+    .await?;
+    collection_repo::replace_user_vars_tx(&mut tx, &id, &user_vars).await?;
+    tx.commit().await?;
+
+    let collection = require_collection(pool, &id).await?;
+
+    let active_collection_id = corridor_repo::get(pool, &input.game_id, collection.is_safe)
+        .await
+        .map_err(CollectionError::Corridor)?
+        .and_then(|state| state.active_collection_id);
+
+    Ok(collection_repo::to_summary(
+        &collection,
+        active_collection_id.as_deref(),
+    ))
+}
+
+/// Members loaded for a new collection, stamped with its id, and the state
+/// they project.
+fn project_new_members(
+    id: &str,
+    mods: &[CollectionMod],
+    objects: &[CollectionObject],
+    mods_path: Option<&str>,
+) -> (
+    Vec<CollectionMod>,
+    Vec<CollectionObject>,
+    ProjectedCollectionState,
+) {
+    let persisted_mods: Vec<CollectionMod> = mods
+        .iter()
+        .map(|entry| CollectionMod {
+            collection_id: id.to_string(),
+            ..entry.clone()
+        })
+        .collect();
+    let persisted_objects: Vec<CollectionObject> = objects
+        .iter()
+        .map(|entry| CollectionObject {
+            collection_id: id.to_string(),
+            ..entry.clone()
+        })
+        .collect();
+    let projected_state = projected_state_service::build_projected_state(
+        &persisted_mods,
+        &persisted_objects,
+        mods_path,
+    );
+    (persisted_mods, persisted_objects, projected_state)
+}
@@ -1,13 +1,12 @@
-//! Collection lifecycle: list, create, delete, rename/update.
+//! Collection lifecycle: list, delete, rename/update.
 
-use super::live_state::{live_runtime_is_safe, load_game_mods_path, load_live_runtime_state};
+use super::live_state::{load_game_mods_path, load_live_runtime_state};
 use super::projection::{
     collection_members_from_projected_state, compute_signature, load_projected_collection_state,
     persist_projected_state, require_collection, require_game_match,
 };
 use crate::domain::collection::{
-    Collection, CollectionMod, CollectionObject, CollectionSummary, CreateCollectionInput,
-    CreateCollectionMode, ProjectedCollectionState, UpdateCollectionInput,
+    Collection, CollectionSummary, ProjectedCollectionState, UpdateCollectionInput,
 };
 use crate::domain::errors::CollectionError;
 use crate::repo::{collection_repo, corridor_repo};
@@ -38,118 +37,6 @@ pub async fn list_collections(
         .collect())
 }
 
-pub async fn create_collection(
-    pool: &SqlitePool,
-    input: CreateCollectionInput,
-) -> Result<CollectionSummary, CollectionError> {
-    let id = uuid::Uuid::new_v4().to_string();
-    let mods_path = load_game_mods_path(pool, &input.game_id).await?;
-    let save_mode = input.save_mode.unwrap_or({
-        if input.source_collection_id.is_some() {
-            CreateCollectionMode::CloneSnapshot
-        } else {
-            CreateCollectionMode::SaveCurrentState
-        }
-    });
-    let (persisted_mods, persisted_objects, projected_state, collection_is_safe) = match save_mode {
-        CreateCollectionMode::CloneSnapshot => {
-            let Some(source_collection_id) = input.source_collection_id.as_deref() else {
-                return Err(CollectionError::Validation(
-                    "Clone snapshot requires a source collection".to_string(),
-                ));
-            };
-            let source = require_collection(pool, source_collection_id).await?;
-            if source.game_id != input.game_id {
-                return Err(CollectionError::Validation(
-                    "Snapshot source does not belong to the active game".to_string(),
-                ));
-            }
-
-            let snapshot =
-                load_projected_collection_state(pool, &source, mods_path.as_deref()).await?;
-            let (mods, objects, _roots) =
-                collection_members_from_projected_state(&id, source.is_safe, &snapshot);
-            (mods, objects, snapshot, source.is_safe)
-        }
-        CreateCollectionMode::SaveCurrentState => {
-            if input.source_collection_id.is_some() {
-                return Err(CollectionError::Validation(
-                    "Save current state cannot use a source collection".to_string(),
-                ));
-            }
-
-            let (mods, objects) = load_live_runtime_state(pool, &input.game_id).await?;
-            if mods.is_empty() {
-                return Err(CollectionError::Validation(
-                    "A collection must contain at least 1 active mod".to_string(),
-                ));
-            }
-            let collection_is_safe = live_runtime_is_safe(pool, &input.game_id).await?;
-
-            let persisted_mods: Vec<CollectionMod> = mods
-                .iter()
-                .map(|entry| CollectionMod {
-                    collection_id: id.clone(),
-                    ..entry.clone()
-                })
-                .collect();
-            let persisted_objects: Vec<CollectionObject> = objects
-                .iter()
-                .map(|entry| CollectionObject {
-                    collection_id: id.clone(),
-                    ..entry.clone()
-                })
-                .collect();
-            let projected_state = projected_state_service::build_projected_state(
-                &persisted_mods,
-                &persisted_objects,
-                mods_path.as_deref(),
-            );
-            (
-                persisted_mods,
-                persisted_objects,
-                projected_state,
-                collection_is_safe,
-            )
-        }
-    };
-
-    let mut tx = pool.begin().await?;
-    collection_repo::create_tx(
-        &mut tx,
-        collection_repo::CreateCollectionRow {
-            id: &id,
-            game_id: &input.game_id,
-            name: &input.name,
-            is_safe: collection_is_safe,
-            is_unsaved: false,
-        },
-    )
-    .await?;
-    persist_projected_state(
-        &mut *tx,
-        &id,
-        collection_is_safe,
-        &persisted_mods,
-        &persisted_objects,
-        &projected_state,
-    )
-    .await?;
-    tx.commit().await?;
-
-    let collection = require_collection(pool, &id).await?;
-
-    let active_collection_id = corridor_repo::get(pool, &input.game_id, collection.is_safe)
-        .await
-        .map_err(CollectionError::Corridor)?
-        .and_then(|state| state.active_collection_id);
-
-    Ok(collection_repo::to_summary(
-        &collection,
-        active_collection_id.as_deref(),
-    ))
-}
-
 pub async fn delete_collection(pool: &SqlitePool, id: &str) -> Result<(), CollectionError> {
     let collection = require_collection(pool, id).await?;
     if collection.is_unsaved {
@@ -166,6 +53,7 @@ pub async fn delete_collection(pool: &SqlitePool, id: &str) -> Result<(), Collec
     corridor_repo::clear_collection_references_tx(&mut tx, id)
         .await
         .map_err(CollectionError::Corridor)?;
+    collection_repo::detach_children_tx(&mut tx, id).await?;
     collection_repo::delete_tx(&mut tx, id).await?;
     tx.commit().await?;
 
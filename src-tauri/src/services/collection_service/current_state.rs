@@ -1,10 +1,12 @@
 //! Snapshots taken from the live runtime: the synthetic "Current Runtime"
 //! summary and overwriting an existing collection with current state.
 
+use super::layers::refresh_layered_descendants;
 use super::live_state::{
     live_runtime_is_safe, load_game_mods_path, load_live_corridor_state, load_live_runtime_state,
 };
 use super::projection::{persist_projected_state, require_collection, require_game_match};
+use super::user_vars::capture_live_user_vars;
 use crate::domain::collection::{CollectionMod, CollectionObject, CollectionSummary};
 use crate::domain::errors::CollectionError;
 use crate::repo::{collection_repo, corridor_repo};
@@ -28,6 +30,7 @@ pub async fn handle_dirty_state(
         signature: Some(signature),
         updated_at: chrono::Utc::now().to_rfc3339(),
         mod_count: projected_state.summary.active_root_count as i32,
+        parent_collection_id: None,
     })
 }
 
@@ -72,8 +75,15 @@ pub async fn replace_collection_with_current_state(
         &persisted_objects,
         mods_path.as_deref(),
     );
+    let user_vars =
+        capture_live_user_vars(pool, game_id, mods_path.as_deref(), &persisted_mods).await?;
+    // The current state replaces whatever the collection was layered on.
+    let mut tx = pool.begin().await?;
+    if collection.parent_collection_id.is_some() {
+        collection_repo::set_layers_tx(&mut tx, &collection.id, None, &[]).await?;
+    }
     persist_projected_state(
-        pool,
+        &mut *tx,
         &collection.id,
         collection.is_safe,
         &persisted_mods,
@@ -81,8 +91,11 @@ pub async fn replace_collection_with_current_state(
         &projected_state,
     )
     .await?;
+    collection_repo::replace_user_vars_tx(&mut tx, &collection.id, &user_vars).await?;
+    tx.commit().await?;
 
     let updated = require_collection(pool, &collection.id).await?;
+    refresh_layered_descendants(pool, &updated).await?;
     let corridor = corridor_repo::get(pool, game_id, collection.is_safe)
         .await
         .map_err(CollectionError::Corridor)?;
@@ -0,0 +1,246 @@
+//! Read-only comparison of two loadouts, or three against a common base.
+//!
+//! `preview_apply` only answers "what would applying this do to the live
+//! state". These compare any collections, or a collection and the live state,
+//! through their projected states, so the per-object answer matches what
+//! apply would enable.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::live_state::load_live_runtime_state;
+use super::projection::{load_projected_collection_state, require_collection, require_game_match};
+use crate::common::path_key::canonical_name_key;
+use crate::domain::collection::{
+    CollectionDiff, CollectionDiffSource, ObjectDiff, ProjectedActiveRoot,
+    ProjectedCollectionState, RootVariantChange, ThreeWayCollectionDiff, ThreeWayObjectDiff,
+    ThreeWayStatus,
+};
+use crate::domain::errors::CollectionError;
+use crate::services::projected_state_service;
+use sqlx::SqlitePool;
+
+const CURRENT_STATE_NAME: &str = "Current state";
+
+async fn load_side(
+    pool: &SqlitePool,
+    game_id: &str,
+    source: &CollectionDiffSource,
+    mods_path: Option<&str>,
+) -> Result<(String, ProjectedCollectionState), CollectionError> {
+    match source {
+        CollectionDiffSource::Current => {
+            let (mods, objects) = load_live_runtime_state(pool, game_id).await?;
+            let state = projected_state_service::build_projected_state(&mods, &objects, mods_path);
+            Ok((CURRENT_STATE_NAME.to_string(), state))
+        }
+        CollectionDiffSource::Collection { collection_id } => {
+            let collection = require_collection(pool, collection_id).await?;
+            require_game_match(&collection, game_id)?;
+            let state = load_projected_collection_state(pool, &collection, mods_path).await?;
+            Ok((collection.name, state))
+        }
+    }
+}
+
+/// One object's side of a comparison: whether it is enabled, if present, and
+/// its active roots by key.
+struct ObjectSide<'a> {
+    is_enabled: Option<bool>,
+    roots: Vec<&'a ProjectedActiveRoot>,
+}
+
+impl ObjectSide<'_> {
+    fn root_keys(&self) -> BTreeSet<&str> {
+        self.roots
+            .iter()
+            .map(|root| root.root_key.as_str())
+            .collect()
+    }
+}
+
+/// Every object either state mentions, with its display name.
+fn object_names<'a>(states: &[&'a ProjectedCollectionState]) -> Vec<(&'a str, &'a str)> {
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    for state in states {
+        for object in &state.object_states {
+            names
+                .entry(object.object_id.as_str())
+                .or_insert(object.display_name.as_str());
+        }
+        for root in &state.active_roots {
+            names
+                .entry(root.object_id.as_str())
+                .or_insert(root.object_id.as_str());
+        }
+    }
+    let mut names: Vec<(&str, &str)> = names.into_iter().collect();
+    names.sort_by_cached_key(|(object_id, name)| (canonical_name_key(name), *object_id));
+    names
+}
+
+fn object_side<'a>(state: &'a ProjectedCollectionState, object_id: &str) -> ObjectSide<'a> {
+    ObjectSide {
+        is_enabled: state
+            .object_states
+            .iter()
+            .find(|object| object.object_id == object_id)
+            .map(|object| object.is_enabled),
+        roots: state
+            .active_roots
+            .iter()
+            .filter(|root| root.object_id == object_id)
+            .collect(),
+    }
+}
+
+/// How `object_id` differs from `base` to `target`; `None` when it does not.
+///
+/// A removed and an added root of the same object pair up as a variant
+/// change, in name order: swapping one skin for another reads as one change,
+/// not two.
+fn diff_object(
+    object_id: &str,
+    display_name: &str,
+    base: &ObjectSide,
+    target: &ObjectSide,
+) -> Option<ObjectDiff> {
+    let base_keys = base.root_keys();
+    let target_keys = target.root_keys();
+    let mut removed: Vec<ProjectedActiveRoot> = base
+        .roots
+        .iter()
+        .filter(|root| !target_keys.contains(root.root_key.as_str()))
+        .map(|root| (*root).clone())
+        .collect();
+    let mut added: Vec<ProjectedActiveRoot> = target
+        .roots
+        .iter()
+        .filter(|root| !base_keys.contains(root.root_key.as_str()))
+        .map(|root| (*root).clone())
+        .collect();
+    let enabled_changed = matches!(
+        (base.is_enabled, target.is_enabled),
+        (Some(before), Some(after)) if before != after
+    );
+    if removed.is_empty() && added.is_empty() && !enabled_changed {
+        return None;
+    }
+
+    let paired = removed.len().min(added.len());
+    let changed = removed
+        .drain(..paired)
+        .zip(added.drain(..paired))
+        .map(|(from, to)| RootVariantChange { from, to })
+        .collect();
+    Some(ObjectDiff {
+        object_id: object_id.to_string(),
+        display_name: display_name.to_string(),
+        base_enabled: base.is_enabled,
+        target_enabled: target.is_enabled,
+        added,
+        removed,
+        changed,
+    })
+}
+
+/// Per-object differences from `base` to `target`, ordered by object name.
+pub fn diff_projected_states(
+    base: &ProjectedCollectionState,
+    target: &ProjectedCollectionState,
+) -> Vec<ObjectDiff> {
+    object_names(&[base, target])
+        .into_iter()
+        .filter_map(|(object_id, display_name)| {
+            diff_object(
+                object_id,
+                display_name,
+                &object_side(base, object_id),
+                &object_side(target, object_id),
+            )
+        })
+        .collect()
+}
+
+/// Per-object changes `ours` and `theirs` each made to `base`, ordered by
+/// object name.
+pub fn diff_projected_states_three_way(
+    base: &ProjectedCollectionState,
+    ours: &ProjectedCollectionState,
+    theirs: &ProjectedCollectionState,
+) -> Vec<ThreeWayObjectDiff> {
+    object_names(&[base, ours, theirs])
+        .into_iter()
+        .filter_map(|(object_id, display_name)| {
+            let base_side = object_side(base, object_id);
+            let ours_side = object_side(ours, object_id);
+            let theirs_side = object_side(theirs, object_id);
+            let ours_diff = diff_object(object_id, display_name, &base_side, &ours_side);
+            let theirs_diff = diff_object(object_id, display_name, &base_side, &theirs_side);
+            let status = match (&ours_diff, &theirs_diff) {
+                (None, None) => return None,
+                (Some(_), None) => ThreeWayStatus::Ours,
+                (None, Some(_)) => ThreeWayStatus::Theirs,
+                (Some(_), Some(_))
+                    if ours_side.root_keys() == theirs_side.root_keys()
+                        && ours_side.is_enabled == theirs_side.is_enabled =>
+                {
+                    ThreeWayStatus::Both
+                }
+                (Some(_), Some(_)) => ThreeWayStatus::Conflict,
+            };
+            Some(ThreeWayObjectDiff {
+                object_id: object_id.to_string(),
+                display_name: display_name.to_string(),
+                status,
+                ours: ours_diff,
+                theirs: theirs_diff,
+            })
+        })
+        .collect()
+}
+
+/// What switching from `base` to `target` would change.
+pub async fn diff_collections(
+    pool: &SqlitePool,
+    game_id: &str,
+    base: &CollectionDiffSource,
+    target: &CollectionDiffSource,
+    mods_path: Option<&str>,
+) -> Result<CollectionDiff, CollectionError> {
+    let (base_name, base_state) = load_side(pool, game_id, base, mods_path).await?;
+    let (target_name, target_state) = load_side(pool, game_id, target, mods_path).await?;
+
+    Ok(CollectionDiff {
+        base_name,
+        target_name,
+        objects: diff_projected_states(&base_state, &target_state),
+        base_tree_nodes: projected_state_service::build_preview_tree_from_projected_state(
+            &base_state,
+        ),
+        target_tree_nodes: projected_state_service::build_preview_tree_from_projected_state(
+            &target_state,
+        ),
+    })
+}
+
+/// What `ours` and `theirs` each changed relative to `base`, and where they
+/// disagree.
+pub async fn compare_collections_three_way(
+    pool: &SqlitePool,
+    game_id: &str,
+    base: &CollectionDiffSource,
+    ours: &CollectionDiffSource,
+    theirs: &CollectionDiffSource,
+    mods_path: Option<&str>,
+) -> Result<ThreeWayCollectionDiff, CollectionError> {
+    let (base_name, base_state) = load_side(pool, game_id, base, mods_path).await?;
+    let (ours_name, ours_state) = load_side(pool, game_id, ours, mods_path).await?;
+    let (theirs_name, theirs_state) = load_side(pool, game_id, theirs, mods_path).await?;
+
+    Ok(ThreeWayCollectionDiff {
+        base_name,
+        ours_name,
+        theirs_name,
+        objects: diff_projected_states_three_way(&base_state, &ours_state, &theirs_state),
+    })
+}
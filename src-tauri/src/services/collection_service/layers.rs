@@ -0,0 +1,197 @@
+//! Layered collections: a collection that builds on a parent and records only
+//! what it changes.
+//!
+//! The overlays are the source of truth; the member tables hold the set they
+//! resolve to, so apply, previews, bundles and signature matching read a
+//! layered collection like any other. Whenever a parent's members change,
+//! its descendants are re-resolved.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::live_state::load_game_mods_path;
+use super::projection::{
+    load_parent_chain, persist_projected_state, require_collection, require_game_match,
+    resolve_layered_members,
+};
+use crate::domain::collection::{
+    Collection, CollectionLayers, CollectionSummary, OverlayTargetKind, SetCollectionLayersInput,
+};
+use crate::domain::errors::CollectionError;
+use crate::repo::{collection_repo, corridor_repo};
+use crate::services::projected_state_service;
+use sqlx::SqlitePool;
+
+/// A collection's parent and overlays.
+pub async fn get_collection_layers(
+    pool: &SqlitePool,
+    game_id: &str,
+    collection_id: &str,
+) -> Result<CollectionLayers, CollectionError> {
+    let collection = require_collection(pool, collection_id).await?;
+    require_game_match(&collection, game_id)?;
+    Ok(CollectionLayers {
+        parent_collection_id: collection.parent_collection_id,
+        overlays: collection_repo::get_overlays(pool, collection_id).await?,
+    })
+}
+
+/// Layers a collection on a parent with the given overlays, or makes it plain
+/// again when no parent is given. A collection made plain keeps the members it
+/// last resolved to.
+pub async fn set_collection_layers(
+    pool: &SqlitePool,
+    input: SetCollectionLayersInput,
+) -> Result<CollectionSummary, CollectionError> {
+    let collection = require_collection(pool, &input.id).await?;
+    require_game_match(&collection, &input.game_id)?;
+    if collection.is_unsaved {
+        return Err(CollectionError::Validation(
+            "Cannot layer an unsaved collection snapshot".to_string(),
+        ));
+    }
+    match input.parent_collection_id.as_deref() {
+        Some(parent_id) => {
+            validate_parent(pool, &collection, parent_id).await?;
+            validate_mod_overlays(pool, &collection, &input).await?;
+        }
+        None if !input.overlays.is_empty() => {
+            return Err(CollectionError::Validation(
+                "Overlays need a parent collection".to_string(),
+            ));
+        }
+        None => {}
+    }
+
+    let mut tx = pool.begin().await?;
+    collection_repo::set_layers_tx(
+        &mut tx,
+        &collection.id,
+        input.parent_collection_id.as_deref(),
+        &input.overlays,
+    )
+    .await?;
+    tx.commit().await?;
+
+    let mods_path = load_game_mods_path(pool, &collection.game_id).await?;
+    let updated = require_collection(pool, &collection.id).await?;
+    materialize_layered_collection(pool, &updated, mods_path.as_deref()).await?;
+    refresh_layered_descendants(pool, &updated).await?;
+
+    let updated = require_collection(pool, &collection.id).await?;
+    let corridor = corridor_repo::get(pool, &collection.game_id, collection.is_safe)
+        .await
+        .map_err(CollectionError::Corridor)?;
+    let active_id = corridor
+        .as_ref()
+        .and_then(|state| state.active_collection_id.as_deref());
+    Ok(collection_repo::to_summary(&updated, active_id))
+}
+
+async fn validate_parent(
+    pool: &SqlitePool,
+    collection: &Collection,
+    parent_id: &str,
+) -> Result<(), CollectionError> {
+    let parent = require_collection(pool, parent_id).await?;
+    require_game_match(&parent, &collection.game_id)?;
+    if parent.is_unsaved {
+        return Err(CollectionError::Validation(
+            "Cannot layer on an unsaved collection snapshot".to_string(),
+        ));
+    }
+    if parent.is_safe != collection.is_safe {
+        return Err(CollectionError::Validation(format!(
+            "Collection '{}' is in the other corridor",
+            parent.name
+        )));
+    }
+    let chain = load_parent_chain(pool, &parent).await?;
+    if parent.id == collection.id || chain.iter().any(|layer| layer.id == collection.id) {
+        return Err(CollectionError::Validation(format!(
+            "Collection '{}' cannot be layered on itself",
+            collection.name
+        )));
+    }
+    Ok(())
+}
+
+/// Every mod an overlay enables must exist and belong to the collection's
+/// corridor.
+async fn validate_mod_overlays(
+    pool: &SqlitePool,
+    collection: &Collection,
+    input: &SetCollectionLayersInput,
+) -> Result<(), CollectionError> {
+    let enabled_ids: Vec<String> = input
+        .overlays
+        .iter()
+        .filter(|overlay| overlay.target_kind == OverlayTargetKind::Mod && overlay.is_enabled)
+        .map(|overlay| overlay.target_key.clone())
+        .collect();
+    if enabled_ids.is_empty() {
+        return Ok(());
+    }
+    let rows =
+        collection_repo::get_mod_rows_by_ids(pool, &collection.game_id, &enabled_ids).await?;
+    if let Some(missing) = enabled_ids
+        .iter()
+        .find(|id| !rows.iter().any(|row| &row.row.mod_id == *id))
+    {
+        return Err(CollectionError::Validation(format!(
+            "Mod '{missing}' is not in this library"
+        )));
+    }
+    if collection.is_safe {
+        if let Some(unsafe_row) = rows.iter().find(|row| !row.is_safe) {
+            return Err(CollectionError::Validation(format!(
+                "Mod '{}' cannot be enabled in a safe collection",
+                unsafe_row.row.display_name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Re-resolves `collection` and stores the result as its members. Plain
+/// collections are left alone.
+async fn materialize_layered_collection(
+    pool: &SqlitePool,
+    collection: &Collection,
+    mods_path: Option<&str>,
+) -> Result<(), CollectionError> {
+    let Some((mods, objects)) = resolve_layered_members(pool, collection).await? else {
+        return Ok(());
+    };
+    let state = projected_state_service::build_projected_state(&mods, &objects, mods_path);
+    persist_projected_state(
+        pool,
+        &collection.id,
+        collection.is_safe,
+        &mods,
+        &objects,
+        &state,
+    )
+    .await
+}
+
+/// Re-resolves every collection layered, directly or not, on `collection`,
+/// parents before children.
+pub(crate) async fn refresh_layered_descendants(
+    pool: &SqlitePool,
+    collection: &Collection,
+) -> Result<(), CollectionError> {
+    let mods_path = load_game_mods_path(pool, &collection.game_id).await?;
+    let mut seen = HashSet::from([collection.id.clone()]);
+    let mut queue = VecDeque::from([collection.id.clone()]);
+    while let Some(parent_id) = queue.pop_front() {
+        for child_id in collection_repo::list_child_ids(pool, &parent_id).await? {
+            if !seen.insert(child_id.clone()) {
+                continue;
+            }
+            let child = require_collection(pool, &child_id).await?;
+            materialize_layered_collection(pool, &child, mods_path.as_deref()).await?;
+            queue.push_back(child_id);
+        }
+    }
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use crate::common::normalizer::is_disabled_folder;
 use crate::domain::collection::{CollectionMod, CollectionObject};
 use crate::domain::errors::CollectionError;
-use crate::repo::collection_repo;
+use crate::repo::collection_repo::{self, LiveActiveModRow};
 use crate::services::collection_preview_tree::resolve_preview_terminal_metadata;
 use sqlx::SqlitePool;
 
@@ -39,27 +39,69 @@ async fn load_live_state(
     is_safe: Option<bool>,
 ) -> Result<(Vec<CollectionMod>, Vec<CollectionObject>), CollectionError> {
     let mods_path = load_game_mods_path(pool, game_id).await?;
-    let current_objects = collection_repo::get_live_objects(pool, game_id).await?;
-    let current_objects: Vec<CollectionObject> = current_objects
+    let current_objects = load_live_objects(pool, game_id).await?;
+    let current_mod_rows =
+        collection_repo::get_live_active_mod_rows(pool, game_id, is_safe).await?;
+    let current_mods = shape_mod_rows(current_mod_rows, &current_objects, mods_path.as_deref());
+
+    Ok((current_mods, current_objects))
+}
+
+/// The mods `mod_ids` names, enabled or not, shaped as members of a new
+/// collection alongside the game's live objects. The flag is whether every
+/// picked mod is safe.
+pub(crate) async fn load_selected_mods_state(
+    pool: &SqlitePool,
+    game_id: &str,
+    mod_ids: &[String],
+) -> Result<(Vec<CollectionMod>, Vec<CollectionObject>, bool), CollectionError> {
+    let mods_path = load_game_mods_path(pool, game_id).await?;
+    let rows = collection_repo::get_mod_rows_by_ids(pool, game_id, mod_ids).await?;
+    let is_safe = rows.iter().all(|selected| selected.is_safe);
+    let rows: Vec<LiveActiveModRow> = rows.into_iter().map(|selected| selected.row).collect();
+    // A picked mod's object is part of the loadout even if its folder is
+    // disabled right now.
+    let objects = load_live_objects(pool, game_id)
+        .await?
+        .into_iter()
+        .map(|object| CollectionObject {
+            is_enabled: object.is_enabled
+                || rows.iter().any(|row| row.object_id == object.object_id),
+            ..object
+        })
+        .collect::<Vec<_>>();
+    let mods = shape_mod_rows(rows, &objects, mods_path.as_deref());
+
+    Ok((mods, objects, is_safe))
+}
+
+pub(crate) async fn load_live_objects(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<CollectionObject>, CollectionError> {
+    let objects = collection_repo::get_live_objects(pool, game_id).await?;
+    Ok(objects
         .into_iter()
         .map(|object| CollectionObject {
             is_enabled: is_object_enabled(object.path_key.as_deref()),
             ..object
         })
-        .collect();
-    let current_mod_rows =
-        collection_repo::get_live_active_mod_rows(pool, game_id, is_safe).await?;
+        .collect())
+}
 
-    let mut current_mods = Vec::with_capacity(current_mod_rows.len());
-    for row in current_mod_rows {
+fn shape_mod_rows(
+    rows: Vec<LiveActiveModRow>,
+    objects: &[CollectionObject],
+    mods_path: Option<&str>,
+) -> Vec<CollectionMod> {
+    let mut mods = Vec::with_capacity(rows.len());
+    for row in rows {
         let mod_id = row.mod_id;
         let mod_path = row.mod_path;
         let mod_path_key = row.mod_path_key;
         let object_id = row.object_id;
         let display_name = row.display_name;
-        let preview_object = current_objects
-            .iter()
-            .find(|object| object.object_id == object_id);
+        let preview_object = objects.iter().find(|object| object.object_id == object_id);
         let preview_seed = CollectionMod {
             kind: crate::domain::collection::MemberKind::Mod,
             collection_id: String::new(),
@@ -74,9 +116,9 @@ async fn load_live_state(
             is_enabled: true,
         };
         let preview_metadata =
-            resolve_preview_terminal_metadata(preview_object, &preview_seed, mods_path.as_deref());
+            resolve_preview_terminal_metadata(preview_object, &preview_seed, mods_path);
 
-        current_mods.push(CollectionMod {
+        mods.push(CollectionMod {
             kind: crate::domain::collection::MemberKind::Mod,
             collection_id: String::new(),
             mod_id: Some(mod_id),
@@ -90,8 +132,7 @@ async fn load_live_state(
             is_enabled: true,
         });
     }
-
-    Ok((current_mods, current_objects))
+    mods
 }
 
 pub(super) async fn live_runtime_is_safe(
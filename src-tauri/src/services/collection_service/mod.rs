@@ -3,22 +3,33 @@
 //! is re-exported here.
 
 mod apply;
+mod bundle;
+mod bundle_resolve;
+mod create;
 mod crud;
 mod current_state;
+mod diff;
+mod layers;
 mod live_state;
 mod path_transition;
 mod preview;
 mod projection;
 mod references;
+mod user_vars;
 
 pub use apply::*;
+pub use bundle::*;
+pub use create::*;
 pub use crud::*;
 pub use current_state::*;
+pub use diff::*;
+pub use layers::*;
 pub(crate) use live_state::*;
 pub(crate) use path_transition::*;
 pub use preview::*;
 pub use projection::*;
 pub use references::*;
+pub use user_vars::*;
 
 #[cfg(test)]
 mod tests;
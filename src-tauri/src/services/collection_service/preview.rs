@@ -1,8 +1,17 @@
 //! Read-only previews: collection contents and the apply diff.
 
+use std::collections::HashSet;
+
+use super::apply::resolve_apply_scope;
 use super::live_state::load_live_runtime_state;
-use super::projection::{load_projected_collection_state, require_collection, require_game_match};
-use crate::domain::collection::{ApplyPreview, CollectionPreview};
+use super::projection::{
+    collection_members_from_projected_state, load_projected_collection_state, require_collection,
+    require_game_match,
+};
+use crate::domain::collection::{
+    ApplyPreview, ApplyScope, Collection, CollectionMod, CollectionObject, CollectionPreview,
+    ProjectedCollectionState,
+};
 use crate::domain::errors::CollectionError;
 use crate::repo::collection_repo;
 use crate::services::projected_state_service;
@@ -43,6 +52,7 @@ pub async fn preview_apply(
     collection_id: &str,
     corridor: crate::domain::corridor::Corridor,
     mods_path: Option<&str>,
+    scope: &ApplyScope,
 ) -> Result<ApplyPreview, CollectionError> {
     let is_safe = corridor.is_safe();
     let collection = require_collection(pool, collection_id).await?;
@@ -70,6 +80,17 @@ pub async fn preview_apply(
     let current_tree_nodes =
         projected_state_service::build_preview_tree_from_projected_state(&current_projected_state);
     let target_state = load_projected_collection_state(pool, &collection, mods_path).await?;
+    let target_state = match resolve_apply_scope(pool, game_id, scope).await? {
+        Some(object_ids) => scoped_target_state(
+            &collection,
+            &target_state,
+            &current_mods,
+            &current_objects,
+            &object_ids,
+            mods_path,
+        ),
+        None => target_state,
+    };
 
     Ok(ApplyPreview {
         collection_name: collection.name,
@@ -83,3 +104,29 @@ pub async fn preview_apply(
         target_projected_state: target_state,
     })
 }
+
+/// The runtime a scoped apply leaves behind: the collection's mods for the
+/// scoped objects, the current ones everywhere else. Objects keep their
+/// current state; apply only toggles mods.
+fn scoped_target_state(
+    collection: &Collection,
+    target_state: &ProjectedCollectionState,
+    current_mods: &[CollectionMod],
+    current_objects: &[CollectionObject],
+    object_ids: &HashSet<String>,
+    mods_path: Option<&str>,
+) -> ProjectedCollectionState {
+    let (target_mods, _, _) =
+        collection_members_from_projected_state(&collection.id, collection.is_safe, target_state);
+    let mods: Vec<CollectionMod> = current_mods
+        .iter()
+        .filter(|member| !object_ids.contains(&member.object_id))
+        .cloned()
+        .chain(
+            target_mods
+                .into_iter()
+                .filter(|member| object_ids.contains(&member.object_id)),
+        )
+        .collect();
+    projected_state_service::build_projected_state(&mods, current_objects, mods_path)
+}
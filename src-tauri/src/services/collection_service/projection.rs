@@ -1,7 +1,11 @@
 //! Projected-state loading and signature computation for a collection.
 
+use std::collections::HashSet;
+
+use super::live_state::{load_live_objects, load_selected_mods_state};
 use crate::domain::collection::{
-    Collection, CollectionMod, CollectionObject, CollectionRoot, ProjectedCollectionState,
+    Collection, CollectionMod, CollectionObject, CollectionOverlay, CollectionRoot,
+    OverlayTargetKind, ProjectedCollectionState,
 };
 use crate::domain::errors::CollectionError;
 use crate::repo::collection_repo;
@@ -13,6 +17,15 @@ pub(crate) async fn load_projected_collection_state(
     collection: &crate::domain::collection::Collection,
     mods_path: Option<&str>,
 ) -> Result<ProjectedCollectionState, CollectionError> {
+    // A layered collection's stored members are only as fresh as its parent
+    // was when they were written, so it is resolved on every load. Storing
+    // the result is left to the mutations that move it.
+    if let Some((mods, objects)) = resolve_layered_members(pool, collection).await? {
+        return Ok(projected_state_service::build_projected_state(
+            &mods, &objects, mods_path,
+        ));
+    }
+
     if mods_path.is_none() {
         if let Some(snapshot_json) = collection.snapshot_json.as_deref() {
             if let Some(snapshot) = projected_state_service::parse_snapshot_json(snapshot_json) {
@@ -50,6 +63,164 @@ pub(crate) async fn load_projected_collection_state(
     Ok(snapshot)
 }
 
+/// The parents `collection` is layered on, nearest first. A parent that no
+/// longer exists ends the chain; one that leads back to `collection` is an
+/// error.
+pub(crate) async fn load_parent_chain(
+    pool: &SqlitePool,
+    collection: &Collection,
+) -> Result<Vec<Collection>, CollectionError> {
+    let mut chain: Vec<Collection> = Vec::new();
+    let mut seen = HashSet::from([collection.id.clone()]);
+    let mut next = collection.parent_collection_id.clone();
+    while let Some(parent_id) = next {
+        if !seen.insert(parent_id.clone()) {
+            return Err(CollectionError::Validation(format!(
+                "Collection '{}' is layered on itself",
+                collection.name
+            )));
+        }
+        let Some(parent) = collection_repo::get_by_id(pool, &parent_id).await? else {
+            break;
+        };
+        next = parent.parent_collection_id.clone();
+        chain.push(parent);
+    }
+    Ok(chain)
+}
+
+/// A layered collection's effective members: its topmost ancestor's members
+/// with each layer's overlays applied on the way down, stamped with
+/// `collection`'s id. `None` for a plain collection.
+pub(crate) async fn resolve_layered_members(
+    pool: &SqlitePool,
+    collection: &Collection,
+) -> Result<Option<(Vec<CollectionMod>, Vec<CollectionObject>)>, CollectionError> {
+    if collection.parent_collection_id.is_none() {
+        return Ok(None);
+    }
+    let chain = load_parent_chain(pool, collection).await?;
+    let Some(base) = chain.last() else {
+        return Ok(None);
+    };
+
+    let mut mods = collection_repo::get_mods(pool, &base.id).await?;
+    let mut objects = collection_repo::get_objects(pool, &base.id).await?;
+    for layer in chain
+        .iter()
+        .rev()
+        .skip(1)
+        .chain(std::iter::once(collection))
+    {
+        let overlays = collection_repo::get_overlays(pool, &layer.id).await?;
+        apply_overlays(
+            pool,
+            &collection.game_id,
+            &overlays,
+            &mut mods,
+            &mut objects,
+        )
+        .await?;
+    }
+
+    for member in &mut mods {
+        member.collection_id = collection.id.clone();
+    }
+    for object in &mut objects {
+        object.collection_id = collection.id.clone();
+    }
+    Ok(Some((mods, objects)))
+}
+
+/// Applies one layer's overlays. Mods go first so that an object overlay in
+/// the same layer wins over the object a mod overlay switched on.
+async fn apply_overlays(
+    pool: &SqlitePool,
+    game_id: &str,
+    overlays: &[CollectionOverlay],
+    mods: &mut Vec<CollectionMod>,
+    objects: &mut Vec<CollectionObject>,
+) -> Result<(), CollectionError> {
+    let mod_ids: Vec<String> = overlays
+        .iter()
+        .filter(|overlay| overlay.target_kind == OverlayTargetKind::Mod)
+        .map(|overlay| overlay.target_key.clone())
+        .collect();
+    let (library_mods, mut live_objects) = if mod_ids.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        let (library_mods, live_objects, _) =
+            load_selected_mods_state(pool, game_id, &mod_ids).await?;
+        (library_mods, live_objects)
+    };
+    if live_objects.is_empty()
+        && overlays
+            .iter()
+            .any(|overlay| overlay.target_kind == OverlayTargetKind::Object)
+    {
+        live_objects = load_live_objects(pool, game_id).await?;
+    }
+
+    for overlay in overlays
+        .iter()
+        .filter(|overlay| overlay.target_kind == OverlayTargetKind::Mod)
+    {
+        let library_mod = library_mods
+            .iter()
+            .find(|entry| entry.mod_id.as_deref() == Some(overlay.target_key.as_str()));
+        let library_key = library_mod.and_then(|entry| entry.mod_path_key.as_deref());
+        // Members saved before the mod had an id are matched by its path key.
+        mods.retain(|member| {
+            member.mod_id.as_deref() != Some(overlay.target_key.as_str())
+                && (library_key.is_none() || member.mod_path_key.as_deref() != library_key)
+        });
+        let Some(library_mod) = library_mod.filter(|_| overlay.is_enabled) else {
+            continue;
+        };
+        mods.push(library_mod.clone());
+        set_object_enabled(objects, &live_objects, &library_mod.object_id, true);
+    }
+
+    for overlay in overlays
+        .iter()
+        .filter(|overlay| overlay.target_kind == OverlayTargetKind::Object)
+    {
+        set_object_enabled(
+            objects,
+            &live_objects,
+            &overlay.target_key,
+            overlay.is_enabled,
+        );
+    }
+    Ok(())
+}
+
+/// Enables or disables `object_id`, taking it from the live objects when the
+/// members do not have it yet. Unknown objects are ignored.
+fn set_object_enabled(
+    objects: &mut Vec<CollectionObject>,
+    live_objects: &[CollectionObject],
+    object_id: &str,
+    is_enabled: bool,
+) {
+    if let Some(object) = objects
+        .iter_mut()
+        .find(|object| object.object_id == object_id)
+    {
+        object.is_enabled = is_enabled;
+        return;
+    }
+    if let Some(object) = live_objects
+        .iter()
+        .find(|object| object.object_id == object_id)
+    {
+        objects.push(CollectionObject {
+            is_enabled,
+            ..object.clone()
+        });
+    }
+}
+
 /// Persist a collection's members plus everything derived from its projected
 /// state (roots, signature, snapshot JSON, display count) in one transaction.
 pub(crate) async fn persist_projected_state<'a, A>(
@@ -52,7 +52,10 @@ async fn apply_collection_returns_missing_mods_before_disk_mutation_when_not_ign
     .await
     .expect("persist collection state");
 
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
     let result = apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: &ctx.pool,
         game_id: "game-1",
         collection_id: &collection.id,
@@ -62,6 +65,7 @@ async fn apply_collection_returns_missing_mods_before_disk_mutation_when_not_ign
         ignore_missing: false,
         settings: AppSettings::default(),
         reconcile_lock: None,
+        scope: ApplyScope::Full,
     })
     .await;
 
@@ -143,7 +147,10 @@ async fn partial_apply_skips_missing_paths_without_replacing_original_collection
     .await
     .expect("persist collection state");
 
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
     let result = apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: &ctx.pool,
         game_id: "game-1",
         collection_id: &collection.id,
@@ -153,6 +160,7 @@ async fn partial_apply_skips_missing_paths_without_replacing_original_collection
         ignore_missing: true,
         settings: AppSettings::default(),
         reconcile_lock: None,
+        scope: ApplyScope::Full,
     })
     .await
     .expect("partial apply succeeds");
@@ -250,7 +258,10 @@ async fn apply_collection_rejects_cross_corridor_request() {
 
     // Corridor enforcement: applying an UNSAFE collection while the request is
     // in the SAFE corridor must be rejected before any filesystem mutation.
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
     let result = apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: &ctx.pool,
         game_id: "game-apply-no-mode",
         collection_id: &collection.id,
@@ -260,6 +271,7 @@ async fn apply_collection_rejects_cross_corridor_request() {
         ignore_missing: false,
         settings: AppSettings::default(),
         reconcile_lock: None,
+        scope: ApplyScope::Full,
     })
     .await;
 
@@ -0,0 +1,402 @@
+use super::*;
+use crate::domain::collection::BundleMatch;
+use crate::repo::object_repo;
+use crate::services::collection_service::{
+    export_collection_bundle, import_collection_bundle, resolve_collection_bundle,
+};
+
+const BLUE_INI: &str = "[TextureOverrideBlueBody]\nhash = 0000b1e0\n";
+const RED_INI: &str =
+    "[TextureOverrideRedBody]\nhash = 0000ed00\n[TextureOverrideRedHair]\nhash = 0000ed01\n";
+
+async fn seed_library_game(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+    game_type: GameType,
+    mods_root: &str,
+) {
+    insert_test_game(
+        pool,
+        &TestGameFixture {
+            id,
+            name: id,
+            game_type,
+            path: mods_root,
+            mods_path: Some(mods_root),
+        },
+    )
+    .await
+    .expect("insert game");
+}
+
+async fn seed_raiden(pool: &sqlx::SqlitePool, id: &str, game_id: &str, folder_path: &str) {
+    insert_test_object(
+        pool,
+        &TestObjectFixture {
+            id,
+            game_id,
+            name: folder_path,
+            folder_path,
+            object_type: "Character",
+        },
+    )
+    .await
+    .expect("insert object");
+    object_repo::apply_canonical_match(
+        pool,
+        id,
+        Some("raiden shogun"),
+        Some("Raiden Shogun"),
+        Some(0.95),
+        Some("test"),
+        Some("scan"),
+    )
+    .await
+    .expect("match object");
+}
+
+async fn seed_library_mod(
+    pool: &sqlx::SqlitePool,
+    mods_root: &std::path::Path,
+    id: &str,
+    game_id: &str,
+    object_id: &str,
+    folder_path: &str,
+    ini: &str,
+) {
+    let folder = mods_root.join(folder_path);
+    std::fs::create_dir_all(&folder).expect("create mod folder");
+    std::fs::write(folder.join("mod.ini"), ini).expect("write mod ini");
+    let mods_root = mods_root.to_string_lossy().to_string();
+    insert_test_mod(
+        pool,
+        &TestModFixture {
+            id,
+            game_id,
+            object_id: Some(object_id),
+            actual_name: folder_path.rsplit('/').next().unwrap_or(folder_path),
+            folder_path,
+            status: ItemStatus::Disabled,
+            is_safe: true,
+            object_type: Some("Character"),
+            mods_path: Some(&mods_root),
+        },
+    )
+    .await
+    .expect("insert mod");
+}
+
+#[tokio::test]
+async fn bundle_round_trips_to_another_library_and_reports_missing_members() {
+    let ctx = init_test_db().await;
+    let home = tempfile::tempdir().expect("home mods");
+    let away = tempfile::tempdir().expect("away mods");
+    let bundle_path = home.path().join("loadout.emmmcol");
+
+    // The exporting machine.
+    seed_library_game(
+        &ctx.pool,
+        "home",
+        GameType::GIMI,
+        &home.path().to_string_lossy(),
+    )
+    .await;
+    seed_raiden(&ctx.pool, "home-raiden", "home", "Raiden").await;
+    seed_library_mod(
+        &ctx.pool,
+        home.path(),
+        "home-blue",
+        "home",
+        "home-raiden",
+        "Raiden/Blue",
+        BLUE_INI,
+    )
+    .await;
+    seed_library_mod(
+        &ctx.pool,
+        home.path(),
+        "home-red",
+        "home",
+        "home-raiden",
+        "Raiden/Red",
+        RED_INI,
+    )
+    .await;
+    seed_library_mod(
+        &ctx.pool,
+        home.path(),
+        "home-gold",
+        "home",
+        "home-raiden",
+        "Raiden/Gold",
+        "",
+    )
+    .await;
+    std::fs::write(
+        home.path().join("Raiden/Blue/info.json"),
+        r#"{"metadata": {"source": "https://gamebanana.com/mods/123456"}}"#,
+    )
+    .expect("write info.json");
+    let source = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "home".to_string(),
+            name: "Raiden Night".to_string(),
+            save_mode: None,
+            source_collection_id: None,
+            mod_ids: Some(vec![
+                "home-blue".to_string(),
+                "home-red".to_string(),
+                "home-gold".to_string(),
+            ]),
+        },
+    )
+    .await
+    .expect("create from selected mods");
+    assert_eq!(
+        collection_repo::get_mods(&ctx.pool, &source.id)
+            .await
+            .expect("source mods")
+            .len(),
+        3
+    );
+
+    let written = export_collection_bundle(&ctx.pool, "home", &source.id, &bundle_path)
+        .await
+        .expect("export");
+    assert_eq!(written, 3);
+    let exported: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&bundle_path).expect("read")).expect("json");
+    let blue = &exported["members"][0];
+    assert_eq!(blue["relative_path"], "Raiden/Blue");
+    assert_eq!(blue["object_entry_key"], "raiden shogun");
+    assert_eq!(blue["source"]["item_id"], 123456);
+    assert!(!exported
+        .to_string()
+        .contains(&*home.path().to_string_lossy()));
+
+    // The importing machine: Blue at the same path, Red renamed under a
+    // differently named object folder, Gold absent.
+    seed_library_game(
+        &ctx.pool,
+        "away",
+        GameType::GIMI,
+        &away.path().to_string_lossy(),
+    )
+    .await;
+    seed_raiden(&ctx.pool, "away-raiden", "away", "Raiden Shogun").await;
+    seed_library_mod(
+        &ctx.pool,
+        away.path(),
+        "away-blue",
+        "away",
+        "away-raiden",
+        "Raiden/Blue",
+        BLUE_INI,
+    )
+    .await;
+    seed_library_mod(
+        &ctx.pool,
+        away.path(),
+        "away-crimson",
+        "away",
+        "away-raiden",
+        "Raiden Shogun/Crimson",
+        RED_INI,
+    )
+    .await;
+
+    let resolution = resolve_collection_bundle(&ctx.pool, "away", &bundle_path)
+        .await
+        .expect("resolve");
+    let matched: Vec<(&str, BundleMatch)> = resolution
+        .resolved
+        .iter()
+        .map(|member| (member.mod_id.as_str(), member.matched_by))
+        .collect();
+    assert_eq!(
+        matched,
+        vec![
+            ("away-blue", BundleMatch::Path),
+            ("away-crimson", BundleMatch::Fingerprint),
+        ]
+    );
+    assert_eq!(resolution.missing.len(), 1);
+    assert_eq!(resolution.missing[0].relative_path, "Raiden/Gold");
+
+    let imported = import_collection_bundle(&ctx.pool, "away", &bundle_path, None)
+        .await
+        .expect("import");
+    assert_eq!(imported.collection.name, "Raiden Night");
+    let mut mod_ids: Vec<String> = collection_repo::get_mods(&ctx.pool, &imported.collection.id)
+        .await
+        .expect("imported mods")
+        .into_iter()
+        .filter_map(|member| member.mod_id)
+        .collect();
+    mod_ids.sort();
+    assert_eq!(mod_ids, vec!["away-blue", "away-crimson"]);
+}
+
+#[tokio::test]
+async fn bundle_import_rejects_another_game_type() {
+    let ctx = init_test_db().await;
+    let home = tempfile::tempdir().expect("home mods");
+    let bundle_path = home.path().join("loadout.emmmcol");
+    seed_library_game(
+        &ctx.pool,
+        "home",
+        GameType::GIMI,
+        &home.path().to_string_lossy(),
+    )
+    .await;
+    seed_raiden(&ctx.pool, "home-raiden", "home", "Raiden").await;
+    seed_library_mod(
+        &ctx.pool,
+        home.path(),
+        "home-blue",
+        "home",
+        "home-raiden",
+        "Raiden/Blue",
+        BLUE_INI,
+    )
+    .await;
+    let source = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "home".to_string(),
+            name: "Raiden Night".to_string(),
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(vec!["home-blue".to_string()]),
+        },
+    )
+    .await
+    .expect("create from selected mods");
+    export_collection_bundle(&ctx.pool, "home", &source.id, &bundle_path)
+        .await
+        .expect("export");
+    seed_library_game(
+        &ctx.pool,
+        "rail",
+        GameType::SRMI,
+        &home.path().to_string_lossy(),
+    )
+    .await;
+
+    let error = import_collection_bundle(&ctx.pool, "rail", &bundle_path, None)
+        .await
+        .expect_err("game type mismatch");
+
+    assert!(matches!(error, CollectionError::Validation(_)));
+}
+
+#[tokio::test]
+async fn bundle_tells_recolors_apart_and_leaves_identical_copies_ambiguous() {
+    let ctx = init_test_db().await;
+    let home = tempfile::tempdir().expect("home mods");
+    let away = tempfile::tempdir().expect("away mods");
+    let bundle_path = home.path().join("recolors.emmmcol");
+
+    // Recolors override the same hashes; only their textures differ.
+    seed_library_game(
+        &ctx.pool,
+        "home",
+        GameType::GIMI,
+        &home.path().to_string_lossy(),
+    )
+    .await;
+    seed_raiden(&ctx.pool, "home-raiden", "home", "Raiden").await;
+    for (id, folder_path, texture) in [
+        ("home-blue", "Raiden/Blue", "blue"),
+        ("home-red", "Raiden/Red", "red"),
+        ("home-gold", "Raiden/Gold", "gold"),
+    ] {
+        seed_library_mod(
+            &ctx.pool,
+            home.path(),
+            id,
+            "home",
+            "home-raiden",
+            folder_path,
+            BLUE_INI,
+        )
+        .await;
+        std::fs::write(home.path().join(folder_path).join("body.dds"), texture)
+            .expect("write texture");
+    }
+    let source = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "home".to_string(),
+            name: "Raiden Colors".to_string(),
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(vec![
+                "home-blue".to_string(),
+                "home-red".to_string(),
+                "home-gold".to_string(),
+            ]),
+        },
+    )
+    .await
+    .expect("create from selected mods");
+    export_collection_bundle(&ctx.pool, "home", &source.id, &bundle_path)
+        .await
+        .expect("export");
+
+    // Every folder is renamed, so the path lookup misses; gold is there twice.
+    seed_library_game(
+        &ctx.pool,
+        "away",
+        GameType::GIMI,
+        &away.path().to_string_lossy(),
+    )
+    .await;
+    seed_raiden(&ctx.pool, "away-raiden", "away", "Raiden Shogun").await;
+    for (id, folder_path, texture) in [
+        ("away-crimson", "Raiden Shogun/Crimson", "red"),
+        ("away-azure", "Raiden Shogun/Azure", "blue"),
+        ("away-gold-a", "Raiden Shogun/Gold A", "gold"),
+        ("away-gold-b", "Raiden Shogun/Gold B", "gold"),
+    ] {
+        seed_library_mod(
+            &ctx.pool,
+            away.path(),
+            id,
+            "away",
+            "away-raiden",
+            folder_path,
+            BLUE_INI,
+        )
+        .await;
+        std::fs::write(away.path().join(folder_path).join("body.dds"), texture)
+            .expect("write texture");
+    }
+
+    let resolution = resolve_collection_bundle(&ctx.pool, "away", &bundle_path)
+        .await
+        .expect("resolve");
+
+    let matched: Vec<(&str, &str, BundleMatch)> = resolution
+        .resolved
+        .iter()
+        .map(|member| {
+            (
+                member.member.relative_path.as_str(),
+                member.mod_id.as_str(),
+                member.matched_by,
+            )
+        })
+        .collect();
+    assert_eq!(
+        matched,
+        vec![
+            ("Raiden/Blue", "away-azure", BundleMatch::Fingerprint),
+            ("Raiden/Red", "away-crimson", BundleMatch::Fingerprint),
+        ]
+    );
+    assert!(resolution.missing.is_empty());
+    assert_eq!(resolution.ambiguous.len(), 1);
+    assert_eq!(resolution.ambiguous[0].relative_path, "Raiden/Gold");
+}
@@ -39,6 +39,7 @@ async fn create_collection_rolls_back_row_when_snapshot_persistence_fails() {
             name: "Atomic Preset".to_string(),
             save_mode: Some(CreateCollectionMode::SaveCurrentState),
             source_collection_id: None,
+            mod_ids: None,
         },
     )
     .await
@@ -92,6 +92,7 @@ async fn deleting_signature_matched_collection_creates_unsaved_snapshot() {
             name: "Named Preset".to_string(),
             save_mode: None,
             source_collection_id: None,
+            mod_ids: None,
         },
     )
     .await
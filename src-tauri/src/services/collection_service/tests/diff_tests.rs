@@ -0,0 +1,201 @@
+use super::*;
+use crate::domain::collection::{
+    CollectionDiffSource, ProjectedActiveRoot, ProjectedObjectState, ThreeWayStatus,
+};
+use crate::services::collection_service::{
+    diff_collections, diff_projected_states, diff_projected_states_three_way,
+};
+
+fn object(object_id: &str, display_name: &str, is_enabled: bool) -> ProjectedObjectState {
+    ProjectedObjectState {
+        object_id: object_id.to_string(),
+        display_name: display_name.to_string(),
+        path_key: display_name.to_lowercase(),
+        is_enabled,
+        active_root_count: 0,
+    }
+}
+
+fn root(object_id: &str, path: &str) -> ProjectedActiveRoot {
+    ProjectedActiveRoot {
+        object_id: object_id.to_string(),
+        root_key: path.to_lowercase(),
+        display_name: path.rsplit('/').next().unwrap_or(path).to_string(),
+        root_type: "FlatModRoot".to_string(),
+        source_path: path.to_string(),
+        thumbnail_hint: None,
+        warnings: Vec::new(),
+        is_missing: false,
+    }
+}
+
+fn state(
+    object_states: Vec<ProjectedObjectState>,
+    active_roots: Vec<ProjectedActiveRoot>,
+) -> ProjectedCollectionState {
+    ProjectedCollectionState {
+        summary: ProjectedStateSummary {
+            object_count: object_states.len(),
+            enabled_object_count: object_states.iter().filter(|o| o.is_enabled).count(),
+            active_root_count: active_roots.len(),
+            missing_root_count: 0,
+        },
+        object_states,
+        active_roots,
+    }
+}
+
+fn objects() -> Vec<ProjectedObjectState> {
+    vec![
+        object("raiden", "Raiden", true),
+        object("ayaka", "Ayaka", true),
+        object("nahida", "Nahida", true),
+    ]
+}
+
+#[test]
+fn diff_pairs_swapped_roots_as_variant_changes() {
+    let base = state(
+        objects(),
+        vec![
+            root("raiden", "Raiden/Blue"),
+            root("ayaka", "Ayaka/Snow"),
+            root("nahida", "Nahida/Leaf"),
+        ],
+    );
+    let mut target_objects = objects();
+    target_objects[2].is_enabled = false;
+    let target = state(
+        target_objects,
+        vec![
+            root("raiden", "Raiden/Red"),
+            root("ayaka", "Ayaka/Snow"),
+            root("ayaka", "Ayaka/Fan"),
+            root("nahida", "Nahida/Leaf"),
+        ],
+    );
+
+    let diff = diff_projected_states(&base, &target);
+
+    // Ordered by name; untouched objects are left out.
+    let names: Vec<&str> = diff
+        .iter()
+        .map(|object| object.display_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Ayaka", "Nahida", "Raiden"]);
+    assert_eq!(diff[0].added.len(), 1);
+    assert_eq!(diff[0].added[0].source_path, "Ayaka/Fan");
+    assert!(diff[0].removed.is_empty() && diff[0].changed.is_empty());
+    assert_eq!(
+        (diff[1].base_enabled, diff[1].target_enabled),
+        (Some(true), Some(false))
+    );
+    assert!(diff[2].added.is_empty() && diff[2].removed.is_empty());
+    assert_eq!(diff[2].changed.len(), 1);
+    assert_eq!(diff[2].changed[0].from.source_path, "Raiden/Blue");
+    assert_eq!(diff[2].changed[0].to.source_path, "Raiden/Red");
+}
+
+#[test]
+fn three_way_compare_separates_one_sided_shared_and_conflicting_changes() {
+    let base = state(
+        objects(),
+        vec![
+            root("raiden", "Raiden/Blue"),
+            root("ayaka", "Ayaka/Snow"),
+            root("nahida", "Nahida/Leaf"),
+        ],
+    );
+    let ours = state(
+        objects(),
+        vec![
+            root("raiden", "Raiden/Red"),
+            root("ayaka", "Ayaka/Fan"),
+            root("nahida", "Nahida/Leaf"),
+        ],
+    );
+    let theirs = state(
+        objects(),
+        vec![root("raiden", "Raiden/Gold"), root("ayaka", "Ayaka/Fan")],
+    );
+
+    let diff = diff_projected_states_three_way(&base, &ours, &theirs);
+
+    let statuses: Vec<(&str, ThreeWayStatus)> = diff
+        .iter()
+        .map(|object| (object.object_id.as_str(), object.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("ayaka", ThreeWayStatus::Both),
+            ("nahida", ThreeWayStatus::Theirs),
+            ("raiden", ThreeWayStatus::Conflict),
+        ]
+    );
+    assert!(diff[1].ours.is_none());
+    assert_eq!(diff[1].theirs.as_ref().map(|d| d.removed.len()), Some(1));
+}
+
+#[tokio::test]
+async fn diff_collections_compares_a_collection_against_the_current_state() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("mods root");
+    let mods_path = mods_root.path().to_string_lossy().to_string();
+    seed_game(&ctx.pool, "game-1", Some(&mods_path)).await;
+    seed_ainoz_object(&ctx.pool, "object-1", "game-1").await;
+    for (id, folder, status) in [
+        ("mod-blue", "AINOZ/Blue", ItemStatus::Enabled),
+        ("mod-red", "AINOZ/Red", ItemStatus::Disabled),
+    ] {
+        create_flat_mod_folder(mods_root.path(), folder);
+        insert_test_mod(
+            &ctx.pool,
+            &TestModFixture {
+                id,
+                game_id: "game-1",
+                object_id: Some("object-1"),
+                actual_name: folder.rsplit('/').next().unwrap_or(folder),
+                folder_path: folder,
+                status,
+                is_safe: true,
+                object_type: Some("Character"),
+                mods_path: Some(&mods_path),
+            },
+        )
+        .await
+        .expect("insert mod");
+    }
+    let red = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "game-1".to_string(),
+            name: "Red".to_string(),
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(vec!["mod-red".to_string()]),
+        },
+    )
+    .await
+    .expect("create collection");
+
+    let diff = diff_collections(
+        &ctx.pool,
+        "game-1",
+        &CollectionDiffSource::Current,
+        &CollectionDiffSource::Collection {
+            collection_id: red.id,
+        },
+        Some(&mods_path),
+    )
+    .await
+    .expect("diff");
+
+    assert_eq!(diff.target_name, "Red");
+    assert_eq!(diff.objects.len(), 1);
+    assert_eq!(diff.objects[0].object_id, "object-1");
+    assert_eq!(diff.objects[0].changed.len(), 1);
+    assert_eq!(diff.objects[0].changed[0].from.display_name, "Blue");
+    assert_eq!(diff.objects[0].changed[0].to.display_name, "Red");
+    assert!(!diff.target_tree_nodes.is_empty());
+}
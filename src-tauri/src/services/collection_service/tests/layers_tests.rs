@@ -0,0 +1,249 @@
+use super::*;
+use crate::domain::collection::{CollectionOverlay, OverlayTargetKind, SetCollectionLayersInput};
+use crate::services::collection_service::{
+    compute_signature, get_collection_layers, get_collection_preview, set_collection_layers,
+};
+
+async fn seed_ainoz_mods(pool: &sqlx::SqlitePool, mods_root: &std::path::Path) {
+    let mods_path = mods_root.to_string_lossy().to_string();
+    seed_game(pool, "game-1", Some(&mods_path)).await;
+    seed_ainoz_object(pool, "object-1", "game-1").await;
+    for (id, folder, status) in [
+        ("mod-blue", "AINOZ/Blue", ItemStatus::Enabled),
+        ("mod-red", "AINOZ/Red", ItemStatus::Disabled),
+        ("mod-gold", "AINOZ/Gold", ItemStatus::Disabled),
+    ] {
+        create_flat_mod_folder(mods_root, folder);
+        insert_test_mod(
+            pool,
+            &TestModFixture {
+                id,
+                game_id: "game-1",
+                object_id: Some("object-1"),
+                actual_name: folder.rsplit('/').next().unwrap_or(folder),
+                folder_path: folder,
+                status,
+                is_safe: true,
+                object_type: Some("Character"),
+                mods_path: Some(&mods_path),
+            },
+        )
+        .await
+        .expect("insert mod");
+    }
+}
+
+async fn create_from_mods(pool: &sqlx::SqlitePool, name: &str, mod_ids: &[&str]) -> String {
+    create_collection(
+        pool,
+        CreateCollectionInput {
+            game_id: "game-1".to_string(),
+            name: name.to_string(),
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(mod_ids.iter().map(|id| id.to_string()).collect()),
+        },
+    )
+    .await
+    .expect("create collection")
+    .id
+}
+
+fn mod_overlay(mod_id: &str, is_enabled: bool) -> CollectionOverlay {
+    CollectionOverlay {
+        target_kind: OverlayTargetKind::Mod,
+        target_key: mod_id.to_string(),
+        is_enabled,
+    }
+}
+
+async fn member_mod_ids(pool: &sqlx::SqlitePool, collection_id: &str) -> Vec<String> {
+    let mut ids: Vec<String> = collection_repo::get_mods(pool, collection_id)
+        .await
+        .expect("collection mods")
+        .into_iter()
+        .filter_map(|member| member.mod_id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn layered_collection_follows_its_parent_and_keeps_its_signature_current() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("mods root");
+    seed_ainoz_mods(&ctx.pool, mods_root.path()).await;
+    let parent_id = create_from_mods(&ctx.pool, "Base", &["mod-blue"]).await;
+    let child_id = create_from_mods(&ctx.pool, "Base but Red", &["mod-blue"]).await;
+
+    let summary = set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: child_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: Some(parent_id.clone()),
+            overlays: vec![mod_overlay("mod-blue", false), mod_overlay("mod-red", true)],
+        },
+    )
+    .await
+    .expect("layer child");
+
+    assert_eq!(
+        summary.parent_collection_id.as_deref(),
+        Some(parent_id.as_str())
+    );
+    assert_eq!(member_mod_ids(&ctx.pool, &child_id).await, vec!["mod-red"]);
+    let layers = get_collection_layers(&ctx.pool, "game-1", &child_id)
+        .await
+        .expect("layers");
+    assert_eq!(layers.overlays.len(), 2);
+
+    // The parent gains Gold; the child inherits it on top of its overlays.
+    sqlx::query("UPDATE mods SET status = ? WHERE id = 'mod-gold'")
+        .bind(ItemStatus::Enabled as i64)
+        .execute(&ctx.pool)
+        .await
+        .expect("enable gold");
+    replace_collection_with_current_state(&ctx.pool, "game-1", &parent_id)
+        .await
+        .expect("replace parent");
+
+    assert_eq!(
+        member_mod_ids(&ctx.pool, &child_id).await,
+        vec!["mod-gold", "mod-red"]
+    );
+    let child = collection_repo::get_by_id(&ctx.pool, &child_id)
+        .await
+        .expect("load child")
+        .expect("child exists");
+    let mods = collection_repo::get_mods(&ctx.pool, &child_id)
+        .await
+        .expect("child mods");
+    let objects = collection_repo::get_objects(&ctx.pool, &child_id)
+        .await
+        .expect("child objects");
+    assert_eq!(child.signature, Some(compute_signature(&mods, &objects)));
+    assert_ne!(child.signature, summary.signature);
+}
+
+#[tokio::test]
+async fn previewing_a_layered_collection_does_not_store_its_members() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("mods root");
+    seed_ainoz_mods(&ctx.pool, mods_root.path()).await;
+    let parent_id = create_from_mods(&ctx.pool, "Base", &["mod-blue"]).await;
+    let child_id = create_from_mods(&ctx.pool, "Base but Red", &["mod-blue"]).await;
+    set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: child_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: Some(parent_id),
+            overlays: vec![mod_overlay("mod-blue", false), mod_overlay("mod-red", true)],
+        },
+    )
+    .await
+    .expect("layer child");
+    sqlx::query("DELETE FROM collection_mods WHERE collection_id = ?")
+        .bind(&child_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("drop stored members");
+    sqlx::query("UPDATE collections SET signature = 'stale' WHERE id = ?")
+        .bind(&child_id)
+        .execute(&ctx.pool)
+        .await
+        .expect("stale signature");
+
+    let preview = get_collection_preview(&ctx.pool, "game-1", &child_id, None)
+        .await
+        .expect("preview child");
+
+    assert_eq!(preview.projected_state.summary.active_root_count, 1);
+    assert!(member_mod_ids(&ctx.pool, &child_id).await.is_empty());
+    let child = collection_repo::get_by_id(&ctx.pool, &child_id)
+        .await
+        .expect("load child")
+        .expect("child exists");
+    assert_eq!(child.signature.as_deref(), Some("stale"));
+}
+
+#[tokio::test]
+async fn layering_rejects_cycles_and_overlays_without_a_parent() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("mods root");
+    seed_ainoz_mods(&ctx.pool, mods_root.path()).await;
+    let first_id = create_from_mods(&ctx.pool, "First", &["mod-blue"]).await;
+    let second_id = create_from_mods(&ctx.pool, "Second", &["mod-blue"]).await;
+    set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: second_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: Some(first_id.clone()),
+            overlays: Vec::new(),
+        },
+    )
+    .await
+    .expect("layer second on first");
+
+    let cycle = set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: first_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: Some(second_id.clone()),
+            overlays: Vec::new(),
+        },
+    )
+    .await
+    .expect_err("cycle");
+    let orphan_overlays = set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: first_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: None,
+            overlays: vec![mod_overlay("mod-red", true)],
+        },
+    )
+    .await
+    .expect_err("overlays without parent");
+
+    assert!(matches!(cycle, CollectionError::Validation(_)));
+    assert!(matches!(orphan_overlays, CollectionError::Validation(_)));
+}
+
+#[tokio::test]
+async fn deleting_a_parent_leaves_its_children_as_plain_collections() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("mods root");
+    seed_ainoz_mods(&ctx.pool, mods_root.path()).await;
+    let parent_id = create_from_mods(&ctx.pool, "Base", &["mod-blue"]).await;
+    let child_id = create_from_mods(&ctx.pool, "Base plus Red", &["mod-blue"]).await;
+    set_collection_layers(
+        &ctx.pool,
+        SetCollectionLayersInput {
+            id: child_id.clone(),
+            game_id: "game-1".to_string(),
+            parent_collection_id: Some(parent_id.clone()),
+            overlays: vec![mod_overlay("mod-red", true)],
+        },
+    )
+    .await
+    .expect("layer child");
+
+    delete_collection(&ctx.pool, &parent_id)
+        .await
+        .expect("delete parent");
+
+    let layers = get_collection_layers(&ctx.pool, "game-1", &child_id)
+        .await
+        .expect("layers");
+    assert!(layers.parent_collection_id.is_none());
+    assert!(layers.overlays.is_empty());
+    assert_eq!(
+        member_mod_ids(&ctx.pool, &child_id).await,
+        vec!["mod-blue", "mod-red"]
+    );
+}
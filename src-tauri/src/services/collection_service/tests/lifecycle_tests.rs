@@ -48,6 +48,7 @@ async fn save_current_state_creates_snapshot_without_touching_legacy_corridor_po
             name: "Saved Snapshot".to_string(),
             save_mode: Some(CreateCollectionMode::SaveCurrentState),
             source_collection_id: None,
+            mod_ids: None,
         },
     )
     .await
@@ -224,6 +225,7 @@ async fn clone_snapshot_does_not_touch_legacy_active_pointer() {
             name: "Source Preset".to_string(),
             save_mode: Some(CreateCollectionMode::SaveCurrentState),
             source_collection_id: None,
+            mod_ids: None,
         },
     )
     .await
@@ -239,6 +241,7 @@ async fn clone_snapshot_does_not_touch_legacy_active_pointer() {
             name: "Cloned Preset".to_string(),
             save_mode: Some(CreateCollectionMode::CloneSnapshot),
             source_collection_id: Some(source.id.clone()),
+            mod_ids: None,
         },
     )
     .await
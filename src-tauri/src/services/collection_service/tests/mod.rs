@@ -6,13 +6,14 @@ use super::{
     ApplyCollectionRequest,
 };
 use crate::domain::collection::{
-    CollectionMod, CollectionObject, CreateCollectionInput, CreateCollectionMode, MemberKind,
-    ProjectedCollectionState, ProjectedStateSummary, UpdateCollectionInput,
+    ApplyScope, CollectionMod, CollectionObject, CreateCollectionInput, CreateCollectionMode,
+    MemberKind, ProjectedCollectionState, ProjectedStateSummary, UpdateCollectionInput,
 };
 use crate::domain::errors::CollectionError;
 use crate::domain::models::{GameType, ItemStatus};
 use crate::repo::{collection_repo, corridor_repo};
 use crate::services::config::AppSettings;
+use crate::services::fs_utils::operation_lock::OperationLock;
 use crate::services::projected_state_service;
 use crate::services::scanner::watcher::WatcherSuppressor;
 use crate::test_utils::{
@@ -93,10 +94,15 @@ fn create_flat_mod_folder(mods_root: &std::path::Path, relative_path: &str) {
 }
 
 mod apply_tests;
+mod bundle_tests;
 mod create_tests;
 mod delete_tests;
+mod diff_tests;
+mod layers_tests;
 mod lifecycle_tests;
 mod preview_tests;
 mod references_tests;
 mod replace_tests;
+mod scoped_apply_tests;
 mod update_tests;
+mod user_vars_tests;
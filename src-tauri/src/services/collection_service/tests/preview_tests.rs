@@ -21,6 +21,7 @@ async fn preview_apply_blocks_when_mods_root_is_unavailable() {
         &collection.id,
         crate::domain::corridor::Corridor::from_is_safe(true),
         Some(&mods_path),
+        &ApplyScope::Full,
     )
     .await;
 
@@ -49,6 +50,7 @@ async fn preview_apply_rejects_cross_corridor_request() {
         &collection.id,
         crate::domain::corridor::Corridor::from_is_safe(true),
         None,
+        &ApplyScope::Full,
     )
     .await;
 
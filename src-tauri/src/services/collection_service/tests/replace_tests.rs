@@ -32,7 +32,10 @@ async fn partial_apply_blocks_when_mods_root_is_unavailable_even_when_ignoring_m
     .await
     .expect("persist collection state");
 
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
     let result = apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: &ctx.pool,
         game_id: "game-1",
         collection_id: &collection.id,
@@ -42,6 +45,7 @@ async fn partial_apply_blocks_when_mods_root_is_unavailable_even_when_ignoring_m
         ignore_missing: true,
         settings: AppSettings::default(),
         reconcile_lock: None,
+        scope: ApplyScope::Full,
     })
     .await;
 
@@ -105,7 +109,10 @@ async fn replace_collection_with_current_state_drops_missing_partial_apply_membe
     .await
     .expect("persist collection state");
 
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
     apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
         pool: &ctx.pool,
         game_id: "game-1",
         collection_id: &collection.id,
@@ -115,6 +122,7 @@ async fn replace_collection_with_current_state_drops_missing_partial_apply_membe
         ignore_missing: true,
         settings: AppSettings::default(),
         reconcile_lock: None,
+        scope: ApplyScope::Full,
     })
     .await
     .expect("partial apply succeeds");
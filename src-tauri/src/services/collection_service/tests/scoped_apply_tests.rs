@@ -0,0 +1,154 @@
+use super::*;
+
+#[tokio::test]
+async fn scoped_apply_converges_only_the_selected_object_types() {
+    let ctx = init_test_db().await;
+    let mods_root = tempfile::tempdir().expect("create mods root");
+    let mods_path = mods_root.path().to_string_lossy().to_string();
+
+    seed_game(&ctx.pool, "game-1", Some(&mods_path)).await;
+    seed_ainoz_object(&ctx.pool, "object-1", "game-1").await;
+    insert_test_object(
+        &ctx.pool,
+        &TestObjectFixture {
+            id: "object-2",
+            game_id: "game-1",
+            name: "Sword",
+            folder_path: "Sword",
+            object_type: "Weapon",
+        },
+    )
+    .await
+    .expect("insert weapon object");
+    for (id, object_id, name, folder_path, status) in [
+        (
+            "mod-blue",
+            "object-1",
+            "Blue",
+            "AINOZ/Blue",
+            ItemStatus::Enabled,
+        ),
+        (
+            "mod-old",
+            "object-2",
+            "Old",
+            "Sword/Old",
+            ItemStatus::Enabled,
+        ),
+        (
+            "mod-new",
+            "object-2",
+            "New",
+            "Sword/DISABLED New",
+            ItemStatus::Disabled,
+        ),
+    ] {
+        create_flat_mod_folder(mods_root.path(), folder_path);
+        insert_test_mod(
+            &ctx.pool,
+            &TestModFixture {
+                id,
+                game_id: "game-1",
+                object_id: Some(object_id),
+                actual_name: name,
+                folder_path,
+                status,
+                is_safe: true,
+                object_type: Some("Character"),
+                mods_path: Some(&mods_path),
+            },
+        )
+        .await
+        .expect("insert mod");
+    }
+
+    // The preset has a new sword and no AINOZ mod at all.
+    let collection =
+        collection_repo::create(&ctx.pool, "collection-1", "game-1", "Preset B", true, false)
+            .await
+            .expect("create collection");
+    let target_mod = CollectionMod {
+        object_id: "object-2".to_string(),
+        ..test_collection_mod(&collection.id, "Sword/New", "New")
+    };
+    let target_objects = vec![
+        test_collection_object(&collection.id),
+        CollectionObject {
+            object_id: "object-2".to_string(),
+            display_name: Some("Sword".to_string()),
+            path_key: Some("Sword".to_string()),
+            ..test_collection_object(&collection.id)
+        },
+    ];
+    let projected_state = projected_state_service::build_projected_state(
+        std::slice::from_ref(&target_mod),
+        &target_objects,
+        Some(&mods_path),
+    );
+    persist_projected_state(
+        &ctx.pool,
+        &collection.id,
+        true,
+        &[target_mod],
+        &target_objects,
+        &projected_state,
+    )
+    .await
+    .expect("persist collection state");
+    let scope = ApplyScope::Objects {
+        object_ids: Vec::new(),
+        object_types: vec!["Weapon".to_string()],
+    };
+
+    let preview = preview_apply(
+        &ctx.pool,
+        "game-1",
+        &collection.id,
+        crate::domain::corridor::Corridor::from_is_safe(true),
+        Some(&mods_path),
+        &scope,
+    )
+    .await
+    .expect("scoped preview");
+    let mut previewed: Vec<&str> = preview
+        .target_projected_state
+        .active_roots
+        .iter()
+        .map(|root| root.display_name.as_str())
+        .collect();
+    previewed.sort_unstable();
+    assert_eq!(previewed, vec!["Blue", "New"]);
+
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    let result = apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
+        pool: &ctx.pool,
+        game_id: "game-1",
+        collection_id: &collection.id,
+        is_safe: true,
+        mods_path: mods_root.path().to_path_buf(),
+        suppressor: Arc::new(WatcherSuppressor::new(false)),
+        ignore_missing: false,
+        settings: AppSettings::default(),
+        reconcile_lock: None,
+        scope: scope.clone(),
+    })
+    .await
+    .expect("scoped apply succeeds");
+
+    assert_eq!((result.mods_enabled, result.mods_disabled), (1, 1));
+    assert!(mods_root.path().join("AINOZ/Blue").is_dir());
+    assert!(mods_root.path().join("Sword/New").is_dir());
+    assert!(mods_root.path().join("Sword/DISABLED Old").is_dir());
+    let payload: Option<String> =
+        sqlx::query_scalar("SELECT payload FROM tasks WHERE target_id = ?")
+            .bind(&collection.id)
+            .fetch_one(&ctx.pool)
+            .await
+            .expect("apply task");
+    assert_eq!(
+        serde_json::from_str::<ApplyScope>(&payload.expect("scope payload")).expect("scope"),
+        scope
+    );
+}
@@ -0,0 +1,143 @@
+use super::*;
+use crate::domain::collection::CollectionUserVar;
+use crate::services::collection_service::{export_collection_bundle, import_collection_bundle};
+use std::path::{Path, PathBuf};
+
+const BLUE_SWAP: &str = "$\\mods\\ainoz\\blue\\mod.ini\\swapvar";
+const RED_SWAP: &str = "$\\mods\\ainoz\\red\\mod.ini\\swapvar";
+
+fn user_ini(blue: &str) -> String {
+    format!("[Constants]\n{BLUE_SWAP} = {blue}\n{RED_SWAP} = 1\n$\\mods\\other\\mod.ini\\x = 5\n")
+}
+
+/// An importer folder whose `Mods` holds an enabled Blue and a disabled Red
+/// under one object, with Blue's swap at `blue` in `d3dx_user.ini`. Returns
+/// the mods root.
+async fn seed_ainoz_library(pool: &sqlx::SqlitePool, importer: &Path, blue: &str) -> PathBuf {
+    let mods_root = importer.join("Mods");
+    let mods_path = mods_root.to_string_lossy().to_string();
+    std::fs::write(importer.join("d3dx.ini"), "[Loader]\n").expect("write d3dx.ini");
+    std::fs::write(importer.join("d3dx_user.ini"), user_ini(blue)).expect("write d3dx_user.ini");
+
+    seed_game(pool, "game-1", Some(&mods_path)).await;
+    seed_ainoz_object(pool, "object-1", "game-1").await;
+    for (id, name, folder_path, status) in [
+        ("mod-blue", "Blue", "AINOZ/Blue", ItemStatus::Enabled),
+        ("mod-red", "Red", "AINOZ/DISABLED Red", ItemStatus::Disabled),
+    ] {
+        create_flat_mod_folder(&mods_root, folder_path);
+        insert_test_mod(
+            pool,
+            &TestModFixture {
+                id,
+                game_id: "game-1",
+                object_id: Some("object-1"),
+                actual_name: name,
+                folder_path,
+                status,
+                is_safe: true,
+                object_type: Some("Character"),
+                mods_path: Some(&mods_path),
+            },
+        )
+        .await
+        .expect("insert mod");
+    }
+    mods_root
+}
+
+#[tokio::test]
+async fn collection_saves_member_variables_and_apply_writes_them_back() {
+    let ctx = init_test_db().await;
+    let importer = tempfile::tempdir().expect("importer folder");
+    let user_ini_path = importer.path().join("d3dx_user.ini");
+    let mods_root = seed_ainoz_library(&ctx.pool, importer.path(), "2").await;
+
+    let collection = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "game-1".to_string(),
+            name: "Blue Night".to_string(),
+            save_mode: Some(CreateCollectionMode::SaveCurrentState),
+            source_collection_id: None,
+            mod_ids: None,
+        },
+    )
+    .await
+    .expect("create collection");
+
+    // Only the enabled member's variables are saved.
+    assert_eq!(
+        collection_repo::get_user_vars(&ctx.pool, &collection.id)
+            .await
+            .expect("saved vars"),
+        vec![CollectionUserVar {
+            var_key: BLUE_SWAP.to_string(),
+            value: "2".to_string(),
+        }]
+    );
+
+    // The outfit is cycled in game; applying the collection brings it back.
+    std::fs::write(&user_ini_path, user_ini("0")).expect("rewrite d3dx_user.ini");
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    apply_collection(ApplyCollectionRequest {
+        op_guard: &op_guard,
+        pool: &ctx.pool,
+        game_id: "game-1",
+        collection_id: &collection.id,
+        is_safe: true,
+        mods_path: mods_root.clone(),
+        suppressor: Arc::new(WatcherSuppressor::new(false)),
+        ignore_missing: false,
+        settings: AppSettings::default(),
+        reconcile_lock: None,
+        scope: ApplyScope::Full,
+    })
+    .await
+    .expect("apply");
+
+    assert_eq!(
+        std::fs::read_to_string(&user_ini_path).expect("read d3dx_user.ini"),
+        user_ini("2")
+    );
+}
+
+#[tokio::test]
+async fn bundle_import_does_not_take_the_live_variables() {
+    let ctx = init_test_db().await;
+    let importer = tempfile::tempdir().expect("importer folder");
+    let bundle_path = importer.path().join("blue.emmmcol");
+    seed_ainoz_library(&ctx.pool, importer.path(), "2").await;
+    let source = create_collection(
+        &ctx.pool,
+        CreateCollectionInput {
+            game_id: "game-1".to_string(),
+            name: "Blue Night".to_string(),
+            save_mode: Some(CreateCollectionMode::SelectedMods),
+            source_collection_id: None,
+            mod_ids: Some(vec!["mod-blue".to_string()]),
+        },
+    )
+    .await
+    .expect("create collection");
+    export_collection_bundle(&ctx.pool, "game-1", &source.id, &bundle_path)
+        .await
+        .expect("export");
+
+    let imported = import_collection_bundle(
+        &ctx.pool,
+        "game-1",
+        &bundle_path,
+        Some("Shared Blue".to_string()),
+    )
+    .await
+    .expect("import");
+
+    assert!(
+        collection_repo::get_user_vars(&ctx.pool, &imported.collection.id)
+            .await
+            .expect("imported vars")
+            .is_empty()
+    );
+}
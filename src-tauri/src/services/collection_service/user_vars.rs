@@ -0,0 +1,241 @@
+//! 3DMigoto `persist` variables saved with a collection.
+//!
+//! Mods keep toggles such as outfit swaps in `d3dx_user.ini`, not in their
+//! folders, so enabling the same folders does not bring a look back on its
+//! own. A collection snapshots its members' lines from that file when it is
+//! saved, and apply writes them back.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::bundle::relative_member_path;
+use super::live_state::load_game_mods_path;
+use super::projection::{load_parent_chain, require_collection, require_game_match};
+use crate::domain::collection::{
+    Collection, CollectionMod, CollectionUserVar, CreateCollectionInput, CreateCollectionMode,
+};
+use crate::domain::errors::CollectionError;
+use crate::repo::{collection_repo, game_repo};
+use crate::services::ini::user_vars;
+use sqlx::SqlitePool;
+
+/// Where a new collection's variables come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum NewCollectionVars {
+    /// The live `d3dx_user.ini`, or the source's saved values for a clone.
+    Capture,
+    /// None: the members were not picked from the running setup, e.g. a
+    /// bundle import, so the live values are not theirs.
+    Skip,
+}
+
+async fn locate_user_ini(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+) -> Result<Option<PathBuf>, CollectionError> {
+    let game_exe = game_repo::get_game_exe(pool, game_id).await?;
+    Ok(user_vars::user_ini_path(
+        mods_root,
+        game_exe.as_deref().map(Path::new),
+    ))
+}
+
+/// Whether a process named like `game_exe` is running.
+fn game_is_running(game_exe: &Path) -> bool {
+    use sysinfo::System;
+
+    let Some(exe_name) = game_exe.file_name() else {
+        return false;
+    };
+    let exe_name = exe_name.to_string_lossy();
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    sys.processes().values().any(|process| {
+        process
+            .name()
+            .to_string_lossy()
+            .eq_ignore_ascii_case(&exe_name)
+    })
+}
+
+/// Namespace prefixes of the folders of `mods`, whatever their current
+/// enabled state.
+fn member_prefixes(user_ini: &Path, mods_root: &Path, mods: &[CollectionMod]) -> Vec<String> {
+    let mods_path = mods_root.to_string_lossy();
+    mods.iter()
+        .filter_map(|member| {
+            let relative = relative_member_path(&member.mod_path, Some(mods_path.as_ref()));
+            user_vars::namespace_prefix(user_ini, &mods_root.join(relative))
+        })
+        .collect()
+}
+
+fn is_member_var(prefixes: &[String], key: &str) -> bool {
+    let key = key.to_lowercase();
+    prefixes.iter().any(|prefix| key.starts_with(prefix))
+}
+
+fn read_member_vars(
+    user_ini: &Path,
+    mods_root: &Path,
+    mods: &[CollectionMod],
+) -> Result<Vec<CollectionUserVar>, CollectionError> {
+    let prefixes = member_prefixes(user_ini, mods_root, mods);
+    let vars = user_vars::read_user_vars(user_ini)
+        .map_err(|error| CollectionError::Io(error.to_string()))?;
+    Ok(vars
+        .into_iter()
+        .filter(|(key, _)| is_member_var(&prefixes, key))
+        .map(|(key, value)| CollectionUserVar {
+            var_key: key.to_lowercase(),
+            value,
+        })
+        .collect())
+}
+
+/// The live `d3dx_user.ini` values of `mods`' variables, for a collection
+/// being saved. Empty when the game has no importer folder; an unreadable
+/// file is logged rather than failing the save, since the mods alone still
+/// make a usable collection.
+pub(crate) async fn capture_live_user_vars(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_path: Option<&str>,
+    mods: &[CollectionMod],
+) -> Result<Vec<CollectionUserVar>, CollectionError> {
+    let Some(mods_root) = mods_path.map(Path::new) else {
+        return Ok(Vec::new());
+    };
+    let Some(user_ini) = locate_user_ini(pool, game_id, mods_root).await? else {
+        return Ok(Vec::new());
+    };
+    match read_member_vars(&user_ini, mods_root, mods) {
+        Ok(vars) => Ok(vars),
+        Err(error) => {
+            log::warn!(
+                "collection user vars: cannot read {}: {error}",
+                user_ini.display()
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// The variables a collection being created starts with: a clone keeps its
+/// source's, a new save reads the live ones of `mods`.
+pub(super) async fn initial_user_vars(
+    pool: &SqlitePool,
+    input: &CreateCollectionInput,
+    save_mode: CreateCollectionMode,
+    mods_path: Option<&str>,
+    mods: &[CollectionMod],
+    vars: NewCollectionVars,
+) -> Result<Vec<CollectionUserVar>, CollectionError> {
+    if vars == NewCollectionVars::Skip {
+        return Ok(Vec::new());
+    }
+    match (save_mode, input.source_collection_id.as_deref()) {
+        (CreateCollectionMode::CloneSnapshot, Some(source_collection_id)) => {
+            Ok(collection_repo::get_user_vars(pool, source_collection_id).await?)
+        }
+        _ => capture_live_user_vars(pool, &input.game_id, mods_path, mods).await,
+    }
+}
+
+/// Re-snapshots the live values of `collection_id`'s members' variables,
+/// replacing the saved ones, e.g. after cycling an outfit in game. Returns
+/// how many were saved.
+pub async fn capture_collection_user_vars(
+    pool: &SqlitePool,
+    game_id: &str,
+    collection_id: &str,
+) -> Result<usize, CollectionError> {
+    let collection = require_collection(pool, collection_id).await?;
+    require_game_match(&collection, game_id)?;
+    if collection.is_unsaved {
+        return Err(CollectionError::Validation(
+            "Cannot save variables into an unsaved collection snapshot".to_string(),
+        ));
+    }
+
+    let mods_root = load_game_mods_path(pool, game_id)
+        .await?
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            CollectionError::Validation(format!("Game '{game_id}' has no mods folder"))
+        })?;
+    let user_ini = locate_user_ini(pool, game_id, &mods_root)
+        .await?
+        .ok_or_else(|| {
+            CollectionError::Validation(
+                "No d3dx.ini found beside the mods folder or the game executable".to_string(),
+            )
+        })?;
+    let mods = collection_repo::get_mods(pool, collection_id).await?;
+    let vars = read_member_vars(&user_ini, &mods_root, &mods)?;
+
+    let mut tx = pool.begin().await?;
+    collection_repo::replace_user_vars_tx(&mut tx, collection_id, &vars).await?;
+    tx.commit().await?;
+    Ok(vars.len())
+}
+
+/// `collection`'s saved values, falling back to its parents' for keys it has
+/// none of: a layer that swaps a few mods keeps the toggles of the rest.
+async fn saved_user_vars(
+    pool: &SqlitePool,
+    collection: &Collection,
+) -> Result<Vec<CollectionUserVar>, CollectionError> {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    let chain = load_parent_chain(pool, collection).await?;
+    for layer in std::iter::once(collection).chain(&chain) {
+        for var in collection_repo::get_user_vars(pool, &layer.id).await? {
+            vars.entry(var.var_key).or_insert(var.value);
+        }
+    }
+    Ok(vars
+        .into_iter()
+        .map(|(var_key, value)| CollectionUserVar { var_key, value })
+        .collect())
+}
+
+/// Writes `collection`'s saved values for `mods`' variables back to
+/// `d3dx_user.ini`, through the backed-up INI save pipeline. Returns how many
+/// values changed.
+///
+/// Refuses while the game runs: 3DMigoto writes its in-memory values over the
+/// file on exit, which would undo the restore without a trace.
+pub(crate) async fn restore_user_vars(
+    pool: &SqlitePool,
+    collection: &Collection,
+    mods_root: &Path,
+    mods: &[CollectionMod],
+) -> Result<usize, CollectionError> {
+    let saved = saved_user_vars(pool, collection).await?;
+    if saved.is_empty() {
+        return Ok(0);
+    }
+    let game_exe = game_repo::get_game_exe(pool, &collection.game_id).await?;
+    let game_exe = game_exe.as_deref().map(Path::new);
+    let Some(user_ini) = user_vars::user_ini_path(mods_root, game_exe) else {
+        return Ok(0);
+    };
+    if game_exe.is_some_and(game_is_running) {
+        return Err(CollectionError::Validation(
+            "The game is running and 3DMigoto rewrites d3dx_user.ini when it exits; \
+             apply again with the game closed"
+                .to_string(),
+        ));
+    }
+
+    let prefixes = member_prefixes(&user_ini, mods_root, mods);
+    let mut vars: Vec<(String, String)> = saved
+        .into_iter()
+        .filter(|var| is_member_var(&prefixes, &var.var_key))
+        .map(|var| (var.var_key, var.value))
+        .collect();
+    vars.sort();
+    user_vars::write_user_vars(&user_ini, &vars)
+        .map_err(|error| CollectionError::Io(error.to_string()))
+}
@@ -43,11 +43,40 @@ impl Default for SafeModeConfig {
     }
 }
 
+/// The protocol the AI rerank endpoint speaks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, specta::Type)]
+pub enum AiProviderKind {
+    /// OpenAI-style chat completions.
+    #[default]
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// Ollama's `/api/generate`.
+    #[serde(rename = "ollama")]
+    Ollama,
+    /// llama.cpp server's `/completion`.
+    #[serde(rename = "llama_cpp")]
+    LlamaCpp,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, specta::Type)]
 pub struct AiConfig {
     pub enabled: bool,
     pub api_key: Option<String>,
     pub base_url: Option<String>,
+    #[serde(default)]
+    pub provider: AiProviderKind,
+    /// Model name sent with each request. `None` uses the provider's default.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Prompt for local providers, with `{folder_tokens}`, `{deep_tokens}`,
+    /// `{section_tokens}`, `{content_tokens}` and `{candidates}` placeholders.
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+    /// Most candidates sent per folder; the rest keep their mechanical scores.
+    #[serde(default)]
+    pub max_candidates: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, specta::Type)]
@@ -270,6 +270,35 @@ pub async fn reconcile_disk_projection(
             crate::repo::runtime_projection_repo::rebuild_game_projection(pool, game_id).await?;
         }
 
+        // The hash index is a lookup cache: a failed refresh is logged and
+        // retried on the next pass rather than failing this one.
+        let index_scope = scoped.then_some(changed_roots.as_slice());
+        if let Err(error) = crate::services::keyviewer::hash_index::sync_hash_index(
+            pool,
+            game_id,
+            mods_path,
+            index_scope,
+        )
+        .await
+        {
+            log::warn!("Disk Reconcile hash index refresh failed for game '{game_id}': {error}");
+        }
+
+        // Persisted matcher signals are a cache too: drop the touched folders'
+        // rows so the next scan re-reads them.
+        if let Err(error) = crate::services::scanner::sync::invalidate_signal_cache(
+            pool,
+            game_id,
+            mods_path,
+            index_scope,
+        )
+        .await
+        {
+            log::warn!(
+                "Disk Reconcile signal cache invalidation failed for game '{game_id}': {error}"
+            );
+        }
+
         objects_changed = write_outcome.objects_changed;
         folders_changed = write_outcome.folders_changed;
 
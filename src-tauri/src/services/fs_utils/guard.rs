@@ -76,6 +76,18 @@ pub fn validate_paths(
         .collect()
 }
 
+/// [`validate_path`] against a mods root the caller already holds, for
+/// services that are handed the root rather than the game's config.
+pub fn validate_path_in_root(
+    mods_root: &Path,
+    candidate_path: &str,
+) -> Result<ValidatedPath, AppError> {
+    let canonical_root = mods_root
+        .canonicalize()
+        .map_err(|e| AppError::Security(format!("Failed to canonicalize mods root: {}", e)))?;
+    validate_against_root(&canonical_root, candidate_path)
+}
+
 fn canonical_mods_root(config: &ConfigService, game_id: &str) -> Result<PathBuf, AppError> {
     // Read only the one field: `get_settings` would deep-clone every game,
     // keyword, and hotkey binding, and this runs per item in bulk loops.
@@ -240,3 +240,28 @@ fn test_mixed_separators() {
 
     let _ = fs::remove_dir_all(&dir);
 }
+
+#[test]
+fn test_importer_dir_prefers_the_package_over_the_exe_folder() {
+    let root = std::env::temp_dir().join("emmm_test_importer_dir");
+    let _ = fs::remove_dir_all(&root);
+    let package = root.join("XXMI/GIMI");
+    let game = root.join("Game");
+    fs::create_dir_all(package.join("Mods")).unwrap();
+    fs::create_dir_all(&game).unwrap();
+    fs::write(game.join("d3dx.ini"), "").unwrap();
+    let game_exe = game.join("Game.exe");
+
+    assert_eq!(
+        importer_dir(&package.join("Mods"), Some(&game_exe)),
+        Some(game.clone())
+    );
+    fs::write(package.join("d3dx.ini"), "").unwrap();
+    assert_eq!(
+        importer_dir(&package.join("Mods"), Some(&game_exe)),
+        Some(package.clone())
+    );
+    assert_eq!(importer_dir(&root.join("Elsewhere/Mods"), None), None);
+
+    let _ = fs::remove_dir_all(&root);
+}
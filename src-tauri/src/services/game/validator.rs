@@ -2,8 +2,10 @@ use crate::domain::errors::AppError;
 use crate::domain::models::{GameInfo, GameType};
 use std::path::{Path, PathBuf};
 
+const D3DX_INI: &str = "d3dx.ini";
+
 /// Files 3DMigoto needs in the game root. Missing ones are soft warnings.
-const CORE_FILES: [&str; 2] = ["d3dx.ini", "d3d11.dll"];
+const CORE_FILES: [&str; 2] = [D3DX_INI, "d3d11.dll"];
 
 /// A game instance discovered under an XXMI root.
 pub struct DetectedGame {
@@ -69,6 +71,17 @@ pub fn validate_instance(raw_path: &Path) -> Result<(GameInfo, Vec<String>), App
     Ok((info, warnings))
 }
 
+/// The directory holding the `d3dx.ini` that loads `mods_root`: the package
+/// directory above the Mods folder, else the game executable's directory,
+/// the same order the reload-key discovery uses.
+pub fn importer_dir(mods_root: &Path, game_exe: Option<&Path>) -> Option<PathBuf> {
+    [mods_root.parent(), game_exe.and_then(Path::parent)]
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.join(D3DX_INI).is_file())
+        .map(Path::to_path_buf)
+}
+
 fn resolve_instance_paths(raw_path: &Path) -> Result<(PathBuf, PathBuf), AppError> {
     if let Some(mods_root) = raw_path.ancestors().find(|candidate| {
         candidate
@@ -82,10 +82,11 @@ pub(super) async fn execute_cycle_preset(
         .find(|collection| collection.name == target_name)
         .ok_or_else(|| AppError::Internal(format!("Target preset '{target_name}' not found")))?;
 
-    let _lock = op_lock.inner().acquire().await?;
+    let op_guard = op_lock.inner().acquire().await?;
 
     let apply_result = crate::services::collection_service::apply_collection(
         crate::services::collection_service::ApplyCollectionRequest {
+            op_guard: &op_guard,
             pool: pool_state.inner(),
             game_id,
             collection_id: &target.id,
@@ -99,6 +100,7 @@ pub(super) async fn execute_cycle_preset(
             >(app, "DiskReconcileState")
             .ok()
             .map(|reconcile| reconcile.game_lock(game_id)),
+            scope: crate::domain::collection::ApplyScope::Full,
         },
     )
     .await?;
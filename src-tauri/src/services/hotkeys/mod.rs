@@ -88,11 +88,34 @@ impl Default for HotkeyConfig {
 pub struct KeyViewerConfig {
     /// Whether KeyViewer generation is enabled.
     pub enabled: bool,
+    /// Keys that keybind remapping may move a colliding binding to, tried in
+    /// order. 3DMigoto spelling (`VK_NUMPAD1`).
+    #[serde(default = "default_remap_pool")]
+    pub remap_pool: Vec<String>,
+}
+
+/// Keys few mods bind: the numpad, then the navigation cluster.
+fn default_remap_pool() -> Vec<String> {
+    let numpad = (0..=9).map(|digit| format!("VK_NUMPAD{digit}"));
+    let navigation = [
+        "VK_INSERT",
+        "VK_HOME",
+        "VK_PRIOR",
+        "VK_DELETE",
+        "VK_END",
+        "VK_NEXT",
+    ]
+    .into_iter()
+    .map(str::to_string);
+    numpad.chain(navigation).collect()
 }
 
 impl Default for KeyViewerConfig {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            remap_pool: default_remap_pool(),
+        }
     }
 }
 
@@ -0,0 +1,267 @@
+//! Syntax tree for 3DMigoto INI files.
+//!
+//! One parser for every consumer that needs more than raw lines: the editor
+//! model in `document`, the keyviewer harvester, the conflict scanner, the
+//! deep matcher's hash signals and the folder classifier. Every node keeps the
+//! index of the `raw_lines` entry it came from so diagnostics and edits can
+//! point back at the source.
+
+mod parse;
+
+use std::collections::HashSet;
+
+pub use parse::{is_resource_ref, parse_ini_ast, parse_ini_text, section_header_name};
+
+/// Half-open range of `raw_lines` indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// What a section is for, from its name. Prefix matches are case-insensitive,
+/// like 3DMigoto's own dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionKind {
+    Constants,
+    Present,
+    TextureOverride,
+    ShaderOverride,
+    ShaderRegex,
+    Resource,
+    CommandList,
+    CustomShader,
+    Key,
+    Other,
+}
+
+impl SectionKind {
+    pub fn from_name(name: &str) -> Self {
+        let lower = name.to_ascii_lowercase();
+        match lower.as_str() {
+            "constants" => return Self::Constants,
+            "present" => return Self::Present,
+            _ => {}
+        }
+        const PREFIXES: &[(&str, SectionKind)] = &[
+            ("textureoverride", SectionKind::TextureOverride),
+            ("shaderoverride", SectionKind::ShaderOverride),
+            ("shaderregex", SectionKind::ShaderRegex),
+            ("resource", SectionKind::Resource),
+            ("commandlist", SectionKind::CommandList),
+            ("customshader", SectionKind::CustomShader),
+            ("key", SectionKind::Key),
+        ];
+        PREFIXES
+            .iter()
+            .find(|(prefix, _)| lower.starts_with(prefix))
+            .map_or(Self::Other, |(_, kind)| *kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignmentKind {
+    /// Any `key = value` without a more specific meaning.
+    Property,
+    /// `$name = value`, optionally qualified `global`/`persist`/`local`.
+    Variable,
+    /// `run = CommandList…` (or a built-in command list).
+    Run { target: String },
+    /// `namespace = …`.
+    Namespace,
+    /// A value naming a `Resource…` section, e.g. `ps-t0 = ResourceBody` or
+    /// `ResourceA = ref ResourceB`; `modifiers` are the words before it.
+    ResourceRef {
+        target: String,
+        modifiers: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    pub line_idx: usize,
+    /// Lowercased words before the key: `global`, `persist`, `pre`, `post`…
+    pub qualifiers: Vec<String>,
+    /// Key as written.
+    pub key: String,
+    /// Value with any inline `;`/`#` comment removed, trimmed.
+    pub value: String,
+    pub kind: AssignmentKind,
+}
+
+impl Assignment {
+    pub fn key_is(&self, name: &str) -> bool {
+        self.qualifiers.is_empty() && self.key.eq_ignore_ascii_case(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchKeyword {
+    If,
+    ElseIf,
+    Else,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Branch {
+    pub keyword: BranchKeyword,
+    pub condition: Option<String>,
+    pub line_idx: usize,
+    pub body: Vec<AstNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conditional {
+    pub branches: Vec<Branch>,
+    /// Line of the closing `endif`, or `None` when the section ended first.
+    pub endif_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AstNode {
+    Assignment(Assignment),
+    Conditional(Conditional),
+    /// A command line without `=`, such as `handling skip`.
+    Statement {
+        line_idx: usize,
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstSection {
+    pub name: String,
+    pub kind: SectionKind,
+    /// Header line through the line before the next header.
+    pub span: LineSpan,
+    pub body: Vec<AstNode>,
+}
+
+impl AstSection {
+    pub fn header_line(&self) -> usize {
+        self.span.start
+    }
+
+    /// Every assignment in source order, including those inside conditionals.
+    pub fn assignments(&self) -> Vec<&Assignment> {
+        let mut out = Vec::new();
+        collect_assignments(&self.body, &mut out);
+        out
+    }
+
+    pub fn is_conditional(&self) -> bool {
+        self.body
+            .iter()
+            .any(|node| matches!(node, AstNode::Conditional(_)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AstIssueKind {
+    /// `[` without a closing `]`; the file is not safely parseable.
+    MalformedHeader,
+    /// `elif`/`else` with no open `if`.
+    OrphanBranch,
+    /// `endif` with no open `if`.
+    UnmatchedEndif,
+    /// `if` still open when its section (or the file) ended.
+    UnterminatedIf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AstIssue {
+    pub kind: AstIssueKind,
+    pub line_idx: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IniAst {
+    /// `namespace =` from the lines before the first section.
+    pub namespace: Option<String>,
+    /// Lines before the first section header.
+    pub preamble: Vec<AstNode>,
+    pub sections: Vec<AstSection>,
+    pub issues: Vec<AstIssue>,
+}
+
+impl IniAst {
+    pub fn is_malformed(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.kind == AstIssueKind::MalformedHeader)
+    }
+
+    /// First section called `name`, case-insensitively.
+    pub fn section(&self, name: &str) -> Option<&AstSection> {
+        self.sections
+            .iter()
+            .find(|section| section.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn sections_of_kind(&self, kind: SectionKind) -> impl Iterator<Item = &AstSection> {
+        self.sections
+            .iter()
+            .filter(move |section| section.kind == kind)
+    }
+
+    /// Every assignment in the file, preamble first, in source order.
+    pub fn assignments(&self) -> Vec<&Assignment> {
+        let mut out = Vec::new();
+        collect_assignments(&self.preamble, &mut out);
+        for section in &self.sections {
+            collect_assignments(&section.body, &mut out);
+        }
+        out
+    }
+
+    /// Follows `ResourceA = ref ResourceB` aliases from `start` and returns
+    /// each resource name visited, `start` first. Stops at a name that is not
+    /// re-pointed anywhere, or at a cycle.
+    pub fn resource_chain(&self, start: &str) -> Vec<String> {
+        let aliases: Vec<(&str, &str)> = self
+            .assignments()
+            .into_iter()
+            .filter_map(|assignment| match &assignment.kind {
+                AssignmentKind::ResourceRef { target, .. }
+                    if SectionKind::from_name(&assignment.key) == SectionKind::Resource =>
+                {
+                    Some((assignment.key.as_str(), target.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut chain = vec![start.to_string()];
+        let mut seen = HashSet::from([start.to_ascii_lowercase()]);
+        let mut current = start.to_string();
+        while let Some((_, next)) = aliases
+            .iter()
+            .find(|(from, _)| from.eq_ignore_ascii_case(&current))
+        {
+            if !seen.insert(next.to_ascii_lowercase()) {
+                break;
+            }
+            chain.push((*next).to_string());
+            current = (*next).to_string();
+        }
+        chain
+    }
+}
+
+fn collect_assignments<'a>(nodes: &'a [AstNode], out: &mut Vec<&'a Assignment>) {
+    for node in nodes {
+        match node {
+            AstNode::Assignment(assignment) => out.push(assignment),
+            AstNode::Conditional(conditional) => {
+                for branch in &conditional.branches {
+                    collect_assignments(&branch.body, out);
+                }
+            }
+            AstNode::Statement { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/ast_tests.rs"]
+mod tests;
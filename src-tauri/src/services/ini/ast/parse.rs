@@ -0,0 +1,245 @@
+//! Line-oriented parser behind [`IniAst`].
+
+use super::{
+    Assignment, AssignmentKind, AstIssue, AstIssueKind, AstNode, AstSection, Branch, BranchKeyword,
+    Conditional, IniAst, LineSpan, SectionKind,
+};
+use crate::services::ini::encoding::split_lines_preserving_terminators;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static SECTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]\s*(?:[;#].*)?$").expect("valid section regex"));
+
+/// A value naming a `[Resource…]` section: `Resource` followed by the start
+/// of a new word (`ResourceBody`, `Resource_1`, `Resource\ns\Body`), so a
+/// path such as `resources/body.dds` or a word like `Resources` is not one.
+static RESOURCE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^Resource[A-Z0-9_\\][A-Za-z0-9_\\]*$").expect("valid resource regex")
+});
+
+/// Whether `name`, written as a value or key, refers to a `[Resource…]`
+/// section.
+pub fn is_resource_ref(name: &str) -> bool {
+    RESOURCE_REF_RE.is_match(name)
+}
+
+/// Trimmed name of a `[Section]` header line, or `None` for any other line.
+/// A comment after the closing bracket is ignored.
+pub fn section_header_name(line: &str) -> Option<&str> {
+    SECTION_RE
+        .captures(line.trim())
+        .and_then(|caps| caps.get(1))
+        .map(|name| name.as_str().trim())
+}
+
+/// Parses text that has not been split yet. Line indices match what
+/// `read_ini_document` would put in `raw_lines` for the same text.
+pub fn parse_ini_text(text: &str) -> IniAst {
+    let (lines, _terminators) = split_lines_preserving_terminators(text);
+    parse_ini_ast(&lines)
+}
+
+pub fn parse_ini_ast(raw_lines: &[String]) -> IniAst {
+    let mut parser = Parser::default();
+    for (idx, line) in raw_lines.iter().enumerate() {
+        parser.line(idx, line);
+    }
+    parser.finish(raw_lines.len())
+}
+
+enum ControlLine<'a> {
+    Branch(BranchKeyword, Option<&'a str>),
+    EndIf,
+}
+
+#[derive(Default)]
+struct Parser {
+    ast: IniAst,
+    section: Option<AstSection>,
+    /// Conditionals still waiting for their `endif`, innermost last.
+    open: Vec<Conditional>,
+}
+
+impl Parser {
+    fn line(&mut self, idx: usize, line: &str) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with([';', '#']) {
+            return;
+        }
+
+        if trimmed.starts_with('[') {
+            if !trimmed.contains(']') {
+                self.issue(AstIssueKind::MalformedHeader, idx);
+                return;
+            }
+            if let Some(name) = section_header_name(trimmed) {
+                self.close_section(idx);
+                self.section = Some(AstSection {
+                    name: name.to_string(),
+                    kind: SectionKind::from_name(name),
+                    span: LineSpan {
+                        start: idx,
+                        end: idx + 1,
+                    },
+                    body: Vec::new(),
+                });
+                return;
+            }
+        }
+
+        match control_line(trimmed) {
+            Some(ControlLine::Branch(BranchKeyword::If, condition)) => {
+                self.open.push(Conditional {
+                    branches: vec![branch(BranchKeyword::If, condition, idx)],
+                    endif_line: None,
+                });
+            }
+            Some(ControlLine::Branch(keyword, condition)) => match self.open.last_mut() {
+                Some(conditional) => conditional.branches.push(branch(keyword, condition, idx)),
+                None => self.issue(AstIssueKind::OrphanBranch, idx),
+            },
+            Some(ControlLine::EndIf) => match self.open.pop() {
+                Some(mut conditional) => {
+                    conditional.endif_line = Some(idx);
+                    self.push(AstNode::Conditional(conditional));
+                }
+                None => self.issue(AstIssueKind::UnmatchedEndif, idx),
+            },
+            None => {
+                let node = parse_statement(idx, trimmed);
+                self.push(node);
+            }
+        }
+    }
+
+    fn issue(&mut self, kind: AstIssueKind, line_idx: usize) {
+        self.ast.issues.push(AstIssue { kind, line_idx });
+    }
+
+    fn push(&mut self, node: AstNode) {
+        let body = match (self.open.last_mut(), self.section.as_mut()) {
+            (Some(conditional), _) => match conditional.branches.last_mut() {
+                Some(branch) => &mut branch.body,
+                None => return,
+            },
+            (None, Some(section)) => &mut section.body,
+            (None, None) => &mut self.ast.preamble,
+        };
+        body.push(node);
+    }
+
+    /// Ends whatever is open at `next_idx`: unterminated conditionals first,
+    /// then the current section.
+    fn close_section(&mut self, next_idx: usize) {
+        while let Some(conditional) = self.open.pop() {
+            let if_line = conditional
+                .branches
+                .first()
+                .map_or(next_idx, |branch| branch.line_idx);
+            self.issue(AstIssueKind::UnterminatedIf, if_line);
+            self.push(AstNode::Conditional(conditional));
+        }
+        if let Some(mut section) = self.section.take() {
+            section.span.end = next_idx;
+            self.ast.sections.push(section);
+        }
+    }
+
+    fn finish(mut self, line_count: usize) -> IniAst {
+        self.close_section(line_count);
+        let mut preamble_assignments = Vec::new();
+        super::collect_assignments(&self.ast.preamble, &mut preamble_assignments);
+        self.ast.namespace = preamble_assignments
+            .into_iter()
+            .find(|assignment| assignment.kind == AssignmentKind::Namespace)
+            .map(|assignment| assignment.value.clone());
+        self.ast.issues.sort_by_key(|issue| issue.line_idx);
+        self.ast
+    }
+}
+
+fn branch(keyword: BranchKeyword, condition: Option<&str>, line_idx: usize) -> Branch {
+    Branch {
+        keyword,
+        condition: condition.map(str::to_string),
+        line_idx,
+        body: Vec::new(),
+    }
+}
+
+fn control_line(trimmed: &str) -> Option<ControlLine<'_>> {
+    let (word, rest) = trimmed
+        .split_once(char::is_whitespace)
+        .map_or((trimmed, ""), |(word, rest)| (word, rest.trim()));
+    let condition = (!rest.is_empty()).then_some(rest);
+    match word.to_ascii_lowercase().as_str() {
+        "if" => Some(ControlLine::Branch(BranchKeyword::If, condition)),
+        "elif" => Some(ControlLine::Branch(BranchKeyword::ElseIf, condition)),
+        "else" => match rest.split_once(char::is_whitespace) {
+            Some((next, tail)) if next.eq_ignore_ascii_case("if") => Some(ControlLine::Branch(
+                BranchKeyword::ElseIf,
+                Some(tail.trim()).filter(|tail| !tail.is_empty()),
+            )),
+            _ if rest.eq_ignore_ascii_case("if") => {
+                Some(ControlLine::Branch(BranchKeyword::ElseIf, None))
+            }
+            _ => Some(ControlLine::Branch(BranchKeyword::Else, None)),
+        },
+        "endif" => Some(ControlLine::EndIf),
+        _ => None,
+    }
+}
+
+fn strip_inline_comment(value: &str) -> &str {
+    value
+        .split_once([';', '#'])
+        .map_or(value, |(code, _comment)| code)
+        .trim()
+}
+
+fn parse_statement(line_idx: usize, trimmed: &str) -> AstNode {
+    let statement = || AstNode::Statement {
+        line_idx,
+        text: trimmed.to_string(),
+    };
+    let Some((lhs, rhs)) = trimmed.split_once('=') else {
+        return statement();
+    };
+    let mut words: Vec<&str> = lhs.split_whitespace().collect();
+    let Some(key) = words.pop() else {
+        return statement();
+    };
+    let value = strip_inline_comment(rhs);
+
+    AstNode::Assignment(Assignment {
+        line_idx,
+        qualifiers: words.iter().map(|word| word.to_ascii_lowercase()).collect(),
+        key: key.to_string(),
+        value: value.to_string(),
+        kind: assignment_kind(key, value),
+    })
+}
+
+fn assignment_kind(key: &str, value: &str) -> AssignmentKind {
+    if key.starts_with('$') {
+        return AssignmentKind::Variable;
+    }
+    if key.eq_ignore_ascii_case("run") {
+        return AssignmentKind::Run {
+            target: value.to_string(),
+        };
+    }
+    if key.eq_ignore_ascii_case("namespace") {
+        return AssignmentKind::Namespace;
+    }
+
+    let mut words: Vec<&str> = value.split_whitespace().collect();
+    match words.pop() {
+        Some(last) if RESOURCE_REF_RE.is_match(last) => AssignmentKind::ResourceRef {
+            target: last.to_string(),
+            modifiers: words.iter().map(|word| word.to_ascii_lowercase()).collect(),
+        },
+        _ => AssignmentKind::Property,
+    }
+}
@@ -1,34 +1,19 @@
 //! Lossless INI read/discovery model for Epic 6.
 
 use crate::domain::errors::AppError;
-use regex::Regex;
+use crate::services::ini::ast::{parse_ini_ast, AssignmentKind, SectionKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
 
 pub use super::encoding::{decode_ini_bytes, IniEncoding, LineTerminator};
 use super::encoding::{decode_ini_source, source_fingerprint, split_lines_preserving_terminators};
 
-static SECTION_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]\s*$").expect("valid section regex"));
-static VARIABLE_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        r"(?i)^\s*(?:(global|persist|local)\s+)?(\$[A-Za-z_][A-Za-z0-9_\.]*)\s*=\s*([^;#\r\n]+)",
-    )
-    .expect("valid variable regex")
-});
-static KEY_BACK_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*(key|back)\s*=\s*([^;#\r\n]+)").expect("valid key regex"));
-
 /// Refuse to build an editable model for files this large — the editor holds
 /// the whole document in memory and round-trips it on save.
 const MAX_INI_BYTES: u64 = 2 * 1024 * 1024;
 
-/// 3DMigoto keybind sections are named `[Key…]`.
-const KEY_SECTION_PREFIX: &str = "key";
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 pub enum IniReadMode {
     Structured,
@@ -142,91 +127,83 @@ fn collect_ini_files(
     Ok(())
 }
 
+/// Variable qualifiers the editor surfaces; `pre`/`post` are command-list
+/// timing and do not make a line a variable declaration.
+const VARIABLE_QUALIFIERS: &[&str] = &["global", "persist", "local"];
+
 /// Structured view of an INI body, or `None` when the file is not safely
 /// parseable and the caller must fall back to raw lines.
 fn parse_structured(raw_lines: &[String]) -> Option<(Vec<IniVariable>, Vec<KeyBinding>)> {
-    let mut variables: Vec<IniVariable> = Vec::new();
-    let mut key_bindings: Vec<KeyBinding> = Vec::new();
-    let mut section_spellings: HashMap<String, String> = HashMap::new();
-    let mut key_section: Option<String> = None;
-    let mut section_binding_start = 0;
+    let ast = parse_ini_ast(raw_lines);
+    // A section header that never closes means the file is malformed.
+    if ast.is_malformed() {
+        return None;
+    }
 
-    for (idx, line) in raw_lines.iter().enumerate() {
-        let trimmed = line.trim();
+    let variables = ast
+        .assignments()
+        .into_iter()
+        .filter(|assignment| {
+            assignment.kind == AssignmentKind::Variable
+                && assignment
+                    .qualifiers
+                    .iter()
+                    .all(|qualifier| VARIABLE_QUALIFIERS.contains(&qualifier.as_str()))
+        })
+        .map(|assignment| IniVariable {
+            qualifier: (!assignment.qualifiers.is_empty()).then(|| assignment.qualifiers.join(" ")),
+            name: assignment.key.clone(),
+            value: assignment.value.clone(),
+            line_idx: assignment.line_idx,
+        })
+        .collect();
 
-        if trimmed.starts_with('[') {
-            // A section header that never closes means the file is malformed.
-            if !trimmed.contains(']') {
-                return None;
-            }
-            if let Some(caps) = SECTION_RE.captures(trimmed) {
-                // Whether this is a keybind section is fixed for the whole
-                // section, so classify once here rather than per line.
-                let name = caps.get(1).map(|m| m.as_str().trim()).unwrap_or_default();
-                // `get` rather than slicing: a non-ASCII section name must not
-                // panic on a byte index that lands mid-character.
-                key_section = name
-                    .get(..KEY_SECTION_PREFIX.len())
-                    .filter(|head| head.eq_ignore_ascii_case(KEY_SECTION_PREFIX))
-                    .map(|_| {
-                        let logical_name = name.to_ascii_lowercase();
-                        section_spellings
-                            .entry(logical_name)
-                            .or_insert_with(|| name.to_string())
-                            .clone()
-                    });
-                section_binding_start = key_bindings.len();
+    let mut key_bindings: Vec<KeyBinding> = Vec::new();
+    let mut section_spellings: HashMap<String, String> = HashMap::new();
+    for section in ast.sections_of_kind(SectionKind::Key) {
+        // Repeated `[KeySwap]`/`[KEYSWAP]` headers are one logical section;
+        // report them under the first spelling.
+        let section_name = section_spellings
+            .entry(section.name.to_ascii_lowercase())
+            .or_insert_with(|| section.name.clone())
+            .clone();
+        let section_binding_start = key_bindings.len();
+
+        for assignment in section.assignments() {
+            let is_key = assignment.key_is("key");
+            if !is_key && !assignment.key_is("back") {
                 continue;
             }
-        }
-
-        if let Some(caps) = VARIABLE_RE.captures(line) {
-            variables.push(IniVariable {
-                qualifier: caps.get(1).map(|value| value.as_str().to_ascii_lowercase()),
-                name: caps[2].trim().to_string(),
-                value: caps[3].trim().to_string(),
-                line_idx: idx,
-            });
-            continue;
-        }
-
-        let Some(section_name) = key_section.as_ref() else {
-            continue;
-        };
-
-        let Some(caps) = KEY_BACK_RE.captures(line) else {
-            continue;
-        };
-        let is_key = caps[1].eq_ignore_ascii_case("key");
-        let entry_index = key_bindings[section_binding_start..]
-            .iter()
-            .rposition(|entry| {
-                if is_key {
-                    entry.key.is_none()
-                } else {
-                    entry.back.is_none()
-                }
-            })
-            .map(|relative| section_binding_start + relative)
-            .unwrap_or_else(|| {
-                key_bindings.push(KeyBinding {
-                    section_name: section_name.to_string(),
-                    key: None,
-                    back: None,
-                    key_line_idx: None,
-                    back_line_idx: None,
+            let entry_index = key_bindings[section_binding_start..]
+                .iter()
+                .rposition(|entry| {
+                    if is_key {
+                        entry.key.is_none()
+                    } else {
+                        entry.back.is_none()
+                    }
+                })
+                .map(|relative| section_binding_start + relative)
+                .unwrap_or_else(|| {
+                    key_bindings.push(KeyBinding {
+                        section_name: section_name.clone(),
+                        key: None,
+                        back: None,
+                        key_line_idx: None,
+                        back_line_idx: None,
+                    });
+                    key_bindings.len() - 1
                 });
-                key_bindings.len() - 1
-            });
-        let entry = &mut key_bindings[entry_index];
-
-        let value = caps[2].trim().to_string();
-        if is_key {
-            entry.key = Some(value);
-            entry.key_line_idx = Some(idx);
-        } else {
-            entry.back = Some(value);
-            entry.back_line_idx = Some(idx);
+            let entry = &mut key_bindings[entry_index];
+
+            let value = assignment.value.clone();
+            if is_key {
+                entry.key = Some(value);
+                entry.key_line_idx = Some(assignment.line_idx);
+            } else {
+                entry.back = Some(value);
+                entry.back_line_idx = Some(assignment.line_idx);
+            }
         }
     }
 
@@ -0,0 +1,303 @@
+//! Typed structural edits over an `IniDocument`.
+//!
+//! Line updates can only rewrite lines that already exist. These operations
+//! insert, remove and rename whole sections and keys, and produce a new line
+//! list that the save pipeline in `write` commits with the same stale check,
+//! encoding round-trip and backup rotation.
+
+use crate::domain::errors::AppError;
+use crate::services::ini::ast::section_header_name;
+use crate::services::ini::document::{IniDocument, NewlineStyle};
+use crate::services::ini::encoding::LineTerminator;
+use serde::{Deserialize, Serialize};
+
+/// One structural change. Section and key names match case-insensitively,
+/// like 3DMigoto itself; when a section name is duplicated in the file, the
+/// first occurrence is the one edited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum IniEdit {
+    /// Appends `[name]` followed by `lines` at the end of the file.
+    InsertSection { name: String, lines: Vec<String> },
+    /// Removes the header and every line up to the next section header.
+    RemoveSection { name: String },
+    /// Rewrites the header only; `run =` references are left to the caller.
+    RenameSection { from: String, to: String },
+    /// Replaces the value of `key` in `section`, or appends `key = value`
+    /// when the section does not have it yet.
+    SetKey {
+        section: String,
+        key: String,
+        value: String,
+    },
+    /// Removes every `key = …` line in `section`.
+    DeleteKey { section: String, key: String },
+    /// Appends a raw line to the end of `section`, or to the end of the file
+    /// when no section is given.
+    AppendLine {
+        section: Option<String>,
+        line: String,
+    },
+    /// Rewrites the line at `line_idx` in place. The index counts lines as
+    /// the buffer holds them, so it refers to the document as read only
+    /// while no earlier edit inserted or removed lines.
+    ReplaceLine {
+        #[specta(type = f64)]
+        line_idx: usize,
+        line: String,
+    },
+}
+
+/// Working copy of the document's lines, each carrying its own terminator so
+/// inserted and removed lines keep the two vectors aligned.
+struct EditBuffer {
+    lines: Vec<(String, LineTerminator)>,
+    default_terminator: LineTerminator,
+    ends_without_newline: bool,
+}
+
+impl EditBuffer {
+    fn new(document: &IniDocument) -> Self {
+        let default_terminator = match document.newline_style {
+            NewlineStyle::Lf => LineTerminator::Lf,
+            NewlineStyle::CrLf => LineTerminator::CrLf,
+        };
+        Self {
+            lines: document
+                .raw_lines
+                .iter()
+                .cloned()
+                .zip(document.line_terminators.iter().copied())
+                .collect(),
+            default_terminator,
+            ends_without_newline: document.line_terminators.last() == Some(&LineTerminator::None),
+        }
+    }
+
+    /// Header index and exclusive end of the first section called `name`.
+    fn section_span(&self, name: &str) -> Option<(usize, usize)> {
+        let start = self.lines.iter().position(|(line, _)| {
+            section_header_name(line).is_some_and(|header| header.eq_ignore_ascii_case(name))
+        })?;
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|(line, _)| section_header_name(line).is_some())
+            .map_or(self.lines.len(), |offset| start + 1 + offset);
+        Some((start, end))
+    }
+
+    fn require_section(&self, name: &str) -> Result<(usize, usize), AppError> {
+        self.section_span(name)
+            .ok_or_else(|| AppError::Validation(format!("INI section [{name}] not found")))
+    }
+
+    /// Insertion point after the last non-blank line of a section, so trailing
+    /// blank separators stay between this section and the next.
+    fn section_tail(&self, (start, end): (usize, usize)) -> usize {
+        self.lines[start + 1..end]
+            .iter()
+            .rposition(|(line, _)| !line.trim().is_empty())
+            .map_or(start + 1, |offset| start + 2 + offset)
+    }
+
+    fn key_lines(&self, (start, end): (usize, usize), key: &str) -> Vec<usize> {
+        (start + 1..end)
+            .filter(|&idx| {
+                key_name(&self.lines[idx].0).is_some_and(|name| name.eq_ignore_ascii_case(key))
+            })
+            .collect()
+    }
+
+    fn insert(&mut self, idx: usize, line: String) {
+        self.lines.insert(idx, (line, self.default_terminator));
+    }
+
+    fn apply(&mut self, edit: &IniEdit) -> Result<(), AppError> {
+        match edit {
+            IniEdit::InsertSection { name, lines } => {
+                validate_section_name(name)?;
+                lines.iter().try_for_each(|line| validate_line(line))?;
+                if self.section_span(name).is_some() {
+                    return Err(AppError::Validation(format!(
+                        "INI section [{name}] already exists"
+                    )));
+                }
+                if self
+                    .lines
+                    .last()
+                    .is_some_and(|(line, _)| !line.trim().is_empty())
+                {
+                    self.insert(self.lines.len(), String::new());
+                }
+                self.insert(self.lines.len(), format!("[{name}]"));
+                for line in lines {
+                    self.insert(self.lines.len(), line.clone());
+                }
+            }
+            IniEdit::RemoveSection { name } => {
+                let (start, end) = self.require_section(name)?;
+                // The section ends at its last key line: comments and blank
+                // lines after it lead into the next section and stay.
+                let mut body_end = self.lines[start + 1..end]
+                    .iter()
+                    .rposition(|(line, _)| !is_blank_or_comment(line))
+                    .map_or(start + 1, |offset| start + 2 + offset);
+                // A blank line already separates what came before, so the
+                // blank ones after the body would double it.
+                if start == 0 || self.lines[start - 1].0.trim().is_empty() {
+                    while body_end < end && self.lines[body_end].0.trim().is_empty() {
+                        body_end += 1;
+                    }
+                }
+                self.lines.drain(start..body_end);
+            }
+            IniEdit::RenameSection { from, to } => {
+                validate_section_name(to)?;
+                let (start, _) = self.require_section(from)?;
+                if !from.eq_ignore_ascii_case(to) && self.section_span(to).is_some() {
+                    return Err(AppError::Validation(format!(
+                        "INI section [{to}] already exists"
+                    )));
+                }
+                let header = &mut self.lines[start].0;
+                let indent_len = header.len() - header.trim_start().len();
+                *header = format!("{}[{to}]", &header[..indent_len]);
+            }
+            IniEdit::SetKey {
+                section,
+                key,
+                value,
+            } => {
+                validate_key_name(key)?;
+                validate_line(value)?;
+                let span = self.require_section(section)?;
+                match self.key_lines(span, key).as_slice() {
+                    [] => {
+                        let at = self.section_tail(span);
+                        self.insert(at, format!("{key} = {value}"));
+                    }
+                    [idx] => {
+                        let line = &mut self.lines[*idx].0;
+                        let indent_len = line.len() - line.trim_start().len();
+                        let existing = key_name(line).unwrap_or(key).to_string();
+                        *line = format!("{}{existing} = {value}", &line[..indent_len]);
+                    }
+                    _ => {
+                        return Err(AppError::Validation(format!(
+                            "Key `{key}` appears more than once in [{section}]; edit the line directly"
+                        )));
+                    }
+                }
+            }
+            IniEdit::DeleteKey { section, key } => {
+                let span = self.require_section(section)?;
+                let matches = self.key_lines(span, key);
+                if matches.is_empty() {
+                    return Err(AppError::Validation(format!(
+                        "Key `{key}` not found in [{section}]"
+                    )));
+                }
+                for idx in matches.into_iter().rev() {
+                    self.lines.remove(idx);
+                }
+            }
+            IniEdit::AppendLine { section, line } => {
+                validate_line(line)?;
+                let at = match section {
+                    Some(name) => {
+                        let span = self.require_section(name)?;
+                        self.section_tail(span)
+                    }
+                    None => self.lines.len(),
+                };
+                self.insert(at, line.clone());
+            }
+            IniEdit::ReplaceLine { line_idx, line } => {
+                validate_line(line)?;
+                let Some((existing, _)) = self.lines.get_mut(*line_idx) else {
+                    return Err(AppError::Validation(format!(
+                        "Line index out of bounds: {line_idx}"
+                    )));
+                };
+                *existing = line.clone();
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores the file's final-newline state: only the last line may lack a
+    /// terminator, and it does exactly when the original did.
+    fn finish(mut self) -> (Vec<String>, Vec<LineTerminator>) {
+        let last = self.lines.len().saturating_sub(1);
+        for (idx, (_, terminator)) in self.lines.iter_mut().enumerate() {
+            if idx == last && self.ends_without_newline {
+                *terminator = LineTerminator::None;
+            } else if *terminator == LineTerminator::None {
+                *terminator = self.default_terminator;
+            }
+        }
+        self.lines.into_iter().unzip()
+    }
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty() || trimmed.starts_with([';', '#'])
+}
+
+/// Name of a `name = value` line, ignoring comments and section headers.
+fn key_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with([';', '#', '[']) {
+        return None;
+    }
+    let (name, _) = trimmed.split_once('=')?;
+    let name = name.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+fn validate_line(line: &str) -> Result<(), AppError> {
+    if line.contains(['\r', '\n']) {
+        return Err(AppError::Validation(
+            "INI edit must not contain a newline".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_section_name(name: &str) -> Result<(), AppError> {
+    validate_line(name)?;
+    if name.trim().is_empty() || name.trim() != name || name.contains(['[', ']']) {
+        return Err(AppError::Validation(format!(
+            "Invalid INI section name: `{name}`"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_key_name(key: &str) -> Result<(), AppError> {
+    validate_line(key)?;
+    if key.trim().is_empty() || key.trim() != key || key.contains('=') {
+        return Err(AppError::Validation(format!(
+            "Invalid INI key name: `{key}`"
+        )));
+    }
+    Ok(())
+}
+
+/// Applies `edits` in order and returns the resulting lines and terminators.
+/// Nothing is written; any failing edit rejects the whole batch.
+pub fn apply_edits(
+    document: &IniDocument,
+    edits: &[IniEdit],
+) -> Result<(Vec<String>, Vec<LineTerminator>), AppError> {
+    let mut buffer = EditBuffer::new(document);
+    for edit in edits {
+        buffer.apply(edit)?;
+    }
+    Ok(buffer.finish())
+}
+
+#[cfg(test)]
+#[path = "tests/edit_tests.rs"]
+mod tests;
@@ -0,0 +1,166 @@
+//! 3DMigoto key syntax.
+//!
+//! A `key =` value is a key name preceded by modifier words: `ctrl VK_F5`
+//! fires only with Ctrl held, `no_ctrl VK_F5` only without it, and a bare
+//! `VK_F5` regardless. The same key is spelled many ways (`F5`, `vk_f5`,
+//! `0x74`), so comparing raw strings misses most real collisions.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Windows,
+}
+
+const ALL_MODIFIERS: [Modifier; 4] = [
+    Modifier::Ctrl,
+    Modifier::Alt,
+    Modifier::Shift,
+    Modifier::Windows,
+];
+
+impl Modifier {
+    /// Left/right variants fold into the plain modifier: two bindings that
+    /// differ only in which Ctrl is held are close enough to count as a clash.
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "ctrl" | "control" | "lctrl" | "rctrl" | "vk_control" | "vk_lcontrol"
+            | "vk_rcontrol" => Some(Self::Ctrl),
+            "alt" | "lalt" | "ralt" | "vk_menu" | "vk_lmenu" | "vk_rmenu" => Some(Self::Alt),
+            "shift" | "lshift" | "rshift" | "vk_shift" | "vk_lshift" | "vk_rshift" => {
+                Some(Self::Shift)
+            }
+            "win" | "windows" | "lwin" | "rwin" | "vk_lwin" | "vk_rwin" => Some(Self::Windows),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Ctrl => "Ctrl",
+            Self::Alt => "Alt",
+            Self::Shift => "Shift",
+            Self::Windows => "Win",
+        }
+    }
+}
+
+/// A parsed key binding: the key plus the modifiers that must and must not be
+/// held for it to fire. Modifiers in neither set are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// Canonical key name without the `VK_` prefix, uppercased: `F5`, `1`.
+    pub key: String,
+    pub required: BTreeSet<Modifier>,
+    pub excluded: BTreeSet<Modifier>,
+}
+
+impl KeyChord {
+    /// Parses a 3DMigoto `key =` value. Returns `None` when no key is named.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let mut chord = Self {
+            key: String::new(),
+            required: BTreeSet::new(),
+            excluded: BTreeSet::new(),
+        };
+        for word in raw.split_whitespace() {
+            let lower = word.to_ascii_lowercase();
+            if !is_modifier_word(&lower) {
+                // 3DMigoto takes the last non-modifier word as the key.
+                chord.key = canonical_key_name(&lower);
+            } else if lower == "no_modifiers" {
+                chord.excluded.extend(ALL_MODIFIERS);
+            } else if let Some(modifier) = lower.strip_prefix("no_").and_then(Modifier::from_word) {
+                chord.excluded.insert(modifier);
+            } else if let Some(modifier) = Modifier::from_word(&lower) {
+                chord.required.insert(modifier);
+            }
+        }
+        // An explicitly required modifier beats a blanket `no_modifiers`.
+        let required = chord.required.clone();
+        chord
+            .excluded
+            .retain(|modifier| !required.contains(modifier));
+        (!chord.key.is_empty()).then_some(chord)
+    }
+
+    /// Parses the app's own hotkey spelling (`Ctrl+F6`). Global hotkeys fire
+    /// only with exactly the listed modifiers, so every other one is excluded.
+    pub fn parse_hotkey(raw: &str) -> Option<Self> {
+        let mut chord = Self::parse(&raw.replace('+', " "))?;
+        chord.excluded = ALL_MODIFIERS
+            .into_iter()
+            .filter(|modifier| !chord.required.contains(modifier))
+            .collect();
+        Some(chord)
+    }
+
+    /// Whether some modifier state fires both bindings.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.key == other.key
+            && self.required.is_disjoint(&other.excluded)
+            && other.required.is_disjoint(&self.excluded)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    /// `Ctrl+F5`; excluded modifiers are left out.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.required {
+            write!(f, "{}+", modifier.label())?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+fn is_modifier_word(lower: &str) -> bool {
+    lower == "no_modifiers"
+        || Modifier::from_word(lower.strip_prefix("no_").unwrap_or(lower)).is_some()
+}
+
+/// Rewrites the key word of a `key =` value and keeps its modifier words, so
+/// `no_alt VK_F5` moved to `VK_NUMPAD1` becomes `no_alt VK_NUMPAD1`.
+pub fn with_key(raw: &str, new_key: &str) -> String {
+    let mut words: Vec<&str> = raw.split_whitespace().collect();
+    match words
+        .iter()
+        .rposition(|word| !is_modifier_word(&word.to_ascii_lowercase()))
+    {
+        Some(idx) => words[idx] = new_key,
+        None => words.push(new_key),
+    }
+    words.join(" ")
+}
+
+/// `vk_f5`, `f5` and `0x74` all become `F5`.
+fn canonical_key_name(lower: &str) -> String {
+    if let Some(code) = lower
+        .strip_prefix("0x")
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+    {
+        return match code {
+            b'0'..=b'9' | b'A'..=b'Z' => char::from(code).to_string(),
+            0x70..=0x87 => format!("F{}", code - 0x6F),
+            _ => format!("0x{code:02X}"),
+        };
+    }
+    let name = lower.strip_prefix("vk_").unwrap_or(lower);
+    let name = match name {
+        "esc" => "escape",
+        "del" => "delete",
+        "ins" => "insert",
+        "enter" => "return",
+        "pgup" | "pageup" => "prior",
+        "pgdn" | "pagedown" => "next",
+        other => other,
+    };
+    name.to_ascii_uppercase()
+}
+
+#[cfg(test)]
+#[path = "tests/keys_tests.rs"]
+mod tests;
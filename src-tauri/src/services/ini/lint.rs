@@ -0,0 +1,327 @@
+//! Static checks over a mod folder's INI files.
+//!
+//! Broken mods otherwise only show up in game: a typo'd `run =` target, a
+//! missing texture or an `if` that never closes. Each finding carries the
+//! `raw_lines` index the editor underlines.
+
+use crate::domain::errors::AppError;
+use crate::services::ini::ast::{
+    is_resource_ref, parse_ini_ast, AssignmentKind, AstIssueKind, IniAst, SectionKind,
+};
+use crate::services::ini::document::{list_ini_files, read_ini_document, IniDocument, IniEncoding};
+use crate::services::ini::keys::KeyChord;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum IniLintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum IniLintCode {
+    MalformedHeader,
+    UnbalancedConditional,
+    UndefinedCommandList,
+    UndefinedResource,
+    MissingFile,
+    DuplicateSection,
+    DuplicateKeybind,
+    LossyDecoding,
+    Unreadable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct IniDiagnostic {
+    /// Path relative to the mod root, `/`-separated like `IniFileEntry`.
+    pub file_name: String,
+    /// `None` for findings about the whole file.
+    #[specta(type = f64)]
+    pub line_idx: Option<usize>,
+    pub severity: IniLintSeverity,
+    pub code: IniLintCode,
+    pub message: String,
+}
+
+/// A key bound by another enabled mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignBinding {
+    pub mod_name: String,
+    pub key: String,
+}
+
+struct ParsedIni {
+    file_name: String,
+    document: IniDocument,
+    ast: IniAst,
+    /// Decoded with replacement characters: its sections still count as
+    /// defined, but its own findings would be noise.
+    lossy: bool,
+}
+
+/// Lints every INI under `mod_root`. `foreign` are the bindings of the other
+/// enabled mods, used to flag keys this mod shares with them.
+pub fn lint_mod_folder(
+    mod_root: &Path,
+    foreign: &[ForeignBinding],
+) -> Result<Vec<IniDiagnostic>, AppError> {
+    let mut diagnostics = Vec::new();
+    let mut parsed = Vec::new();
+
+    for path in list_ini_files(mod_root)? {
+        let file_name = path
+            .strip_prefix(mod_root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let document = match read_ini_document(&path) {
+            Ok(document) => document,
+            Err(error) => {
+                diagnostics.push(file_diagnostic(
+                    &file_name,
+                    IniLintSeverity::Error,
+                    IniLintCode::Unreadable,
+                    error.to_string(),
+                ));
+                continue;
+            }
+        };
+        let lossy = document.encoding == IniEncoding::LossyUtf8;
+        if lossy {
+            // Replacement characters make every structural finding suspect.
+            diagnostics.push(file_diagnostic(
+                &file_name,
+                IniLintSeverity::Warning,
+                IniLintCode::LossyDecoding,
+                "File is neither UTF-8 nor Shift-JIS; some characters could not be decoded"
+                    .to_string(),
+            ));
+        }
+        let ast = parse_ini_ast(&document.raw_lines);
+        parsed.push(ParsedIni {
+            file_name,
+            document,
+            ast,
+            lossy,
+        });
+    }
+
+    // 3DMigoto resolves section references across every INI it loads, so a
+    // `run =` in one file may target a command list defined in another.
+    let defined: HashSet<String> = parsed
+        .iter()
+        .flat_map(|ini| ini.ast.sections.iter())
+        .map(|section| section.name.to_ascii_lowercase())
+        .collect();
+
+    for ini in parsed.iter().filter(|ini| !ini.lossy) {
+        lint_structure(ini, &mut diagnostics);
+        lint_references(ini, &defined, &mut diagnostics);
+        lint_files(ini, mod_root, &mut diagnostics);
+    }
+    lint_keybinds(&parsed, foreign, &mut diagnostics);
+
+    Ok(diagnostics)
+}
+
+fn file_diagnostic(
+    file_name: &str,
+    severity: IniLintSeverity,
+    code: IniLintCode,
+    message: String,
+) -> IniDiagnostic {
+    IniDiagnostic {
+        file_name: file_name.to_string(),
+        line_idx: None,
+        severity,
+        code,
+        message,
+    }
+}
+
+fn line_diagnostic(
+    ini: &ParsedIni,
+    line_idx: usize,
+    severity: IniLintSeverity,
+    code: IniLintCode,
+    message: String,
+) -> IniDiagnostic {
+    IniDiagnostic {
+        line_idx: Some(line_idx),
+        ..file_diagnostic(&ini.file_name, severity, code, message)
+    }
+}
+
+fn lint_structure(ini: &ParsedIni, out: &mut Vec<IniDiagnostic>) {
+    for issue in &ini.ast.issues {
+        let (code, message) = match issue.kind {
+            AstIssueKind::MalformedHeader => (
+                IniLintCode::MalformedHeader,
+                "Section header is missing its closing `]`",
+            ),
+            AstIssueKind::OrphanBranch => (
+                IniLintCode::UnbalancedConditional,
+                "`else`/`elif` without a matching `if`",
+            ),
+            AstIssueKind::UnmatchedEndif => (
+                IniLintCode::UnbalancedConditional,
+                "`endif` without a matching `if`",
+            ),
+            AstIssueKind::UnterminatedIf => (
+                IniLintCode::UnbalancedConditional,
+                "`if` is never closed with `endif` before the section ends",
+            ),
+        };
+        out.push(line_diagnostic(
+            ini,
+            issue.line_idx,
+            IniLintSeverity::Error,
+            code,
+            message.to_string(),
+        ));
+    }
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for section in &ini.ast.sections {
+        let lower = section.name.to_ascii_lowercase();
+        match seen.get(&lower) {
+            Some(first) => out.push(line_diagnostic(
+                ini,
+                section.header_line(),
+                IniLintSeverity::Warning,
+                IniLintCode::DuplicateSection,
+                format!(
+                    "[{}] is already defined on line {}; the two definitions shadow each other",
+                    section.name,
+                    first + 1
+                ),
+            )),
+            None => {
+                seen.insert(lower, section.header_line());
+            }
+        }
+    }
+}
+
+/// Targets 3DMigoto provides itself, or that live in another mod's namespace
+/// and cannot be resolved from this folder alone.
+fn is_external_target(target: &str) -> bool {
+    target
+        .get(..7)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("builtin"))
+        || target.contains('\\')
+}
+
+fn lint_references(ini: &ParsedIni, defined: &HashSet<String>, out: &mut Vec<IniDiagnostic>) {
+    for assignment in ini.ast.assignments() {
+        let mut targets = Vec::new();
+        match &assignment.kind {
+            AssignmentKind::Run { target } => {
+                targets.push((target.as_str(), IniLintCode::UndefinedCommandList))
+            }
+            AssignmentKind::ResourceRef { target, .. } => {
+                targets.push((target.as_str(), IniLintCode::UndefinedResource));
+                if is_resource_ref(&assignment.key) {
+                    targets.push((assignment.key.as_str(), IniLintCode::UndefinedResource));
+                }
+            }
+            _ => {}
+        }
+        for (target, code) in targets {
+            if target.is_empty()
+                || is_external_target(target)
+                || defined.contains(&target.to_ascii_lowercase())
+            {
+                continue;
+            }
+            out.push(line_diagnostic(
+                ini,
+                assignment.line_idx,
+                IniLintSeverity::Error,
+                code,
+                format!("[{target}] is not defined in any INI of this mod"),
+            ));
+        }
+    }
+}
+
+fn lint_files(ini: &ParsedIni, mod_root: &Path, out: &mut Vec<IniDiagnostic>) {
+    let ini_dir = ini.document.file_path.parent().unwrap_or(mod_root);
+    for section in ini.ast.sections_of_kind(SectionKind::Resource) {
+        for assignment in section.assignments() {
+            if !assignment.key_is("filename") {
+                continue;
+            }
+            let relative = assignment
+                .value
+                .trim_matches(|c| c == '"' || c == '\'')
+                .replace('\\', "/");
+            if relative.is_empty() || ini_dir.join(&relative).exists() {
+                continue;
+            }
+            out.push(line_diagnostic(
+                ini,
+                assignment.line_idx,
+                IniLintSeverity::Error,
+                IniLintCode::MissingFile,
+                format!("`{relative}` does not exist next to this INI"),
+            ));
+        }
+    }
+}
+
+fn lint_keybinds(parsed: &[ParsedIni], foreign: &[ForeignBinding], out: &mut Vec<IniDiagnostic>) {
+    let foreign: Vec<(KeyChord, &str)> = foreign
+        .iter()
+        .filter_map(|binding| {
+            KeyChord::parse(&binding.key).map(|chord| (chord, binding.mod_name.as_str()))
+        })
+        .collect();
+
+    // Every key this mod binds so far, with the (file, section) binding it.
+    let mut own: Vec<(KeyChord, &str, &str)> = Vec::new();
+    for ini in parsed.iter().filter(|ini| !ini.lossy) {
+        for binding in &ini.document.key_bindings {
+            let (Some(key), Some(line_idx)) = (&binding.key, binding.key_line_idx) else {
+                continue;
+            };
+            let Some(chord) = KeyChord::parse(key) else {
+                continue;
+            };
+            if let Some((_, file, section)) = own.iter().find(|(other, ..)| other.overlaps(&chord))
+            {
+                out.push(line_diagnostic(
+                    ini,
+                    line_idx,
+                    IniLintSeverity::Warning,
+                    IniLintCode::DuplicateKeybind,
+                    format!("`{key}` is also bound by [{section}] in {file}"),
+                ));
+            }
+
+            let mut owners: Vec<&str> = Vec::new();
+            for (other, mod_name) in &foreign {
+                if other.overlaps(&chord) && !owners.contains(mod_name) {
+                    owners.push(mod_name);
+                }
+            }
+            if !owners.is_empty() {
+                out.push(line_diagnostic(
+                    ini,
+                    line_idx,
+                    IniLintSeverity::Warning,
+                    IniLintCode::DuplicateKeybind,
+                    format!("`{key}` is also bound by {}", owners.join(", ")),
+                ));
+            }
+            own.push((chord, &ini.file_name, &binding.section_name));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/lint_tests.rs"]
+mod tests;
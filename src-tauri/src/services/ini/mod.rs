@@ -1,3 +1,8 @@
+pub mod ast;
 pub mod document;
+pub mod edit;
 mod encoding;
+pub mod keys;
+pub mod lint;
+pub mod user_vars;
 pub mod write;
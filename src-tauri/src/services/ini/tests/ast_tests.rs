@@ -0,0 +1,177 @@
+use super::*;
+
+const SAMPLE: &str = "namespace = Mods\\Alice
+[Constants]
+global persist $swap = 0
+
+[KeySwap]
+key = VK_F5
+$swap = 0,1
+
+[TextureOverrideAliceBody]
+hash = abcd1234 ; body
+if $swap == 0
+    ps-t0 = ResourceBodyA
+else if $swap == 1
+    ps-t0 = ref ResourceBodyB
+else
+    run = CommandListFallback
+endif
+
+[CommandListFallback]
+ResourceBodyB = ref ResourceBodyA
+handling skip
+
+[ResourceBodyA]
+filename = Body/Diffuse.dds
+";
+
+#[test]
+fn sections_are_classified_and_spanned_to_the_next_header() {
+    let ast = parse_ini_text(SAMPLE);
+
+    let kinds: Vec<_> = ast.sections.iter().map(|section| section.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            SectionKind::Constants,
+            SectionKind::Key,
+            SectionKind::TextureOverride,
+            SectionKind::CommandList,
+            SectionKind::Resource,
+        ]
+    );
+    let body = ast.section("textureoverridealicebody").unwrap();
+    assert_eq!(body.span, LineSpan { start: 8, end: 18 });
+    assert_eq!(ast.namespace.as_deref(), Some("Mods\\Alice"));
+    assert!(ast.issues.is_empty());
+}
+
+#[test]
+fn conditionals_nest_branches_with_their_conditions() {
+    let ast = parse_ini_text(SAMPLE);
+    let body = ast.section("TextureOverrideAliceBody").unwrap();
+
+    let AstNode::Conditional(conditional) = &body.body[1] else {
+        panic!("expected a conditional, got {:?}", body.body[1]);
+    };
+    let keywords: Vec<_> = conditional
+        .branches
+        .iter()
+        .map(|branch| (branch.keyword, branch.condition.as_deref()))
+        .collect();
+    assert_eq!(
+        keywords,
+        vec![
+            (BranchKeyword::If, Some("$swap == 0")),
+            (BranchKeyword::ElseIf, Some("$swap == 1")),
+            (BranchKeyword::Else, None),
+        ]
+    );
+    assert_eq!(conditional.endif_line, Some(16));
+    assert!(body.is_conditional());
+    assert_eq!(body.assignments().len(), 4);
+}
+
+#[test]
+fn assignments_carry_qualifiers_kinds_and_comment_free_values() {
+    let ast = parse_ini_text(SAMPLE);
+    let all = ast.assignments();
+
+    let swap = all.iter().find(|a| a.line_idx == 2).unwrap();
+    assert_eq!(swap.qualifiers, vec!["global", "persist"]);
+    assert_eq!(swap.kind, AssignmentKind::Variable);
+
+    let hash = all.iter().find(|a| a.key_is("hash")).unwrap();
+    assert_eq!(hash.value, "abcd1234");
+
+    let run = all.iter().find(|a| a.key_is("run")).unwrap();
+    assert_eq!(
+        run.kind,
+        AssignmentKind::Run {
+            target: "CommandListFallback".to_string()
+        }
+    );
+
+    let by_ref = all.iter().find(|a| a.line_idx == 13).unwrap();
+    assert_eq!(
+        by_ref.kind,
+        AssignmentKind::ResourceRef {
+            target: "ResourceBodyB".to_string(),
+            modifiers: vec!["ref".to_string()],
+        }
+    );
+}
+
+#[test]
+fn resource_chain_follows_ref_aliases_and_stops_on_cycles() {
+    let ast = parse_ini_text(SAMPLE);
+    assert_eq!(
+        ast.resource_chain("ResourceBodyB"),
+        vec!["ResourceBodyB", "ResourceBodyA"]
+    );
+
+    let cyclic =
+        parse_ini_text("[CommandListX]\nResourceA = ref ResourceB\nResourceB = ResourceA\n");
+    assert_eq!(
+        cyclic.resource_chain("ResourceA"),
+        vec!["ResourceA", "ResourceB"]
+    );
+}
+
+#[test]
+fn unbalanced_conditionals_are_reported_per_line() {
+    let ast = parse_ini_text("[CommandListA]\nendif\nif $a\nx = 1\n[CommandListB]\nelse\n");
+
+    let issues: Vec<_> = ast
+        .issues
+        .iter()
+        .map(|issue| (issue.kind, issue.line_idx))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            (AstIssueKind::UnmatchedEndif, 1),
+            (AstIssueKind::UnterminatedIf, 2),
+            (AstIssueKind::OrphanBranch, 5),
+        ]
+    );
+    // The unterminated block still owns the lines it opened over.
+    assert_eq!(ast.sections[0].assignments().len(), 1);
+}
+
+#[test]
+fn unclosed_header_marks_the_file_malformed() {
+    let ast = parse_ini_text("[Section\n$var = 1\n");
+    assert!(ast.is_malformed());
+    assert_eq!(ast.preamble.len(), 1);
+}
+
+#[test]
+fn headers_with_a_trailing_comment_still_open_a_section() {
+    assert_eq!(
+        section_header_name("[TextureOverrideBody] ; main body"),
+        Some("TextureOverrideBody")
+    );
+    assert_eq!(section_header_name("[Present]# frame"), Some("Present"));
+    assert_eq!(section_header_name("[Key] trailing text"), None);
+
+    let ast = parse_ini_text("[TextureOverrideBody] ; main body\nhash = abcd1234\n");
+    assert_eq!(ast.sections.len(), 1);
+    assert_eq!(ast.sections[0].kind, SectionKind::TextureOverride);
+}
+
+#[test]
+fn only_resource_identifiers_are_resource_refs() {
+    let ast = parse_ini_text(
+        "[ResourceA]\nfilename = resources/body.dds\n\
+         [CommandListB]\nps-t1 = Resources\nps-t2 = Resource\\Mods\\Alice\\Body\nps-t3 = Resource_2\n",
+    );
+    let kinds: Vec<_> = ast
+        .sections
+        .iter()
+        .flat_map(|section| section.assignments())
+        .map(|assignment| matches!(assignment.kind, AssignmentKind::ResourceRef { .. }))
+        .collect();
+    assert_eq!(kinds, vec![false, false, true, true]);
+}
@@ -0,0 +1,221 @@
+use super::*;
+use crate::services::ini::document::parse_ini_document;
+use crate::services::ini::encoding::render_lines;
+use std::path::Path;
+
+fn edited(source: &str, edits: &[IniEdit]) -> Result<String, AppError> {
+    let document = parse_ini_document(Path::new("mod.ini"), source.as_bytes());
+    let (lines, terminators) = apply_edits(&document, edits)?;
+    render_lines(&lines, &terminators)
+}
+
+#[test]
+fn insert_section_appends_after_a_blank_separator() {
+    let output = edited(
+        "[Constants]\n$swap = 0\n",
+        &[IniEdit::InsertSection {
+            name: "KeySwap".to_string(),
+            lines: vec!["key = VK_F5".to_string(), "$swap = 0,1".to_string()],
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "[Constants]\n$swap = 0\n\n[KeySwap]\nkey = VK_F5\n$swap = 0,1\n"
+    );
+}
+
+#[test]
+fn insert_section_rejects_an_existing_name_case_insensitively() {
+    let error = edited(
+        "[KeySwap]\nkey = 1\n",
+        &[IniEdit::InsertSection {
+            name: "keyswap".to_string(),
+            lines: Vec::new(),
+        }],
+    )
+    .unwrap_err();
+
+    assert!(error.to_string().contains("already exists"));
+}
+
+#[test]
+fn remove_section_drops_its_body_up_to_the_next_header() {
+    let output = edited(
+        "[TextureOverrideDead]\nhash = abcd1234\nps-t0 = Res\n\n[Constants]\n$x = 1\n",
+        &[IniEdit::RemoveSection {
+            name: "TextureOverrideDead".to_string(),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(output, "[Constants]\n$x = 1\n");
+}
+
+#[test]
+fn remove_section_keeps_the_comments_leading_into_the_next_section() {
+    let output = edited(
+        "[Constants]\n$x = 1\n\n[KeyDead]\nkey = VK_F5\n; swaps the body\n$swap = 0\n\n; Body toggles\n[KeyBody]\nkey = VK_F6\n",
+        &[IniEdit::RemoveSection {
+            name: "KeyDead".to_string(),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "[Constants]\n$x = 1\n\n; Body toggles\n[KeyBody]\nkey = VK_F6\n"
+    );
+}
+
+#[test]
+fn rename_section_keeps_indentation_and_body() {
+    let output = edited(
+        "  [KeyOld]\nkey = 1\n",
+        &[IniEdit::RenameSection {
+            from: "keyold".to_string(),
+            to: "KeyNew".to_string(),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(output, "  [KeyNew]\nkey = 1\n");
+}
+
+#[test]
+fn set_key_replaces_in_place_or_appends_before_trailing_blank_lines() {
+    let output = edited(
+        "[KeySwap]\n  Key = VK_F5\n\n[Constants]\n",
+        &[
+            IniEdit::SetKey {
+                section: "KeySwap".to_string(),
+                key: "key".to_string(),
+                value: "VK_F6".to_string(),
+            },
+            IniEdit::SetKey {
+                section: "KeySwap".to_string(),
+                key: "back".to_string(),
+                value: "VK_F7".to_string(),
+            },
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "[KeySwap]\n  Key = VK_F6\nback = VK_F7\n\n[Constants]\n"
+    );
+}
+
+#[test]
+fn set_key_refuses_to_guess_between_duplicate_keys() {
+    let error = edited(
+        "[KeySwap]\nkey = 1\nkey = 2\n",
+        &[IniEdit::SetKey {
+            section: "KeySwap".to_string(),
+            key: "key".to_string(),
+            value: "3".to_string(),
+        }],
+    )
+    .unwrap_err();
+
+    assert!(error.to_string().contains("more than once"));
+}
+
+#[test]
+fn delete_key_removes_every_occurrence_but_not_comments() {
+    let output = edited(
+        "[KeySwap]\nkey = 1\n; key = commented\nkey = 2\ntype = cycle\n",
+        &[IniEdit::DeleteKey {
+            section: "KeySwap".to_string(),
+            key: "KEY".to_string(),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(output, "[KeySwap]\n; key = commented\ntype = cycle\n");
+}
+
+#[test]
+fn append_line_preserves_crlf_and_a_missing_final_newline() {
+    let output = edited(
+        "[Constants]\r\n$x = 1",
+        &[
+            IniEdit::AppendLine {
+                section: Some("Constants".to_string()),
+                line: "$y = 2".to_string(),
+            },
+            IniEdit::AppendLine {
+                section: None,
+                line: "; end".to_string(),
+            },
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(output, "[Constants]\r\n$x = 1\r\n$y = 2\r\n; end");
+}
+
+#[test]
+fn replace_line_rewrites_one_of_duplicate_keys_by_index() {
+    let source = "[KeySwap]\nkey = VK_F5\nkey = XB_A\n";
+    let output = edited(
+        source,
+        &[IniEdit::ReplaceLine {
+            line_idx: 2,
+            line: "key = XB_B".to_string(),
+        }],
+    )
+    .unwrap();
+    let out_of_range = edited(
+        source,
+        &[IniEdit::ReplaceLine {
+            line_idx: 3,
+            line: "key = XB_B".to_string(),
+        }],
+    );
+
+    assert_eq!(output, "[KeySwap]\nkey = VK_F5\nkey = XB_B\n");
+    assert!(out_of_range.is_err());
+}
+
+#[test]
+fn a_failing_edit_rejects_the_whole_batch() {
+    let error = edited(
+        "[Constants]\n",
+        &[
+            IniEdit::AppendLine {
+                section: None,
+                line: "$x = 1".to_string(),
+            },
+            IniEdit::RemoveSection {
+                name: "Missing".to_string(),
+            },
+        ],
+    )
+    .unwrap_err();
+
+    assert!(error.to_string().contains("[Missing] not found"));
+}
+
+#[test]
+fn edits_reject_embedded_newlines_and_bracketed_names() {
+    let newline = edited(
+        "[Constants]\n",
+        &[IniEdit::AppendLine {
+            section: None,
+            line: "a\nb".to_string(),
+        }],
+    );
+    let bracket = edited(
+        "[Constants]\n",
+        &[IniEdit::RenameSection {
+            from: "Constants".to_string(),
+            to: "Bad]Name".to_string(),
+        }],
+    );
+
+    assert!(newline.is_err());
+    assert!(bracket.is_err());
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+fn chord(raw: &str) -> KeyChord {
+    KeyChord::parse(raw).unwrap()
+}
+
+#[test]
+fn spellings_of_the_same_key_normalize_together() {
+    assert_eq!(chord("VK_F5"), chord("f5"));
+    assert_eq!(chord("0x74"), chord("F5"));
+    assert_eq!(chord("vk_1"), chord("1"));
+    assert_eq!(chord("PageUp").key, "PRIOR");
+    assert_eq!(KeyChord::parse("ctrl shift"), None);
+    assert_eq!(KeyChord::parse(""), None);
+}
+
+#[test]
+fn modifiers_split_into_required_and_excluded() {
+    let parsed = chord("no_ctrl  LShift VK_F5");
+    assert_eq!(parsed.required, BTreeSet::from([Modifier::Shift]));
+    assert_eq!(parsed.excluded, BTreeSet::from([Modifier::Ctrl]));
+    assert_eq!(parsed.to_string(), "Shift+F5");
+
+    let exact = chord("no_modifiers alt 1");
+    assert_eq!(exact.required, BTreeSet::from([Modifier::Alt]));
+    assert_eq!(exact.excluded.len(), 3);
+}
+
+#[test]
+fn overlap_requires_a_modifier_state_that_fires_both() {
+    assert!(chord("VK_F5").overlaps(&chord("ctrl VK_F5")));
+    assert!(!chord("no_ctrl VK_F5").overlaps(&chord("ctrl VK_F5")));
+    assert!(chord("no_ctrl VK_F5").overlaps(&chord("shift F5")));
+    assert!(!chord("VK_F5").overlaps(&chord("VK_F6")));
+}
+
+#[test]
+fn app_hotkeys_match_only_their_exact_modifiers() {
+    let next_preset = KeyChord::parse_hotkey("Ctrl+F6").unwrap();
+    assert_eq!(next_preset.to_string(), "Ctrl+F6");
+
+    assert!(next_preset.overlaps(&chord("VK_F6")));
+    assert!(next_preset.overlaps(&chord("ctrl no_alt VK_F6")));
+    assert!(!next_preset.overlaps(&chord("shift VK_F6")));
+    assert!(!next_preset.overlaps(&chord("no_ctrl VK_F6")));
+}
+
+#[test]
+fn with_key_swaps_the_key_word_and_keeps_modifiers() {
+    assert_eq!(with_key("no_alt  VK_F5", "VK_NUMPAD1"), "no_alt VK_NUMPAD1");
+    assert_eq!(with_key("ctrl", "VK_F9"), "ctrl VK_F9");
+}
@@ -0,0 +1,170 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn mod_with(files: &[(&str, &[u8])]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, bytes) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, bytes).unwrap();
+    }
+    dir
+}
+
+fn codes(diagnostics: &[IniDiagnostic]) -> Vec<(IniLintCode, Option<usize>)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.line_idx))
+        .collect()
+}
+
+#[test]
+fn clean_mod_has_no_findings() {
+    let dir = mod_with(&[
+        (
+            "mod.ini",
+            b"[TextureOverrideBody]\nhash = abcd1234\nps-t0 = ResourceBody\nrun = CommandListSwap\n\n[CommandListSwap]\nif $x == 1\nrun = BuiltInCommandListUnbindAllRenderTargets\nendif\n\n[ResourceBody]\nfilename = Tex\\Body.dds\n",
+        ),
+        ("Tex/Body.dds", b"DDS "),
+    ]);
+
+    assert_eq!(lint_mod_folder(dir.path(), &[]).unwrap(), Vec::new());
+}
+
+#[test]
+fn unbalanced_conditionals_and_malformed_headers_are_errors() {
+    let dir = mod_with(&[(
+        "mod.ini",
+        b"[CommandListA]\nif $a\nx = 1\n[CommandListB]\nendif\n[Broken\n",
+    )]);
+
+    let diagnostics = lint_mod_folder(dir.path(), &[]).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![
+            (IniLintCode::UnbalancedConditional, Some(1)),
+            (IniLintCode::UnbalancedConditional, Some(4)),
+            (IniLintCode::MalformedHeader, Some(5)),
+        ]
+    );
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity == IniLintSeverity::Error));
+}
+
+#[test]
+fn references_resolve_across_the_mods_ini_files() {
+    let dir = mod_with(&[
+        (
+            "a.ini",
+            b"[TextureOverrideA]\nrun = CommandListShared\nrun = CommandListTypo\nps-t0 = ResourceMissing\nrun = CommandList\\Mods\\Other\\X\n",
+        ),
+        ("b.ini", b"[CommandListShared]\nhandling skip\n"),
+    ]);
+
+    let diagnostics = lint_mod_folder(dir.path(), &[]).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![
+            (IniLintCode::UndefinedCommandList, Some(2)),
+            (IniLintCode::UndefinedResource, Some(3)),
+        ]
+    );
+    assert_eq!(diagnostics[0].file_name, "a.ini");
+}
+
+#[test]
+fn missing_files_resolve_relative_to_the_ini() {
+    let dir = mod_with(&[
+        (
+            "Sub/mod.ini",
+            b"[ResourceA]\nfilename = \"A.dds\"\n[ResourceB]\nfilename = ..\\B.buf\n[ResourceC]\nfilename = C.ib\n",
+        ),
+        ("Sub/A.dds", b"DDS "),
+        ("B.buf", b"\0"),
+    ]);
+
+    let diagnostics = lint_mod_folder(dir.path(), &[]).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![(IniLintCode::MissingFile, Some(5))]
+    );
+    assert_eq!(diagnostics[0].file_name, "Sub/mod.ini");
+}
+
+#[test]
+fn duplicate_sections_are_flagged_at_the_later_header() {
+    let dir = mod_with(&[(
+        "mod.ini",
+        b"[KeySwap]\nkey = VK_F5\n[keyswap]\nkey = VK_F6\n",
+    )]);
+
+    let diagnostics = lint_mod_folder(dir.path(), &[]).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![(IniLintCode::DuplicateSection, Some(2))]
+    );
+    assert!(diagnostics[0].message.contains("line 1"));
+}
+
+#[test]
+fn keys_shared_within_the_mod_or_with_other_mods_are_warnings() {
+    let dir = mod_with(&[
+        ("a.ini", b"[KeyA]\nkey = VK_F5\n"),
+        ("b.ini", b"[KeyB]\nkey = f5\n[KeyC]\nkey = ctrl  VK_1\n"),
+    ]);
+    let foreign = vec![
+        ForeignBinding {
+            mod_name: "Other Mod".to_string(),
+            key: "0x31".to_string(),
+        },
+        ForeignBinding {
+            mod_name: "Unmodified Only".to_string(),
+            key: "no_ctrl 1".to_string(),
+        },
+    ];
+
+    let diagnostics = lint_mod_folder(dir.path(), &foreign).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![
+            (IniLintCode::DuplicateKeybind, Some(1)),
+            (IniLintCode::DuplicateKeybind, Some(3)),
+        ]
+    );
+    assert!(diagnostics[0].message.contains("[KeyA] in a.ini"));
+    assert!(diagnostics[1].message.ends_with("bound by Other Mod"));
+}
+
+#[test]
+fn lossy_files_get_one_warning_and_no_structural_findings() {
+    let dir = mod_with(&[
+        ("mod.ini", b"[CommandListA]\nendif\n; \xff\xfe\x80\n"),
+        // The lossy file still defines what the other one runs.
+        ("other.ini", b"[TextureOverrideB]\nrun = CommandListA\n"),
+    ]);
+
+    let diagnostics = lint_mod_folder(dir.path(), &[]).unwrap();
+
+    assert_eq!(
+        codes(&diagnostics),
+        vec![(IniLintCode::LossyDecoding, None)]
+    );
+    assert_eq!(diagnostics[0].severity, IniLintSeverity::Warning);
+}
+
+#[test]
+fn values_that_only_look_like_resources_are_not_references() {
+    let dir = mod_with(&[(
+        "mod.ini",
+        b"[CommandListA]\n$path = resources/body.dds\nResources = 2\nps-t0 = ResourceBody\n\n[ResourceBody]\n",
+    )]);
+
+    assert_eq!(lint_mod_folder(dir.path(), &[]).unwrap(), Vec::new());
+}
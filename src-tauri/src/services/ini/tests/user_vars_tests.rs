@@ -0,0 +1,85 @@
+use super::*;
+use crate::services::ini::write::backup_path_for;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn namespace_prefix_is_the_lowercased_folder_under_the_importer() {
+    let user_ini = Path::new("/game/3dmigoto/d3dx_user.ini");
+
+    assert_eq!(
+        namespace_prefix(
+            user_ini,
+            Path::new("/game/3dmigoto/Mods/Raiden/Blue Kimono")
+        )
+        .as_deref(),
+        Some("$\\mods\\raiden\\blue kimono\\")
+    );
+    assert_eq!(
+        namespace_prefix(user_ini, Path::new("/elsewhere/Mods/Raiden")),
+        None
+    );
+}
+
+#[test]
+fn write_user_vars_updates_existing_values_and_appends_new_ones() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join(USER_INI_FILE);
+    let original = "; Generated by 3DMigoto\r\n[Constants]\r\n$\\mods\\raiden\\blue\\mod.ini\\swapvar = 0\r\n$\\mods\\ayaka\\mod.ini\\swapvar = 2\r\n";
+    fs::write(&path, original).unwrap();
+
+    let changed = write_user_vars(
+        &path,
+        &[
+            (
+                "$\\mods\\raiden\\blue\\mod.ini\\swapvar".to_string(),
+                "1".to_string(),
+            ),
+            (
+                "$\\mods\\raiden\\blue\\mod.ini\\hat".to_string(),
+                "0".to_string(),
+            ),
+            (
+                "$\\mods\\ayaka\\mod.ini\\swapvar".to_string(),
+                "2".to_string(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(changed, 2);
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "; Generated by 3DMigoto\r\n[Constants]\r\n$\\mods\\raiden\\blue\\mod.ini\\swapvar = 1\r\n$\\mods\\ayaka\\mod.ini\\swapvar = 2\r\n$\\mods\\raiden\\blue\\mod.ini\\hat = 0\r\n"
+    );
+    assert_eq!(
+        fs::read_to_string(backup_path_for(&path).unwrap()).unwrap(),
+        original
+    );
+    assert_eq!(read_user_vars(&path).unwrap().len(), 3);
+}
+
+#[test]
+fn write_user_vars_creates_a_missing_file() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join(USER_INI_FILE);
+
+    write_user_vars(
+        &path,
+        &[(
+            "$\\mods\\raiden\\mod.ini\\swapvar".to_string(),
+            "3".to_string(),
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_user_vars(&path).unwrap(),
+        vec![(
+            "$\\mods\\raiden\\mod.ini\\swapvar".to_string(),
+            "3".to_string()
+        )]
+    );
+    // Written through a temporary sibling that is renamed into place.
+    assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 1);
+}
@@ -208,3 +208,81 @@ fn failed_commit_restores_recovery_file() {
     assert!(!recovery.exists());
     assert!(!temp.exists());
 }
+
+#[test]
+fn structural_edits_share_the_stale_check_and_backup_rotation() {
+    let tmp = TempDir::new().unwrap();
+    let ini_path = tmp.path().join("config.ini");
+    let original = "[TextureOverrideDead]\nhash = abcd1234\n\n[Constants]\n$x = 0\n";
+    fs::write(&ini_path, original).unwrap();
+    let document = read_ini_document(&ini_path).unwrap();
+
+    let stale = save_ini_with_edits(
+        &document,
+        "not-the-current-hash",
+        &[IniEdit::RemoveSection {
+            name: "TextureOverrideDead".to_string(),
+        }],
+    );
+    assert!(stale.unwrap_err().to_string().contains("changed on disk"));
+
+    save_ini_with_edits(
+        &document,
+        &document.source_hash,
+        &[
+            IniEdit::RemoveSection {
+                name: "TextureOverrideDead".to_string(),
+            },
+            IniEdit::InsertSection {
+                name: "KeySwap".to_string(),
+                lines: vec!["key = VK_F5".to_string()],
+            },
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&ini_path).unwrap(),
+        "[Constants]\n$x = 0\n\n[KeySwap]\nkey = VK_F5\n"
+    );
+    assert_eq!(
+        fs::read_to_string(backup_path_for(&ini_path).unwrap()).unwrap(),
+        original
+    );
+}
+
+#[test]
+fn batch_save_puts_committed_files_back_when_a_later_one_fails() {
+    let tmp = TempDir::new().unwrap();
+    let first = tmp.path().join("a.ini");
+    let second = tmp.path().join("b.ini");
+    fs::write(&first, "[TextureOverrideA]\nhash = 1\n").unwrap();
+    fs::write(&second, "[TextureOverrideB]\nhash = 2\n").unwrap();
+    let edit = |section: &str| IniEdit::SetKey {
+        section: section.to_string(),
+        key: "match_priority".to_string(),
+        value: "-1".to_string(),
+    };
+    let batch = vec![
+        (
+            read_ini_document(&first).unwrap(),
+            vec![edit("TextureOverrideA")],
+        ),
+        (
+            read_ini_document(&second).unwrap(),
+            vec![edit("TextureOverrideB")],
+        ),
+    ];
+    // Edited behind the batch's back: its stale check fails.
+    fs::write(&second, "[TextureOverrideB]\nhash = 3\n").unwrap();
+
+    assert!(save_ini_batch_with_edits(&batch).is_err());
+    assert_eq!(
+        fs::read_to_string(&first).unwrap(),
+        "[TextureOverrideA]\nhash = 1\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&second).unwrap(),
+        "[TextureOverrideB]\nhash = 3\n"
+    );
+}
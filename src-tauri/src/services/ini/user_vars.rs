@@ -0,0 +1,127 @@
+//! 3DMigoto's `d3dx_user.ini`: the `persist` variable values it saves
+//! between sessions, one `$\<ini path>\<name> = <value>` line per variable
+//! under `[Constants]`, namespaced by the INI that declares them.
+
+use crate::domain::errors::AppError;
+use crate::services::ini::ast::section_header_name;
+use crate::services::ini::document::read_ini_document;
+use crate::services::ini::edit::IniEdit;
+use crate::services::ini::write::save_ini_with_edits;
+use crate::services::keyviewer::generator::atomic_write;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+pub const USER_INI_FILE: &str = "d3dx_user.ini";
+const CONSTANTS_SECTION: &str = "Constants";
+
+/// `d3dx_user.ini` beside the `d3dx.ini` that loads `mods_root`. The file
+/// itself may not exist yet.
+pub fn user_ini_path(mods_root: &Path, game_exe: Option<&Path>) -> Option<PathBuf> {
+    crate::services::game::validator::importer_dir(mods_root, game_exe)
+        .map(|dir| dir.join(USER_INI_FILE))
+}
+
+/// Prefix of every variable key declared by INIs under `folder`, e.g.
+/// `$\mods\raiden\blue\`. Lowercased, like the keys 3DMigoto writes; `None`
+/// when `folder` is not under the directory holding `user_ini`.
+pub fn namespace_prefix(user_ini: &Path, folder: &Path) -> Option<String> {
+    let relative = folder.strip_prefix(user_ini.parent()?).ok()?;
+    let mut prefix = String::from("$");
+    for component in relative.components() {
+        let Component::Normal(part) = component else {
+            return None;
+        };
+        prefix.push('\\');
+        prefix.push_str(&part.to_string_lossy());
+    }
+    prefix.push('\\');
+    Some(prefix.to_lowercase())
+}
+
+/// `key = value` of a `$…` line, without its inline comment.
+fn variable_line(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('$') {
+        return None;
+    }
+    let (key, value) = trimmed.split_once('=')?;
+    let value = value.split_once(';').map_or(value, |(value, _)| value);
+    Some((key.trim(), value.trim()))
+}
+
+/// Every saved variable, in file order. A missing file has none.
+pub fn read_user_vars(path: &Path) -> Result<Vec<(String, String)>, AppError> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let document = read_ini_document(path)?;
+    Ok(document
+        .raw_lines
+        .iter()
+        .filter_map(|line| variable_line(line))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+/// Sets each variable in `vars`, adding the ones the file lacks, through the
+/// backed-up INI save pipeline. A missing file is created with an atomic
+/// write, as there is nothing to back up. Returns how many values changed.
+///
+/// 3DMigoto rewrites this file from memory when the game exits, so values
+/// written while it runs are lost; callers check for that first.
+pub fn write_user_vars(path: &Path, vars: &[(String, String)]) -> Result<usize, AppError> {
+    if vars.is_empty() {
+        return Ok(0);
+    }
+    if !path.exists() {
+        let mut text = format!("[{CONSTANTS_SECTION}]\r\n");
+        for (key, value) in vars {
+            text.push_str(&format!("{key} = {value}\r\n"));
+        }
+        atomic_write(path, &text)?;
+        return Ok(vars.len());
+    }
+
+    let document = read_ini_document(path)?;
+    let current: HashMap<String, &str> = document
+        .raw_lines
+        .iter()
+        .filter_map(|line| variable_line(line))
+        .map(|(key, value)| (key.to_lowercase(), value))
+        .collect();
+    let changed: Vec<&(String, String)> = vars
+        .iter()
+        .filter(|(key, value)| current.get(&key.to_lowercase()) != Some(&value.as_str()))
+        .collect();
+    if changed.is_empty() {
+        return Ok(0);
+    }
+
+    let has_constants = document.raw_lines.iter().any(|line| {
+        section_header_name(line).is_some_and(|name| name.eq_ignore_ascii_case(CONSTANTS_SECTION))
+    });
+    let edits = if has_constants {
+        changed
+            .iter()
+            .map(|(key, value)| IniEdit::SetKey {
+                section: CONSTANTS_SECTION.to_string(),
+                key: key.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    } else {
+        vec![IniEdit::InsertSection {
+            name: CONSTANTS_SECTION.to_string(),
+            lines: changed
+                .iter()
+                .map(|(key, value)| format!("{key} = {value}"))
+                .collect(),
+        }]
+    };
+    save_ini_with_edits(&document, &document.source_hash, &edits)?;
+    Ok(changed.len())
+}
+
+#[cfg(test)]
+#[path = "tests/user_vars_tests.rs"]
+mod tests;
@@ -2,7 +2,10 @@
 
 use crate::domain::errors::AppError;
 use crate::services::ini::document::{IniDocument, IniReadMode};
-use crate::services::ini::encoding::{encode_ini_text, render_lines, source_fingerprint};
+use crate::services::ini::edit::{apply_edits, IniEdit};
+use crate::services::ini::encoding::{
+    encode_ini_text, render_lines, source_fingerprint, LineTerminator,
+};
 use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::Write;
@@ -119,21 +122,105 @@ fn restore_after_failed_commit(
     }
 }
 
-pub fn save_ini_with_updates(
-    document: &IniDocument,
-    expected_source_hash: &str,
-    line_updates: &[(usize, String)],
-) -> Result<(), AppError> {
+fn ensure_structured(document: &IniDocument) -> Result<(), AppError> {
     if document.mode == IniReadMode::RawFallback {
         return Err(AppError::Validation(
             "Cannot save INI while parser is in raw fallback mode".to_string(),
         ));
     }
+    Ok(())
+}
+
+pub fn save_ini_with_updates(
+    document: &IniDocument,
+    expected_source_hash: &str,
+    line_updates: &[(usize, String)],
+) -> Result<(), AppError> {
+    ensure_structured(document)?;
     validate_updates(document, line_updates)?;
     if line_updates.is_empty() {
         return Ok(());
     }
 
+    let mut lines = document.raw_lines.clone();
+    for (line_idx, new_line) in line_updates {
+        lines[*line_idx] = new_line.clone();
+    }
+    commit_lines(
+        document,
+        expected_source_hash,
+        &lines,
+        &document.line_terminators,
+    )
+}
+
+/// Structural counterpart of `save_ini_with_updates`: applies `edits` on top
+/// of the document and commits the result through the same pipeline.
+pub fn save_ini_with_edits(
+    document: &IniDocument,
+    expected_source_hash: &str,
+    edits: &[IniEdit],
+) -> Result<(), AppError> {
+    ensure_structured(document)?;
+    if edits.is_empty() {
+        return Ok(());
+    }
+
+    let (lines, terminators) = apply_edits(document, edits)?;
+    commit_lines(document, expected_source_hash, &lines, &terminators)
+}
+
+/// Saves edits to several INIs as one change. Every file is edited in memory
+/// before any is written, and when a commit fails the files already committed
+/// get their original bytes back from the backup the commit just wrote.
+pub fn save_ini_batch_with_edits(batch: &[(IniDocument, Vec<IniEdit>)]) -> Result<(), AppError> {
+    let mut staged = Vec::with_capacity(batch.len());
+    for (document, edits) in batch {
+        ensure_structured(document)?;
+        if edits.is_empty() {
+            continue;
+        }
+        let (lines, terminators) = apply_edits(document, edits)?;
+        staged.push((document, lines, terminators));
+    }
+
+    let mut committed: Vec<&Path> = Vec::new();
+    for (document, lines, terminators) in &staged {
+        if let Err(error) = commit_lines(document, &document.source_hash, lines, terminators) {
+            for path in committed.iter().rev() {
+                if let Err(restore_error) = restore_from_backup(path) {
+                    log::error!(
+                        "INI batch rollback failed for {}: {restore_error}",
+                        path.display()
+                    );
+                }
+            }
+            return Err(error);
+        }
+        committed.push(&document.file_path);
+    }
+    Ok(())
+}
+
+/// Puts the bytes of `file_path`'s newest backup back in place.
+fn restore_from_backup(file_path: &Path) -> Result<(), AppError> {
+    let original = fs::read(backup_path_for(file_path)?)?;
+    let temp_path = unique_sibling(file_path, "tmp")?;
+    write_synced(&temp_path, &original)?;
+    if let Err(error) = fs::rename(&temp_path, file_path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Stale check, re-encode, backup rotation and recoverable replace.
+fn commit_lines(
+    document: &IniDocument,
+    expected_source_hash: &str,
+    lines: &[String],
+    terminators: &[LineTerminator],
+) -> Result<(), AppError> {
     let current_bytes = fs::read(&document.file_path)?;
     let current_hash = source_fingerprint(&current_bytes);
     if current_hash != expected_source_hash || current_hash != document.source_hash {
@@ -142,11 +229,7 @@ pub fn save_ini_with_updates(
         ));
     }
 
-    let mut lines = document.raw_lines.clone();
-    for (line_idx, new_line) in line_updates {
-        lines[*line_idx] = new_line.clone();
-    }
-    let text = render_lines(&lines, &document.line_terminators)?;
+    let text = render_lines(lines, terminators)?;
     let output = encode_ini_text(&text, document.encoding, document.had_bom)?;
 
     let temp_path = unique_sibling(&document.file_path, "tmp")?;
@@ -0,0 +1,174 @@
+//! Keys bound by more than one enabled mod, or by a mod and an app hotkey.
+//!
+//! 3DMigoto fires every section bound to a pressed key, so two mods that
+//! both toggle on `F5` flip together and an app hotkey like `Ctrl+F6` also
+//! cycles whatever mod listens on `F6`.
+
+use crate::services::hotkeys::HotkeyConfig;
+use crate::services::ini::document::KeyBinding;
+use crate::services::ini::keys::KeyChord;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum KeybindOwner {
+    Mod {
+        mod_name: String,
+        /// Stored `mods.folder_path`; tells apart mods that share a name.
+        folder_path: String,
+    },
+    /// One of the app's own global hotkeys, named by its `HotkeyConfig` field.
+    Hotkey { action: String },
+}
+
+/// One `key =`/`back =` line, or one app hotkey.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct BoundKey {
+    pub owner: KeybindOwner,
+    pub object_name: Option<String>,
+    pub section_name: Option<String>,
+    /// The value as written.
+    pub raw: String,
+    /// Normalized form, e.g. `Ctrl+F5`.
+    pub chord: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct KeybindCollision {
+    /// Key name shared by every binding below, without modifiers.
+    pub key: String,
+    /// Sorted by object, then owner, so one object's bindings sit together.
+    pub bindings: Vec<BoundKey>,
+}
+
+/// A binding read from an enabled mod, before normalization.
+#[derive(Debug, Clone)]
+pub struct ModKeybind {
+    pub mod_name: String,
+    pub folder_path: String,
+    pub object_name: Option<String>,
+    pub section_name: String,
+    pub raw: String,
+}
+
+impl ModKeybind {
+    /// One entry per `key =` and `back =` value of one mod's bindings.
+    pub fn expand(
+        mod_name: &str,
+        folder_path: &str,
+        object_name: Option<&str>,
+        keybinds: &[KeyBinding],
+    ) -> Vec<Self> {
+        keybinds
+            .iter()
+            .flat_map(|kb| {
+                [&kb.key, &kb.back].into_iter().flatten().map(|raw| Self {
+                    mod_name: mod_name.to_string(),
+                    folder_path: folder_path.to_string(),
+                    object_name: object_name.map(str::to_string),
+                    section_name: kb.section_name.clone(),
+                    raw: raw.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The app hotkeys that are live, as bindings. Empty when hotkeys are off.
+pub fn hotkey_bindings(hotkeys: &HotkeyConfig) -> Vec<(KeyChord, BoundKey)> {
+    if !hotkeys.enabled {
+        return Vec::new();
+    }
+    [
+        ("next_preset", &hotkeys.next_preset),
+        ("prev_preset", &hotkeys.prev_preset),
+        ("toggle_overlay", &hotkeys.toggle_overlay),
+        ("next_variant", &hotkeys.next_variant),
+        ("prev_variant", &hotkeys.prev_variant),
+    ]
+    .into_iter()
+    .filter_map(|(action, raw)| {
+        let chord = KeyChord::parse_hotkey(raw)?;
+        let bound = BoundKey {
+            owner: KeybindOwner::Hotkey {
+                action: action.to_string(),
+            },
+            object_name: None,
+            section_name: None,
+            raw: raw.clone(),
+            chord: chord.to_string(),
+        };
+        Some((chord, bound))
+    })
+    .collect()
+}
+
+fn mod_binding(keybind: &ModKeybind) -> Option<(KeyChord, BoundKey)> {
+    let chord = KeyChord::parse(&keybind.raw)?;
+    let bound = BoundKey {
+        owner: KeybindOwner::Mod {
+            mod_name: keybind.mod_name.clone(),
+            folder_path: keybind.folder_path.clone(),
+        },
+        object_name: keybind.object_name.clone(),
+        section_name: Some(keybind.section_name.clone()),
+        raw: keybind.raw.clone(),
+        chord: chord.to_string(),
+    };
+    Some((chord, bound))
+}
+
+/// Groups bindings by key and keeps those some binding from a different
+/// owner can fire alongside. Clashes inside one mod are the INI lint's job.
+pub fn find_collisions(
+    mod_keybinds: &[ModKeybind],
+    hotkeys: &HotkeyConfig,
+) -> Vec<KeybindCollision> {
+    let mut by_key: BTreeMap<String, Vec<(KeyChord, BoundKey)>> = BTreeMap::new();
+    for (chord, bound) in mod_keybinds
+        .iter()
+        .filter_map(mod_binding)
+        .chain(hotkey_bindings(hotkeys))
+    {
+        by_key
+            .entry(chord.key.clone())
+            .or_default()
+            .push((chord, bound));
+    }
+
+    let mut collisions = Vec::new();
+    for (key, entries) in by_key {
+        let mut bindings: Vec<BoundKey> = entries
+            .iter()
+            .filter(|(chord, bound)| {
+                entries.iter().any(|(other, other_bound)| {
+                    other_bound.owner != bound.owner && other.overlaps(chord)
+                })
+            })
+            .map(|(_, bound)| bound.clone())
+            .collect();
+        if bindings.is_empty() {
+            continue;
+        }
+        bindings.sort_by(|a, b| {
+            (&a.object_name, owner_sort_key(&a.owner), &a.section_name).cmp(&(
+                &b.object_name,
+                owner_sort_key(&b.owner),
+                &b.section_name,
+            ))
+        });
+        collisions.push(KeybindCollision { key, bindings });
+    }
+    collisions
+}
+
+fn owner_sort_key(owner: &KeybindOwner) -> (&str, &str) {
+    match owner {
+        KeybindOwner::Mod {
+            mod_name,
+            folder_path,
+        } => (mod_name, folder_path),
+        KeybindOwner::Hotkey { action } => ("", action),
+    }
+}
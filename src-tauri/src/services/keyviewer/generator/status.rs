@@ -17,6 +17,8 @@ pub struct StatusFields {
     pub folder_name: Option<String>,
     pub scope_name: Option<String>,
     pub conflict_count: Option<usize>,
+    /// Keys that fire in more than one enabled mod or app hotkey.
+    pub keybind_collision_count: Option<usize>,
 }
 
 /// Generate status banner text content.
@@ -45,6 +47,10 @@ pub fn generate_status_text(
         segments.push(format!("Conflicts: {}", conflict_count));
     }
 
+    if let Some(collision_count) = fields.keybind_collision_count.filter(|count| *count > 0) {
+        segments.push(format!("Key clashes: {}", collision_count));
+    }
+
     if segments.is_empty() {
         segments.push("Runtime ready".to_string());
     }
@@ -4,25 +4,13 @@
 //! are 64-bit identities and belong to the conflict scanner, not this matcher.
 
 use crate::domain::errors::AppError;
-use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
 
+use crate::services::ini::ast::{parse_ini_text, SectionKind};
 use crate::services::ini::document::list_ini_files;
 
-/// Regex matching `hash = XXXXXXXX` (8 hex digits, case-insensitive).
-static HASH_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^\s*hash\s*=\s*(?:0x)?([0-9a-f]{8})\s*(?:[;#].*)?$").expect("valid hash regex")
-});
-
-/// Regex matching resource override sections such as `[TextureOverrideFoo]`.
-static TEXTURE_OVERRIDE_SECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)^\s*\[(TextureOverride[^\]]*)\]\s*$")
-        .expect("valid texture override section regex")
-});
-
 /// A hash extracted from an INI file.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HarvestedHash {
@@ -43,7 +31,7 @@ const DENYLIST_PREFIXES: &[&str] = &[
 ];
 
 /// Check if a section name is deny-listed.
-fn is_denylisted(section_name: &str) -> bool {
+pub(crate) fn is_denylisted(section_name: &str) -> bool {
     let lower = section_name.to_ascii_lowercase();
     DENYLIST_PREFIXES
         .iter()
@@ -64,39 +52,39 @@ pub fn harvest_hashes_from_ini(file_path: &Path) -> Result<Vec<HarvestedHash>, A
 }
 
 /// Hash harvest over INI text already decoded by the caller.
-fn harvest_hashes_from_text(text: &str, file_path: &Path) -> Vec<HarvestedHash> {
-    let mut results = Vec::new();
-    // `Some` exactly while inside a non-denylisted override section.
-    let mut current_section: Option<String> = None;
-
-    for line in text.lines() {
-        let trimmed = line.trim();
-
-        // Any section header resets tracking; an override one re-arms it.
-        if trimmed.starts_with('[') {
-            current_section = TEXTURE_OVERRIDE_SECTION_RE
-                .captures(trimmed)
-                .map(|caps| caps[1].to_string())
-                .filter(|section_name| !is_denylisted(section_name));
-            if trimmed.contains(']') {
-                continue;
-            }
-        }
-
-        let Some(section_name) = current_section.as_ref() else {
-            continue;
-        };
-
-        if let Some(caps) = HASH_RE.captures(trimmed) {
-            results.push(HarvestedHash {
-                hash: caps[1].to_ascii_lowercase(),
-                section_name: section_name.clone(),
-                file_path: file_path.to_path_buf(),
-            });
-        }
-    }
+///
+/// The deep matcher reads the same INI texts for its signals, so it calls this
+/// directly instead of decoding every file a second time.
+pub(crate) fn harvest_hashes_from_text(text: &str, file_path: &Path) -> Vec<HarvestedHash> {
+    let ast = parse_ini_text(text);
+    ast.sections_of_kind(SectionKind::TextureOverride)
+        .filter(|section| !is_denylisted(&section.name))
+        .flat_map(|section| {
+            section
+                .assignments()
+                .into_iter()
+                .filter(|assignment| assignment.key_is("hash"))
+                .filter_map(|assignment| resource_hash(&assignment.value))
+                .map(|hash| HarvestedHash {
+                    hash,
+                    section_name: section.name.clone(),
+                    file_path: file_path.to_path_buf(),
+                })
+        })
+        .collect()
+}
 
-    results
+/// `hash = XXXXXXXX` value as lowercase hex: exactly 8 digits, `0x` optional.
+fn resource_hash(value: &str) -> Option<String> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    (digits.len() == 8
+        && digits
+            .chars()
+            .all(|character| character.is_ascii_hexdigit()))
+    .then(|| digits.to_ascii_lowercase())
 }
 
 /// Harvest hashes from all INI files in a mod folder.
@@ -146,52 +134,3 @@ pub fn harvest_keybinds_from_mod(
 
     Ok(all_keybinds)
 }
-
-/// Everything one pass over a mod's INI files yields.
-#[derive(Debug, Default)]
-pub struct ModHarvest {
-    /// Hash -> every occurrence of it, for deduplication and counting.
-    pub hashes: HashMap<String, Vec<HarvestedHash>>,
-    pub keybinds: Vec<crate::services::ini::document::KeyBinding>,
-}
-
-/// Harvest hashes and key bindings from a mod folder in a single pass.
-///
-/// Post-apply needs both. Asking for them separately listed the directory
-/// twice and read and decoded every INI twice — per enabled mod, on every
-/// toggle, apply and workspace switch.
-pub fn harvest_mod(mod_path: &Path) -> Result<ModHarvest, AppError> {
-    use crate::services::ini::document;
-
-    let ini_files = list_ini_files(mod_path)?;
-    let mut harvest = ModHarvest::default();
-
-    for ini_path in ini_files {
-        let bytes = match fs::read(&ini_path) {
-            Ok(bytes) => bytes,
-            Err(error) => {
-                // A single unreadable INI must not sink the whole mod.
-                log::warn!("[keyviewer] Failed to read {}: {error}", ini_path.display());
-                continue;
-            }
-        };
-
-        let (text, _had_bom, _clean) = document::decode_ini_bytes(&bytes);
-        for hash in harvest_hashes_from_text(&text, &ini_path) {
-            harvest
-                .hashes
-                .entry(hash.hash.clone())
-                .or_default()
-                .push(hash);
-        }
-
-        // Matches `read_ini_document`, which refuses anything larger.
-        if bytes.len() as u64 <= document::MAX_PARSEABLE_INI_BYTES {
-            harvest
-                .keybinds
-                .extend(document::parse_ini_document(&ini_path, &bytes).key_bindings);
-        }
-    }
-
-    Ok(harvest)
-}
@@ -0,0 +1,296 @@
+//! Persisted resource- and shader-hash index (`mod_hash_index`).
+//!
+//! Disk reconcile keeps the index current: each pass fingerprints the INI
+//! files of the mods it touched and re-harvests only those whose fingerprint
+//! moved. "Which mods carry hash X", the keyviewer's hash harvest and the
+//! choice of INIs the conflict scanner parses are lookups rather than rescans
+//! of every mod's INIs.
+
+use crate::domain::conflicts::ConflictKind;
+use crate::domain::conflicts::HashIndexHit;
+use crate::domain::errors::AppError;
+use crate::domain::mod_path::ModFolderPath;
+use crate::repo::hash_index_repo::{self, HashIndexRow};
+use crate::repo::mod_repo::is_effectively_enabled_path;
+use crate::services::ini::ast::parse_ini_text;
+use crate::services::ini::document::{decode_ini_bytes, list_ini_files};
+use crate::services::keyviewer::harvester;
+use crate::services::scanner::conflict::hash_scan::conflict_kind;
+use sqlx::SqlitePool;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Fingerprint of a mod's INI set: relative path, size and mtime of each INI.
+/// `None` when the folder is gone or unreadable.
+pub fn ini_fingerprint(mod_root: &Path) -> Option<String> {
+    let ini_files = list_ini_files(mod_root).ok()?;
+    let mut hasher = blake3::Hasher::new();
+    for ini_path in ini_files {
+        let metadata = std::fs::metadata(&ini_path).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |elapsed| elapsed.as_nanos());
+        hasher.update(relative_ini_path(mod_root, &ini_path).as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&metadata.len().to_le_bytes());
+        hasher.update(&modified.to_le_bytes());
+    }
+    Some(hasher.finalize().to_hex().to_string())
+}
+
+fn relative_ini_path(mod_root: &Path, ini_path: &Path) -> String {
+    ini_path
+        .strip_prefix(mod_root)
+        .unwrap_or(ini_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// `hash = …` value as lowercase hex, `0x` optional. Any length: resource
+/// hashes are 8 digits, shader hashes 16.
+fn index_hash(value: &str) -> Option<String> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    (!digits.is_empty()
+        && digits
+            .chars()
+            .all(|character| character.is_ascii_hexdigit()))
+    .then(|| digits.to_ascii_lowercase())
+}
+
+/// Every `hash` of every `TextureOverride*`/`ShaderOverride*` section, deny-
+/// listed ones included: the keyviewer filters those out, conflicts do not.
+fn ini_rows(mod_root: &Path, ini_path: &Path) -> Result<Vec<HashIndexRow>, AppError> {
+    let bytes = std::fs::read(ini_path)?;
+    let (text, _had_bom, _clean) = decode_ini_bytes(&bytes);
+    let file_path = relative_ini_path(mod_root, ini_path);
+    let mut rows = Vec::new();
+    for section in &parse_ini_text(&text).sections {
+        let Some(kind) = conflict_kind(section.kind) else {
+            continue;
+        };
+        for assignment in section.assignments() {
+            if let Some(hash) = assignment
+                .key_is("hash")
+                .then(|| index_hash(&assignment.value))
+                .flatten()
+            {
+                rows.push(HashIndexRow {
+                    kind,
+                    hash,
+                    section_name: section.name.clone(),
+                    file_path: file_path.clone(),
+                });
+            }
+        }
+    }
+    Ok(rows)
+}
+
+fn index_rows(mod_root: &Path) -> Result<Vec<HashIndexRow>, AppError> {
+    let mut rows = Vec::new();
+    for ini_path in list_ini_files(mod_root)? {
+        match ini_rows(mod_root, &ini_path) {
+            Ok(found) => rows.extend(found),
+            // One unreadable INI must not drop the rest of the mod.
+            Err(error) => log::warn!(
+                "[hash_index] Failed to read {}: {error}",
+                ini_path.display()
+            ),
+        }
+    }
+    rows.sort_by(|left, right| {
+        (&left.file_path, &left.section_name, &left.hash).cmp(&(
+            &right.file_path,
+            &right.section_name,
+            &right.hash,
+        ))
+    });
+    Ok(rows)
+}
+
+/// First component of a mod folder below the mods root, which is how disk
+/// reconcile names the roots it touched.
+pub(crate) fn top_level_root(mods_root: &Path, mod_root: &Path) -> Option<String> {
+    match mod_root.strip_prefix(mods_root).ok()?.components().next()? {
+        Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
+struct Reindex {
+    mod_id: String,
+    fingerprint: String,
+    rows: Vec<HashIndexRow>,
+}
+
+/// Brings `mod_hash_index` up to date for a game and returns how many mods
+/// were re-harvested.
+///
+/// `changed_roots` limits the pass to mods under those top-level folders;
+/// `None` fingerprints every mod. Mods deleted from the table drop their rows
+/// through the foreign-key cascade.
+pub async fn sync_hash_index(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+    changed_roots: Option<&[String]>,
+) -> Result<usize, AppError> {
+    let candidates: Vec<(String, PathBuf, Option<String>)> =
+        hash_index_repo::list_mod_fingerprints(pool, game_id)
+            .await?
+            .into_iter()
+            .map(|(mod_id, folder_path, fingerprint)| {
+                (mod_id, folder_path.resolve(mods_root), fingerprint)
+            })
+            .filter(|(_, mod_root, _)| {
+                changed_roots.is_none_or(|roots| {
+                    top_level_root(mods_root, mod_root).is_some_and(|root| {
+                        roots
+                            .iter()
+                            .any(|changed| changed.eq_ignore_ascii_case(&root))
+                    })
+                })
+            })
+            .collect();
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    let reindex = tokio::task::spawn_blocking(move || {
+        candidates
+            .into_iter()
+            .filter_map(|(mod_id, mod_root, stored)| {
+                let fingerprint = ini_fingerprint(&mod_root)?;
+                if stored.as_deref() == Some(fingerprint.as_str()) {
+                    return None;
+                }
+                match index_rows(&mod_root) {
+                    Ok(rows) => Some(Reindex {
+                        mod_id,
+                        fingerprint,
+                        rows,
+                    }),
+                    Err(error) => {
+                        log::warn!(
+                            "[hash_index] Failed to harvest {}: {error}",
+                            mod_root.display()
+                        );
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    for entry in &reindex {
+        hash_index_repo::replace_mod_hashes(
+            pool,
+            game_id,
+            &entry.mod_id,
+            &entry.fingerprint,
+            &entry.rows,
+        )
+        .await?;
+    }
+    if !reindex.is_empty() {
+        hash_index_repo::refresh_collision_counts(pool, game_id).await?;
+    }
+    Ok(reindex.len())
+}
+
+/// Every indexed mod section carrying `hash` (`0x` prefix and case ignored).
+pub async fn find_mods_by_hash_service(
+    pool: &SqlitePool,
+    game_id: &str,
+    hash: &str,
+) -> Result<Vec<HashIndexHit>, AppError> {
+    let trimmed = hash.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if digits.is_empty()
+        || !digits
+            .chars()
+            .all(|character| character.is_ascii_hexdigit())
+    {
+        return Err(AppError::Validation(format!("Not a hex hash: {hash}")));
+    }
+    Ok(hash_index_repo::find_mods_by_hash(pool, game_id, &digits.to_ascii_lowercase()).await?)
+}
+
+/// Enabled mods' indexed rows, keyed by the stored `mods.folder_path`.
+async fn enabled_rows(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<Vec<hash_index_repo::EnabledHashRow>, AppError> {
+    Ok(hash_index_repo::enabled_mod_hashes(pool, game_id)
+        .await?
+        .into_iter()
+        .filter(|row| is_effectively_enabled_path(&row.folder_path))
+        .collect())
+}
+
+/// The keyviewer's harvest, read from the index: per enabled mod, each 8-digit
+/// `TextureOverride` hash outside the deny-listed sections and how often its
+/// INIs carry it.
+pub async fn keyviewer_hashes(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<HashMap<ModFolderPath, HashMap<String, usize>>, AppError> {
+    let mut out: HashMap<ModFolderPath, HashMap<String, usize>> = HashMap::new();
+    for row in enabled_rows(pool, game_id).await? {
+        if row.hash_kind != ConflictKind::ResourceHash
+            || row.hash.len() != 8
+            || harvester::is_denylisted(&row.section_name)
+        {
+            continue;
+        }
+        *out.entry(ModFolderPath::from_stored(row.folder_path))
+            .or_default()
+            .entry(row.hash)
+            .or_default() += 1;
+    }
+    Ok(out)
+}
+
+/// `(mod root, INI path)` pairs the conflict scanner has to parse: INIs of
+/// enabled mods carrying a hash another enabled mod carries too, plus every
+/// `ShaderOverride`, which may compete with a `*_replace` file instead.
+pub async fn conflict_ini_files(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>, AppError> {
+    let rows = enabled_rows(pool, game_id).await?;
+    let mut owners: HashMap<(ConflictKind, &str), BTreeSet<&str>> = HashMap::new();
+    for row in &rows {
+        owners
+            .entry((row.hash_kind, row.hash.as_str()))
+            .or_default()
+            .insert(row.folder_path.as_str());
+    }
+
+    let files: BTreeSet<(PathBuf, PathBuf)> = rows
+        .iter()
+        .filter(|row| {
+            row.hash_kind == ConflictKind::ShaderHash
+                || owners
+                    .get(&(row.hash_kind, row.hash.as_str()))
+                    .is_some_and(|mods| mods.len() > 1)
+        })
+        .map(|row| {
+            let mod_root = ModFolderPath::from_stored(row.folder_path.clone()).resolve(mods_root);
+            let ini_path = mod_root.join(&row.file_path);
+            (mod_root, ini_path)
+        })
+        .collect();
+    Ok(files.into_iter().collect())
+}
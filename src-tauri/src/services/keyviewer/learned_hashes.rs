@@ -0,0 +1,127 @@
+//! Resource hashes learned from the user's own library.
+//!
+//! The bundled `hash_db` is empty for many objects and lags behind game
+//! updates. Once mods sit under an object whose MasterDB match is confident,
+//! the hashes their `TextureOverride` sections carry are strong evidence for
+//! that object. This opt-in pass counts, per object, how many of its mods
+//! carry each hash in `mod_hash_index` and keeps the hashes most of them share
+//! and no other object's mods carry. They are stored apart from the bundled
+//! hashes, so the KeyViewer matcher can weigh them lower and a reset drops
+//! exactly them.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use sqlx::SqlitePool;
+
+use crate::domain::errors::AppError;
+use crate::repo::learned_hash_repo::{self, LearnedHashRow};
+use crate::services::fs_utils::operation_lock::OpGuard;
+use crate::services::keyviewer::hash_index;
+
+/// Only objects matched at least this confidently teach hashes.
+pub const LEARN_MIN_CONFIDENCE: f64 = 0.8;
+
+/// A hash is learned for an object when at least this share of the object's
+/// mods carry it. Lower would pick up one mod's extra accessory.
+const MIN_MOD_SHARE: f64 = 0.5;
+
+/// What a learning pass kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct LearnedHashSummary {
+    /// Objects that learned at least one hash.
+    pub objects: u32,
+    pub hashes: u32,
+}
+
+/// The hashes to learn from `(object_id, hash, mod_count)` frequencies, given
+/// how many indexed mods each object has.
+fn select_learned_hashes(
+    frequencies: Vec<(String, String, i64)>,
+    object_mod_counts: &HashMap<String, i64>,
+) -> Vec<LearnedHashRow> {
+    let mut owners: HashMap<&str, usize> = HashMap::new();
+    for (_, hash, _) in &frequencies {
+        *owners.entry(hash.as_str()).or_default() += 1;
+    }
+    let shared: HashSet<String> = owners
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(hash, _)| hash.to_string())
+        .collect();
+
+    frequencies
+        .into_iter()
+        .filter(|(_, hash, _)| !shared.contains(hash))
+        .filter_map(|(object_id, hash, mod_count)| {
+            let object_mod_count = *object_mod_counts.get(&object_id)?;
+            let share = mod_count as f64 / object_mod_count.max(1) as f64;
+            (share >= MIN_MOD_SHARE).then_some(LearnedHashRow {
+                object_id,
+                hash,
+                mod_count,
+                object_mod_count,
+            })
+        })
+        .collect()
+}
+
+/// Re-learn a game's hashes from its current library, replacing what an
+/// earlier pass learned.
+///
+/// Brings the hash index up to date first, so mods added since the last disk
+/// reconcile count.
+pub async fn learn_object_hashes(
+    _op_guard: &OpGuard,
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+) -> Result<LearnedHashSummary, AppError> {
+    hash_index::sync_hash_index(pool, game_id, mods_root, None).await?;
+
+    let frequencies =
+        learned_hash_repo::object_hash_frequencies(pool, game_id, LEARN_MIN_CONFIDENCE).await?;
+    let object_mod_counts: HashMap<String, i64> =
+        learned_hash_repo::object_indexed_mod_counts(pool, game_id, LEARN_MIN_CONFIDENCE)
+            .await?
+            .into_iter()
+            .collect();
+    let rows = select_learned_hashes(frequencies, &object_mod_counts);
+    learned_hash_repo::replace_learned_hashes(pool, game_id, &rows).await?;
+
+    // Rows arrive grouped by object.
+    let mut objects: Vec<&str> = rows.iter().map(|row| row.object_id.as_str()).collect();
+    objects.dedup();
+    let summary = LearnedHashSummary {
+        objects: u32::try_from(objects.len()).unwrap_or(u32::MAX),
+        hashes: u32::try_from(rows.len()).unwrap_or(u32::MAX),
+    };
+    log::info!(
+        "[learned_hashes] game={game_id} objects={} hashes={}",
+        summary.objects,
+        summary.hashes
+    );
+    Ok(summary)
+}
+
+/// Forget every hash learned for a game. Returns how many there were.
+pub async fn reset_learned_hashes(
+    _op_guard: &OpGuard,
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<u32, AppError> {
+    let removed = learned_hash_repo::delete_learned_hashes(pool, game_id).await?;
+    Ok(u32::try_from(removed).unwrap_or(u32::MAX))
+}
+
+/// A game's learned hashes by object name, for building matcher entries.
+pub async fn load_learned_hashes(
+    pool: &SqlitePool,
+    game_id: &str,
+) -> Result<HashMap<String, Vec<String>>, AppError> {
+    let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, hash) in learned_hash_repo::list_learned_hashes_by_name(pool, game_id).await? {
+        by_name.entry(name).or_default().push(hash);
+    }
+    Ok(by_name)
+}
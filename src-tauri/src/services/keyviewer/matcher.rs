@@ -2,9 +2,10 @@
 //!
 //! **Algorithm:**
 //! 1. For each `KvObjectEntry`: compute intersection `I = active_hashes ∩ known_hashes`
-//! 2. Score each entry: base per-hash + occurrence bonus + rarity bonus
+//!    (bundled `code_hashes` plus `learned_hashes`)
+//! 2. Score each entry: base per-hash (reduced for learned hashes) + occurrence bonus + rarity bonus
 //! 3. Pick best match if `score ≥ threshold`; tiebreak: score desc → name asc
-//! 4. Select sentinel hashes owned by exactly one object
+//! 4. Select sentinel hashes owned by exactly one object, bundled before learned
 
 use std::collections::{HashMap, HashSet};
 
@@ -25,6 +26,9 @@ pub struct MatchConfig {
     pub rarity_max_objects: usize,
     /// Number of sentinel hashes to select per matched object.
     pub sentinel_count: usize,
+    /// Share of `base_per_hash` a learned hash earns. Learned hashes are
+    /// inferred from the user's library, not curated.
+    pub learned_hash_weight: f32,
 }
 
 impl Default for MatchConfig {
@@ -36,6 +40,7 @@ impl Default for MatchConfig {
             rarity_bonus: 5.0,
             rarity_max_objects: 2,
             sentinel_count: 3,
+            learned_hash_weight: 0.5,
         }
     }
 }
@@ -55,6 +60,9 @@ pub struct KvObjectEntry {
     pub code_hashes: Vec<String>,
     /// Skin/variant name → associated hashes (original structure from `hash_db`).
     pub skin_hashes: HashMap<String, Vec<String>>,
+    /// Hashes the local learning pass attributed to this object that
+    /// `code_hashes` lacks. Deduplicated, lowercase.
+    pub learned_hashes: Vec<String>,
     /// Search tags from the MasterDb entry.
     pub tags: Vec<String>,
     /// Optional thumbnail path (relative to resources dir).
@@ -74,6 +82,9 @@ pub struct MatchResult {
     pub matched_hashes: Vec<String>,
     /// Selected sentinel hashes for runtime detection.
     pub sentinel_hashes: Vec<String>,
+    /// The `matched_hashes` that came from `learned_hashes`, not the bundled
+    /// `hash_db`.
+    pub learned_hashes: Vec<String>,
     /// Confidence level based on score relative to threshold.
     pub confidence: MatchConfidence,
 }
@@ -100,7 +111,7 @@ pub enum MatchConfidence {
 fn build_hash_object_index(entries: &[KvObjectEntry]) -> HashMap<&str, HashSet<&str>> {
     let mut index: HashMap<&str, HashSet<&str>> = HashMap::new();
     for entry in entries {
-        for hash in &entry.code_hashes {
+        for hash in entry.code_hashes.iter().chain(&entry.learned_hashes) {
             index
                 .entry(hash.as_str())
                 .or_default()
@@ -112,17 +123,19 @@ fn build_hash_object_index(entries: &[KvObjectEntry]) -> HashMap<&str, HashSet<&
 
 /// Score a single entry against the active hashes.
 ///
-/// Returns `None` if the intersection is empty (no possible match).
+/// Returns `None` if the intersection is empty (no possible match); otherwise
+/// the score, the intersection, and the part of it that was learned.
 fn score_entry(
     entry: &KvObjectEntry,
     active_hashes: &HashSet<String>,
     occurrence_counts: &HashMap<String, usize>,
     hash_object_index: &HashMap<&str, HashSet<&str>>,
     config: &MatchConfig,
-) -> Option<(f32, Vec<String>)> {
+) -> Option<(f32, Vec<String>, Vec<String>)> {
     let mut intersection: Vec<String> = entry
         .code_hashes
         .iter()
+        .chain(&entry.learned_hashes)
         .filter(|h| active_hashes.contains(h.as_str()))
         .cloned()
         .collect();
@@ -133,11 +146,21 @@ fn score_entry(
         return None;
     }
 
+    let learned: Vec<String> = intersection
+        .iter()
+        .filter(|hash| !entry.code_hashes.contains(hash))
+        .cloned()
+        .collect();
+
     let mut score: f32 = 0.0;
 
     for hash in &intersection {
         // Base score per hash
-        score += config.base_per_hash;
+        score += if learned.contains(hash) {
+            config.base_per_hash * config.learned_hash_weight
+        } else {
+            config.base_per_hash
+        };
 
         // Occurrence bonus: more occurrences → slight boost (log-scaled)
         let occ = occurrence_counts.get(hash).copied().unwrap_or(1) as f32;
@@ -153,7 +176,7 @@ fn score_entry(
         }
     }
 
-    Some((score, intersection))
+    Some((score, intersection, learned))
 }
 
 /// Match active mod hashes against all KvObjectEntries and return ranked results.
@@ -177,7 +200,7 @@ pub fn match_objects(
     let mut results: Vec<MatchResult> = entries
         .iter()
         .filter_map(|entry| {
-            let (score, matched_hashes) = score_entry(
+            let (score, matched_hashes, learned_hashes) = score_entry(
                 entry,
                 active_hashes,
                 occurrence_counts,
@@ -189,7 +212,8 @@ pub fn match_objects(
                 return None;
             }
 
-            let sentinel_hashes = select_sentinels(&matched_hashes, &hash_object_index, config);
+            let sentinel_hashes =
+                select_sentinels(&matched_hashes, &learned_hashes, &hash_object_index, config);
             if sentinel_hashes.is_empty() {
                 return None;
             }
@@ -208,6 +232,7 @@ pub fn match_objects(
                 score,
                 matched_hashes,
                 sentinel_hashes,
+                learned_hashes,
                 confidence,
             })
         })
@@ -229,32 +254,39 @@ pub fn match_objects(
 /// Select sentinel hashes from the matched intersection.
 ///
 /// Only hashes owned by exactly one object are safe runtime sentinels.
-/// Returns up to `sentinel_count` hashes.
+/// Bundled hashes are preferred; `learned_hashes` only fill the remaining
+/// slots. Returns up to `sentinel_count` hashes.
 fn select_sentinels(
     matched_hashes: &[String],
+    learned_hashes: &[String],
     hash_object_index: &HashMap<&str, HashSet<&str>>,
     config: &MatchConfig,
 ) -> Vec<String> {
     // Score each hash by rarity (fewer objects = better sentinel)
-    let mut scored: Vec<(&String, usize)> = matched_hashes
+    let mut scored: Vec<(&String, bool, usize)> = matched_hashes
         .iter()
         .map(|h| {
             let count = hash_object_index
                 .get(h.as_str())
                 .map(|s| s.len())
                 .unwrap_or(1);
-            (h, count)
+            (h, learned_hashes.contains(h), count)
         })
         .collect();
 
-    scored.retain(|(_, count)| *count == 1);
+    scored.retain(|(_, _, count)| *count == 1);
 
-    // Sort by object count ascending (rarest first), then hash value for stability
-    scored.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+    // Bundled first, then object count ascending (rarest first), then hash
+    // value for stability
+    scored.sort_by(|a, b| {
+        a.1.cmp(&b.1)
+            .then_with(|| a.2.cmp(&b.2))
+            .then_with(|| a.0.cmp(b.0))
+    });
 
     scored
         .into_iter()
         .take(config.sentinel_count)
-        .map(|(h, _)| h.clone())
+        .map(|(h, _, _)| h.clone())
         .collect()
 }
@@ -5,8 +5,11 @@
 //! MasterDb, selects sentinel hashes for runtime detection, and generates the
 //! `KeyViewer.ini` file consumed by 3DMigoto.
 
+pub mod collisions;
 pub mod generator;
 pub mod harvester;
+pub mod hash_index;
+pub mod learned_hashes;
 pub mod matcher;
 
 #[cfg(test)]
@@ -0,0 +1,81 @@
+//! Unit tests for the keybind collision detector.
+
+use crate::services::hotkeys::HotkeyConfig;
+use crate::services::keyviewer::collisions::{find_collisions, KeybindOwner, ModKeybind};
+
+fn keybind(mod_name: &str, object: Option<&str>, section: &str, raw: &str) -> ModKeybind {
+    ModKeybind {
+        mod_name: mod_name.to_string(),
+        folder_path: format!("Chars/{mod_name}"),
+        object_name: object.map(str::to_string),
+        section_name: section.to_string(),
+        raw: raw.to_string(),
+    }
+}
+
+fn hotkeys_off() -> HotkeyConfig {
+    HotkeyConfig {
+        enabled: false,
+        ..HotkeyConfig::default()
+    }
+}
+
+#[test]
+fn two_mods_on_the_same_key_collide_across_spellings() {
+    let keybinds = vec![
+        keybind("Body", Some("Alice"), "KeySwap", "VK_F5"),
+        keybind("Hair", Some("Bob"), "KeyHair", "ctrl f5"),
+        keybind("Other", Some("Bob"), "KeyOther", "F9"),
+    ];
+
+    let collisions = find_collisions(&keybinds, &hotkeys_off());
+
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].key, "F5");
+    let chords: Vec<_> = collisions[0]
+        .bindings
+        .iter()
+        .map(|bound| (bound.object_name.as_deref(), bound.chord.as_str()))
+        .collect();
+    assert_eq!(
+        chords,
+        vec![(Some("Alice"), "F5"), (Some("Bob"), "Ctrl+F5")]
+    );
+}
+
+#[test]
+fn exclusive_modifiers_keep_bindings_apart() {
+    let keybinds = vec![
+        keybind("A", None, "KeyA", "no_ctrl VK_F5"),
+        keybind("B", None, "KeyB", "ctrl VK_F5"),
+    ];
+
+    assert!(find_collisions(&keybinds, &hotkeys_off()).is_empty());
+}
+
+#[test]
+fn repeats_inside_one_mod_are_left_to_the_lint() {
+    let keybinds = vec![
+        keybind("A", None, "KeyOne", "1"),
+        keybind("A", None, "KeyTwo", "VK_1"),
+    ];
+
+    assert!(find_collisions(&keybinds, &hotkeys_off()).is_empty());
+}
+
+#[test]
+fn app_hotkeys_collide_with_mods_only_while_enabled() {
+    let keybinds = vec![keybind("A", Some("Alice"), "KeyA", "VK_F7")];
+
+    let collisions = find_collisions(&keybinds, &HotkeyConfig::default());
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].key, "F7");
+    assert_eq!(
+        collisions[0].bindings[0].owner,
+        KeybindOwner::Hotkey {
+            action: "toggle_overlay".to_string()
+        }
+    );
+
+    assert!(find_collisions(&keybinds, &hotkeys_off()).is_empty());
+}
@@ -29,6 +29,7 @@ fn make_match_result(name: &str, sentinels: &[&str]) -> MatchResult {
         score: 50.0,
         matched_hashes: sentinels.iter().map(|s| s.to_string()).collect(),
         sentinel_hashes: sentinels.iter().map(|s| s.to_string()).collect(),
+        learned_hashes: vec![],
         confidence: MatchConfidence::High,
     }
 }
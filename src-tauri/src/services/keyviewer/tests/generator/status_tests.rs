@@ -23,6 +23,7 @@ fn status_text_with_folder() {
         folder_name: Some("Cape".to_string()),
         scope_name: Some("Albedo".to_string()),
         conflict_count: Some(0),
+        keybind_collision_count: Some(0),
     };
     let text = generate_status_text(&fields, &crate::services::hotkeys::HotkeyConfig::default());
     assert!(text.contains("Folder: Cape"));
@@ -38,12 +39,29 @@ fn status_text_within_limits() {
         folder_name: Some("SomeFolderName".to_string()),
         scope_name: Some("SomeScope".to_string()),
         conflict_count: Some(0),
+        keybind_collision_count: Some(3),
     };
     let text = generate_status_text(&fields, &crate::services::hotkeys::HotkeyConfig::default());
     assert!(text.lines().count() <= 10);
     assert!(text.len() <= 4096);
 }
 
+#[test]
+fn status_text_counts_key_clashes_only_when_present() {
+    let hotkeys = crate::services::hotkeys::HotkeyConfig::default();
+    let clashing = StatusFields {
+        keybind_collision_count: Some(2),
+        ..Default::default()
+    };
+    let clean = StatusFields {
+        keybind_collision_count: Some(0),
+        ..Default::default()
+    };
+
+    assert_eq!(generate_status_text(&clashing, &hotkeys), "Key clashes: 2");
+    assert_eq!(generate_status_text(&clean, &hotkeys), "Runtime ready");
+}
+
 #[test]
 fn write_status_file_atomic() {
     let dir = TempDir::new().unwrap();
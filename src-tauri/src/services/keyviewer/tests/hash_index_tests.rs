@@ -0,0 +1,168 @@
+//! Tests for the persisted resource-hash index.
+
+use std::fs;
+use tempfile::TempDir;
+
+use crate::domain::mod_path::ModFolderPath;
+use crate::domain::models::{GameType, ItemStatus};
+use crate::services::keyviewer::hash_index::{
+    conflict_ini_files, find_mods_by_hash_service, ini_fingerprint, keyviewer_hashes,
+    sync_hash_index,
+};
+use crate::test_utils::{
+    init_test_db, insert_test_game, insert_test_mod, TestGameFixture, TestModFixture,
+};
+
+const BODY_INI: &str = "[TextureOverrideBody]\nhash = 0xAABBCCDD\n";
+
+async fn seed_game(pool: &sqlx::SqlitePool, mods_root: &str, mods: &[&str]) {
+    insert_test_game(
+        pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some(mods_root),
+        },
+    )
+    .await
+    .unwrap();
+    for folder in mods {
+        insert_test_mod(
+            pool,
+            &TestModFixture {
+                id: folder,
+                game_id: "g1",
+                object_id: None,
+                actual_name: folder,
+                folder_path: folder,
+                status: ItemStatus::Enabled,
+                is_safe: true,
+                object_type: None,
+                mods_path: Some(mods_root),
+            },
+        )
+        .await
+        .unwrap();
+    }
+}
+
+#[test]
+fn fingerprint_moves_when_an_ini_changes() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("mod.ini"), BODY_INI).unwrap();
+    let before = ini_fingerprint(dir.path()).unwrap();
+    assert_eq!(ini_fingerprint(dir.path()).unwrap(), before);
+
+    fs::write(dir.path().join("mod.ini"), format!("{BODY_INI}; edited\n")).unwrap();
+    assert_ne!(ini_fingerprint(dir.path()).unwrap(), before);
+    assert_eq!(ini_fingerprint(&dir.path().join("missing")), None);
+}
+
+#[tokio::test]
+async fn sync_reindexes_only_changed_mods_and_counts_collisions() {
+    let pool = init_test_db().await.pool;
+    let mods = TempDir::new().unwrap();
+    let mods_root = mods.path().to_string_lossy().to_string();
+    for folder in ["Alpha", "Beta"] {
+        fs::create_dir(mods.path().join(folder)).unwrap();
+        fs::write(mods.path().join(folder).join("mod.ini"), BODY_INI).unwrap();
+    }
+    seed_game(&pool, &mods_root, &["Alpha", "Beta"]).await;
+
+    assert_eq!(
+        sync_hash_index(&pool, "g1", mods.path(), None)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        sync_hash_index(&pool, "g1", mods.path(), None)
+            .await
+            .unwrap(),
+        0
+    );
+
+    let hits = find_mods_by_hash_service(&pool, "g1", "0xAABBCCDD")
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit.collision_count == 1));
+    assert_eq!(hits[0].file_path, "mod.ini");
+
+    fs::write(
+        mods.path().join("Beta").join("mod.ini"),
+        "[TextureOverrideHair]\nhash = 11223344\n",
+    )
+    .unwrap();
+    let scope = vec!["beta".to_string()];
+    assert_eq!(
+        sync_hash_index(&pool, "g1", mods.path(), Some(&scope))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let hits = find_mods_by_hash_service(&pool, "g1", "aabbccdd")
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].collision_count, 0);
+    assert!(find_mods_by_hash_service(&pool, "g1", "not-hex")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn conflicts_and_keyviewer_read_every_hash_kind_from_the_index() {
+    let pool = init_test_db().await.pool;
+    let mods = TempDir::new().unwrap();
+    let mods_root = mods.path().to_string_lossy().to_string();
+    let inis = [
+        (
+            "Alpha",
+            "[TextureOverrideBody]\nhash = 0xAABBCCDD\n[TextureOverrideUIIcon]\nhash = 99887766\n",
+        ),
+        ("Beta", "[TextureOverrideHead]\nhash = aabbccdd\n"),
+        ("Gamma", "[ShaderOverrideSky]\nhash = 0123456789abcdef\n"),
+        ("Delta", "[TextureOverrideHair]\nhash = 11223344\n"),
+    ];
+    for (folder, ini) in inis {
+        fs::create_dir(mods.path().join(folder)).unwrap();
+        fs::write(mods.path().join(folder).join("mod.ini"), ini).unwrap();
+    }
+    seed_game(&pool, &mods_root, &["Alpha", "Beta", "Gamma", "Delta"]).await;
+    sync_hash_index(&pool, "g1", mods.path(), None)
+        .await
+        .unwrap();
+
+    let shader = find_mods_by_hash_service(&pool, "g1", "0123456789ABCDEF")
+        .await
+        .unwrap();
+    assert_eq!(shader.len(), 1);
+    assert_eq!(shader[0].section_name, "ShaderOverrideSky");
+
+    // Delta's hash is its own, so only the shared and shader INIs are parsed.
+    let files: Vec<_> = conflict_ini_files(&pool, "g1", mods.path())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(mod_root, _ini)| mod_root)
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            mods.path().join("Alpha"),
+            mods.path().join("Beta"),
+            mods.path().join("Gamma"),
+        ]
+    );
+
+    // The keyviewer only takes 8-digit resource hashes outside UI sections.
+    let harvested = keyviewer_hashes(&pool, "g1").await.unwrap();
+    let alpha = &harvested[&ModFolderPath::from_stored("Alpha")];
+    assert_eq!(alpha.len(), 1);
+    assert_eq!(alpha["aabbccdd"], 1);
+    assert!(!harvested.contains_key(&ModFolderPath::from_stored("Gamma")));
+}
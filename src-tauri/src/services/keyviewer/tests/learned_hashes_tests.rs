@@ -0,0 +1,140 @@
+//! Tests for learning resource hashes from the user's library.
+
+use std::collections::HashMap;
+use std::fs;
+use tempfile::TempDir;
+
+use crate::domain::models::{GameType, ItemStatus};
+use crate::repo::object_repo;
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::keyviewer::learned_hashes::{
+    learn_object_hashes, load_learned_hashes, reset_learned_hashes, LearnedHashSummary,
+};
+use crate::test_utils::{
+    init_test_db, insert_test_game, insert_test_mod, insert_test_object, TestGameFixture,
+    TestModFixture, TestObjectFixture,
+};
+
+fn ini(hashes: &[&str]) -> String {
+    hashes
+        .iter()
+        .enumerate()
+        .map(|(index, hash)| format!("[TextureOverridePart{index}]\nhash = {hash}\n"))
+        .collect()
+}
+
+async fn seed_object(pool: &sqlx::SqlitePool, id: &str, name: &str, confidence: f64) {
+    insert_test_object(
+        pool,
+        &TestObjectFixture {
+            id,
+            game_id: "g1",
+            name,
+            folder_path: name,
+            object_type: "Character",
+        },
+    )
+    .await
+    .unwrap();
+    object_repo::apply_canonical_match(
+        pool,
+        id,
+        Some(&name.to_lowercase()),
+        Some(name),
+        Some(confidence),
+        Some("test"),
+        Some("scan"),
+    )
+    .await
+    .unwrap();
+}
+
+async fn seed_mod(
+    pool: &sqlx::SqlitePool,
+    mods: &TempDir,
+    object_id: &str,
+    folder: &str,
+    hashes: &[&str],
+) {
+    fs::create_dir_all(mods.path().join(folder)).unwrap();
+    fs::write(mods.path().join(folder).join("mod.ini"), ini(hashes)).unwrap();
+    let mods_root = mods.path().to_string_lossy().to_string();
+    insert_test_mod(
+        pool,
+        &TestModFixture {
+            id: folder,
+            game_id: "g1",
+            object_id: Some(object_id),
+            actual_name: folder,
+            folder_path: folder,
+            status: ItemStatus::Enabled,
+            is_safe: true,
+            object_type: Some("Character"),
+            mods_path: Some(&mods_root),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn learns_common_unshared_hashes_of_confident_objects_and_resets() {
+    let pool = init_test_db().await.pool;
+    let mods = TempDir::new().unwrap();
+    let mods_root = mods.path().to_string_lossy().to_string();
+    insert_test_game(
+        &pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some(&mods_root),
+        },
+    )
+    .await
+    .unwrap();
+    seed_object(&pool, "o1", "Katheryne", 0.9).await;
+    seed_object(&pool, "o2", "Paimon", 0.95).await;
+    seed_object(&pool, "o3", "Unsure", 0.4).await;
+    // Carried by every Katheryne mod: learned. One mod's extra: not.
+    seed_mod(&pool, &mods, "o1", "K1", &["0badf00d", "aa000001"]).await;
+    seed_mod(&pool, &mods, "o1", "K2", &["0badf00d", "5ca1ab1e"]).await;
+    seed_mod(&pool, &mods, "o1", "K3", &["0BADF00D"]).await;
+    // Shared with Katheryne: learned for neither.
+    seed_mod(&pool, &mods, "o2", "P1", &["ffff0001", "5ca1ab1e"]).await;
+    // The object's match is not confident enough to teach.
+    seed_mod(&pool, &mods, "o3", "U1", &["eeee0001"]).await;
+
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.unwrap();
+
+    let summary = learn_object_hashes(&op_guard, &pool, "g1", mods.path())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        LearnedHashSummary {
+            objects: 2,
+            hashes: 2
+        }
+    );
+    assert_eq!(
+        load_learned_hashes(&pool, "g1").await.unwrap(),
+        HashMap::from([
+            ("Katheryne".to_string(), vec!["0badf00d".to_string()]),
+            ("Paimon".to_string(), vec!["ffff0001".to_string()]),
+        ])
+    );
+
+    // A second pass replaces rather than accumulates.
+    learn_object_hashes(&op_guard, &pool, "g1", mods.path())
+        .await
+        .unwrap();
+    assert_eq!(
+        reset_learned_hashes(&op_guard, &pool, "g1").await.unwrap(),
+        2
+    );
+    assert!(load_learned_hashes(&pool, "g1").await.unwrap().is_empty());
+}
@@ -0,0 +1,42 @@
+//! Matcher tests for hashes learned from the library, next to the bundled ones.
+
+use super::matcher_tests::{make_active_hashes, make_kv_entry, make_occurrences};
+use crate::services::keyviewer::matcher::{match_objects, MatchConfig};
+
+#[test]
+fn learned_hashes_match_objects_without_bundled_hashes() {
+    let mut katheryne = make_kv_entry("Katheryne", &[]);
+    katheryne.learned_hashes = vec!["0badf00d".to_string(), "0badf00e".to_string()];
+    let active = make_active_hashes(&["0badf00d", "0badf00e"]);
+    let occ = make_occurrences(&["0badf00d", "0badf00e"], 1);
+    let config = MatchConfig::default();
+
+    let results = match_objects(&[katheryne], &active, &occ, &config);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].learned_hashes, vec!["0badf00d", "0badf00e"]);
+    assert_eq!(results[0].sentinel_hashes, vec!["0badf00d", "0badf00e"]);
+}
+
+#[test]
+fn learned_hashes_score_lower_and_yield_sentinel_slots_to_bundled() {
+    let bundled = make_kv_entry("Bundled", &["aa000001", "aa000002"]);
+    let mut learned = make_kv_entry("Learned", &["bb000001"]);
+    learned.learned_hashes = vec!["bb000002".to_string()];
+    let active = make_active_hashes(&["aa000001", "aa000002", "bb000001", "bb000002"]);
+    let occ = make_occurrences(&["aa000001", "aa000002", "bb000001", "bb000002"], 1);
+    let config = MatchConfig {
+        score_threshold: 1.0,
+        sentinel_count: 1,
+        ..Default::default()
+    };
+
+    let results = match_objects(&[bundled, learned], &active, &occ, &config);
+
+    assert_eq!(results[0].object_name, "Bundled");
+    assert!(results[0].learned_hashes.is_empty());
+    let learned = &results[1];
+    assert!(learned.score < results[0].score);
+    assert_eq!(learned.learned_hashes, vec!["bb000002"]);
+    assert_eq!(learned.sentinel_hashes, vec!["bb000001"]);
+}
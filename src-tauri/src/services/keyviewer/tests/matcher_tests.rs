@@ -5,7 +5,7 @@ use std::collections::{HashMap, HashSet};
 use crate::services::keyviewer::matcher::KvObjectEntry;
 use crate::services::keyviewer::matcher::{match_objects, MatchConfidence, MatchConfig};
 
-fn make_kv_entry(name: &str, hashes: &[&str]) -> KvObjectEntry {
+pub(super) fn make_kv_entry(name: &str, hashes: &[&str]) -> KvObjectEntry {
     let code_hashes: Vec<String> = hashes.iter().map(|h| h.to_string()).collect();
     let mut skin_hashes = HashMap::new();
     skin_hashes.insert("Default".to_string(), code_hashes.clone());
@@ -14,16 +14,17 @@ fn make_kv_entry(name: &str, hashes: &[&str]) -> KvObjectEntry {
         object_type: "Character".to_string(),
         code_hashes,
         skin_hashes,
+        learned_hashes: vec![],
         tags: vec![],
         thumbnail_path: None,
     }
 }
 
-fn make_active_hashes(hashes: &[&str]) -> HashSet<String> {
+pub(super) fn make_active_hashes(hashes: &[&str]) -> HashSet<String> {
     hashes.iter().map(|h| h.to_string()).collect()
 }
 
-fn make_occurrences(hashes: &[&str], count: usize) -> HashMap<String, usize> {
+pub(super) fn make_occurrences(hashes: &[&str], count: usize) -> HashMap<String, usize> {
     hashes.iter().map(|h| (h.to_string(), count)).collect()
 }
 
@@ -1,3 +1,7 @@
+pub mod collisions_tests;
 pub mod generator;
 pub mod harvester_tests;
+pub mod hash_index_tests;
+pub mod learned_hashes_tests;
+pub mod matcher_learned_tests;
 pub mod matcher_tests;
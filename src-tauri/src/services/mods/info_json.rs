@@ -156,6 +156,7 @@ pub struct ModInfoUpdate {
     pub preset_name_add: Option<Vec<String>>,
     pub preset_name_remove: Option<Vec<String>>,
     pub metadata: Option<std::collections::HashMap<String, String>>,
+    pub metadata_remove: Option<Vec<String>>,
 }
 
 /// Update specific fields in an existing info.json (merge, not overwrite).
@@ -226,6 +227,9 @@ pub fn update_info_json(mod_path: &Path, update: &ModInfoUpdate) -> Result<ModIn
             info.metadata.insert(k.clone(), v.clone());
         }
     }
+    if let Some(ref remove) = update.metadata_remove {
+        info.metadata.retain(|k, _| !remove.contains(k));
+    }
 
     // Write back
     let info_path = mod_path.join("info.json");
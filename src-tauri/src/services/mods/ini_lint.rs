@@ -0,0 +1,29 @@
+//! Gathers what the INI lint needs from the database and runs it.
+
+use crate::domain::errors::AppError;
+use crate::services::app::keybind_collisions::enabled_mod_keybinds;
+use crate::services::ini::lint::{lint_mod_folder, ForeignBinding, IniDiagnostic};
+use std::path::Path;
+
+/// Lints the INIs of the mod at the validated, canonical `mod_root`.
+pub async fn lint_mod_ini_service(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    mod_root: &Path,
+) -> Result<Vec<IniDiagnostic>, AppError> {
+    // A game without a mods path has no other mods to clash with.
+    let foreign: Vec<ForeignBinding> =
+        match enabled_mod_keybinds(pool, game_id, Some(mod_root)).await {
+            Ok(keybinds) => keybinds
+                .into_iter()
+                .map(|keybind| ForeignBinding {
+                    mod_name: keybind.mod_name,
+                    key: keybind.raw,
+                })
+                .collect(),
+            Err(AppError::NotFound(_)) => Vec::new(),
+            Err(error) => return Err(error),
+        };
+    let mod_root = mod_root.to_path_buf();
+    tokio::task::spawn_blocking(move || lint_mod_folder(&mod_root, &foreign)).await?
+}
@@ -0,0 +1,302 @@
+//! Moves a mod's colliding keybinds to free keys, and back.
+//!
+//! Lines are rewritten through the stale-checked `ini::write` batch pipeline,
+//! so every touched INI gets a backup and the files change together or not
+//! at all. The original values go into the mod's
+//! `info.json` `metadata` under [`REMAP_METADATA_KEY`]; a revert restores them
+//! only on lines that still hold the remapped value.
+
+use crate::domain::errors::AppError;
+use crate::services::app::keybind_collisions::enabled_mod_keybinds;
+use crate::services::app::runtime_effects::{finalize_mutation, MutationOutcome};
+use crate::services::config::ConfigService;
+use crate::services::fs_utils::guard::ValidatedPath;
+use crate::services::fs_utils::operation_lock::OpGuard;
+use crate::services::hotkeys::HotkeyConfig;
+use crate::services::ini::document::{list_ini_files, read_ini_document, IniDocument};
+use crate::services::ini::edit::IniEdit;
+use crate::services::ini::keys::KeyChord;
+use crate::services::ini::write::save_ini_batch_with_edits;
+use crate::services::keyviewer::collisions::hotkey_bindings;
+use crate::services::mods::info_json::{self, ModInfoUpdate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+mod plan;
+
+use plan::rewrite_value;
+pub use plan::{plan_remap, OwnKeybind};
+
+/// `info.json` `metadata` entry holding the JSON list of remapped lines.
+pub const REMAP_METADATA_KEY: &str = "keybind_remap";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum KeybindField {
+    Key,
+    Back,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct RemappedKeybind {
+    /// INI path relative to the mod root, `/`-separated.
+    pub file_name: String,
+    pub section_name: String,
+    pub field: KeybindField,
+    #[specta(type = f64)]
+    pub line_idx: usize,
+    pub original: String,
+    pub remapped: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct KeybindRemapReport {
+    pub changed: Vec<RemappedKeybind>,
+    /// Bindings left alone: no free key in the pool, or (on revert) a line
+    /// edited since the remap.
+    pub skipped: Vec<String>,
+}
+
+struct ModInis {
+    documents: BTreeMap<String, IniDocument>,
+}
+
+impl ModInis {
+    fn read(mod_root: &Path) -> Result<Self, AppError> {
+        let mut documents = BTreeMap::new();
+        for path in list_ini_files(mod_root)? {
+            let file_name = path
+                .strip_prefix(mod_root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            match read_ini_document(&path) {
+                Ok(document) => {
+                    documents.insert(file_name, document);
+                }
+                // Too large to edit safely; its bindings are left alone.
+                Err(error) => log::warn!("[keybind_remap] Skipping {file_name}: {error}"),
+            }
+        }
+        Ok(Self { documents })
+    }
+
+    fn keybinds(&self) -> Vec<OwnKeybind> {
+        let mut out = Vec::new();
+        for (file_name, document) in &self.documents {
+            for binding in &document.key_bindings {
+                let fields = [
+                    (KeybindField::Key, &binding.key, binding.key_line_idx),
+                    (KeybindField::Back, &binding.back, binding.back_line_idx),
+                ];
+                for (field, value, line_idx) in fields {
+                    if let (Some(raw), Some(line_idx)) = (value, line_idx) {
+                        out.push(OwnKeybind {
+                            file_name: file_name.clone(),
+                            section_name: binding.section_name.clone(),
+                            field,
+                            line_idx,
+                            raw: raw.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Writes `(line_idx, new value)` pairs per file as one batch: every file
+    /// is stale-checked against the hash read at the start of the operation,
+    /// and a failed write restores the files already rewritten.
+    fn write(mut self, by_file: BTreeMap<&str, Vec<(usize, &str)>>) -> Result<(), AppError> {
+        let mut batch = Vec::with_capacity(by_file.len());
+        for (file_name, values) in by_file {
+            let Some(document) = self.documents.remove(file_name) else {
+                continue;
+            };
+            let edits: Vec<IniEdit> = values
+                .into_iter()
+                .map(|(line_idx, value)| IniEdit::ReplaceLine {
+                    line_idx,
+                    line: rewrite_value(&document.raw_lines[line_idx], value),
+                })
+                .collect();
+            batch.push((document, edits));
+        }
+        save_ini_batch_with_edits(&batch)
+    }
+}
+
+fn recorded_remaps(mod_root: &Path) -> Result<Vec<RemappedKeybind>, AppError> {
+    let info = info_json::read_info_json(mod_root)?;
+    match info
+        .as_ref()
+        .and_then(|info| info.metadata.get(REMAP_METADATA_KEY))
+    {
+        Some(json) => Ok(serde_json::from_str(json)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Records the originals of the colliding bindings of the mod at `mod_root`,
+/// then remaps them. A line remapped twice keeps its first original.
+pub fn remap_mod_folder(
+    mod_root: &Path,
+    taken: &[KeyChord],
+    pool: &[String],
+) -> Result<KeybindRemapReport, AppError> {
+    let inis = ModInis::read(mod_root)?;
+    let (changed, skipped) = plan_remap(&inis.keybinds(), taken, pool);
+    if changed.is_empty() {
+        return Ok(KeybindRemapReport { changed, skipped });
+    }
+
+    let mut by_file: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+    for remap in &changed {
+        by_file
+            .entry(&remap.file_name)
+            .or_default()
+            .push((remap.line_idx, &remap.remapped));
+    }
+    // The originals are recorded before any INI changes. The INIs are written
+    // as one batch, so a failed write leaves none of them remapped and revert
+    // skips every line as no longer holding the remapped value.
+    let mut recorded = recorded_remaps(mod_root)?;
+    for remap in &changed {
+        match recorded.iter_mut().find(|entry| {
+            entry.file_name == remap.file_name
+                && entry.line_idx == remap.line_idx
+                && entry.field == remap.field
+        }) {
+            Some(entry) => entry.remapped = remap.remapped.clone(),
+            None => recorded.push(remap.clone()),
+        }
+    }
+    info_json::update_info_json(
+        mod_root,
+        &ModInfoUpdate {
+            metadata: Some(HashMap::from([(
+                REMAP_METADATA_KEY.to_string(),
+                serde_json::to_string(&recorded)?,
+            )])),
+            ..Default::default()
+        },
+    )?;
+    inis.write(by_file)?;
+    Ok(KeybindRemapReport { changed, skipped })
+}
+
+/// Restores every recorded original whose line still holds the remapped
+/// value, then drops the record.
+pub fn revert_mod_folder(mod_root: &Path) -> Result<KeybindRemapReport, AppError> {
+    let recorded = recorded_remaps(mod_root)?;
+    if recorded.is_empty() {
+        return Ok(KeybindRemapReport::default());
+    }
+    let inis = ModInis::read(mod_root)?;
+    let current = inis.keybinds();
+
+    let mut report = KeybindRemapReport::default();
+    let mut by_file: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+    for entry in &recorded {
+        let still_remapped = current.iter().any(|binding| {
+            binding.file_name == entry.file_name
+                && binding.line_idx == entry.line_idx
+                && binding.field == entry.field
+                && binding.raw == entry.remapped
+        });
+        if still_remapped {
+            by_file
+                .entry(&entry.file_name)
+                .or_default()
+                .push((entry.line_idx, &entry.original));
+            report.changed.push(entry.clone());
+        } else {
+            report.skipped.push(format!(
+                "[{}] in {} was edited after the remap; left as is",
+                entry.section_name, entry.file_name
+            ));
+        }
+    }
+    inis.write(by_file)?;
+
+    info_json::update_info_json(
+        mod_root,
+        &ModInfoUpdate {
+            metadata_remove: Some(vec![REMAP_METADATA_KEY.to_string()]),
+            ..Default::default()
+        },
+    )?;
+    Ok(report)
+}
+
+/// Object id of the mod at `mod_root`, for a scoped overlay refresh.
+async fn mod_object_id(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    mod_root: &ValidatedPath,
+) -> Result<Option<String>, AppError> {
+    let Some(mods_root) = crate::repo::game_repo::get_mod_path(pool, game_id).await? else {
+        return Ok(None);
+    };
+    let original = Path::new(mod_root.original());
+    let rel_path = original
+        .strip_prefix(&mods_root)
+        .unwrap_or(original)
+        .to_string_lossy()
+        .replace('\\', "/");
+    Ok(crate::repo::mod_repo::get_object_id_by_folder_and_game(pool, &rel_path, game_id).await?)
+}
+
+/// Remaps the mod's bindings that collide with another enabled mod or an app
+/// hotkey, then regenerates the overlay so the keybind text shows the new keys.
+pub async fn remap_keybinds_service(
+    _op_guard: &OpGuard,
+    pool: &sqlx::SqlitePool,
+    config: &ConfigService,
+    game_id: &str,
+    mod_root: &ValidatedPath,
+) -> Result<KeybindRemapReport, AppError> {
+    let (hotkeys, remap_pool): (HotkeyConfig, Vec<String>) = config.with_settings(|settings| {
+        (
+            settings.hotkeys.clone(),
+            settings.keyviewer.remap_pool.clone(),
+        )
+    });
+    let mut taken: Vec<KeyChord> = enabled_mod_keybinds(pool, game_id, Some(mod_root))
+        .await?
+        .iter()
+        .filter_map(|keybind| KeyChord::parse(&keybind.raw))
+        .collect();
+    taken.extend(
+        hotkey_bindings(&hotkeys)
+            .into_iter()
+            .map(|(chord, _)| chord),
+    );
+
+    let report = remap_mod_folder(mod_root, &taken, &remap_pool)?;
+    if !report.changed.is_empty() {
+        let object_id = mod_object_id(pool, game_id, mod_root).await?;
+        finalize_mutation(pool, config, game_id, MutationOutcome::objects(object_id)).await;
+    }
+    Ok(report)
+}
+
+pub async fn revert_keybind_remap_service(
+    _op_guard: &OpGuard,
+    pool: &sqlx::SqlitePool,
+    config: &ConfigService,
+    game_id: &str,
+    mod_root: &ValidatedPath,
+) -> Result<KeybindRemapReport, AppError> {
+    let report = revert_mod_folder(mod_root)?;
+    if !report.changed.is_empty() {
+        let object_id = mod_object_id(pool, game_id, mod_root).await?;
+        finalize_mutation(pool, config, game_id, MutationOutcome::objects(object_id)).await;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "../tests/keybind_remap_tests.rs"]
+mod tests;
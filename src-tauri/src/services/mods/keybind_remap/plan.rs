@@ -0,0 +1,92 @@
+//! Choosing new keys; no filesystem access.
+
+use super::{KeybindField, RemappedKeybind};
+use crate::services::ini::keys::{with_key, KeyChord};
+use std::collections::HashMap;
+
+/// One `key =`/`back =` value of the mod being remapped.
+#[derive(Debug, Clone)]
+pub struct OwnKeybind {
+    pub file_name: String,
+    pub section_name: String,
+    pub field: KeybindField,
+    pub line_idx: usize,
+    pub raw: String,
+}
+
+/// Picks a new key for every binding in `own` that overlaps `taken`.
+///
+/// Modifier words are kept. Bindings that shared a key before keep sharing
+/// one, so a `key = VK_F5` / `back = shift VK_F5` pair moves as a pair.
+pub fn plan_remap(
+    own: &[OwnKeybind],
+    taken: &[KeyChord],
+    pool: &[String],
+) -> (Vec<RemappedKeybind>, Vec<String>) {
+    let original_keys: Vec<Option<String>> = own
+        .iter()
+        .map(|binding| KeyChord::parse(&binding.raw).map(|chord| chord.key))
+        .collect();
+    let mut current: Vec<Option<KeyChord>> = own
+        .iter()
+        .map(|binding| KeyChord::parse(&binding.raw))
+        .collect();
+    let mut moved: HashMap<String, String> = HashMap::new();
+    let mut changed = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (idx, binding) in own.iter().enumerate() {
+        let Some(chord) = current[idx].clone() else {
+            continue;
+        };
+        if !taken.iter().any(|other| other.overlaps(&chord)) {
+            continue;
+        }
+        let candidates = moved.get(&chord.key).into_iter().chain(pool.iter());
+        let replacement = candidates.into_iter().find_map(|pool_key| {
+            let raw = with_key(&binding.raw, pool_key);
+            let candidate = KeyChord::parse(&raw)?;
+            let clashes_with_own = current.iter().enumerate().any(|(other_idx, other)| {
+                other_idx != idx
+                    && original_keys[other_idx] != original_keys[idx]
+                    && other
+                        .as_ref()
+                        .is_some_and(|other| other.overlaps(&candidate))
+            });
+            let free = !clashes_with_own && !taken.iter().any(|other| other.overlaps(&candidate));
+            free.then(|| (pool_key.clone(), raw, candidate))
+        });
+        match replacement {
+            Some((pool_key, raw, candidate)) => {
+                moved.insert(chord.key.clone(), pool_key);
+                current[idx] = Some(candidate);
+                changed.push(RemappedKeybind {
+                    file_name: binding.file_name.clone(),
+                    section_name: binding.section_name.clone(),
+                    field: binding.field,
+                    line_idx: binding.line_idx,
+                    original: binding.raw.clone(),
+                    remapped: raw,
+                });
+            }
+            None => skipped.push(format!(
+                "[{}] `{}` in {}: no free key left in the remap pool",
+                binding.section_name, binding.raw, binding.file_name
+            )),
+        }
+    }
+    (changed, skipped)
+}
+
+/// Replaces the value of a `key = value ; comment` line, keeping indentation,
+/// the key's spelling and any trailing comment.
+pub(super) fn rewrite_value(line: &str, value: &str) -> String {
+    let Some((lhs, rhs)) = line.split_once('=') else {
+        return line.to_string();
+    };
+    let comment = rhs.find([';', '#']).map(|at| rhs[at..].trim_end());
+    match comment {
+        Some(comment) => format!("{} = {value} {comment}", lhs.trim_end()),
+        None => format!("{} = {value}", lhs.trim_end()),
+    }
+}
@@ -201,7 +201,14 @@ pub async fn get_active_mod_conflicts(
         .ok_or_else(|| AppError::NotFound(format!("Game {game_id} has no mods path")))?;
     let rows = crate::repo::mod_repo::get_enabled_mods_paths(pool, game_id).await?;
 
-    Ok(conflicts_for_enabled_paths(Path::new(&mods_path), &rows))
+    let mut conflicts = indexed_conflicts(pool, game_id, Path::new(&mods_path), &rows).await?;
+    crate::services::scanner::conflict::resolution::annotate_resolutions(
+        pool,
+        game_id,
+        &mut conflicts,
+    )
+    .await?;
+    Ok(conflicts)
 }
 
 /// Conflict detection over an enabled-mod path list the caller already has.
@@ -237,6 +244,40 @@ pub fn conflicts_for_enabled_paths(
     crate::services::scanner::conflict::detect_conflicts_with_roots(&ini_files, &mod_roots)
 }
 
+/// Conflicts among `enabled_paths` plus the loose replacements in the game's
+/// `ShaderFixes/` folder. Brings `mod_hash_index` up to date, then parses
+/// only the INIs it shows to carry a shared or shader hash.
+pub async fn indexed_conflicts(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+    enabled_paths: &[crate::domain::mod_path::ModFolderPath],
+) -> Result<Vec<crate::services::scanner::conflict::ConflictInfo>, AppError> {
+    crate::services::keyviewer::hash_index::sync_hash_index(pool, game_id, mods_root, None).await?;
+    let game_shader_fixes =
+        crate::services::scanner::conflict::shader_fixes::game_shader_fixes_dir(
+            pool, game_id, mods_root,
+        )
+        .await?;
+    let ini_files =
+        crate::services::keyviewer::hash_index::conflict_ini_files(pool, game_id, mods_root)
+            .await?;
+    // Replacement shaders are files rather than INI lines, so every enabled
+    // mod is still checked for them.
+    let mod_roots: Vec<_> = enabled_paths
+        .iter()
+        .map(|stored| stored.resolve(mods_root))
+        .filter(|path| path.exists())
+        .collect();
+    Ok(
+        crate::services::scanner::conflict::detect_conflicts_with_shader_fixes(
+            &ini_files,
+            &mod_roots,
+            game_shader_fixes.as_deref(),
+        ),
+    )
+}
+
 #[cfg(test)]
 #[path = "tests/metadata_conflict_tests.rs"]
 mod metadata_conflict_tests;
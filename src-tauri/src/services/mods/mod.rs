@@ -3,6 +3,8 @@ pub mod arrival;
 pub mod bulk;
 pub mod core_ops;
 pub mod info_json;
+pub mod ini_lint;
+pub mod keybind_remap;
 pub mod metadata;
 pub mod object_switch;
 pub mod organizer_duplicates;
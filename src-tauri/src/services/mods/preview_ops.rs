@@ -4,6 +4,7 @@
 
 use crate::domain::errors::{AppError, MetadataError};
 use crate::services::ini::document::{self as ini_document, IniDocument};
+use crate::services::ini::edit::IniEdit;
 use crate::services::ini::write as ini_write;
 use crate::services::mods::preview_image;
 use crate::services::scanner::core::thumbnail;
@@ -180,6 +181,27 @@ pub async fn write_mod_ini_locked_inner(
     write_mod_ini_inner(mod_root, file_name, expected_source_hash, line_updates)
 }
 
+pub fn edit_mod_ini_inner(
+    mod_root: &Path,
+    file_name: &str,
+    expected_source_hash: &str,
+    edits: &[IniEdit],
+) -> Result<(), AppError> {
+    let ini_path = resolve_ini_path(mod_root, file_name)?;
+    let document = ini_document::read_ini_document(&ini_path)?;
+    ini_write::save_ini_with_edits(&document, expected_source_hash, edits)
+}
+
+pub async fn edit_mod_ini_locked_inner(
+    _op_guard: &crate::services::fs_utils::operation_lock::OpGuard,
+    mod_root: &Path,
+    file_name: &str,
+    expected_source_hash: &str,
+    edits: Vec<IniEdit>,
+) -> Result<(), AppError> {
+    edit_mod_ini_inner(mod_root, file_name, expected_source_hash, &edits)
+}
+
 pub fn list_mod_preview_images_inner(mod_root: &Path) -> Result<Vec<String>, AppError> {
     if !mod_root.exists() || !mod_root.is_dir() {
         return Err(AppError::Metadata(MetadataError::Validation(format!(
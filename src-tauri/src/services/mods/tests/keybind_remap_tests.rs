@@ -0,0 +1,138 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn pool(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|key| key.to_string()).collect()
+}
+
+fn taken(keys: &[&str]) -> Vec<KeyChord> {
+    keys.iter()
+        .map(|key| KeyChord::parse(key).unwrap())
+        .collect()
+}
+
+fn mod_with_ini(ini: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("mod.ini"), ini).unwrap();
+    dir
+}
+
+#[test]
+fn rewrite_value_keeps_indent_key_spelling_and_comment() {
+    assert_eq!(
+        rewrite_value("  Key=VK_F5   ; swap body", "VK_NUMPAD0"),
+        "  Key = VK_NUMPAD0 ; swap body"
+    );
+    assert_eq!(rewrite_value("back = 2", "3"), "back = 3");
+}
+
+#[test]
+fn plan_moves_pairs_together_and_skips_taken_pool_keys() {
+    let own = vec![
+        OwnKeybind {
+            file_name: "mod.ini".to_string(),
+            section_name: "KeySwap".to_string(),
+            field: KeybindField::Key,
+            line_idx: 1,
+            raw: "VK_F5".to_string(),
+        },
+        OwnKeybind {
+            file_name: "mod.ini".to_string(),
+            section_name: "KeySwap".to_string(),
+            field: KeybindField::Back,
+            line_idx: 2,
+            raw: "shift VK_F5".to_string(),
+        },
+    ];
+
+    let (changed, skipped) = plan_remap(
+        &own,
+        &taken(&["F5", "VK_NUMPAD0"]),
+        &pool(&["VK_NUMPAD0", "VK_NUMPAD1"]),
+    );
+
+    let remapped: Vec<_> = changed.iter().map(|r| r.remapped.as_str()).collect();
+    assert_eq!(remapped, vec!["VK_NUMPAD1", "shift VK_NUMPAD1"]);
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn plan_reports_bindings_the_pool_cannot_place() {
+    let own = vec![OwnKeybind {
+        file_name: "mod.ini".to_string(),
+        section_name: "KeySwap".to_string(),
+        field: KeybindField::Key,
+        line_idx: 1,
+        raw: "VK_F5".to_string(),
+    }];
+
+    let (changed, skipped) = plan_remap(&own, &taken(&["F5", "1"]), &pool(&["VK_1"]));
+
+    assert!(changed.is_empty());
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].contains("[KeySwap]"));
+}
+
+#[test]
+fn remap_then_revert_round_trips_the_ini_and_info_json() {
+    let source = "[KeySwap]\nkey = VK_F5 ; body\nback = 7\n$swap = 0,1\n";
+    let dir = mod_with_ini(source);
+
+    let report = remap_mod_folder(dir.path(), &taken(&["F5"]), &pool(&["VK_NUMPAD0"])).unwrap();
+
+    assert_eq!(report.changed.len(), 1);
+    let rewritten = fs::read_to_string(dir.path().join("mod.ini")).unwrap();
+    assert_eq!(
+        rewritten,
+        "[KeySwap]\nkey = VK_NUMPAD0 ; body\nback = 7\n$swap = 0,1\n"
+    );
+    let recorded = recorded_remaps(dir.path()).unwrap();
+    assert_eq!(recorded[0].original, "VK_F5");
+
+    let reverted = revert_mod_folder(dir.path()).unwrap();
+
+    assert_eq!(reverted.changed.len(), 1);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("mod.ini")).unwrap(),
+        "[KeySwap]\nkey = VK_F5 ; body\nback = 7\n$swap = 0,1\n"
+    );
+    let info = info_json::read_info_json(dir.path()).unwrap().unwrap();
+    assert!(!info.metadata.contains_key(REMAP_METADATA_KEY));
+}
+
+#[test]
+fn revert_leaves_lines_edited_after_the_remap() {
+    let dir = mod_with_ini("[KeySwap]\nkey = VK_F5\n");
+    remap_mod_folder(dir.path(), &taken(&["F5"]), &pool(&["VK_NUMPAD0"])).unwrap();
+    fs::write(dir.path().join("mod.ini"), "[KeySwap]\nkey = VK_F9\n").unwrap();
+
+    let reverted = revert_mod_folder(dir.path()).unwrap();
+
+    assert!(reverted.changed.is_empty());
+    assert_eq!(reverted.skipped.len(), 1);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("mod.ini")).unwrap(),
+        "[KeySwap]\nkey = VK_F9\n"
+    );
+}
+
+#[test]
+fn a_failed_write_leaves_every_ini_of_the_mod_untouched() {
+    let dir = mod_with_ini("[KeySwap]\nkey = VK_F5\n");
+    fs::write(dir.path().join("z.ini"), "[KeyOther]\nkey = VK_F5\n").unwrap();
+    let inis = ModInis::read(dir.path()).unwrap();
+    // Edited behind the remap's back: its stale check fails.
+    fs::write(dir.path().join("z.ini"), "[KeyOther]\nkey = VK_F6\n").unwrap();
+
+    let by_file = BTreeMap::from([
+        ("z.ini", vec![(1, "VK_NUMPAD0")]),
+        ("mod.ini", vec![(1, "VK_NUMPAD1")]),
+    ]);
+
+    assert!(inis.write(by_file).is_err());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("mod.ini")).unwrap(),
+        "[KeySwap]\nkey = VK_F5\n"
+    );
+}
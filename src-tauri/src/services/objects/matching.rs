@@ -1,4 +1,5 @@
 use crate::domain::errors::AppError;
+use crate::services::scanner::master_db::corrections::{self, MANUAL_MATCH_SOURCE};
 
 /// The canonical match payload written onto the resolved object.
 pub struct ObjectMatchFields<'a> {
@@ -11,13 +12,17 @@ pub struct ObjectMatchFields<'a> {
 
 /// Resolves the object a match applies to (explicit id, else physical folder
 /// lookup) and writes the canonical match onto it.
+///
+/// A manual match that changes the object's entry is also recorded as a
+/// matcher correction. Returns whether one was, so the caller can drop the
+/// cached MasterDB.
 pub async fn apply_object_match(
     pool: &sqlx::SqlitePool,
     game_id: &str,
     object_id: Option<&str>,
     folder_path: Option<&str>,
     matched: ObjectMatchFields<'_>,
-) -> Result<(), AppError> {
+) -> Result<bool, AppError> {
     let target_object_id = match object_id {
         Some(object_id) => object_id.to_string(),
         None => {
@@ -39,6 +44,24 @@ pub async fn apply_object_match(
         }
     };
 
+    let source = matched.source.unwrap_or(MANUAL_MATCH_SOURCE);
+    let correction = match matched.entry_key {
+        Some(entry_key) if source == MANUAL_MATCH_SOURCE => {
+            crate::repo::object_repo::get_folder_and_matched_entry_key(pool, &target_object_id)
+                .await
+                .map_err(|error| AppError::Db(error.to_string()))?
+                .and_then(|(object_folder, previous_entry_key)| {
+                    corrections::manual_match_correction(
+                        folder_path,
+                        &object_folder,
+                        previous_entry_key.as_deref(),
+                        entry_key,
+                    )
+                })
+        }
+        _ => None,
+    };
+
     crate::repo::object_repo::apply_canonical_match(
         pool,
         &target_object_id,
@@ -46,10 +69,13 @@ pub async fn apply_object_match(
         matched.alias_name,
         matched.confidence,
         matched.reason,
-        Some(matched.source.unwrap_or("manual_match")),
+        Some(source),
     )
     .await
     .map_err(|error| AppError::Db(error.to_string()))?;
 
-    Ok(())
+    let Some(correction) = correction else {
+        return Ok(false);
+    };
+    Ok(corrections::record_corrections(pool, game_id, &[correction]).await? > 0)
 }
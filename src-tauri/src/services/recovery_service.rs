@@ -5,16 +5,18 @@
 
 use sqlx::SqlitePool;
 
+use crate::domain::collection::ApplyScope;
 use crate::domain::errors::AppError;
 use crate::domain::task::{PipelineTask, RecoveryAction, TaskStatus};
 use crate::services::config::models::AppSettings;
 use crate::services::config::ConfigService;
+use crate::services::fs_utils::operation_lock::OpGuard;
 use crate::services::scanner::watcher::WatcherState;
 
-/// Resolve one recovery task. The caller is responsible for holding the
-/// operation lock: resuming an apply mutates the filesystem and must be
-/// mutually excluded from concurrent runtime ops.
+/// Resolve one recovery task. Resuming an apply mutates the filesystem, so
+/// it runs under the operation lock like a normal apply.
 pub async fn resolve_recovery_task(
+    op_guard: &OpGuard,
     pool: &SqlitePool,
     config: &ConfigService,
     watcher_state: &WatcherState,
@@ -47,11 +49,29 @@ pub async fn resolve_recovery_task(
 
     match action {
         RecoveryAction::Retry => {
-            retry_task(pool, watcher_state, &task, settings, mods_path, is_safe).await?;
+            retry_task(
+                op_guard,
+                pool,
+                watcher_state,
+                &task,
+                settings,
+                mods_path,
+                is_safe,
+            )
+            .await?;
             mark_task(pool, task_id, TaskStatus::Completed).await
         }
         RecoveryAction::Rollback => {
-            rollback_task(pool, watcher_state, &task, settings, mods_path, is_safe).await?;
+            rollback_task(
+                op_guard,
+                pool,
+                watcher_state,
+                &task,
+                settings,
+                mods_path,
+                is_safe,
+            )
+            .await?;
             mark_task(pool, task_id, TaskStatus::Completed).await
         }
         RecoveryAction::Ignore => mark_task(pool, task_id, TaskStatus::Failed).await,
@@ -69,7 +89,22 @@ fn target_collection_id(task: &PipelineTask) -> Result<&str, AppError> {
         .ok_or_else(|| AppError::Validation("Missing target collection ID".to_string()))
 }
 
+/// The scope the interrupted apply ran with. Tasks without a payload were
+/// full applies.
+fn task_apply_scope(task: &PipelineTask) -> Result<ApplyScope, AppError> {
+    let Some(payload) = task.payload.as_deref() else {
+        return Ok(ApplyScope::Full);
+    };
+    serde_json::from_str(payload).map_err(|error| {
+        AppError::Validation(format!(
+            "Task {} has an unreadable apply scope: {error}",
+            task.id
+        ))
+    })
+}
+
 async fn retry_task(
+    op_guard: &OpGuard,
     pool: &SqlitePool,
     watcher_state: &WatcherState,
     task: &PipelineTask,
@@ -81,9 +116,11 @@ async fn retry_task(
         "apply_collection" => {
             // Existence is validated downstream by `validate_corridor`.
             let collection_id = target_collection_id(task)?;
+            let scope = task_apply_scope(task)?;
 
             crate::services::collection_service::apply_collection(
                 crate::services::collection_service::ApplyCollectionRequest {
+                    op_guard,
                     pool,
                     game_id: &task.game_id,
                     collection_id,
@@ -93,6 +130,7 @@ async fn retry_task(
                     ignore_missing: true,
                     settings,
                     reconcile_lock: None,
+                    scope,
                 },
             )
             .await?;
@@ -113,6 +151,7 @@ async fn retry_task(
 }
 
 async fn rollback_task(
+    op_guard: &OpGuard,
     pool: &SqlitePool,
     watcher_state: &WatcherState,
     task: &PipelineTask,
@@ -130,12 +169,15 @@ async fn rollback_task(
         }
         "apply_collection" => {
             let collection_id = target_collection_id(task)?;
+            // A scoped apply is rolled back over the same objects only.
+            let scope = task_apply_scope(task)?;
 
             let rollback_collection_id =
                 resolve_rollback_target(pool, task, collection_id, is_safe).await?;
 
             crate::services::collection_service::apply_collection(
                 crate::services::collection_service::ApplyCollectionRequest {
+                    op_guard,
                     pool,
                     game_id: &task.game_id,
                     collection_id: &rollback_collection_id,
@@ -145,6 +187,7 @@ async fn rollback_task(
                     ignore_missing: true,
                     settings,
                     reconcile_lock: None,
+                    scope,
                 },
             )
             .await?;
@@ -0,0 +1,99 @@
+//! Re-applying stored conflict decisions.
+//!
+//! A collection apply can enable a mod that was disabled to settle a
+//! conflict, and a reinstalled mod comes back without its `match_priority`
+//! lines. Either way the same mods conflict on the same hash again, so the
+//! stored decision still holds and is carried out once more.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use sqlx::SqlitePool;
+
+use crate::domain::conflicts::ConflictResolutionStrategy;
+use crate::domain::errors::AppError;
+use crate::services::fs_utils::operation_lock::OpGuard;
+use crate::services::ini::document::IniDocument;
+use crate::services::ini::edit::IniEdit;
+use crate::services::ini::write::save_ini_batch_with_edits;
+use crate::services::runtime_mutation_engine::{
+    toggle_mods_mixed, RuntimeToggleBatchRequest, RuntimeToggleOperation,
+};
+
+use super::resolution::{conflict_mod_ids, pending_priority_edits};
+
+/// Disables the losers that are enabled again and re-demotes the override
+/// sections that lost their `match_priority`, for every conflict whose stored
+/// decision still matches its mods. Returns every path changed on disk, for
+/// the caller's reconcile.
+pub async fn enforce_resolutions(
+    _op_guard: &OpGuard,
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+) -> Result<Vec<String>, AppError> {
+    let conflicts =
+        crate::services::mods::metadata::get_active_mod_conflicts(pool, game_id).await?;
+
+    // Two conflicts can demote sections of the same INI: one save each.
+    let mut batch: BTreeMap<PathBuf, (IniDocument, Vec<IniEdit>)> = BTreeMap::new();
+    // A loser of two conflicts is disabled once.
+    let mut to_disable = BTreeSet::new();
+    for conflict in &conflicts {
+        let Some(resolution) = &conflict.resolution else {
+            continue;
+        };
+        let Some(ids) = conflict_mod_ids(pool, game_id, conflict).await? else {
+            continue;
+        };
+        let loser_paths: Vec<String> = ids
+            .into_iter()
+            .filter(|(_, id)| resolution.loser_mod_ids.contains(id))
+            .map(|(path, _)| path)
+            .collect();
+        match resolution.strategy {
+            ConflictResolutionStrategy::MatchPriority => {
+                for (document, edits) in pending_priority_edits(conflict, &loser_paths)? {
+                    batch
+                        .entry(document.file_path.clone())
+                        .or_insert_with(|| (document, Vec::new()))
+                        .1
+                        .extend(edits);
+                }
+            }
+            ConflictResolutionStrategy::DisableLosers => {
+                to_disable.extend(loser_paths.iter().filter_map(|path| {
+                    let relative = Path::new(path).strip_prefix(mods_root).ok()?;
+                    Some(relative.to_string_lossy().replace('\\', "/"))
+                }));
+            }
+        }
+    }
+
+    let mut changed_paths: Vec<String> = batch
+        .keys()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    save_ini_batch_with_edits(&batch.into_values().collect::<Vec<_>>())?;
+    if !to_disable.is_empty() {
+        let result = toggle_mods_mixed(RuntimeToggleBatchRequest {
+            mods_path: mods_root.to_path_buf(),
+            operations: to_disable
+                .into_iter()
+                .map(|folder_path| RuntimeToggleOperation {
+                    folder_path,
+                    target_enabled: false,
+                })
+                .collect(),
+        })
+        .await
+        .map_err(|failure| {
+            for warning in &failure.rollback_warnings {
+                log::warn!("[conflict] enforce rollback: {warning}");
+            }
+            AppError::from(failure.error)
+        })?;
+        changed_paths.extend(result.changed_paths);
+    }
+    Ok(changed_paths)
+}
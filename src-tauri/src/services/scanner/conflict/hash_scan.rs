@@ -1,18 +1,15 @@
 //! Evidence-backed 3DMigoto resource and shader conflict detection.
 
+use crate::services::ini::ast::{parse_ini_text, Assignment, SectionKind};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, specta::Type)]
-#[serde(rename_all = "snake_case")]
-pub enum ConflictKind {
-    ResourceHash,
-    ShaderHash,
-    ShaderReplacement,
-}
+use super::shader_fixes::{scan_loose_replacements, scan_shader_replacements};
+
+use crate::domain::conflicts::ConflictResolution;
+pub use crate::domain::conflicts::{ConflictKind, ConflictOwner};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "snake_case")]
@@ -23,7 +20,10 @@ pub enum ConflictCertainty {
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct ConflictEvidence {
+    /// The owning folder: a mod, or the game's `ShaderFixes/` (see `owner`).
     pub mod_path: String,
+    #[serde(default)]
+    pub owner: ConflictOwner,
     pub source_path: String,
     pub section_name: String,
     pub namespace: Option<String>,
@@ -31,6 +31,10 @@ pub struct ConflictEvidence {
     pub priority: Option<i64>,
     pub match_first_index: Option<u32>,
     pub shader_stage: Option<String>,
+    /// BLAKE3 of a replacement shader's body, whitespace-normalized for HLSL,
+    /// so two mods shipping the same fix are not reported against each other.
+    #[serde(default)]
+    pub content_hash: Option<String>,
 }
 
 /// A potential collision plus the source facts needed to judge it.
@@ -38,21 +42,28 @@ pub struct ConflictEvidence {
 pub struct ConflictInfo {
     pub hash: String,
     pub section_name: String,
+    /// The mods involved; a loose `ShaderFixes/` file is only in `evidence`.
     pub mod_paths: Vec<String>,
     pub is_active: bool,
     pub kind: ConflictKind,
     pub certainty: ConflictCertainty,
     pub has_conditional_evidence: bool,
     pub evidence: Vec<ConflictEvidence>,
+    /// The stored winner decision for this hash, filled in by the services
+    /// that report conflicts; detection itself never sets it.
+    #[serde(default)]
+    pub resolution: Option<ConflictResolution>,
 }
 
 #[derive(Debug, Clone)]
-struct HashEntry {
-    kind: ConflictKind,
-    hash: String,
-    evidence: ConflictEvidence,
+pub(super) struct HashEntry {
+    pub(super) kind: ConflictKind,
+    pub(super) hash: String,
+    pub(super) evidence: ConflictEvidence,
 }
 
+type GroupKey = (ConflictKind, String, Option<String>);
+
 /// Detect collisions across INI overrides and legacy ShaderFixes replacements.
 pub fn detect_conflicts(ini_files: &[(PathBuf, PathBuf)]) -> Vec<ConflictInfo> {
     let mod_roots: Vec<_> = ini_files
@@ -67,8 +78,18 @@ pub fn detect_conflicts_with_roots(
     ini_files: &[(PathBuf, PathBuf)],
     mod_roots: &[PathBuf],
 ) -> Vec<ConflictInfo> {
-    let mut groups: HashMap<(ConflictKind, String, Option<String>), Vec<HashEntry>> =
-        HashMap::new();
+    detect_conflicts_with_shader_fixes(ini_files, mod_roots, None)
+}
+
+/// Full scan that also weighs the game's own `ShaderFixes/` folder: a loose
+/// `*_replace` file there competes with any mod replacing or overriding the
+/// same shader, and is reported as evidence owned by the game rather than a mod.
+pub fn detect_conflicts_with_shader_fixes(
+    ini_files: &[(PathBuf, PathBuf)],
+    mod_roots: &[PathBuf],
+    game_shader_fixes: Option<&Path>,
+) -> Vec<ConflictInfo> {
+    let mut groups: HashMap<GroupKey, Vec<HashEntry>> = HashMap::new();
 
     for (mod_root, ini_path) in ini_files {
         add_entries(&mut groups, parse_ini_hashes(ini_path, mod_root));
@@ -79,10 +100,19 @@ pub fn detect_conflicts_with_roots(
             add_entries(&mut groups, scan_shader_replacements(mod_root));
         }
     }
+    if let Some(shader_fixes) = game_shader_fixes {
+        for entry in scan_loose_replacements(shader_fixes) {
+            groups
+                .entry((ConflictKind::ShaderHash, entry.hash.clone(), None))
+                .or_default()
+                .push(entry.clone());
+            add_entries(&mut groups, vec![entry]);
+        }
+    }
 
     let mut conflicts: Vec<_> = groups
         .into_iter()
-        .filter_map(|((_kind, _hash, _stage), entries)| build_conflict(entries))
+        .filter_map(|((kind, _hash, _stage), entries)| build_conflict(kind, entries))
         .collect();
     conflicts.sort_by(|left, right| {
         left.hash
@@ -92,10 +122,7 @@ pub fn detect_conflicts_with_roots(
     conflicts
 }
 
-fn add_entries(
-    groups: &mut HashMap<(ConflictKind, String, Option<String>), Vec<HashEntry>>,
-    entries: Vec<HashEntry>,
-) {
+fn add_entries(groups: &mut HashMap<GroupKey, Vec<HashEntry>>, entries: Vec<HashEntry>) {
     for entry in entries {
         let key = (
             entry.kind,
@@ -106,7 +133,7 @@ fn add_entries(
     }
 }
 
-fn build_conflict(entries: Vec<HashEntry>) -> Option<ConflictInfo> {
+fn build_conflict(kind: ConflictKind, entries: Vec<HashEntry>) -> Option<ConflictInfo> {
     let involved: Vec<_> = entries
         .iter()
         .filter(|entry| {
@@ -120,15 +147,23 @@ fn build_conflict(entries: Vec<HashEntry>) -> Option<ConflictInfo> {
         })
         .collect();
 
-    let mut mod_paths: Vec<_> = involved
+    let owners: HashSet<_> = involved
         .iter()
-        .map(|entry| entry.evidence.mod_path.clone())
-        .collect::<HashSet<_>>()
-        .into_iter()
+        .map(|entry| (entry.evidence.owner, entry.evidence.mod_path.clone()))
         .collect();
-    if mod_paths.len() < 2 {
+    if owners.len() < 2 || (kind == ConflictKind::ShaderReplacement && same_body(&involved)) {
         return None;
     }
+    // The game's ShaderFixes is always loaded, so it counts as active.
+    let active_owners = owners
+        .iter()
+        .filter(|(owner, path)| *owner == ConflictOwner::GameShaderFixes || path_is_active(path))
+        .count();
+    let mut mod_paths: Vec<_> = owners
+        .into_iter()
+        .filter(|(owner, _)| *owner == ConflictOwner::Mod)
+        .map(|(_, path)| path)
+        .collect();
     mod_paths.sort();
 
     let first = involved.first()?;
@@ -146,8 +181,8 @@ fn build_conflict(entries: Vec<HashEntry>) -> Option<ConflictInfo> {
     Some(ConflictInfo {
         hash: first.hash.clone(),
         section_name: first.evidence.section_name.clone(),
-        is_active: mod_paths.iter().filter(|path| path_is_active(path)).count() >= 2,
-        kind: first.kind,
+        is_active: active_owners >= 2,
+        kind,
         certainty: if evidence.iter().any(|item| item.condition.is_some()) {
             ConflictCertainty::Potential
         } else {
@@ -156,9 +191,19 @@ fn build_conflict(entries: Vec<HashEntry>) -> Option<ConflictInfo> {
         has_conditional_evidence: evidence.iter().any(|item| item.condition.is_some()),
         mod_paths,
         evidence,
+        resolution: None,
     })
 }
 
+/// Every replacement carries a body hash and they all agree.
+fn same_body(entries: &[&HashEntry]) -> bool {
+    let bodies: HashSet<_> = entries
+        .iter()
+        .map(|entry| entry.evidence.content_hash.as_deref())
+        .collect();
+    bodies.len() == 1 && !bodies.contains(&None)
+}
+
 fn indices_can_overlap(left: Option<u32>, right: Option<u32>) -> bool {
     left.is_none() || right.is_none() || left == right
 }
@@ -178,26 +223,18 @@ fn parse_ini_hashes(ini_path: &Path, mod_root: &Path) -> Vec<HashEntry> {
         }
     };
     let (content, _, _) = crate::services::ini::document::decode_ini_bytes(&bytes);
-    let namespace = content
-        .lines()
-        .find_map(|line| property(line, "namespace").map(str::to_string));
+    let ast = parse_ini_text(&content);
 
     let mut entries = Vec::new();
-    let mut section: Option<SectionEvidence> = None;
-    for line in content.lines() {
-        if let Some(name) = section_name(line) {
-            if let Some(previous) = section.take() {
-                entries.extend(previous.finish(ini_path, mod_root, namespace.as_deref()));
-            }
-            section = section_kind(&name).map(|kind| SectionEvidence::new(kind, name));
+    for section in &ast.sections {
+        let Some(kind) = conflict_kind(section.kind) else {
             continue;
+        };
+        let mut evidence = SectionEvidence::new(kind, section.name.clone());
+        for assignment in section.assignments() {
+            evidence.observe(assignment);
         }
-        if let Some(current) = section.as_mut() {
-            current.observe(line);
-        }
-    }
-    if let Some(last) = section {
-        entries.extend(last.finish(ini_path, mod_root, namespace.as_deref()));
+        entries.extend(evidence.finish(ini_path, mod_root, ast.namespace.as_deref()));
     }
     entries
 }
@@ -223,19 +260,22 @@ impl SectionEvidence {
         }
     }
 
-    fn observe(&mut self, line: &str) {
-        if let Some(value) = property(line, "hash").and_then(normalize_hash) {
-            self.hashes.push(value);
+    fn observe(&mut self, assignment: &Assignment) {
+        let value = assignment.value.as_str();
+        if value.is_empty() {
+            return;
         }
-        if let Some(value) = property(line, "condition") {
+        if assignment.key_is("hash") {
+            if let Some(hash) = normalize_hash(value) {
+                self.hashes.push(hash);
+            }
+        } else if assignment.key_is("condition") {
             self.condition = Some(value.to_string());
+        } else if assignment.key_is("priority") {
+            self.priority = value.parse().ok().or(self.priority);
+        } else if assignment.key_is("match_first_index") {
+            self.match_first_index = parse_u32(value).or(self.match_first_index);
         }
-        self.priority = property(line, "priority")
-            .and_then(|value| value.parse().ok())
-            .or(self.priority);
-        self.match_first_index = property(line, "match_first_index")
-            .and_then(parse_u32)
-            .or(self.match_first_index);
     }
 
     fn finish(self, ini_path: &Path, mod_root: &Path, namespace: Option<&str>) -> Vec<HashEntry> {
@@ -246,6 +286,7 @@ impl SectionEvidence {
                 hash,
                 evidence: ConflictEvidence {
                     mod_path: mod_root.to_string_lossy().to_string(),
+                    owner: ConflictOwner::Mod,
                     source_path: ini_path.to_string_lossy().to_string(),
                     section_name: self.name.clone(),
                     namespace: namespace.map(str::to_string),
@@ -253,36 +294,20 @@ impl SectionEvidence {
                     priority: self.priority,
                     match_first_index: self.match_first_index,
                     shader_stage: None,
+                    content_hash: None,
                 },
             })
             .collect()
     }
 }
 
-fn section_name(line: &str) -> Option<String> {
-    let trimmed = line.trim();
-    let name = trimmed.strip_prefix('[')?.split_once(']')?.0.trim();
-    (!name.is_empty()).then(|| name.to_string())
-}
-
-fn section_kind(name: &str) -> Option<ConflictKind> {
-    let lower = name.to_ascii_lowercase();
-    if lower.starts_with("textureoverride") {
-        return Some(ConflictKind::ResourceHash);
-    }
-    if lower.starts_with("shaderoverride") {
-        return Some(ConflictKind::ShaderHash);
+/// The conflict a hash in a section of `kind` can take part in.
+pub(crate) fn conflict_kind(kind: SectionKind) -> Option<ConflictKind> {
+    match kind {
+        SectionKind::TextureOverride => Some(ConflictKind::ResourceHash),
+        SectionKind::ShaderOverride => Some(ConflictKind::ShaderHash),
+        _ => None,
     }
-    None
-}
-
-fn property<'a>(line: &'a str, expected: &str) -> Option<&'a str> {
-    let code = line.split(';').next()?.trim();
-    let (key, value) = code.split_once('=')?;
-    key.trim()
-        .eq_ignore_ascii_case(expected)
-        .then(|| value.trim())
-        .filter(|value| !value.is_empty())
 }
 
 fn normalize_hash(value: &str) -> Option<String> {
@@ -301,48 +326,6 @@ fn parse_u32(value: &str) -> Option<u32> {
         )
 }
 
-fn scan_shader_replacements(mod_root: &Path) -> Vec<HashEntry> {
-    WalkDir::new(mod_root)
-        .max_depth(8)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| entry.file_type().is_file())
-        .filter_map(|entry| replacement_entry(entry.path(), mod_root))
-        .collect()
-}
-
-fn replacement_entry(path: &Path, mod_root: &Path) -> Option<HashEntry> {
-    let extension = path.extension()?.to_str()?;
-    if !matches!(extension.to_ascii_lowercase().as_str(), "txt" | "bin") {
-        return None;
-    }
-    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
-    let (hash, suffix) = stem.split_once('-')?;
-    let stage = suffix.strip_suffix("_replace")?;
-    if hash.len() != 16
-        || !hash.chars().all(|character| character.is_ascii_hexdigit())
-        || !matches!(stage, "ps" | "vs" | "cs" | "gs" | "hs" | "ds")
-    {
-        return None;
-    }
-
-    Some(HashEntry {
-        kind: ConflictKind::ShaderReplacement,
-        hash: hash.to_string(),
-        evidence: ConflictEvidence {
-            mod_path: mod_root.to_string_lossy().to_string(),
-            source_path: path.to_string_lossy().to_string(),
-            section_name: path.file_name()?.to_string_lossy().to_string(),
-            namespace: None,
-            condition: None,
-            priority: None,
-            match_first_index: None,
-            shader_stage: Some(stage.to_string()),
-        },
-    })
-}
-
 #[cfg(test)]
 #[path = "tests/conflict_tests.rs"]
 mod tests;
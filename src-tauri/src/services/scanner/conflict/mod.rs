@@ -4,12 +4,19 @@
 //! - `hash_scan` — filesystem: parse `.ini` for `[TextureOverride*]` hashes and
 //!   report mods that share one.
 //! - `duplicates` — database: find and resolve competing mods for one object.
+//! - `resolution` — settle a hash conflict by picking a winning mod.
+//! - `enforce` — carry stored decisions out again after an apply or rescan.
+//! - `shader_fixes` — filesystem: `*_replace` shader files in mods and in the
+//!   game's own `ShaderFixes/`.
 //!
 //! # Covers: US-2.Z, TC-2.4-01
 
 pub mod detect;
 pub mod duplicates;
+pub mod enforce;
 pub mod hash_scan;
+pub mod resolution;
+pub mod shader_fixes;
 
 pub use duplicates::*;
 pub use hash_scan::*;
@@ -0,0 +1,271 @@
+//! Settling a hash conflict in favour of one mod.
+//!
+//! The losers are either disabled through the regular toggle path, or keep
+//! running with their override sections demoted by a `match_priority` line.
+//! The decision is stored in `conflict_resolutions` keyed by hash, together
+//! with the mods it was made between, so a rescan or a collection apply that
+//! brings the same conflict back still knows which mod the user picked (see
+//! `enforce`). A conflict whose mods changed is open again.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use sqlx::SqlitePool;
+
+use crate::domain::conflicts::{ConflictKind, ConflictResolution, ConflictResolutionStrategy};
+use crate::domain::errors::AppError;
+use crate::services::fs_utils::guard::ValidatedPath;
+use crate::services::fs_utils::operation_lock::OpGuard;
+use crate::services::ini::ast::parse_ini_ast;
+use crate::services::ini::document::{read_ini_document, IniDocument};
+use crate::services::ini::edit::IniEdit;
+use crate::services::ini::write::save_ini_batch_with_edits;
+use crate::services::mods::core_ops::toggle_mod_inner;
+use crate::services::scanner::conflict::ConflictInfo;
+use crate::services::scanner::watcher::WatcherState;
+
+/// Written into every losing override section. 3DMigoto picks the highest
+/// `match_priority` among sections matching the same hash; unset is 0.
+pub const LOSER_MATCH_PRIORITY: &str = "-1";
+
+pub struct ResolveHashConflictRequest<'a> {
+    pub pool: &'a SqlitePool,
+    pub state: &'a WatcherState,
+    pub op_guard: &'a OpGuard,
+    pub game_id: &'a str,
+    pub hash: &'a str,
+    pub kind: ConflictKind,
+    pub winner: &'a ValidatedPath,
+    pub strategy: ConflictResolutionStrategy,
+}
+
+/// The stored decision plus every path the resolution touched on disk, for
+/// the caller's reconcile.
+#[derive(Debug)]
+pub struct HashConflictOutcome {
+    pub resolution: ConflictResolution,
+    pub changed_paths: Vec<String>,
+}
+
+/// `(path, mod id)` of every mod in `conflict`, or `None` while one of them
+/// is not indexed.
+pub(super) async fn conflict_mod_ids(
+    pool: &SqlitePool,
+    game_id: &str,
+    conflict: &ConflictInfo,
+) -> Result<Option<Vec<(String, String)>>, AppError> {
+    let mut ids = Vec::with_capacity(conflict.mod_paths.len());
+    for path in &conflict.mod_paths {
+        match crate::repo::mod_repo::get_mod_id_and_status_by_path(pool, path, game_id).await? {
+            Some((id, _, _)) => ids.push((path.clone(), id)),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(ids))
+}
+
+/// Attaches the stored decisions to freshly detected conflicts. A decision
+/// only holds for the exact mods it was made between: one more or one fewer
+/// and the conflict is reported unresolved.
+pub async fn annotate_resolutions(
+    pool: &SqlitePool,
+    game_id: &str,
+    conflicts: &mut [ConflictInfo],
+) -> Result<(), AppError> {
+    let resolutions = crate::repo::conflict_repo::list_conflict_resolutions(pool, game_id).await?;
+    if resolutions.is_empty() {
+        return Ok(());
+    }
+    for conflict in conflicts.iter_mut() {
+        let Some(stored) = resolutions.iter().find(|stored| {
+            stored.kind == conflict.kind && stored.hash.eq_ignore_ascii_case(&conflict.hash)
+        }) else {
+            continue;
+        };
+        let decided: BTreeSet<&str> = std::iter::once(stored.winner_mod_id.as_str())
+            .chain(stored.loser_mod_ids.iter().map(String::as_str))
+            .collect();
+        let current = conflict_mod_ids(pool, game_id, conflict).await?;
+        let same_mods = current.is_some_and(|ids| {
+            ids.iter()
+                .map(|(_, id)| id.as_str())
+                .collect::<BTreeSet<_>>()
+                == decided
+        });
+        conflict.resolution = same_mods.then(|| stored.clone());
+    }
+    Ok(())
+}
+
+fn same_folder(reported: &str, winner: &Path) -> bool {
+    std::fs::canonicalize(reported).is_ok_and(|canonical| canonical == winner)
+}
+
+/// `SetKey` edits demoting every section a losing mod contributed to the
+/// conflict, grouped by INI file.
+pub fn match_priority_edits(
+    conflict: &ConflictInfo,
+    loser_paths: &[String],
+) -> BTreeMap<PathBuf, Vec<IniEdit>> {
+    let mut edits: BTreeMap<PathBuf, Vec<IniEdit>> = BTreeMap::new();
+    for evidence in &conflict.evidence {
+        if !loser_paths.contains(&evidence.mod_path) {
+            continue;
+        }
+        let edit = IniEdit::SetKey {
+            section: evidence.section_name.clone(),
+            key: "match_priority".to_string(),
+            value: LOSER_MATCH_PRIORITY.to_string(),
+        };
+        let file_edits = edits
+            .entry(PathBuf::from(&evidence.source_path))
+            .or_default();
+        if !file_edits.contains(&edit) {
+            file_edits.push(edit);
+        }
+    }
+    edits
+}
+
+/// [`match_priority_edits`] read against the INIs on disk, without the
+/// sections already demoted.
+pub(super) fn pending_priority_edits(
+    conflict: &ConflictInfo,
+    loser_paths: &[String],
+) -> Result<Vec<(IniDocument, Vec<IniEdit>)>, AppError> {
+    let mut batch = Vec::new();
+    for (ini_path, edits) in match_priority_edits(conflict, loser_paths) {
+        let document = read_ini_document(&ini_path)?;
+        let ast = parse_ini_ast(&document.raw_lines);
+        let pending: Vec<IniEdit> = edits
+            .into_iter()
+            .filter(|edit| {
+                let IniEdit::SetKey { section, .. } = edit else {
+                    return true;
+                };
+                !ast.sections
+                    .iter()
+                    .filter(|candidate| candidate.name.eq_ignore_ascii_case(section))
+                    .flat_map(|candidate| candidate.assignments())
+                    .any(|assignment| {
+                        assignment.key_is("match_priority")
+                            && assignment.value == LOSER_MATCH_PRIORITY
+                    })
+            })
+            .collect();
+        if !pending.is_empty() {
+            batch.push((document, pending));
+        }
+    }
+    Ok(batch)
+}
+
+async fn mod_id_for(pool: &SqlitePool, game_id: &str, path: &str) -> Result<String, AppError> {
+    crate::repo::mod_repo::get_mod_id_and_status_by_path(pool, path, game_id)
+        .await?
+        .map(|(id, _, _)| id)
+        .ok_or_else(|| AppError::NotFound(format!("Mod is not indexed yet: {path}")))
+}
+
+/// Picks `winner` for the conflict on `hash`, applies the strategy to the
+/// other mods and stores the decision.
+pub async fn resolve_hash_conflict_service(
+    request: ResolveHashConflictRequest<'_>,
+) -> Result<HashConflictOutcome, AppError> {
+    let ResolveHashConflictRequest {
+        pool,
+        state,
+        op_guard: _,
+        game_id,
+        hash,
+        kind,
+        winner,
+        strategy,
+    } = request;
+
+    // `match_priority` only orders `TextureOverride` sections; 3DMigoto
+    // ignores it on shader overrides and replacements.
+    if strategy == ConflictResolutionStrategy::MatchPriority && kind != ConflictKind::ResourceHash {
+        return Err(AppError::Validation(
+            "Only texture overrides can be demoted with match_priority; disable the losers instead"
+                .to_string(),
+        ));
+    }
+
+    let conflict = crate::services::mods::metadata::get_active_mod_conflicts(pool, game_id)
+        .await?
+        .into_iter()
+        .find(|conflict| conflict.kind == kind && conflict.hash.eq_ignore_ascii_case(hash))
+        .ok_or_else(|| AppError::NotFound(format!("No active conflict on hash {hash}")))?;
+
+    let winner_path = conflict
+        .mod_paths
+        .iter()
+        .find(|path| same_folder(path, winner))
+        .cloned()
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "{} is not part of the conflict on hash {hash}",
+                winner.original()
+            ))
+        })?;
+    let loser_paths: Vec<String> = conflict
+        .mod_paths
+        .iter()
+        .filter(|path| **path != winner_path)
+        .cloned()
+        .collect();
+    if loser_paths.is_empty() {
+        // The only other side is a loose file in the game's ShaderFixes.
+        return Err(AppError::Validation(format!(
+            "The conflict on hash {hash} is with the game's ShaderFixes folder, not another mod"
+        )));
+    }
+
+    let winner_mod_id = mod_id_for(pool, game_id, &winner_path).await?;
+    let mut loser_mod_ids = Vec::with_capacity(loser_paths.len());
+    for path in &loser_paths {
+        loser_mod_ids.push(mod_id_for(pool, game_id, path).await?);
+    }
+
+    let mut changed_paths = Vec::new();
+    match strategy {
+        ConflictResolutionStrategy::DisableLosers => {
+            for path in &loser_paths {
+                let disabled = toggle_mod_inner(state, path.clone(), false).await?;
+                changed_paths.push(path.clone());
+                changed_paths.push(disabled);
+            }
+        }
+        ConflictResolutionStrategy::MatchPriority => {
+            // All losers' INIs change together or not at all.
+            let batch = pending_priority_edits(&conflict, &loser_paths)?;
+            save_ini_batch_with_edits(&batch)?;
+            changed_paths.extend(
+                batch
+                    .iter()
+                    .map(|(document, _)| document.file_path.to_string_lossy().to_string()),
+            );
+        }
+    }
+
+    let resolution = crate::repo::conflict_repo::upsert_conflict_resolution(
+        pool,
+        game_id,
+        &conflict.hash,
+        kind,
+        strategy,
+        &winner_mod_id,
+        &loser_mod_ids,
+    )
+    .await?;
+
+    Ok(HashConflictOutcome {
+        resolution,
+        changed_paths,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/resolution_tests.rs"]
+mod tests;
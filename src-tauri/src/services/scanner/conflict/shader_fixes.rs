@@ -0,0 +1,122 @@
+//! `*-ps_replace.txt` / `.bin` shader replacements, inside mods and in the
+//! game's own `ShaderFixes/` folder next to `d3dx.ini`.
+//!
+//! 3DMigoto loads every replacement in the game's `ShaderFixes/` regardless of
+//! which mods are enabled, so a loose file there silently competes with any
+//! mod touching the same shader.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sqlx::SqlitePool;
+use walkdir::WalkDir;
+
+use super::hash_scan::{ConflictEvidence, ConflictKind, ConflictOwner, HashEntry};
+use crate::domain::errors::AppError;
+
+const SHADER_FIXES_DIR: &str = "ShaderFixes";
+
+/// Every replacement file anywhere inside a mod folder.
+pub(super) fn scan_shader_replacements(mod_root: &Path) -> Vec<HashEntry> {
+    WalkDir::new(mod_root)
+        .max_depth(8)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| replacement_entry(entry.path(), mod_root, ConflictOwner::Mod))
+        .collect()
+}
+
+/// Replacement files directly in the game's `ShaderFixes/`; 3DMigoto does not
+/// descend into subfolders there.
+pub(super) fn scan_loose_replacements(shader_fixes: &Path) -> Vec<HashEntry> {
+    let Ok(entries) = fs::read_dir(shader_fixes) else {
+        return Vec::new();
+    };
+    let mut found: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .filter_map(|entry| {
+            replacement_entry(&entry.path(), shader_fixes, ConflictOwner::GameShaderFixes)
+        })
+        .collect();
+    found.sort_by(|left, right| left.evidence.source_path.cmp(&right.evidence.source_path));
+    found
+}
+
+fn replacement_entry(path: &Path, folder: &Path, owner: ConflictOwner) -> Option<HashEntry> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !matches!(extension.as_str(), "txt" | "bin") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let (hash, suffix) = stem.split_once('-')?;
+    let stage = suffix.strip_suffix("_replace")?;
+    if hash.len() != 16
+        || !hash.chars().all(|character| character.is_ascii_hexdigit())
+        || !matches!(stage, "ps" | "vs" | "cs" | "gs" | "hs" | "ds")
+    {
+        return None;
+    }
+
+    Some(HashEntry {
+        kind: ConflictKind::ShaderReplacement,
+        hash: hash.to_string(),
+        evidence: ConflictEvidence {
+            mod_path: folder.to_string_lossy().to_string(),
+            owner,
+            source_path: path.to_string_lossy().to_string(),
+            section_name: path.file_name()?.to_string_lossy().to_string(),
+            namespace: None,
+            condition: None,
+            priority: None,
+            match_first_index: None,
+            shader_stage: Some(stage.to_string()),
+            content_hash: body_hash(path, extension == "txt"),
+        },
+    })
+}
+
+/// Hash of a replacement's body. HLSL is compared line by line with trailing
+/// whitespace and blank lines dropped, so a CRLF re-save of the same fix does
+/// not read as a different shader; compiled `.bin` files compare raw.
+fn body_hash(path: &Path, is_hlsl: bool) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    let hash = if is_hlsl {
+        let text = String::from_utf8_lossy(&bytes);
+        let mut hasher = blake3::Hasher::new();
+        for line in text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+        {
+            hasher.update(line.as_bytes());
+            hasher.update(b"\n");
+        }
+        hasher.finalize()
+    } else {
+        blake3::hash(&bytes)
+    };
+    Some(hash.to_hex().to_string())
+}
+
+/// `ShaderFixes/` beside the `d3dx.ini` that loads `mods_root`.
+pub fn shader_fixes_dir(mods_root: &Path, game_exe: Option<&Path>) -> Option<PathBuf> {
+    crate::services::game::validator::importer_dir(mods_root, game_exe)
+        .map(|dir| dir.join(SHADER_FIXES_DIR))
+        .filter(|dir| dir.is_dir())
+}
+
+/// The game's `ShaderFixes/` folder, when it has one.
+pub async fn game_shader_fixes_dir(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+) -> Result<Option<PathBuf>, AppError> {
+    let game_exe = crate::repo::game_repo::get_game_exe(pool, game_id).await?;
+    Ok(shader_fixes_dir(
+        mods_root,
+        game_exe.as_deref().map(Path::new),
+    ))
+}
@@ -267,3 +267,82 @@ fn test_multiple_conflicting_hashes_each_produce_a_conflict() {
         assert_eq!(c.mod_paths.len(), 2);
     }
 }
+
+#[test]
+fn identical_shader_replacements_are_not_a_conflict() {
+    let dir = TempDir::new().unwrap();
+    let mod_a = dir.path().join("ModA");
+    let mod_b = dir.path().join("ModB");
+    fs::create_dir_all(mod_a.join("ShaderFixes")).unwrap();
+    fs::create_dir_all(mod_b.join("ShaderFixes")).unwrap();
+    let filename = "0123456789abcdef-ps_replace.txt";
+    fs::write(mod_a.join("ShaderFixes").join(filename), "o0 = 1;\n").unwrap();
+    fs::write(
+        mod_b.join("ShaderFixes").join(filename),
+        "o0 = 1;  \r\n\r\n",
+    )
+    .unwrap();
+
+    let conflicts = detect_conflicts_with_roots(&[], &[mod_a, mod_b]);
+
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn game_shader_fixes_competes_with_mod_replacement_and_override() {
+    let dir = TempDir::new().unwrap();
+    let shader_fixes = dir.path().join("ShaderFixes");
+    let mod_a = dir.path().join("Mods/ModA");
+    let mod_b = dir.path().join("Mods/ModB");
+    fs::create_dir_all(&shader_fixes).unwrap();
+    fs::create_dir_all(mod_a.join("ShaderFixes")).unwrap();
+    fs::create_dir_all(&mod_b).unwrap();
+    let filename = "0123456789abcdef-ps_replace.txt";
+    fs::write(shader_fixes.join(filename), "loose").unwrap();
+    fs::write(mod_a.join("ShaderFixes").join(filename), "mod").unwrap();
+    let ini_b = create_ini(
+        &mod_b,
+        "b.ini",
+        "[ShaderOverrideGlow]\nhash = 0123456789abcdef\n",
+    );
+
+    let conflicts = detect_conflicts_with_shader_fixes(
+        &[(mod_b.clone(), ini_b)],
+        &[mod_a.clone(), mod_b.clone()],
+        Some(&shader_fixes),
+    );
+
+    assert_eq!(conflicts.len(), 2);
+    for (kind, mod_root) in [
+        (ConflictKind::ShaderReplacement, &mod_a),
+        (ConflictKind::ShaderHash, &mod_b),
+    ] {
+        let conflict = conflicts.iter().find(|c| c.kind == kind).unwrap();
+        // The loose file is evidence, not a mod anything can be done to.
+        assert_eq!(conflict.mod_paths, [mod_root.to_string_lossy()]);
+        assert!(conflict.is_active);
+        assert!(conflict.evidence.iter().any(|item| {
+            item.owner == ConflictOwner::GameShaderFixes
+                && item.mod_path == shader_fixes.to_string_lossy()
+        }));
+    }
+}
+
+#[test]
+fn shader_fixes_dir_sits_beside_d3dx_ini() {
+    let dir = TempDir::new().unwrap();
+    let mods_root = dir.path().join("Mods");
+    fs::create_dir_all(&mods_root).unwrap();
+    fs::create_dir_all(dir.path().join("ShaderFixes")).unwrap();
+
+    assert_eq!(
+        crate::services::scanner::conflict::shader_fixes::shader_fixes_dir(&mods_root, None),
+        None
+    );
+
+    fs::write(dir.path().join("d3dx.ini"), "[Loader]").unwrap();
+    assert_eq!(
+        crate::services::scanner::conflict::shader_fixes::shader_fixes_dir(&mods_root, None),
+        Some(dir.path().join("ShaderFixes"))
+    );
+}
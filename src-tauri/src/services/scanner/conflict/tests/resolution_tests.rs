@@ -0,0 +1,329 @@
+use super::*;
+use crate::domain::models::{GameType, ItemStatus};
+use crate::services::config::ConfigService;
+use crate::services::fs_utils::guard::validate_path;
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::test_utils::{
+    init_test_db, insert_test_game, insert_test_mod, TestGameFixture, TestModFixture,
+};
+use std::fs;
+use tempfile::TempDir;
+
+const SHARED_INI: &str = "[TextureOverrideBody]\nhash = 0a1b2c3d\nib = Body\n";
+
+struct Fixture {
+    _mods: TempDir,
+    mods_root: PathBuf,
+    pool: SqlitePool,
+    config: ConfigService,
+}
+
+async fn two_mods_sharing_a_hash() -> Fixture {
+    let mods = TempDir::new().unwrap();
+    let mods_root = fs::canonicalize(mods.path()).unwrap();
+    let mods_root_str = mods_root.to_string_lossy().to_string();
+    let pool = init_test_db().await.pool;
+    insert_test_game(
+        &pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some(&mods_root_str),
+        },
+    )
+    .await
+    .unwrap();
+    for name in ["Alpha", "Beta"] {
+        fs::create_dir(mods_root.join(name)).unwrap();
+        fs::write(mods_root.join(name).join("mod.ini"), SHARED_INI).unwrap();
+        insert_test_mod(
+            &pool,
+            &TestModFixture {
+                id: name,
+                game_id: "g1",
+                object_id: None,
+                actual_name: name,
+                folder_path: name,
+                status: ItemStatus::Enabled,
+                is_safe: true,
+                object_type: None,
+                mods_path: Some(&mods_root_str),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let config = ConfigService::new_for_test_async(pool.clone()).await;
+    Fixture {
+        _mods: mods,
+        mods_root,
+        pool,
+        config,
+    }
+}
+
+async fn resolve(
+    fixture: &Fixture,
+    strategy: ConflictResolutionStrategy,
+) -> Result<HashConflictOutcome, AppError> {
+    resolve_as(fixture, ConflictKind::ResourceHash, strategy).await
+}
+
+async fn resolve_as(
+    fixture: &Fixture,
+    kind: ConflictKind,
+    strategy: ConflictResolutionStrategy,
+) -> Result<HashConflictOutcome, AppError> {
+    let winner = validate_path(
+        &fixture.config,
+        "g1",
+        &fixture.mods_root.join("Alpha").to_string_lossy(),
+    )
+    .unwrap();
+    let lock = OperationLock::new();
+    let op_guard = lock.acquire().await.unwrap();
+    let state = WatcherState::new();
+    resolve_hash_conflict_service(ResolveHashConflictRequest {
+        pool: &fixture.pool,
+        state: &state,
+        op_guard: &op_guard,
+        game_id: "g1",
+        hash: "0A1B2C3D",
+        kind,
+        winner: &winner,
+        strategy,
+    })
+    .await
+}
+
+#[tokio::test]
+async fn match_priority_demotes_the_loser_and_is_reported_on_the_conflict() {
+    let fixture = two_mods_sharing_a_hash().await;
+
+    let outcome = resolve(&fixture, ConflictResolutionStrategy::MatchPriority)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.resolution.winner_mod_id, "Alpha");
+    assert_eq!(outcome.resolution.loser_mod_ids, vec!["Beta".to_string()]);
+    assert_eq!(
+        fs::read_to_string(fixture.mods_root.join("Beta/mod.ini")).unwrap(),
+        "[TextureOverrideBody]\nhash = 0a1b2c3d\nib = Body\nmatch_priority = -1\n"
+    );
+    assert_eq!(
+        fs::read_to_string(fixture.mods_root.join("Alpha/mod.ini")).unwrap(),
+        SHARED_INI
+    );
+
+    let conflicts = crate::services::mods::metadata::get_active_mod_conflicts(&fixture.pool, "g1")
+        .await
+        .unwrap();
+    assert_eq!(conflicts.len(), 1);
+    let stored = conflicts[0].resolution.as_ref().unwrap();
+    assert_eq!(stored.strategy, ConflictResolutionStrategy::MatchPriority);
+}
+
+#[tokio::test]
+async fn disable_losers_renames_the_other_mods_off() {
+    let fixture = two_mods_sharing_a_hash().await;
+
+    let outcome = resolve(&fixture, ConflictResolutionStrategy::DisableLosers)
+        .await
+        .unwrap();
+
+    assert!(fixture.mods_root.join("Alpha").is_dir());
+    assert!(!fixture.mods_root.join("Beta").exists());
+    assert!(outcome
+        .changed_paths
+        .iter()
+        .any(|path| path.ends_with("DISABLED Beta")));
+    let stored = crate::repo::conflict_repo::list_conflict_resolutions(&fixture.pool, "g1")
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].hash, "0a1b2c3d");
+}
+
+#[tokio::test]
+async fn a_winner_outside_the_conflict_is_rejected() {
+    let fixture = two_mods_sharing_a_hash().await;
+    fs::write(
+        fixture.mods_root.join("Beta/mod.ini"),
+        "[TextureOverrideBody]\nhash = ffffffff\n",
+    )
+    .unwrap();
+
+    let error = resolve(&fixture, ConflictResolutionStrategy::DisableLosers)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, AppError::NotFound(_)));
+    assert!(fixture.mods_root.join("Beta").is_dir());
+}
+
+#[tokio::test]
+async fn match_priority_is_refused_for_shader_conflicts() {
+    let fixture = two_mods_sharing_a_hash().await;
+
+    let error = resolve_as(
+        &fixture,
+        ConflictKind::ShaderHash,
+        ConflictResolutionStrategy::MatchPriority,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(error, AppError::Validation(_)));
+    assert_eq!(
+        fs::read_to_string(fixture.mods_root.join("Beta/mod.ini")).unwrap(),
+        SHARED_INI
+    );
+}
+
+#[tokio::test]
+async fn a_decision_no_longer_holds_once_another_mod_joins_the_conflict() {
+    let fixture = two_mods_sharing_a_hash().await;
+    resolve(&fixture, ConflictResolutionStrategy::MatchPriority)
+        .await
+        .unwrap();
+
+    fs::create_dir(fixture.mods_root.join("Gamma")).unwrap();
+    fs::write(fixture.mods_root.join("Gamma/mod.ini"), SHARED_INI).unwrap();
+    let mods_root_str = fixture.mods_root.to_string_lossy().to_string();
+    insert_test_mod(
+        &fixture.pool,
+        &TestModFixture {
+            id: "Gamma",
+            game_id: "g1",
+            object_id: None,
+            actual_name: "Gamma",
+            folder_path: "Gamma",
+            status: ItemStatus::Enabled,
+            is_safe: true,
+            object_type: None,
+            mods_path: Some(&mods_root_str),
+        },
+    )
+    .await
+    .unwrap();
+
+    let conflicts = crate::services::mods::metadata::get_active_mod_conflicts(&fixture.pool, "g1")
+        .await
+        .unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].mod_paths.len(), 3);
+    assert!(conflicts[0].resolution.is_none());
+}
+
+#[tokio::test]
+async fn enforcing_demotes_a_reinstalled_loser_again() {
+    let fixture = two_mods_sharing_a_hash().await;
+    resolve(&fixture, ConflictResolutionStrategy::MatchPriority)
+        .await
+        .unwrap();
+    // A reinstall brings the loser back without its match_priority line.
+    fs::write(fixture.mods_root.join("Beta/mod.ini"), SHARED_INI).unwrap();
+
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.unwrap();
+    let changed = crate::services::scanner::conflict::enforce::enforce_resolutions(
+        &op_guard,
+        &fixture.pool,
+        "g1",
+        &fixture.mods_root,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(changed.len(), 1);
+    assert!(changed[0].ends_with("mod.ini"));
+    assert_eq!(
+        fs::read_to_string(fixture.mods_root.join("Beta/mod.ini")).unwrap(),
+        "[TextureOverrideBody]\nhash = 0a1b2c3d\nib = Body\nmatch_priority = -1\n"
+    );
+    // Already demoted: nothing left to do.
+    assert!(
+        crate::services::scanner::conflict::enforce::enforce_resolutions(
+            &op_guard,
+            &fixture.pool,
+            "g1",
+            &fixture.mods_root,
+        )
+        .await
+        .unwrap()
+        .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn a_conflict_with_the_game_shader_fixes_has_nothing_to_resolve() {
+    let root = TempDir::new().unwrap();
+    let root_path = fs::canonicalize(root.path()).unwrap();
+    let mods_root = root_path.join("Mods");
+    fs::create_dir_all(mods_root.join("Alpha")).unwrap();
+    fs::create_dir_all(root_path.join("ShaderFixes")).unwrap();
+    fs::write(root_path.join("d3dx.ini"), "").unwrap();
+    fs::write(
+        root_path.join("ShaderFixes/0123456789abcdef-ps_replace.txt"),
+        "o0 = 1;\n",
+    )
+    .unwrap();
+    fs::write(
+        mods_root.join("Alpha/mod.ini"),
+        "[ShaderOverrideGlow]\nhash = 0123456789abcdef\n",
+    )
+    .unwrap();
+    let mods_root_str = mods_root.to_string_lossy().to_string();
+    let pool = init_test_db().await.pool;
+    insert_test_game(
+        &pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some(&mods_root_str),
+        },
+    )
+    .await
+    .unwrap();
+    insert_test_mod(
+        &pool,
+        &TestModFixture {
+            id: "Alpha",
+            game_id: "g1",
+            object_id: None,
+            actual_name: "Alpha",
+            folder_path: "Alpha",
+            status: ItemStatus::Enabled,
+            is_safe: true,
+            object_type: None,
+            mods_path: Some(&mods_root_str),
+        },
+    )
+    .await
+    .unwrap();
+    let config = ConfigService::new_for_test_async(pool.clone()).await;
+    let winner = validate_path(&config, "g1", &mods_root.join("Alpha").to_string_lossy()).unwrap();
+    let lock = OperationLock::new();
+    let op_guard = lock.acquire().await.unwrap();
+
+    let error = resolve_hash_conflict_service(ResolveHashConflictRequest {
+        pool: &pool,
+        state: &WatcherState::new(),
+        op_guard: &op_guard,
+        game_id: "g1",
+        hash: "0123456789abcdef",
+        kind: ConflictKind::ShaderHash,
+        winner: &winner,
+        strategy: ConflictResolutionStrategy::DisableLosers,
+    })
+    .await
+    .unwrap_err();
+
+    assert!(matches!(error, AppError::Validation(_)));
+    assert!(mods_root.join("Alpha").is_dir());
+    assert!(root_path.join("ShaderFixes").is_dir());
+}
@@ -68,6 +68,7 @@ pub fn match_status_label(status: &MatchStatus) -> &'static str {
         MatchStatus::AutoMatched => "AutoMatched",
         MatchStatus::NeedsReview => "NeedsReview",
         MatchStatus::NoMatch => "NoMatch",
+        MatchStatus::MultiObjectPack => "MultiObjectPack",
     }
 }
 
@@ -80,7 +81,7 @@ pub fn staged_confidence_label(result: &StagedMatchResult) -> &'static str {
             .map(|candidate| confidence_value_label(&candidate.confidence))
             .unwrap_or("High"),
         MatchStatus::NeedsReview => "Low",
-        MatchStatus::NoMatch => "None",
+        MatchStatus::NoMatch | MatchStatus::MultiObjectPack => "None",
     }
 }
 
@@ -90,7 +91,8 @@ pub fn staged_primary_candidate(result: &StagedMatchResult) -> Option<&Candidate
             .best
             .as_ref()
             .or_else(|| result.candidates_topk.first()),
-        MatchStatus::NoMatch => None,
+        // A pack has no single object to file the folder under.
+        MatchStatus::NoMatch | MatchStatus::MultiObjectPack => None,
     }
 }
 
@@ -0,0 +1,177 @@
+//! Collapsing the identical files found by `blob_store` into hardlinks.
+
+use crate::domain::errors::{AppError, ScannerError};
+use crate::repo::blob_repo::{self, BlobRow};
+use crate::services::scanner::watcher::{SuppressionGuard, WatcherSuppressor};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::blob_store::{copy_indices, file_stamp, sync_blob_store};
+use super::hashing::full_blake3_hash;
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkAllSummary {
+    #[specta(type = f64)]
+    pub total: usize,
+    #[specta(type = f64)]
+    pub linked: usize,
+    #[specta(type = f64)]
+    pub failed: usize,
+    #[specta(type = f64)]
+    pub reclaimed_bytes: u64,
+    pub errors: Vec<HardlinkError>,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkError {
+    pub file_path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkProgress {
+    #[specta(type = f64)]
+    pub current: usize,
+    #[specta(type = f64)]
+    pub total: usize,
+    pub file_path: String,
+}
+
+/// Replaces every duplicate physical copy with a hardlink to the first copy
+/// of its group. Callers hold the `OperationLock`; the watcher is suppressed
+/// for the whole job because its write set spans every game.
+pub async fn hardlink_all_duplicates<F>(
+    pool: &SqlitePool,
+    _op_guard: &crate::services::fs_utils::operation_lock::OpGuard,
+    watcher_suppressor: &Arc<WatcherSuppressor>,
+    mut on_progress: F,
+) -> Result<HardlinkAllSummary, AppError>
+where
+    F: FnMut(HardlinkProgress),
+{
+    sync_blob_store(pool).await?;
+    let rows = blob_repo::list_duplicate_blobs(pool).await?;
+    let targets = tokio::task::spawn_blocking(move || link_targets(rows)).await?;
+
+    let _suppression_guard = SuppressionGuard::new(watcher_suppressor);
+
+    let total = targets.len();
+    let mut summary = HardlinkAllSummary {
+        total,
+        linked: 0,
+        failed: 0,
+        reclaimed_bytes: 0,
+        errors: Vec::new(),
+    };
+    let mut relinked = Vec::new();
+    for (index, target) in targets.into_iter().enumerate() {
+        on_progress(HardlinkProgress {
+            current: index + 1,
+            total,
+            file_path: target.row.file_path.clone(),
+        });
+
+        let row = target.row.clone();
+        let outcome = tokio::task::spawn_blocking(move || link_to_any(&target)).await?;
+        match outcome {
+            Ok(()) => {
+                summary.linked += 1;
+                summary.reclaimed_bytes += row.size_bytes.max(0) as u64;
+                // The link takes the keeper's mtime; record it so the next
+                // pass does not re-hash a file that did not change.
+                if let Some((size_bytes, mtime_ns)) = file_stamp(Path::new(&row.file_path)) {
+                    relinked.push(BlobRow {
+                        size_bytes,
+                        mtime_ns,
+                        ..row
+                    });
+                }
+            }
+            Err(error) => {
+                summary.failed += 1;
+                summary.errors.push(HardlinkError {
+                    file_path: row.file_path,
+                    message: error.to_string(),
+                });
+            }
+        }
+    }
+    blob_repo::upsert_blobs(pool, &relinked).await?;
+
+    Ok(summary)
+}
+
+struct LinkTarget {
+    row: BlobRow,
+    /// Earlier physical copies of the same content, first one preferred.
+    /// Later ones are fallbacks for a target on another volume.
+    keepers: Vec<PathBuf>,
+}
+
+fn link_targets(rows: Vec<BlobRow>) -> Vec<LinkTarget> {
+    let mut targets = Vec::new();
+    for chunk in rows.chunk_by(|left, right| left.content_hash == right.content_hash) {
+        let paths: Vec<_> = chunk.iter().map(|row| row.file_path.as_str()).collect();
+        let mut keepers: Vec<PathBuf> = Vec::new();
+        for (row, copy_index) in chunk.iter().zip(copy_indices(&paths)) {
+            let copy_index = copy_index as usize;
+            if copy_index < keepers.len() {
+                // Already a hardlink of an earlier copy.
+                continue;
+            }
+            if !keepers.is_empty() {
+                targets.push(LinkTarget {
+                    row: row.clone(),
+                    keepers: keepers.clone(),
+                });
+            }
+            keepers.push(PathBuf::from(&row.file_path));
+        }
+    }
+    targets
+}
+
+fn link_to_any(target: &LinkTarget) -> Result<(), ScannerError> {
+    let path = Path::new(&target.row.file_path);
+    // The store may be a moment old; only link what still hashes the same.
+    if full_blake3_hash(path)? != target.row.content_hash {
+        return Err(ScannerError::Validation(
+            "File changed since it was indexed".to_string(),
+        ));
+    }
+
+    let mut last_error = ScannerError::Validation("No keeper file available".to_string());
+    for keeper in &target.keepers {
+        if !full_blake3_hash(keeper).is_ok_and(|hash| hash == target.row.content_hash) {
+            continue;
+        }
+        match replace_with_hardlink(keeper, path) {
+            Ok(()) => return Ok(()),
+            Err(error) => last_error = error.into(),
+        }
+    }
+    Err(last_error)
+}
+
+/// Links `keeper` beside `target` under a temporary name, then renames it
+/// over `target`, so a failure never leaves the target missing.
+fn replace_with_hardlink(keeper: &Path, target: &Path) -> std::io::Result<()> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let staging = target.with_file_name(format!(".{file_name}.emmm-link"));
+    let _ = fs::remove_file(&staging);
+    fs::hard_link(keeper, &staging)?;
+    if let Err(error) = fs::rename(&staging, target) {
+        let _ = fs::remove_file(&staging);
+        return Err(error);
+    }
+    Ok(())
+}
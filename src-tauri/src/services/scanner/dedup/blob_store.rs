@@ -0,0 +1,254 @@
+//! Cross-game, content-addressed store of the heavy mod payload files.
+//!
+//! The pair scan in `scanner.rs` hashes per game and forgets the hashes when
+//! it ends. This store keeps a BLAKE3 hash for every `.dds`/`.buf`/`.ib` under
+//! every configured game's mods folder in `file_blobs`, re-hashing a file only
+//! when its size or mtime moved. Identical files are then a query, and can be
+//! collapsed into hardlinks in one job (see `blob_hardlink`).
+
+use crate::domain::errors::AppError;
+use crate::repo::blob_repo::{self, BlobRow};
+use rayon::prelude::*;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use super::hashing::full_blake3_hash;
+
+const BLOB_EXTS: &[&str] = &["dds", "buf", "ib"];
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobFile {
+    pub game_id: String,
+    pub file_path: String,
+    /// Files sharing a `copy_index` are already hardlinks of one another.
+    pub copy_index: u32,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobDuplicateGroup {
+    pub content_hash: String,
+    #[specta(type = f64)]
+    pub size_bytes: u64,
+    pub files: Vec<BlobFile>,
+    /// Bytes freed by collapsing every physical copy but one.
+    #[specta(type = f64)]
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobDedupReport {
+    #[specta(type = f64)]
+    pub indexed_files: usize,
+    #[specta(type = f64)]
+    pub rehashed_files: usize,
+    pub groups: Vec<BlobDuplicateGroup>,
+    #[specta(type = f64)]
+    pub reclaimable_bytes: u64,
+}
+
+/// Counts from one incremental pass over every game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlobSyncStats {
+    pub indexed_files: usize,
+    pub rehashed_files: usize,
+    pub removed_files: usize,
+}
+
+struct GameSync {
+    upserts: Vec<BlobRow>,
+    removed: Vec<String>,
+    indexed: usize,
+}
+
+/// Brings `file_blobs` up to date for every configured game.
+pub async fn sync_blob_store(pool: &SqlitePool) -> Result<BlobSyncStats, AppError> {
+    let mut stats = BlobSyncStats::default();
+    for game in crate::repo::game_repo::get_all_games(pool).await? {
+        let mods_root = game
+            .mods_path
+            .as_deref()
+            .filter(|path| !path.trim().is_empty())
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir());
+        let Some(mods_root) = mods_root else {
+            blob_repo::clear_game_blobs(pool, &game.id).await?;
+            continue;
+        };
+
+        let stored = blob_repo::list_game_blobs(pool, &game.id).await?;
+        let game_id = game.id.clone();
+        let sync =
+            tokio::task::spawn_blocking(move || sync_game_blocking(&game_id, &mods_root, stored))
+                .await?;
+
+        blob_repo::upsert_blobs(pool, &sync.upserts).await?;
+        blob_repo::delete_blobs(pool, &sync.removed).await?;
+        stats.indexed_files += sync.indexed;
+        stats.rehashed_files += sync.upserts.len();
+        stats.removed_files += sync.removed.len();
+    }
+    Ok(stats)
+}
+
+fn sync_game_blocking(game_id: &str, mods_root: &Path, stored: Vec<BlobRow>) -> GameSync {
+    let mut stored: HashMap<String, BlobRow> = stored
+        .into_iter()
+        .map(|row| (row.file_path.clone(), row))
+        .collect();
+
+    let on_disk: Vec<(String, i64, i64)> = WalkDir::new(mods_root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && is_blob_file(entry.path()))
+        .filter_map(|entry| {
+            let (size_bytes, mtime_ns) = file_stamp(entry.path())?;
+            // Empty files are all identical and linking them frees nothing.
+            (size_bytes > 0).then(|| {
+                (
+                    entry.path().to_string_lossy().to_string(),
+                    size_bytes,
+                    mtime_ns,
+                )
+            })
+        })
+        .collect();
+    let indexed = on_disk.len();
+
+    let stale: Vec<_> = on_disk
+        .into_iter()
+        .filter(|(path, size_bytes, mtime_ns)| {
+            let current = stored.remove(path);
+            current.is_none_or(|row| row.size_bytes != *size_bytes || row.mtime_ns != *mtime_ns)
+        })
+        .collect();
+    let upserts = stale
+        .into_par_iter()
+        .filter_map(|(file_path, size_bytes, mtime_ns)| {
+            match full_blake3_hash(Path::new(&file_path)) {
+                Ok(content_hash) => Some(BlobRow {
+                    file_path,
+                    game_id: game_id.to_string(),
+                    size_bytes,
+                    mtime_ns,
+                    content_hash,
+                }),
+                Err(error) => {
+                    log::warn!("[blob_store] Failed to hash {file_path}: {error}");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    // Whatever was not seen on disk this pass is gone.
+    GameSync {
+        upserts,
+        removed: stored.into_keys().collect(),
+        indexed,
+    }
+}
+
+fn is_blob_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            BLOB_EXTS
+                .iter()
+                .any(|blob_ext| extension.eq_ignore_ascii_case(blob_ext))
+        })
+}
+
+/// Size and mtime in nanoseconds, the pair a stored hash is trusted against.
+pub(super) fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let mtime_ns = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |elapsed| elapsed.as_nanos() as i64);
+    Some((metadata.len() as i64, mtime_ns))
+}
+
+/// Syncs the store, then reports every set of identical files across games
+/// and what hardlinking them would free.
+pub async fn blob_dedup_report(pool: &SqlitePool) -> Result<BlobDedupReport, AppError> {
+    let stats = sync_blob_store(pool).await?;
+    let rows = blob_repo::list_duplicate_blobs(pool).await?;
+    let groups = tokio::task::spawn_blocking(move || build_groups(rows)).await?;
+    Ok(BlobDedupReport {
+        indexed_files: stats.indexed_files,
+        rehashed_files: stats.rehashed_files,
+        reclaimable_bytes: groups.iter().map(|group| group.reclaimable_bytes).sum(),
+        groups,
+    })
+}
+
+fn build_groups(rows: Vec<BlobRow>) -> Vec<BlobDuplicateGroup> {
+    let mut groups = Vec::new();
+    for chunk in rows.chunk_by(|left, right| left.content_hash == right.content_hash) {
+        let paths: Vec<_> = chunk.iter().map(|row| row.file_path.as_str()).collect();
+        let copies = copy_indices(&paths);
+        let physical_copies = copies.iter().copied().max().map_or(0, |max| max + 1);
+        if physical_copies < 2 {
+            continue;
+        }
+        let size_bytes = chunk[0].size_bytes.max(0) as u64;
+        groups.push(BlobDuplicateGroup {
+            content_hash: chunk[0].content_hash.clone(),
+            size_bytes,
+            files: chunk
+                .iter()
+                .zip(copies)
+                .map(|(row, copy_index)| BlobFile {
+                    game_id: row.game_id.clone(),
+                    file_path: row.file_path.clone(),
+                    copy_index,
+                })
+                .collect(),
+            reclaimable_bytes: size_bytes * u64::from(physical_copies - 1),
+        });
+    }
+    groups.sort_by(|left, right| {
+        right
+            .reclaimable_bytes
+            .cmp(&left.reclaimable_bytes)
+            .then_with(|| left.content_hash.cmp(&right.content_hash))
+    });
+    groups
+}
+
+/// Numbers each path by the physical file behind it, so paths that are
+/// already hardlinks of one another share an index.
+pub(super) fn copy_indices(paths: &[&str]) -> Vec<u32> {
+    // `None` stands for a file that could not be opened right now; it
+    // counts as its own copy and never matches another.
+    let mut handles: Vec<Option<same_file::Handle>> = Vec::new();
+    paths
+        .iter()
+        .map(|path| {
+            let handle = same_file::Handle::from_path(path).ok();
+            let known = handle.as_ref().and_then(|handle| {
+                handles
+                    .iter()
+                    .position(|other| other.as_ref() == Some(handle))
+            });
+            known.unwrap_or_else(|| {
+                handles.push(handle);
+                handles.len() - 1
+            }) as u32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "tests/dedup_blob_store_tests.rs"]
+mod tests;
@@ -7,6 +7,7 @@ use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
 use super::snapshot::ModSnapshot;
+use super::texture_phash::texture_phash;
 
 const KEY_EXTS: &[&str] = &["ini", "dds", "buf", "ib", "vb"];
 /// Textures carry the colour, meshes carry the shape. The two are hashed into
@@ -21,6 +22,9 @@ pub(crate) struct HashProfile {
     pub key_file_hashes: BTreeMap<String, String>,
     pub texture_samples: BTreeMap<String, String>,
     pub mesh_hashes: BTreeMap<String, String>,
+    /// Perceptual hashes of the textures that could be sampled; see
+    /// `texture_phash`.
+    pub texture_phashes: BTreeMap<String, u64>,
 }
 
 pub(crate) fn hash_snapshot(snapshot: &ModSnapshot) -> HashProfile {
@@ -30,6 +34,11 @@ pub(crate) fn hash_snapshot(snapshot: &ModSnapshot) -> HashProfile {
             continue;
         }
         let is_texture = file.extension == TEXTURE_EXT;
+        if is_texture {
+            if let Some(phash) = texture_phash(&file.abs_path) {
+                profile.texture_phashes.insert(file.rel_path.clone(), phash);
+            }
+        }
         let hash = if is_texture && file.size_bytes > PARTIAL_HASH_THRESHOLD_BYTES {
             partial_blake3_hash(&file.abs_path)
         } else {
@@ -49,7 +58,7 @@ pub(crate) fn hash_snapshot(snapshot: &ModSnapshot) -> HashProfile {
     profile
 }
 
-fn full_blake3_hash(path: &Path) -> Result<String, ScannerError> {
+pub(super) fn full_blake3_hash(path: &Path) -> Result<String, ScannerError> {
     let file = File::open(path)?;
     // blake3's own reader does the buffering; an 8 KiB hand-rolled loop is
     // below the 16 KiB the multi-threaded fast path needs.
@@ -1,9 +1,13 @@
+pub mod blob_hardlink;
+pub mod blob_store;
 pub mod grouping;
 pub mod hashing;
 pub mod resolver;
 pub mod scanner;
 pub mod signals;
 pub mod snapshot;
+mod texture_luma;
+pub mod texture_phash;
 
 /// Similarity of two byte sizes, in `0.0..=1.0`.
 ///
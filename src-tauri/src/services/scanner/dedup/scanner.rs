@@ -123,7 +123,7 @@ fn run_pipeline_blocking(
             let right_hash = hash_profiles.get(&right)?;
             let (score, signals, reason) =
                 aggregate_signals(&snapshots[left], &snapshots[right], left_hash, right_hash);
-            if score < super::signals::weights::MIN_REPORTED_SCORE {
+            if !super::signals::is_reported_score(score) {
                 return None;
             }
             Some((left, right, score, signals, reason))
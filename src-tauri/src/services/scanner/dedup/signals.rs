@@ -9,6 +9,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use super::hashing::HashProfile;
 use super::snapshot::ModSnapshot;
+use super::texture_phash::phash_similarity;
 
 /// Every tunable in the duplicate-similarity model, in one place.
 ///
@@ -46,10 +47,17 @@ pub(super) mod weights {
     /// Bonuses applied before clamping.
     pub const RECOLOR_BONUS: f64 = 25.0;
     pub const LOGICAL_OVERLAP_BONUS: f64 = 15.0;
+    /// Scaled by the share of textures with a perceptual twin, so a pair whose
+    /// textures were only re-exported still climbs toward the duplicate band.
+    pub const PERCEPTUAL_TEXTURE_BONUS: f64 = 15.0;
 
     /// Thresholds.
     pub const RECOLOR_MIN_SIZE_RATIO: f64 = 0.95;
     pub const LOGICAL_OVERLAP_BONUS_MIN: f64 = 0.8;
+    /// Two texture hashes this similar (share of 64 dHash bits) look the same.
+    pub const PERCEPTUAL_TWIN_MIN_SIMILARITY: f64 = 0.9;
+    /// Below this share of twinned textures the signal is noise.
+    pub const PERCEPTUAL_TEXTURE_MIN: f64 = 0.5;
     pub const MAX_INEXACT_SCORE: f64 = 99.0;
     /// A same-name/different-version pair is a duplicate regardless of content drift.
     pub const VERSION_UPGRADE_FLOOR: u8 = 85;
@@ -60,6 +68,11 @@ pub(super) mod weights {
     pub const HIGH_SIMILARITY_SCORE: u8 = 80;
 }
 
+/// Whether a pair scoring `score` is shown to the user as a duplicate.
+pub(crate) fn is_reported_score(score: u8) -> bool {
+    score >= weights::MIN_REPORTED_SCORE
+}
+
 pub(crate) fn aggregate_signals(
     left: &ModSnapshot,
     right: &ModSnapshot,
@@ -84,6 +97,9 @@ pub(crate) fn aggregate_signals(
     let (mesh_score, exact_mesh_match) =
         hash_similarity(&left_hash.mesh_hashes, &right_hash.mesh_hashes);
 
+    let perceptual_texture =
+        perceptual_texture_similarity(&left_hash.texture_phashes, &right_hash.texture_phashes);
+
     let physical = ((extension_score * w::PHYSICAL_EXTENSIONS)
         + (texture_score * w::PHYSICAL_TEXTURES)
         + (mesh_score * w::PHYSICAL_MESHES))
@@ -128,6 +144,11 @@ pub(crate) fn aggregate_signals(
         weighted += w::LOGICAL_OVERLAP_BONUS;
     }
 
+    let is_visual_twin = perceptual_texture >= w::PERCEPTUAL_TEXTURE_MIN && texture_score < 1.0;
+    if is_visual_twin {
+        weighted += perceptual_texture * w::PERCEPTUAL_TEXTURE_BONUS;
+    }
+
     let mut score = weighted.round().clamp(0.0, w::MAX_INEXACT_SCORE) as u8;
 
     if is_version_upgrade {
@@ -165,10 +186,20 @@ pub(crate) fn aggregate_signals(
         ));
     }
 
+    if perceptual_texture > 0.0 {
+        signals.push(build_signal(
+            "texture_perceptual",
+            "Visually near-identical textures (DDS perceptual hash)",
+            perceptual_texture,
+        ));
+    }
+
     let reason = if is_potential_recolor {
         "Potential Recolor / Retexture Variant".to_string()
     } else if is_version_upgrade {
         "Version Upgrade Detected".to_string()
+    } else if is_visual_twin {
+        "Visually Near-Identical Textures (Re-export)".to_string()
     } else if logical_overlap > w::LOGICAL_OVERLAP_BONUS_MIN {
         "Logical INI Hash Override Conflict".to_string()
     } else if score >= w::HIGH_SIMILARITY_SCORE {
@@ -236,6 +267,28 @@ fn hash_similarity(
     (score, score == 1.0 && left.len() == right.len())
 }
 
+/// Share of textures, counted against the larger side, that have a
+/// perceptual twin on the other side. Paths are ignored: a re-export often
+/// renames its textures too.
+fn perceptual_texture_similarity(
+    left: &BTreeMap<String, u64>,
+    right: &BTreeMap<String, u64>,
+) -> f64 {
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+    let twinned = left
+        .values()
+        .filter(|left_hash| {
+            right.values().any(|right_hash| {
+                phash_similarity(**left_hash, *right_hash)
+                    >= weights::PERCEPTUAL_TWIN_MIN_SIMILARITY
+            })
+        })
+        .count();
+    twinned as f64 / left.len().max(right.len()) as f64
+}
+
 fn set_overlap_score(left: &BTreeSet<String>, right: &BTreeSet<String>) -> f64 {
     if left.is_empty() || right.is_empty() {
         return 0.0;
@@ -0,0 +1,128 @@
+use super::{blob_dedup_report, sync_blob_store};
+use crate::services::fs_utils::operation_lock::OperationLock;
+use crate::services::scanner::dedup::blob_hardlink::hardlink_all_duplicates;
+use crate::services::scanner::watcher::WatcherSuppressor;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+struct TestContext {
+    _temp: TempDir,
+    mods_a: PathBuf,
+    mods_b: PathBuf,
+    pool: sqlx::SqlitePool,
+}
+
+async fn setup_context() -> TestContext {
+    let temp = TempDir::new().unwrap();
+    let mods_a = temp.path().join("GameA").join("Mods");
+    let mods_b = temp.path().join("GameB").join("Mods");
+    fs::create_dir_all(&mods_a).unwrap();
+    fs::create_dir_all(&mods_b).unwrap();
+
+    let ctx = crate::test_utils::init_test_db().await;
+    let pool = ctx.pool;
+    for (id, mods_root) in [("game-a", &mods_a), ("game-b", &mods_b)] {
+        crate::test_utils::insert_test_game(
+            &pool,
+            &crate::test_utils::TestGameFixture {
+                id,
+                name: id,
+                game_type: crate::domain::models::GameType::GIMI,
+                path: mods_root.parent().unwrap().to_str().unwrap(),
+                mods_path: Some(mods_root.to_str().unwrap()),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    TestContext {
+        _temp: temp,
+        mods_a,
+        mods_b,
+        pool,
+    }
+}
+
+fn write_file(path: &Path, content: &[u8]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[tokio::test]
+async fn reports_identical_payloads_across_games() {
+    let context = setup_context().await;
+    write_file(&context.mods_a.join("Alpha/body.dds"), &[7; 4096]);
+    write_file(&context.mods_b.join("Beta/body.dds"), &[7; 4096]);
+    write_file(&context.mods_b.join("Beta/other.ib"), &[1; 64]);
+    write_file(&context.mods_b.join("Beta/merged.ini"), &[7; 4096]);
+
+    let report = blob_dedup_report(&context.pool).await.unwrap();
+
+    assert_eq!(report.indexed_files, 3);
+    assert_eq!(report.groups.len(), 1);
+    let group = &report.groups[0];
+    assert_eq!(group.files.len(), 2);
+    assert_eq!(group.reclaimable_bytes, 4096);
+    assert_eq!(report.reclaimable_bytes, 4096);
+    let games: Vec<_> = group
+        .files
+        .iter()
+        .map(|file| file.game_id.as_str())
+        .collect();
+    assert!(games.contains(&"game-a") && games.contains(&"game-b"));
+}
+
+#[tokio::test]
+async fn sync_rehashes_only_changed_files() {
+    let context = setup_context().await;
+    let texture = context.mods_a.join("Alpha/body.dds");
+    write_file(&texture, &[7; 128]);
+    write_file(&context.mods_a.join("Alpha/mesh.buf"), &[3; 128]);
+
+    let first = sync_blob_store(&context.pool).await.unwrap();
+    assert_eq!(first.rehashed_files, 2);
+
+    let second = sync_blob_store(&context.pool).await.unwrap();
+    assert_eq!(second.rehashed_files, 0);
+    assert_eq!(second.indexed_files, 2);
+
+    write_file(&texture, &[8; 256]);
+    fs::remove_file(context.mods_a.join("Alpha/mesh.buf")).unwrap();
+    let third = sync_blob_store(&context.pool).await.unwrap();
+    assert_eq!(third.rehashed_files, 1);
+    assert_eq!(third.removed_files, 1);
+}
+
+#[tokio::test]
+async fn hardlink_all_collapses_copies_and_clears_reclaimable() {
+    let context = setup_context().await;
+    let keeper = context.mods_a.join("Alpha/body.dds");
+    let copy = context.mods_b.join("Beta/body.dds");
+    write_file(&keeper, &[5; 2048]);
+    write_file(&copy, &[5; 2048]);
+
+    let lock = OperationLock::new();
+    let guard = lock.acquire().await.unwrap();
+    let suppressor = Arc::new(WatcherSuppressor::new(false));
+    let mut progress_events = 0;
+    let summary = hardlink_all_duplicates(&context.pool, &guard, &suppressor, |_| {
+        progress_events += 1;
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(summary.total, 1);
+    assert_eq!(summary.linked, 1);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.reclaimed_bytes, 2048);
+    assert_eq!(progress_events, 1);
+    assert!(same_file::is_same_file(&keeper, &copy).unwrap());
+    assert_eq!(fs::read(&copy).unwrap(), vec![5; 2048]);
+
+    let report = blob_dedup_report(&context.pool).await.unwrap();
+    assert!(report.groups.is_empty());
+    assert_eq!(report.rehashed_files, 0);
+}
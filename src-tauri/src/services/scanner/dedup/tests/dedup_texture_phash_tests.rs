@@ -0,0 +1,103 @@
+use super::{phash_similarity, texture_phash};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SIDE: u32 = 64;
+
+/// A diagonal ramp with a bright square in one corner.
+fn pixel(x: u32, y: u32, inverted: bool) -> u8 {
+    let base = ((x + y) * 255 / (2 * SIDE - 2)) as u8;
+    let value = if x < 16 && y < 16 { 255 } else { base };
+    if inverted {
+        255 - value
+    } else {
+        value
+    }
+}
+
+fn header(four_cc: &[u8; 4], pixel_flags: u32, bit_count: u32, masks: [u32; 3]) -> Vec<u8> {
+    let mut bytes = vec![0_u8; 128];
+    bytes[..4].copy_from_slice(b"DDS ");
+    bytes[4..8].copy_from_slice(&124_u32.to_le_bytes());
+    bytes[12..16].copy_from_slice(&SIDE.to_le_bytes());
+    bytes[16..20].copy_from_slice(&SIDE.to_le_bytes());
+    bytes[28..32].copy_from_slice(&1_u32.to_le_bytes());
+    bytes[76..80].copy_from_slice(&32_u32.to_le_bytes());
+    bytes[80..84].copy_from_slice(&pixel_flags.to_le_bytes());
+    bytes[84..88].copy_from_slice(four_cc);
+    bytes[88..92].copy_from_slice(&bit_count.to_le_bytes());
+    for (index, mask) in masks.iter().enumerate() {
+        let offset = 92 + index * 4;
+        bytes[offset..offset + 4].copy_from_slice(&mask.to_le_bytes());
+    }
+    bytes
+}
+
+fn write_bgra(path: &Path, inverted: bool) {
+    let mut bytes = header(&[0; 4], 0x40, 32, [0x00ff_0000, 0x0000_ff00, 0x0000_00ff]);
+    for y in 0..SIDE {
+        for x in 0..SIDE {
+            let value = pixel(x, y, inverted);
+            bytes.extend_from_slice(&[value, value, value, 255]);
+        }
+    }
+    fs::write(path, bytes).unwrap();
+}
+
+/// BC1 with both endpoints set to the block's top-left pixel.
+fn write_bc1(path: &Path, inverted: bool) {
+    let mut bytes = header(b"DXT1", 0x4, 0, [0; 3]);
+    for block_y in 0..SIDE / 4 {
+        for block_x in 0..SIDE / 4 {
+            let value = u16::from(pixel(block_x * 4, block_y * 4, inverted));
+            let rgb565 = ((value >> 3) << 11) | ((value >> 2) << 5) | (value >> 3);
+            bytes.extend_from_slice(&rgb565.to_le_bytes());
+            bytes.extend_from_slice(&rgb565.to_le_bytes());
+            bytes.extend_from_slice(&[0; 4]);
+        }
+    }
+    fs::write(path, bytes).unwrap();
+}
+
+#[test]
+fn a_re_export_in_another_format_hashes_alike() {
+    let dir = TempDir::new().unwrap();
+    let uncompressed = dir.path().join("body.dds");
+    let compressed = dir.path().join("body_bc1.dds");
+    write_bgra(&uncompressed, false);
+    write_bc1(&compressed, false);
+
+    let left = texture_phash(&uncompressed).unwrap();
+    let right = texture_phash(&compressed).unwrap();
+
+    assert!(phash_similarity(left, right) >= 0.9);
+}
+
+#[test]
+fn a_different_image_hashes_apart() {
+    let dir = TempDir::new().unwrap();
+    let original = dir.path().join("body.dds");
+    let inverted = dir.path().join("inverted.dds");
+    write_bgra(&original, false);
+    write_bgra(&inverted, true);
+
+    let left = texture_phash(&original).unwrap();
+    let right = texture_phash(&inverted).unwrap();
+
+    assert!(phash_similarity(left, right) < 0.5);
+}
+
+#[test]
+fn flat_and_non_dds_files_have_no_hash() {
+    let dir = TempDir::new().unwrap();
+    let flat = dir.path().join("flat.dds");
+    let mut bytes = header(&[0; 4], 0x40, 32, [0x00ff_0000, 0x0000_ff00, 0x0000_00ff]);
+    bytes.extend(std::iter::repeat_n(128_u8, (SIDE * SIDE * 4) as usize));
+    fs::write(&flat, bytes).unwrap();
+    let text = dir.path().join("notes.dds");
+    fs::write(&text, "not a texture").unwrap();
+
+    assert_eq!(texture_phash(&flat), None);
+    assert_eq!(texture_phash(&text), None);
+}
@@ -0,0 +1,196 @@
+//! Decoding just enough of a DDS mip to hash it: luminance per pixel for
+//! uncompressed formats, per 4×4 block from the endpoints for BCn, and the
+//! box filter that brings either down to the hash grid.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Format {
+    Bc1,
+    Bc2,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc7,
+    Rgba8,
+    Bgra8,
+}
+
+impl Format {
+    pub(super) fn block_bytes(self) -> Option<u64> {
+        match self {
+            Self::Bc1 | Self::Bc4 => Some(8),
+            Self::Bc2 | Self::Bc3 | Self::Bc5 | Self::Bc7 => Some(16),
+            Self::Rgba8 | Self::Bgra8 => None,
+        }
+    }
+}
+
+/// Luminance samples, one per pixel or per 4×4 block, row by row.
+pub(super) struct LumaGrid {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl LumaGrid {
+    /// Brightest minus darkest sample.
+    pub(super) fn spread(&self) -> f32 {
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        max - min
+    }
+
+    /// Box-filters the grid down to `W`×`H` cells.
+    pub(super) fn resized<const W: usize, const H: usize>(&self) -> [[f32; W]; H] {
+        let mut cells = [[0.0_f32; W]; H];
+        for (row, cells_row) in cells.iter_mut().enumerate() {
+            let y_start = row * self.height / H;
+            let y_end = ((row + 1) * self.height / H).max(y_start + 1);
+            for (column, cell) in cells_row.iter_mut().enumerate() {
+                let x_start = column * self.width / W;
+                let x_end = ((column + 1) * self.width / W).max(x_start + 1);
+                let mut sum = 0.0;
+                let mut count = 0.0;
+                for y in y_start..y_end.min(self.height) {
+                    for x in x_start..x_end.min(self.width) {
+                        if let Some(value) = self.values.get(y * self.width + x) {
+                            sum += value;
+                            count += 1.0;
+                        }
+                    }
+                }
+                *cell = if count > 0.0 { sum / count } else { 0.0 };
+            }
+        }
+        cells
+    }
+}
+
+fn luma(red: f32, green: f32, blue: f32) -> f32 {
+    0.299 * red + 0.587 * green + 0.114 * blue
+}
+
+pub(super) fn pixel_luma_grid(
+    bytes: &[u8],
+    format: Format,
+    width: usize,
+    height: usize,
+) -> LumaGrid {
+    let values = bytes
+        .chunks_exact(4)
+        .map(|pixel| {
+            let [first, green, third, _] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(f32::from);
+            match format {
+                Format::Bgra8 => luma(third, green, first),
+                _ => luma(first, green, third),
+            }
+        })
+        .collect();
+    LumaGrid {
+        width,
+        height,
+        values,
+    }
+}
+
+pub(super) fn block_luma_grid(
+    bytes: &[u8],
+    format: Format,
+    width: usize,
+    height: usize,
+) -> LumaGrid {
+    let block_bytes = format.block_bytes().unwrap_or(16) as usize;
+    let values = bytes
+        .chunks_exact(block_bytes)
+        .map(|block| block_luma(block, format))
+        .collect();
+    LumaGrid {
+        width,
+        height,
+        values,
+    }
+}
+
+/// Average luminance of a block's endpoints.
+fn block_luma(block: &[u8], format: Format) -> f32 {
+    match format {
+        Format::Bc1 => rgb565_endpoints_luma(&block[..4]),
+        Format::Bc2 | Format::Bc3 => rgb565_endpoints_luma(&block[8..12]),
+        // Single-channel formats: the red endpoints are the image.
+        Format::Bc4 | Format::Bc5 => (f32::from(block[0]) + f32::from(block[1])) / 2.0,
+        Format::Bc7 => bc7_endpoints_luma(block),
+        Format::Rgba8 | Format::Bgra8 => 0.0,
+    }
+}
+
+fn rgb565_endpoints_luma(bytes: &[u8]) -> f32 {
+    let endpoint = |raw: u16| {
+        let red = f32::from((raw >> 11) & 0x1f) * 255.0 / 31.0;
+        let green = f32::from((raw >> 5) & 0x3f) * 255.0 / 63.0;
+        let blue = f32::from(raw & 0x1f) * 255.0 / 31.0;
+        luma(red, green, blue)
+    };
+    let first = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let second = u16::from_le_bytes([bytes[2], bytes[3]]);
+    (endpoint(first) + endpoint(second)) / 2.0
+}
+
+/// BC7 per-mode layout: subsets, partition bits, rotation bits, index
+/// selector bits, colour bits per channel.
+const BC7_MODES: [(usize, usize, usize, usize, usize); 8] = [
+    (3, 4, 0, 0, 4),
+    (2, 6, 0, 0, 6),
+    (3, 6, 0, 0, 5),
+    (2, 6, 0, 0, 7),
+    (1, 0, 2, 1, 5),
+    (1, 0, 2, 0, 7),
+    (1, 0, 0, 0, 7),
+    (2, 6, 0, 0, 5),
+];
+
+fn bc7_endpoints_luma(block: &[u8]) -> f32 {
+    let mut reader = BitReader { block, position: 0 };
+    let Some(mode) = (0..8).find(|_| reader.take(1) == 1) else {
+        return 0.0;
+    };
+    let (subsets, partition_bits, rotation_bits, selector_bits, color_bits) = BC7_MODES[mode];
+    reader.take(partition_bits + rotation_bits + selector_bits);
+
+    let endpoints = subsets * 2;
+    let mut channels = [0.0_f32; 3];
+    for channel in &mut channels {
+        let mut sum = 0.0;
+        for _ in 0..endpoints {
+            let value = reader.take(color_bits);
+            // Widen to 8 bits by replicating the top bits; p-bits are ignored.
+            let widened = (value << (8 - color_bits)) | (value >> (2 * color_bits - 8));
+            sum += widened as f32;
+        }
+        *channel = sum / endpoints as f32;
+    }
+    luma(channels[0], channels[1], channels[2])
+}
+
+struct BitReader<'a> {
+    block: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn take(&mut self, count: usize) -> u32 {
+        let mut value = 0_u32;
+        for bit in 0..count {
+            let index = self.position + bit;
+            if index >= self.block.len() * 8 {
+                break;
+            }
+            let set = (self.block[index / 8] >> (index % 8)) & 1;
+            value |= u32::from(set) << bit;
+        }
+        self.position += count;
+        value
+    }
+}
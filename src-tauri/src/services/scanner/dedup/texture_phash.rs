@@ -0,0 +1,193 @@
+//! Perceptual hashes of DDS textures, for re-exports that BLAKE3 cannot see.
+//!
+//! A texture re-saved with another block format or encoder is byte-for-byte
+//! different but looks the same. This reads one small mip straight out of the
+//! DDS file, reduces every 4×4 block to its endpoint average (exact for
+//! uncompressed formats, close enough for BCn), and difference-hashes the
+//! result. Index bits are never decoded; the hash only needs the coarse shape
+//! of the image, and the endpoints carry it.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::texture_luma::{block_luma_grid, pixel_luma_grid, Format, LumaGrid};
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_LEN: u64 = 128;
+const DX10_HEADER_LEN: u64 = 20;
+/// The smallest mip we read still has this many blocks (or pixels, for
+/// uncompressed formats) on each side, so the 9×8 hash grid never upsamples.
+const MIN_SAMPLE_SIDE: u32 = 9;
+const HASH_WIDTH: usize = 9;
+const HASH_HEIGHT: usize = 8;
+/// Refuse to buffer a sample mip larger than this; a header that asks for it
+/// is broken or hostile.
+const MAX_SAMPLE_BYTES: u64 = 4 * 1024 * 1024;
+/// A sample whose luminance spans less than this is flat (a blank mask, a
+/// neutral normal map). Every flat texture hashes to zero, so they would all
+/// read as twins of one another.
+const MIN_LUMA_SPREAD: f32 = 4.0;
+
+struct Header {
+    width: u32,
+    height: u32,
+    mip_count: u32,
+    format: Format,
+    data_offset: u64,
+}
+
+/// 64-bit difference hash of the texture at `path`, or `None` for anything
+/// that is not a DDS in a format we sample.
+pub(crate) fn texture_phash(path: &Path) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let header = read_header(&mut file)?;
+    let (offset, width, height) = sample_mip(&header)?;
+    let grid = match header.format.block_bytes() {
+        Some(block_bytes) => {
+            let blocks_x = width.div_ceil(4);
+            let blocks_y = height.div_ceil(4);
+            let bytes = read_at(&mut file, offset, blocks_x * blocks_y * block_bytes)?;
+            block_luma_grid(&bytes, header.format, blocks_x as usize, blocks_y as usize)
+        }
+        None => {
+            let bytes = read_at(&mut file, offset, width * height * 4)?;
+            pixel_luma_grid(&bytes, header.format, width as usize, height as usize)
+        }
+    };
+    (grid.spread() >= MIN_LUMA_SPREAD).then(|| difference_hash(&grid))
+}
+
+/// Share of `0.0..=1.0` bits two hashes agree on.
+pub(crate) fn phash_similarity(left: u64, right: u64) -> f64 {
+    1.0 - f64::from((left ^ right).count_ones()) / 64.0
+}
+
+fn read_header(file: &mut File) -> Option<Header> {
+    let mut raw = [0_u8; HEADER_LEN as usize];
+    file.read_exact(&mut raw).ok()?;
+    if &raw[..4] != DDS_MAGIC {
+        return None;
+    }
+    let word = |offset: usize| u32::from_le_bytes(raw[offset..offset + 4].try_into().unwrap());
+    let height = word(12);
+    let width = word(16);
+    let mip_count = word(28).max(1);
+    let pixel_flags = word(80);
+    let four_cc = &raw[84..88];
+
+    let (format, data_offset) = if four_cc == b"DX10" {
+        let mut dx10 = [0_u8; DX10_HEADER_LEN as usize];
+        file.read_exact(&mut dx10).ok()?;
+        let dxgi = u32::from_le_bytes(dx10[..4].try_into().unwrap());
+        (dxgi_format(dxgi)?, HEADER_LEN + DX10_HEADER_LEN)
+    } else if pixel_flags & 0x4 != 0 {
+        (four_cc_format(four_cc)?, HEADER_LEN)
+    } else {
+        (
+            legacy_rgb_format(word(88), word(92), word(96), word(100))?,
+            HEADER_LEN,
+        )
+    };
+    (width > 0 && height > 0).then_some(Header {
+        width,
+        height,
+        mip_count,
+        format,
+        data_offset,
+    })
+}
+
+fn four_cc_format(four_cc: &[u8]) -> Option<Format> {
+    match four_cc {
+        b"DXT1" => Some(Format::Bc1),
+        b"DXT2" | b"DXT3" => Some(Format::Bc2),
+        b"DXT4" | b"DXT5" => Some(Format::Bc3),
+        b"ATI1" | b"BC4U" => Some(Format::Bc4),
+        b"ATI2" | b"BC5U" => Some(Format::Bc5),
+        _ => None,
+    }
+}
+
+fn dxgi_format(dxgi: u32) -> Option<Format> {
+    match dxgi {
+        28 | 29 => Some(Format::Rgba8),
+        71 | 72 => Some(Format::Bc1),
+        74 | 75 => Some(Format::Bc2),
+        77 | 78 => Some(Format::Bc3),
+        80 | 81 => Some(Format::Bc4),
+        83 | 84 => Some(Format::Bc5),
+        87 | 91 => Some(Format::Bgra8),
+        98 | 99 => Some(Format::Bc7),
+        _ => None,
+    }
+}
+
+fn legacy_rgb_format(bit_count: u32, red: u32, green: u32, blue: u32) -> Option<Format> {
+    match (bit_count, red, green, blue) {
+        (32, 0x0000_00ff, 0x0000_ff00, 0x00ff_0000) => Some(Format::Rgba8),
+        (32, 0x00ff_0000, 0x0000_ff00, 0x0000_00ff) => Some(Format::Bgra8),
+        _ => None,
+    }
+}
+
+/// Byte offset and size of the smallest mip still big enough to hash.
+fn sample_mip(header: &Header) -> Option<(u64, u64, u64)> {
+    let unit = if header.format.block_bytes().is_some() {
+        4
+    } else {
+        1
+    };
+    let level_bytes = |width: u32, height: u32| match header.format.block_bytes() {
+        Some(block_bytes) => {
+            u64::from(width.div_ceil(4).max(1)) * u64::from(height.div_ceil(4).max(1)) * block_bytes
+        }
+        None => u64::from(width) * u64::from(height) * 4,
+    };
+    let mut offset = header.data_offset;
+    let (mut width, mut height) = (header.width, header.height);
+    for _ in 1..header.mip_count {
+        let (next_width, next_height) = ((width / 2).max(1), (height / 2).max(1));
+        if next_width / unit < MIN_SAMPLE_SIDE || next_height / unit < MIN_SAMPLE_SIDE {
+            break;
+        }
+        offset += level_bytes(width, height);
+        (width, height) = (next_width, next_height);
+    }
+    if width / unit < MIN_SAMPLE_SIDE || height / unit < MIN_SAMPLE_SIDE {
+        return None;
+    }
+    (level_bytes(width, height) <= MAX_SAMPLE_BYTES).then_some((
+        offset,
+        u64::from(width),
+        u64::from(height),
+    ))
+}
+
+fn read_at(file: &mut File, offset: u64, len: u64) -> Option<Vec<u8>> {
+    if len > MAX_SAMPLE_BYTES {
+        return None;
+    }
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut bytes = vec![0_u8; len as usize];
+    file.read_exact(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Sets one bit per horizontal step of the 9×8 grid that gets brighter.
+fn difference_hash(grid: &LumaGrid) -> u64 {
+    let cells = grid.resized::<HASH_WIDTH, HASH_HEIGHT>();
+    let mut hash = 0_u64;
+    for (row, cells_row) in cells.iter().enumerate() {
+        for (column, pair) in cells_row.windows(2).enumerate() {
+            if pair[1] > pair[0] {
+                hash |= 1 << (row * (HASH_WIDTH - 1) + column);
+            }
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+#[path = "tests/dedup_texture_phash_tests.rs"]
+mod tests;
@@ -0,0 +1,307 @@
+//! Rerank provider for local LLM servers, so AI rerank works offline.
+//!
+//! Speaks Ollama's `/api/generate` and the llama.cpp server's `/completion`.
+//! Both take a single prompt and return free text, which is expected to hold
+//! the same `{"<id>": score}` object the chat provider asks for.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::ScannerError;
+use crate::services::config::models::{AiConfig, AiProviderKind};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::{
+    AiRerankProvider, AiRerankRequest,
+};
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+
+pub const DEFAULT_PROMPT_TEMPLATE: &str = "You are helping a mod manager categorize mod folders. \
+Given signals extracted from a folder and a list of possible Master Database candidates, \
+score each candidate by how likely it is to be a match for the folder. \
+Output ONLY a JSON object where the keys are the candidate IDs and the values are \
+confidence scores between 0.0 and 1.0.\n\n\
+## Folder Signals\n\
+- Folder Name Tokens: {folder_tokens}\n\
+- Deep Extracted Tokens: {deep_tokens}\n\
+- INI Section Tokens: {section_tokens}\n\
+- INI Content Tokens: {content_tokens}\n\n\
+## Candidates\n\
+{candidates}";
+
+const DEFAULT_OLLAMA_URL: &str = "http://127.0.0.1:11434/api/generate";
+const DEFAULT_LLAMA_CPP_URL: &str = "http://127.0.0.1:8080/completion";
+const DEFAULT_OLLAMA_MODEL: &str = "llama3.1";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_CANDIDATES: usize = 8;
+/// Token budget for the reply; a score object for a handful of ids is small.
+const MAX_PREDICT_TOKENS: u32 = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalAiProtocol {
+    /// `POST /api/generate`, reply in `response`.
+    Ollama,
+    /// `POST /completion`, reply in `content`.
+    LlamaCpp,
+}
+
+impl LocalAiProtocol {
+    fn default_url(self) -> &'static str {
+        match self {
+            Self::Ollama => DEFAULT_OLLAMA_URL,
+            Self::LlamaCpp => DEFAULT_LLAMA_CPP_URL,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalAiSettings {
+    pub protocol: LocalAiProtocol,
+    /// Full endpoint URL. `None` uses the server's default local address.
+    pub url: Option<String>,
+    /// `None` lets llama.cpp use its loaded model; Ollama falls back to
+    /// [`DEFAULT_OLLAMA_MODEL`].
+    pub model: Option<String>,
+    pub prompt_template: Option<String>,
+    pub timeout: Duration,
+    pub max_candidates: usize,
+}
+
+impl LocalAiSettings {
+    /// Settings for a local provider, or `None` when `config` selects a remote one.
+    pub fn from_config(config: &AiConfig) -> Option<Self> {
+        let protocol = match config.provider {
+            AiProviderKind::OpenAi => return None,
+            AiProviderKind::Ollama => LocalAiProtocol::Ollama,
+            AiProviderKind::LlamaCpp => LocalAiProtocol::LlamaCpp,
+        };
+        Some(Self {
+            protocol,
+            url: non_blank(config.base_url.as_deref()),
+            model: non_blank(config.model.as_deref()),
+            prompt_template: non_blank(config.prompt_template.as_deref()),
+            timeout: config
+                .timeout_secs
+                .filter(|secs| *secs > 0)
+                .map(|secs| Duration::from_secs(u64::from(secs)))
+                .unwrap_or(DEFAULT_TIMEOUT),
+            max_candidates: config
+                .max_candidates
+                .filter(|max| *max > 0)
+                .map(|max| max as usize)
+                .unwrap_or(DEFAULT_MAX_CANDIDATES),
+        })
+    }
+}
+
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub struct LocalAiRerankProvider {
+    client: Client,
+    protocol: LocalAiProtocol,
+    url: String,
+    model: Option<String>,
+    prompt_template: String,
+    max_candidates: usize,
+}
+
+impl LocalAiRerankProvider {
+    pub fn new(settings: LocalAiSettings) -> Result<Self, ScannerError> {
+        let client = Client::builder().timeout(settings.timeout).build()?;
+        let model = match settings.protocol {
+            LocalAiProtocol::Ollama => settings
+                .model
+                .or_else(|| Some(DEFAULT_OLLAMA_MODEL.to_string())),
+            LocalAiProtocol::LlamaCpp => settings.model,
+        };
+        Ok(Self {
+            client,
+            protocol: settings.protocol,
+            url: settings
+                .url
+                .unwrap_or_else(|| settings.protocol.default_url().to_string()),
+            model,
+            prompt_template: settings
+                .prompt_template
+                .unwrap_or_else(|| DEFAULT_PROMPT_TEMPLATE.to_string()),
+            max_candidates: settings.max_candidates.max(1),
+        })
+    }
+
+    fn request_body(&self, prompt: String) -> serde_json::Value {
+        match self.protocol {
+            LocalAiProtocol::Ollama => serde_json::to_value(OllamaRequest {
+                model: self.model.clone().unwrap_or_default(),
+                prompt,
+                stream: false,
+                format: "json",
+                options: OllamaOptions { temperature: 0.0 },
+            }),
+            LocalAiProtocol::LlamaCpp => serde_json::to_value(LlamaCppRequest {
+                model: self.model.clone(),
+                prompt,
+                n_predict: MAX_PREDICT_TOKENS,
+                temperature: 0.0,
+                stream: false,
+            }),
+        }
+        .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize)]
+struct OllamaRequest {
+    model: String,
+    prompt: String,
+    stream: bool,
+    format: &'static str,
+    options: OllamaOptions,
+}
+
+#[derive(Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    response: String,
+}
+
+#[derive(Serialize)]
+struct LlamaCppRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    prompt: String,
+    n_predict: u32,
+    temperature: f32,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct LlamaCppResponse {
+    content: String,
+}
+
+/// Fill the template's placeholders from the folder signals and candidates.
+pub fn render_prompt(
+    template: &str,
+    signals: &FolderSignals,
+    candidate_ids: &[usize],
+    db: &MasterDb,
+) -> String {
+    let candidates = candidate_ids
+        .iter()
+        .filter_map(|&entry_id| {
+            let entry = db.entries.get(entry_id)?;
+            Some(format!(
+                "- ID: {entry_id}, Name: {}, Tags: {:?}",
+                entry.name, entry.tags
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    template
+        .replace("{folder_tokens}", &format!("{:?}", signals.folder_tokens))
+        .replace("{deep_tokens}", &format!("{:?}", signals.deep_name_tokens))
+        .replace(
+            "{section_tokens}",
+            &format!("{:?}", signals.ini_section_tokens),
+        )
+        .replace(
+            "{content_tokens}",
+            &format!("{:?}", signals.ini_content_tokens),
+        )
+        .replace("{candidates}", &candidates)
+}
+
+/// Pull the score object out of a model reply. Local models often wrap the
+/// JSON in prose or a code fence even when asked not to.
+fn parse_scores(reply: &str) -> Result<HashMap<String, f32>, ScannerError> {
+    let object = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => {
+            return Err(ScannerError::Parse {
+                what: "AI rerank reply".to_string(),
+                detail: "no JSON object in reply".to_string(),
+            })
+        }
+    };
+    Ok(serde_json::from_str(object)?)
+}
+
+impl AiRerankProvider for LocalAiRerankProvider {
+    fn cache_identity(&self) -> String {
+        // The server URL is left out: moving a model to another host keeps its
+        // scores. A custom template is hashed; its text can be long. The
+        // candidate window is kept: scores over a different window differ.
+        let prompt = blake3::hash(self.prompt_template.as_bytes()).to_hex();
+        format!(
+            "{:?}:{}:prompt-{}:top-{}",
+            self.protocol,
+            self.model.as_deref().unwrap_or_default(),
+            &prompt[..16],
+            self.max_candidates
+        )
+    }
+
+    fn rerank(
+        &self,
+        request: &AiRerankRequest,
+        signals: &FolderSignals,
+        db: &MasterDb,
+    ) -> Result<HashMap<usize, f32>, ScannerError> {
+        let candidate_ids: Vec<usize> = request
+            .candidate_entry_ids
+            .iter()
+            .copied()
+            .take(self.max_candidates)
+            .collect();
+        if candidate_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let prompt = render_prompt(&self.prompt_template, signals, &candidate_ids, db);
+        let res = self
+            .client
+            .post(&self.url)
+            .json(&self.request_body(prompt))
+            .send()?;
+
+        if !res.status().is_success() {
+            let status = res.status();
+            let text = res.text().unwrap_or_default();
+            return Err(ScannerError::Network(format!(
+                "Local AI server error {status}: {text}"
+            )));
+        }
+
+        let reply = match self.protocol {
+            LocalAiProtocol::Ollama => res.json::<OllamaResponse>()?.response,
+            LocalAiProtocol::LlamaCpp => res.json::<LlamaCppResponse>()?.content,
+        };
+
+        let mut result = HashMap::new();
+        for (string_id, score) in parse_scores(&reply)? {
+            let Ok(entry_id) = string_id.trim().parse::<usize>() else {
+                continue;
+            };
+            if candidate_ids.contains(&entry_id) {
+                result.insert(entry_id, score.clamp(0.0, 1.0));
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/analysis/ai_local_provider_tests.rs"]
+mod ai_local_provider_tests;
@@ -1,10 +1,18 @@
 use crate::domain::errors::ScannerError;
+use crate::services::config::models::AiConfig;
+use crate::services::scanner::deep_matcher::analysis::ai_local_provider::{
+    LocalAiRerankProvider, LocalAiSettings,
+};
 use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankProvider;
 use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
+const CHAT_MODEL: &str = "gpt-3.5-turbo-1106";
+/// Bumped whenever the prompt built in `rerank` changes wording.
+const CHAT_PROMPT_VERSION: u32 = 1;
+
 pub struct HttpAiRerankProvider {
     client: Client,
     api_key: String,
@@ -22,6 +30,26 @@ impl HttpAiRerankProvider {
     }
 }
 
+/// The rerank provider `config` selects, or `None` when AI rerank is off or the
+/// remote provider has no API key. Local providers need no key.
+pub fn build_rerank_provider(
+    config: &AiConfig,
+) -> Result<Option<Box<dyn AiRerankProvider>>, ScannerError> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    if let Some(settings) = LocalAiSettings::from_config(config) {
+        return Ok(Some(Box::new(LocalAiRerankProvider::new(settings)?)));
+    }
+    let Some(api_key) = config.api_key.clone().filter(|key| !key.trim().is_empty()) else {
+        return Ok(None);
+    };
+    Ok(Some(Box::new(HttpAiRerankProvider::new(
+        api_key,
+        config.base_url.clone(),
+    ))))
+}
+
 #[derive(Serialize)]
 struct ChatMessage {
     role: String,
@@ -58,6 +86,13 @@ struct ChatMessageResponse {
 }
 
 impl AiRerankProvider for HttpAiRerankProvider {
+    fn cache_identity(&self) -> String {
+        format!(
+            "chat:{}:{CHAT_MODEL}:prompt-v{CHAT_PROMPT_VERSION}",
+            self.base_url
+        )
+    }
+
     fn rerank(
         &self,
         request: &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankRequest,
@@ -107,7 +142,7 @@ impl AiRerankProvider for HttpAiRerankProvider {
         }
 
         let payload = ChatRequest {
-            model: "gpt-3.5-turbo-1106".to_string(),
+            model: CHAT_MODEL.to_string(),
             messages: vec![ChatMessage {
                 role: "user".to_string(),
                 content: prompt,
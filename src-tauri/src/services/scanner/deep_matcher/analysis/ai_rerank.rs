@@ -1,9 +1,9 @@
-use crate::common::sync::lock;
 use crate::domain::errors::ScannerError;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::Arc;
 
+pub use super::ai_rerank_cache::{AiRerankCache, AiRerankCacheKey};
 use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
 use crate::services::scanner::deep_matcher::analysis::scoring::cap_reasons;
 use crate::services::scanner::deep_matcher::{
@@ -14,12 +14,6 @@ pub const AI_ACCEPT_THRESHOLD: f32 = 0.7;
 pub const AI_ACCEPT_MARGIN: f32 = 0.15;
 const DEFAULT_DB_VERSION: &str = "db-version-unknown";
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct AiRerankCacheKey {
-    pub signals_hash: String,
-    pub db_version: String,
-}
-
 #[derive(Debug, Clone)]
 pub struct AiRerankRequest {
     pub mode: MatchMode,
@@ -28,6 +22,10 @@ pub struct AiRerankRequest {
 }
 
 pub trait AiRerankProvider: Send + Sync {
+    /// Provider, model and prompt this provider scores with. Part of the
+    /// cache key, so switching any of them does not reuse earlier scores.
+    fn cache_identity(&self) -> String;
+
     fn rerank(
         &self,
         request: &AiRerankRequest,
@@ -36,21 +34,6 @@ pub trait AiRerankProvider: Send + Sync {
     ) -> Result<HashMap<usize, f32>, ScannerError>;
 }
 
-#[derive(Debug, Default)]
-pub struct AiRerankCache {
-    entries: Mutex<HashMap<AiRerankCacheKey, HashMap<usize, f32>>>,
-}
-
-impl AiRerankCache {
-    pub fn get(&self, key: &AiRerankCacheKey) -> Option<HashMap<usize, f32>> {
-        lock(&self.entries).get(key).cloned()
-    }
-
-    pub fn insert(&self, key: AiRerankCacheKey, scores: HashMap<usize, f32>) {
-        lock(&self.entries).insert(key, scores);
-    }
-}
-
 #[derive(Default)]
 pub struct AiRerankConfig<'a> {
     pub ai_enabled: bool,
@@ -59,10 +42,31 @@ pub struct AiRerankConfig<'a> {
     pub cache: Option<&'a AiRerankCache>,
 }
 
+/// Owned form of [`AiRerankConfig`], for scans that move their matching onto
+/// worker threads. The default has no provider, so AI rerank stays off.
+#[derive(Clone, Default)]
+pub struct AiRerankContext {
+    pub provider: Option<Arc<dyn AiRerankProvider>>,
+    pub cache: Option<Arc<AiRerankCache>>,
+    pub db_version: Option<String>,
+}
+
+impl AiRerankContext {
+    pub fn config(&self) -> AiRerankConfig<'_> {
+        AiRerankConfig {
+            ai_enabled: self.provider.is_some(),
+            db_version: self.db_version.as_deref(),
+            provider: self.provider.as_deref(),
+            cache: self.cache.as_deref(),
+        }
+    }
+}
+
 pub fn build_ai_cache_key(
     signals: &FolderSignals,
     mode: MatchMode,
     db_version: &str,
+    provider: &str,
 ) -> AiRerankCacheKey {
     let mut digest = blake3::Hasher::new();
     digest.update(mode.to_string().as_bytes());
@@ -76,6 +80,7 @@ pub fn build_ai_cache_key(
     update_string_vec(&mut digest, b"hash", &signals.ini_hashes);
 
     AiRerankCacheKey {
+        provider: provider.to_string(),
         signals_hash: digest.finalize().to_hex().to_string(),
         db_version: db_version.to_string(),
     }
@@ -100,7 +105,7 @@ pub fn maybe_apply_ai_rerank(
         .db_version
         .filter(|value| !value.trim().is_empty())
         .unwrap_or(DEFAULT_DB_VERSION);
-    let cache_key = build_ai_cache_key(signals, mode, db_version);
+    let cache_key = build_ai_cache_key(signals, mode, db_version, &provider.cache_identity());
     let request = AiRerankRequest {
         mode,
         cache_key: cache_key.clone(),
@@ -150,6 +155,8 @@ fn resolve_ai_scores(
         }
     }
 
+    // A failure is not cached: the cache is persistent, and a timeout would
+    // otherwise pin empty scores to this folder for good.
     let scores: HashMap<usize, f32> = match provider.rerank(request, signals, db) {
         Ok(res) => res
             .into_iter()
@@ -157,7 +164,7 @@ fn resolve_ai_scores(
             .collect(),
         Err(e) => {
             log::error!("AI Rerank failed: {}", e);
-            HashMap::new()
+            return HashMap::new();
         }
     };
 
@@ -0,0 +1,192 @@
+//! Provider scores kept across scans and restarts.
+//!
+//! The file is JSON Lines: a `{"version": N}` header, then one line per
+//! cached result. An insert appends its line instead of rewriting the file,
+//! and a later line for the same key wins. Opening the cache compacts a file
+//! that carries superseded or unreadable lines.
+
+use crate::common::sync::lock;
+use crate::domain::errors::ScannerError;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::keyviewer::generator::atomic_write;
+
+/// Bumped when the on-disk cache layout changes; older files are discarded.
+const CACHE_FILE_VERSION: u32 = 2;
+
+/// Everything a provider score depends on. Entry ids are positions in the
+/// MasterDB, so `db_version` must change whenever those positions can.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AiRerankCacheKey {
+    /// Provider, model and prompt, from `AiRerankProvider::cache_identity`.
+    pub provider: String,
+    pub signals_hash: String,
+    pub db_version: String,
+}
+
+/// Provider scores by cache key. Opened with [`AiRerankCache::open`], every
+/// insert is appended to disk so results survive restarts.
+#[derive(Debug, Default)]
+pub struct AiRerankCache {
+    entries: Mutex<HashMap<AiRerankCacheKey, HashMap<usize, f32>>>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheHeader {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedScores {
+    provider: String,
+    signals_hash: String,
+    db_version: String,
+    scores: HashMap<usize, f32>,
+}
+
+impl CachedScores {
+    fn new(key: &AiRerankCacheKey, scores: &HashMap<usize, f32>) -> Self {
+        Self {
+            provider: key.provider.clone(),
+            signals_hash: key.signals_hash.clone(),
+            db_version: key.db_version.clone(),
+            scores: scores.clone(),
+        }
+    }
+}
+
+impl AiRerankCache {
+    /// Loads the cache persisted at `path`. A missing, unreadable or outdated
+    /// file starts an empty cache: losing it only costs provider calls.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = match load_cache_file(&path) {
+            Ok((entries, false)) => entries,
+            Ok((entries, true)) => {
+                if let Err(error) = save_cache_file(&path, &entries) {
+                    log::warn!("Failed to compact AI rerank cache: {error}");
+                }
+                entries
+            }
+            Err(error) => {
+                log::warn!(
+                    "AI rerank cache {} unreadable, starting empty: {error}",
+                    path.display()
+                );
+                HashMap::new()
+            }
+        };
+        Self {
+            entries: Mutex::new(entries),
+            path: Some(path),
+        }
+    }
+
+    pub fn get(&self, key: &AiRerankCacheKey) -> Option<HashMap<usize, f32>> {
+        lock(&self.entries).get(key).cloned()
+    }
+
+    pub fn insert(&self, key: AiRerankCacheKey, scores: HashMap<usize, f32>) {
+        let mut entries = lock(&self.entries);
+        // Appended under the lock so concurrent inserts land whole and in order.
+        if let Some(path) = &self.path {
+            if let Err(error) = append_entry(path, &CachedScores::new(&key, &scores)) {
+                log::warn!("Failed to persist AI rerank cache: {error}");
+            }
+        }
+        entries.insert(key, scores);
+    }
+
+    pub fn len(&self) -> usize {
+        lock(&self.entries).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+type CacheEntries = HashMap<AiRerankCacheKey, HashMap<usize, f32>>;
+
+/// The cached entries, and whether the file should be rewritten.
+fn load_cache_file(path: &Path) -> Result<(CacheEntries, bool), ScannerError> {
+    if !path.exists() {
+        return Ok((HashMap::new(), false));
+    }
+    let mut lines = BufReader::new(File::open(path)?).lines();
+    let header = match lines.next() {
+        Some(line) => serde_json::from_str::<CacheHeader>(&line?).ok(),
+        None => None,
+    };
+    if header.is_none_or(|header| header.version != CACHE_FILE_VERSION) {
+        return Ok((HashMap::new(), true));
+    }
+
+    let mut entries = HashMap::new();
+    let mut stale = false;
+    for line in lines {
+        // A torn last line from a crash mid-append is dropped, not fatal.
+        let Ok(cached) = serde_json::from_str::<CachedScores>(&line?) else {
+            stale = true;
+            continue;
+        };
+        let key = AiRerankCacheKey {
+            provider: cached.provider,
+            signals_hash: cached.signals_hash,
+            db_version: cached.db_version,
+        };
+        stale |= entries.insert(key, cached.scores).is_some();
+    }
+    Ok((entries, stale))
+}
+
+fn header_line() -> Result<String, ScannerError> {
+    Ok(serde_json::to_string(&CacheHeader {
+        version: CACHE_FILE_VERSION,
+    })?)
+}
+
+fn append_entry(path: &Path, cached: &CachedScores) -> Result<(), ScannerError> {
+    let mut text = String::new();
+    if !path.exists() {
+        text.push_str(&header_line()?);
+        text.push('\n');
+    }
+    text.push_str(&serde_json::to_string(cached)?);
+    text.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+fn save_cache_file(path: &Path, entries: &CacheEntries) -> Result<(), ScannerError> {
+    let mut cached: Vec<CachedScores> = entries
+        .iter()
+        .map(|(key, scores)| CachedScores::new(key, scores))
+        .collect();
+    cached.sort_by(|left, right| {
+        (&left.provider, &left.db_version, &left.signals_hash).cmp(&(
+            &right.provider,
+            &right.db_version,
+            &right.signals_hash,
+        ))
+    });
+    let mut text = header_line()?;
+    text.push('\n');
+    for entry in &cached {
+        text.push_str(&serde_json::to_string(entry)?);
+        text.push('\n');
+    }
+    atomic_write(path, &text).map_err(|error| ScannerError::Io(error.to_string()))
+}
+
+#[cfg(test)]
+#[path = "../tests/analysis/ai_rerank_cache_tests.rs"]
+mod ai_rerank_cache_tests;
@@ -6,9 +6,11 @@
 //! - Deep signal collector with budgets (stubs for Task 8+)
 
 use crate::domain::errors::ScannerError;
+use crate::services::ini::ast::parse_ini_text;
 use std::fs;
 use std::path::Path;
 mod signal_collector;
+mod token_filters;
 mod tokenizer;
 
 pub use signal_collector::{
@@ -16,9 +18,8 @@ pub use signal_collector::{
     FULL_MAX_TOTAL_INI_BYTES, QUICK_MAX_INI_BYTES_PER_FILE, QUICK_MAX_INI_FILES,
     QUICK_MAX_NAME_ITEMS,
 };
-pub use tokenizer::{
-    extract_structural_ini_tokens, IniTokenBuckets, IniTokenizationConfig, PreparedTokenFilters,
-};
+pub use token_filters::{IniTokenizationConfig, PreparedTokenFilters};
+pub use tokenizer::{extract_structural_ini_tokens, IniTokenBuckets};
 
 /// Extract hashes from raw INI text.
 ///
@@ -31,34 +32,12 @@ pub use tokenizer::{
 ///
 /// # Covers: Task 6 - Hash Extraction
 pub fn extract_hashes_from_ini_text(text: &str) -> Vec<String> {
-    let mut hashes = Vec::new();
-
-    for line in text.lines() {
-        let trimmed = line.trim();
-
-        // Split on '=' to separate key and value. No pre-filter on the whole
-        // line: it would allocate a lowercased copy of every line in the file
-        // to answer a question the key check below answers exactly.
-        let Some((key, value)) = trimmed.split_once('=') else {
-            continue;
-        };
-
-        if !key.trim().eq_ignore_ascii_case("hash") {
-            continue;
-        }
-
-        let value = value.trim();
-        if value.is_empty() {
-            continue;
-        }
-
-        // Normalize and validate hash
-        if let Some(normalized) = normalize_hash(value) {
-            hashes.push(normalized);
-        }
-    }
-
-    hashes
+    parse_ini_text(text)
+        .assignments()
+        .into_iter()
+        .filter(|assignment| assignment.key_is("hash"))
+        .filter_map(|assignment| normalize_hash(&assignment.value))
+        .collect()
 }
 
 /// Normalize a hash value to lowercase 8-hex.
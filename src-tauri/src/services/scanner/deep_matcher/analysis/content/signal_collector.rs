@@ -3,6 +3,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::common::normalizer;
+use crate::services::keyviewer::harvester;
 use crate::services::scanner::core::walker::FolderContent;
 use crate::services::scanner::deep_matcher::MatchMode;
 
@@ -18,7 +19,7 @@ pub const FULL_MAX_INI_FILES: usize = 10;
 pub const FULL_MAX_TOTAL_INI_BYTES: usize = 1024 * 1024;
 pub const FULL_MAX_NAME_ITEMS: usize = 500;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct FolderSignals {
     pub folder_tokens: Vec<String>,
     pub deep_name_tokens: Vec<String>,
@@ -33,6 +34,10 @@ pub struct FolderSignals {
     pub ini_section_tokens: Vec<String>,
     pub ini_content_tokens: Vec<String>,
     pub ini_hashes: Vec<String>,
+    /// Resource hashes of `TextureOverride*` sections only, read the way the
+    /// KeyViewer harvester reads them. `ini_hashes` also carries shader and
+    /// utility hashes; these are the ones that identify an object.
+    pub override_hashes: Vec<String>,
     pub scanned_ini_files: usize,
     pub scanned_name_items: usize,
     pub scanned_ini_bytes: usize,
@@ -105,6 +110,7 @@ pub fn collect_deep_signals(
     let mut ini_section_tokens = BTreeSet::new();
     let mut ini_content_tokens = BTreeSet::new();
     let mut ini_hashes = BTreeSet::new();
+    let mut override_hashes = BTreeSet::new();
     let mut ini_derived_strings_set = BTreeSet::new();
     let mut scanned_ini_files = 0_usize;
     let mut scanned_ini_bytes = 0_usize;
@@ -147,6 +153,9 @@ pub fn collect_deep_signals(
         for hash in extract_hashes_from_ini_text(&ini_text) {
             ini_hashes.insert(hash);
         }
+        for harvested in harvester::harvest_hashes_from_text(&ini_text, &ini_path) {
+            override_hashes.insert(harvested.hash);
+        }
 
         let buckets = extract_structural_ini_tokens(&ini_text, ini_filters);
         for token in buckets.section_tokens {
@@ -193,6 +202,7 @@ pub fn collect_deep_signals(
         ini_section_tokens: ini_section_tokens.into_iter().collect(),
         ini_content_tokens: ini_content_tokens.into_iter().collect(),
         ini_hashes: ini_hashes.into_iter().collect(),
+        override_hashes: override_hashes.into_iter().collect(),
         scanned_ini_files,
         scanned_name_items: name_items.len(),
         scanned_ini_bytes,
@@ -279,6 +289,7 @@ fn compute_fingerprint(signals: &FolderSignals) -> String {
     hash_string_vec(&mut hasher, b"section", &signals.ini_section_tokens);
     hash_string_vec(&mut hasher, b"content", &signals.ini_content_tokens);
     hash_string_vec(&mut hasher, b"hash", &signals.ini_hashes);
+    hash_string_vec(&mut hasher, b"override", &signals.override_hashes);
     hasher.update(&signals.scanned_ini_files.to_le_bytes());
     hasher.update(&signals.scanned_name_items.to_le_bytes());
     hasher.update(&signals.scanned_ini_bytes.to_le_bytes());
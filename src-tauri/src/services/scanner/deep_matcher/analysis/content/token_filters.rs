@@ -0,0 +1,149 @@
+//! The stopword and INI key filters the tokenizer applies.
+
+use std::collections::BTreeSet;
+
+use super::tokenizer::{normalize_key, normalize_simple};
+
+const DEFAULT_STOPWORDS: &[&str] = &[
+    "mod",
+    "skin",
+    "preset",
+    "version",
+    "ver",
+    "v",
+    "fix",
+    "shader",
+    "tex",
+    "texture",
+    "override",
+    "resource",
+    "commandlist",
+    "key",
+    "ini",
+    "dds",
+];
+
+const DEFAULT_INI_KEY_BLACKLIST: &[&str] = &[
+    "run",
+    "handling",
+    "match_priority",
+    "drawindexed",
+    "vb",
+    "ib",
+    "ps",
+    "vs",
+    "cs",
+    "format",
+    "stride",
+];
+
+const DEFAULT_INI_KEY_WHITELIST: &[&str] = &[
+    "texture",
+    "resource",
+    "filename",
+    "path",
+    "name",
+    "character",
+];
+
+/// The tokenization rules as configured — a spec, not a working set.
+///
+/// Merging this with the defaults costs roughly forty `String`s and four
+/// B-trees, so call [`IniTokenizationConfig::prepare`] once per scan and pass
+/// the result down. It used to be merged on entry to
+/// [`extract_structural_ini_tokens`], i.e. once per INI file, which is over a
+/// million throwaway allocations at the 10k-mod design target.
+#[derive(Debug, Clone, Default)]
+pub struct IniTokenizationConfig {
+    pub stopwords: Vec<String>,
+    pub short_token_whitelist: Vec<String>,
+    pub ini_key_blacklist: Vec<String>,
+    pub ini_key_whitelist: Vec<String>,
+}
+
+/// [`IniTokenizationConfig`] merged with the defaults, ready to filter with.
+#[derive(Debug, Clone, Default)]
+pub struct PreparedTokenFilters {
+    pub(super) stopwords: BTreeSet<String>,
+    pub(super) short_whitelist: BTreeSet<String>,
+    pub(super) key_blacklist: BTreeSet<String>,
+    pub(super) key_whitelist: BTreeSet<String>,
+}
+
+impl IniTokenizationConfig {
+    /// Merge with the built-in defaults. Do this once per scan.
+    pub fn prepare(&self) -> PreparedTokenFilters {
+        PreparedTokenFilters {
+            stopwords: merged_stopwords(self),
+            short_whitelist: normalized_set(&self.short_token_whitelist),
+            key_blacklist: merged_key_blacklist(self),
+            key_whitelist: merged_key_whitelist(self),
+        }
+    }
+}
+
+impl PreparedTokenFilters {
+    /// Every filter set under a stable label, for fingerprinting the filters
+    /// that signals were collected with.
+    pub fn labeled_sets(&self) -> [(&'static str, &BTreeSet<String>); 4] {
+        [
+            ("stopwords", &self.stopwords),
+            ("short", &self.short_whitelist),
+            ("keyblack", &self.key_blacklist),
+            ("keywhite", &self.key_whitelist),
+        ]
+    }
+}
+
+fn merged_stopwords(config: &IniTokenizationConfig) -> BTreeSet<String> {
+    let mut set = BTreeSet::new();
+    for token in DEFAULT_STOPWORDS {
+        set.insert((*token).to_string());
+    }
+    for token in &config.stopwords {
+        let normalized = normalize_simple(token);
+        if !normalized.is_empty() {
+            set.insert(normalized);
+        }
+    }
+    set
+}
+
+fn merged_key_blacklist(config: &IniTokenizationConfig) -> BTreeSet<String> {
+    let mut set = BTreeSet::new();
+    for key in DEFAULT_INI_KEY_BLACKLIST {
+        set.insert((*key).to_string());
+    }
+    for key in &config.ini_key_blacklist {
+        let normalized = normalize_key(key);
+        if !normalized.is_empty() {
+            set.insert(normalized);
+        }
+    }
+    set
+}
+
+fn merged_key_whitelist(config: &IniTokenizationConfig) -> BTreeSet<String> {
+    let mut set = DEFAULT_INI_KEY_WHITELIST
+        .iter()
+        .map(|key| (*key).to_string())
+        .collect::<BTreeSet<_>>();
+    for key in &config.ini_key_whitelist {
+        let normalized = normalize_key(key);
+        if !normalized.is_empty() {
+            set.insert(normalized);
+        }
+    }
+    set
+}
+
+fn normalized_set(values: &[String]) -> BTreeSet<String> {
+    let mut set = BTreeSet::new();
+    for value in values {
+        let normalized = normalize_simple(value);
+        if !normalized.is_empty() {
+            set.insert(normalized);
+        }
+    }
+    set
+}
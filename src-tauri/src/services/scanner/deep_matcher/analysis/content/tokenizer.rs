@@ -1,46 +1,6 @@
 use std::collections::BTreeSet;
 
-const DEFAULT_STOPWORDS: &[&str] = &[
-    "mod",
-    "skin",
-    "preset",
-    "version",
-    "ver",
-    "v",
-    "fix",
-    "shader",
-    "tex",
-    "texture",
-    "override",
-    "resource",
-    "commandlist",
-    "key",
-    "ini",
-    "dds",
-];
-
-const DEFAULT_INI_KEY_BLACKLIST: &[&str] = &[
-    "run",
-    "handling",
-    "match_priority",
-    "drawindexed",
-    "vb",
-    "ib",
-    "ps",
-    "vs",
-    "cs",
-    "format",
-    "stride",
-];
-
-const DEFAULT_INI_KEY_WHITELIST: &[&str] = &[
-    "texture",
-    "resource",
-    "filename",
-    "path",
-    "name",
-    "character",
-];
+use super::token_filters::PreparedTokenFilters;
 
 const SECTION_PREFIX_BLACKLIST: &[&str] = &[
     "textureoverride",
@@ -67,42 +27,6 @@ pub struct IniTokenBuckets {
     pub path_strings: Vec<String>,
 }
 
-/// The tokenization rules as configured — a spec, not a working set.
-///
-/// Merging this with the defaults costs roughly forty `String`s and four
-/// B-trees, so call [`IniTokenizationConfig::prepare`] once per scan and pass
-/// the result down. It used to be merged on entry to
-/// [`extract_structural_ini_tokens`], i.e. once per INI file, which is over a
-/// million throwaway allocations at the 10k-mod design target.
-#[derive(Debug, Clone, Default)]
-pub struct IniTokenizationConfig {
-    pub stopwords: Vec<String>,
-    pub short_token_whitelist: Vec<String>,
-    pub ini_key_blacklist: Vec<String>,
-    pub ini_key_whitelist: Vec<String>,
-}
-
-/// [`IniTokenizationConfig`] merged with the defaults, ready to filter with.
-#[derive(Debug, Clone, Default)]
-pub struct PreparedTokenFilters {
-    stopwords: BTreeSet<String>,
-    short_whitelist: BTreeSet<String>,
-    key_blacklist: BTreeSet<String>,
-    key_whitelist: BTreeSet<String>,
-}
-
-impl IniTokenizationConfig {
-    /// Merge with the built-in defaults. Do this once per scan.
-    pub fn prepare(&self) -> PreparedTokenFilters {
-        PreparedTokenFilters {
-            stopwords: merged_stopwords(self),
-            short_whitelist: normalized_set(&self.short_token_whitelist),
-            key_blacklist: merged_key_blacklist(self),
-            key_whitelist: merged_key_whitelist(self),
-        }
-    }
-}
-
 /// Extract structural token buckets from INI text.
 ///
 /// Buckets are deterministic (sorted, deduped):
@@ -185,59 +109,6 @@ pub fn extract_structural_ini_tokens(
     }
 }
 
-fn merged_stopwords(config: &IniTokenizationConfig) -> BTreeSet<String> {
-    let mut set = BTreeSet::new();
-    for token in DEFAULT_STOPWORDS {
-        set.insert((*token).to_string());
-    }
-    for token in &config.stopwords {
-        let normalized = normalize_simple(token);
-        if !normalized.is_empty() {
-            set.insert(normalized);
-        }
-    }
-    set
-}
-
-fn merged_key_blacklist(config: &IniTokenizationConfig) -> BTreeSet<String> {
-    let mut set = BTreeSet::new();
-    for key in DEFAULT_INI_KEY_BLACKLIST {
-        set.insert((*key).to_string());
-    }
-    for key in &config.ini_key_blacklist {
-        let normalized = normalize_key(key);
-        if !normalized.is_empty() {
-            set.insert(normalized);
-        }
-    }
-    set
-}
-
-fn merged_key_whitelist(config: &IniTokenizationConfig) -> BTreeSet<String> {
-    let mut set = DEFAULT_INI_KEY_WHITELIST
-        .iter()
-        .map(|key| (*key).to_string())
-        .collect::<BTreeSet<_>>();
-    for key in &config.ini_key_whitelist {
-        let normalized = normalize_key(key);
-        if !normalized.is_empty() {
-            set.insert(normalized);
-        }
-    }
-    set
-}
-
-fn normalized_set(values: &[String]) -> BTreeSet<String> {
-    let mut set = BTreeSet::new();
-    for value in values {
-        let normalized = normalize_simple(value);
-        if !normalized.is_empty() {
-            set.insert(normalized);
-        }
-    }
-    set
-}
-
 fn insert_tokens<'a>(
     destination: &mut BTreeSet<String>,
     tokens: impl Iterator<Item = &'a str>,
@@ -303,11 +174,11 @@ fn prepare_path_like_rhs(input: &str) -> String {
     prepared
 }
 
-fn normalize_simple(input: &str) -> String {
+pub(super) fn normalize_simple(input: &str) -> String {
     input.trim().to_ascii_lowercase()
 }
 
-fn normalize_key(input: &str) -> String {
+pub(super) fn normalize_key(input: &str) -> String {
     input
         .trim()
         .chars()
@@ -15,9 +15,6 @@ static GB_URL_REGEX: LazyLock<Regex> =
 /// Returns deduplicated refs. Scans ini_content_tokens, deep_name_strings,
 /// and folder_tokens.
 pub fn detect_gamebanana_ids(signals: &FolderSignals) -> Vec<GameBananaRef> {
-    let mut seen = std::collections::HashSet::new();
-    let mut refs = Vec::new();
-
     let all_strings = signals
         .ini_content_tokens
         .iter()
@@ -25,7 +22,19 @@ pub fn detect_gamebanana_ids(signals: &FolderSignals) -> Vec<GameBananaRef> {
         .chain(signals.folder_tokens.iter())
         .chain(signals.ini_derived_strings.iter());
 
-    for text in all_strings {
+    detect_gamebanana_ids_in_text(all_strings.map(String::as_str))
+}
+
+/// Scan free text (an `info.json` description, a readme) for
+/// `gamebanana.com/<type>/<id>` patterns. Returns deduplicated refs in order
+/// of first appearance.
+pub fn detect_gamebanana_ids_in_text<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+) -> Vec<GameBananaRef> {
+    let mut seen = std::collections::HashSet::new();
+    let mut refs = Vec::new();
+
+    for text in texts {
         for capture in GB_URL_REGEX.captures_iter(text) {
             let item_type = capitalize_type(&capture[1]);
             let Ok(item_id) = capture[2].parse::<u64>() else {
@@ -1,8 +1,9 @@
 //! Deterministic token/hash indexes for staged matcher seeding and scoring.
 
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
-use crate::services::scanner::deep_matcher::DbEntry;
+use crate::common::normalizer;
+use crate::services::scanner::deep_matcher::{DbEntry, UserCorrection};
 
 /// Deterministic posting list keyed by normalized token/hash.
 pub type PostingList = Vec<usize>;
@@ -14,8 +15,25 @@ pub struct MatcherIndexes {
     pub hash_index: BTreeMap<String, PostingList>,
     pub token_df: BTreeMap<String, usize>,
     pub hash_df: BTreeMap<String, usize>,
+    /// User corrections keyed by normalized folder name.
+    pub correction_patterns: BTreeMap<String, usize>,
+    /// The same corrections as word sets, for folders that carry every word
+    /// of a corrected name plus some of their own (a version, an author).
+    pub correction_hints: Vec<CorrectionHint>,
 }
 
+/// A user correction reduced to the words of its folder-name pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectionHint {
+    pub pattern: String,
+    pub tokens: BTreeSet<String>,
+    pub entry_id: usize,
+}
+
+/// A one-word pattern as a hint would fire on every folder carrying that
+/// word; it only ever matches exactly.
+const MIN_HINT_TOKENS: usize = 2;
+
 impl MatcherIndexes {
     /// Build deterministic token/hash indexes and document-frequency maps.
     pub fn build(entries: &[DbEntry], keywords: &[(usize, HashSet<String>)]) -> Self {
@@ -55,7 +73,46 @@ impl MatcherIndexes {
             hash_index,
             token_df,
             hash_df,
+            correction_patterns: BTreeMap::new(),
+            correction_hints: Vec::new(),
+        }
+    }
+
+    /// Index user corrections against `entries`.
+    ///
+    /// A correction naming no entry is dropped, and a pattern corrected twice
+    /// keeps the later entry, matching how the store overwrites it.
+    pub fn attach_corrections(&mut self, entries: &[DbEntry], corrections: &[UserCorrection]) {
+        let entry_ids: BTreeMap<&str, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(entry_id, entry)| (entry.name.as_str(), entry_id))
+            .collect();
+
+        for correction in corrections {
+            let Some(&entry_id) = entry_ids.get(correction.entry_name.as_str()) else {
+                continue;
+            };
+            let pattern = normalizer::normalize_for_matching_default(&correction.folder_name);
+            if pattern.is_empty() {
+                continue;
+            }
+            self.correction_patterns.insert(pattern, entry_id);
         }
+
+        self.correction_hints = self
+            .correction_patterns
+            .iter()
+            .filter_map(|(pattern, entry_id)| {
+                let tokens: BTreeSet<String> =
+                    pattern.split_whitespace().map(str::to_string).collect();
+                (tokens.len() >= MIN_HINT_TOKENS).then(|| CorrectionHint {
+                    pattern: pattern.clone(),
+                    tokens,
+                    entry_id: *entry_id,
+                })
+            })
+            .collect();
     }
 
     /// IDF-lite helper: ln((N+1)/(df+1)) + 1.
@@ -93,7 +150,7 @@ fn finalize_postings(index: &mut BTreeMap<String, PostingList>) {
     }
 }
 
-fn normalize_hash(raw: &str) -> Option<String> {
+pub(crate) fn normalize_hash(raw: &str) -> Option<String> {
     let mut hash = raw.trim().to_lowercase();
     if let Some(stripped) = hash.strip_prefix("0x") {
         hash = stripped.to_string();
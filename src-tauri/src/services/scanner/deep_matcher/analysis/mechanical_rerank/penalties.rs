@@ -4,7 +4,16 @@ use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{Candidate, Reason};
 
-pub(super) fn count_foreign_strong_hits(
+use super::config::MechanicalRerankConfig;
+
+const PENALTY_FOREIGN: f32 = -3.0;
+const PENALTY_FOREIGN_CAP: f32 = -12.0;
+const PENALTY_MULTI_ENTITY: f32 = -4.0;
+const PENALTY_TYPE_MISMATCH: f32 = -2.0;
+const PENALTY_RESCUE_ONLY: f32 = -8.0;
+pub(super) const PENALTY_GB_CATEGORY_MISMATCH: f32 = -15.0; // Heavy penalty for wrong object category
+
+fn count_foreign_strong_hits(
     candidate: &Candidate,
     _signals: &FolderSignals,
     _db: &MasterDb,
@@ -18,17 +27,17 @@ pub(super) fn count_foreign_strong_hits(
 
 /// Multi-entity detection: not yet tracked via Reason variants.
 /// Placeholder for future `Reason::AmbiguityNote`.
-pub(super) fn is_multi_entity(_candidate: &Candidate) -> bool {
+fn is_multi_entity(_candidate: &Candidate) -> bool {
     false
 }
 
 /// Type mismatch detection: not yet tracked via Reason variants.
 /// Placeholder for future `Reason::ObjectTypeMismatch`.
-pub(super) fn has_type_mismatch(_candidate: &Candidate) -> bool {
+fn has_type_mismatch(_candidate: &Candidate) -> bool {
     false
 }
 
-pub(super) fn is_rescue_only(candidate: &Candidate) -> bool {
+fn is_rescue_only(candidate: &Candidate) -> bool {
     candidate.reasons.iter().all(|reason| {
         matches!(
             reason,
@@ -36,3 +45,50 @@ pub(super) fn is_rescue_only(candidate: &Candidate) -> bool {
         )
     }) && !candidate.reasons.is_empty()
 }
+
+/// The penalties `candidate` takes, zero or negative. `object_type` is its
+/// MasterDB entry's type, checked against the GameBanana category.
+pub(super) fn penalty_points(
+    candidate: &Candidate,
+    signals: &FolderSignals,
+    db: &MasterDb,
+    object_type: &str,
+    config: &MechanicalRerankConfig,
+) -> f32 {
+    let mut penalties = 0.0_f32;
+    let foreign_strong = count_foreign_strong_hits(candidate, signals, db);
+    penalties += (PENALTY_FOREIGN * foreign_strong as f32).max(PENALTY_FOREIGN_CAP);
+
+    if is_multi_entity(candidate) {
+        penalties += PENALTY_MULTI_ENTITY;
+    }
+
+    if has_type_mismatch(candidate) {
+        penalties += PENALTY_TYPE_MISMATCH;
+    }
+
+    if is_rescue_only(candidate) {
+        penalties += PENALTY_RESCUE_ONLY;
+    }
+
+    // Category mismatch
+    if let Some(ref gb_cat) = config.gb_root_category {
+        let cat_lower = gb_cat.to_lowercase();
+        let obj_type_lower = object_type.to_lowercase();
+
+        if cat_lower == "skins" {
+            // "Skins" should generally only map to Character/Avatar/NPC
+            let is_character_type = obj_type_lower.contains("character")
+                || obj_type_lower.contains("avatar")
+                || obj_type_lower.contains("npc")
+                || obj_type_lower.contains("monster");
+
+            if !is_character_type {
+                penalties += PENALTY_GB_CATEGORY_MISMATCH;
+            }
+        }
+        // Could expand to "Weapons" -> contains("weapon"), etc.
+    }
+
+    penalties
+}
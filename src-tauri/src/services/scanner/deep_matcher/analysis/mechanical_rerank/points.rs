@@ -1,4 +1,4 @@
-//! Points-based scoring for each candidate, plus penalty helpers.
+//! Points-based scoring for each candidate.
 
 use crate::common::normalizer;
 use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
@@ -6,9 +6,7 @@ use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{Candidate, Reason};
 
 use super::config::MechanicalRerankConfig;
-use super::penalties::{
-    count_foreign_strong_hits, has_type_mismatch, is_multi_entity, is_rescue_only,
-};
+use super::penalties::penalty_points;
 
 // ── Point Constants ──────────────────────────────────────────────────
 
@@ -36,24 +34,42 @@ const PT_DEEP_RATIO_MED: f32 = 7.0;
 const PT_DEEP_RATIO_LOW: f32 = 4.0;
 const PT_INI_HITS_2: f32 = 6.0;
 const PT_INI_HITS_1: f32 = 3.0;
-const PENALTY_FOREIGN: f32 = -3.0;
-const PENALTY_FOREIGN_CAP: f32 = -12.0;
-const PENALTY_MULTI_ENTITY: f32 = -4.0;
-const PENALTY_TYPE_MISMATCH: f32 = -2.0;
-const PENALTY_RESCUE_ONLY: f32 = -8.0;
-const PENALTY_GB_CATEGORY_MISMATCH: f32 = -15.0; // Heavy penalty for wrong object category
 
 const MAX_NAME_WORD_HITS: usize = 2;
 const MAX_TAG_HITS: usize = 2;
 
 // ── Scoring ──────────────────────────────────────────────────────────
 
+/// A candidate's points, with the penalties kept apart for tracing.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct PointsBreakdown {
+    pub points: f32,
+    /// Zero or negative.
+    pub penalties: f32,
+}
+
+impl PointsBreakdown {
+    pub fn total(self) -> f32 {
+        self.points + self.penalties
+    }
+}
+
+#[cfg(test)]
 pub(super) fn compute_points(
     candidate: &Candidate,
     signals: &FolderSignals,
     db: &MasterDb,
     config: &MechanicalRerankConfig,
 ) -> f32 {
+    compute_points_breakdown(candidate, signals, db, config).total()
+}
+
+pub(super) fn compute_points_breakdown(
+    candidate: &Candidate,
+    signals: &FolderSignals,
+    db: &MasterDb,
+    config: &MechanicalRerankConfig,
+) -> PointsBreakdown {
     let entry = &db.entries[candidate.entry_id];
     let name_norm = normalizer::normalize_for_matching_default(&entry.name);
     let name_condensed = name_norm.replace(' ', "");
@@ -282,42 +298,10 @@ pub(super) fn compute_points(
         pts += PT_INI_HITS_1;
     }
 
-    // ── Penalties ────────────────────────────────────────────────
-    let foreign_strong = count_foreign_strong_hits(candidate, signals, db);
-    pts += (PENALTY_FOREIGN * foreign_strong as f32).max(PENALTY_FOREIGN_CAP);
-
-    if is_multi_entity(candidate) {
-        pts += PENALTY_MULTI_ENTITY;
-    }
-
-    if has_type_mismatch(candidate) {
-        pts += PENALTY_TYPE_MISMATCH;
-    }
-
-    if is_rescue_only(candidate) {
-        pts += PENALTY_RESCUE_ONLY;
-    }
-
-    // 3. Category Mismatch Penalty
-    if let Some(ref gb_cat) = config.gb_root_category {
-        let cat_lower = gb_cat.to_lowercase();
-        let obj_type_lower = entry.object_type.to_lowercase();
-
-        if cat_lower == "skins" {
-            // "Skins" should generally only map to Character/Avatar/NPC
-            let is_character_type = obj_type_lower.contains("character")
-                || obj_type_lower.contains("avatar")
-                || obj_type_lower.contains("npc")
-                || obj_type_lower.contains("monster");
-
-            if !is_character_type {
-                pts += PENALTY_GB_CATEGORY_MISMATCH;
-            }
-        }
-        // Could expand to "Weapons" -> contains("weapon"), etc.
+    PointsBreakdown {
+        points: pts,
+        penalties: penalty_points(candidate, signals, db, &entry.object_type, config),
     }
-
-    pts
 }
 
 #[cfg(test)]
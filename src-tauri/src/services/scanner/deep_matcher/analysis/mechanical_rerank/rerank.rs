@@ -6,13 +6,14 @@ use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
 use crate::services::scanner::deep_matcher::analysis::scoring::{
     cap_reasons, has_primary_evidence,
 };
+use crate::services::scanner::deep_matcher::models::trace::{MatchTracer, RerankPointsTrace};
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{
     sort_candidates_deterministic, Confidence, MatchStatus, Reason, StagedMatchResult,
 };
 
 use super::config::MechanicalRerankConfig;
-use super::points::compute_points;
+use super::points::compute_points_breakdown;
 
 const SCORE_DIVISOR: f32 = 30.0;
 const MIN_POINT_DELTA: f32 = 1.0;
@@ -28,6 +29,17 @@ pub fn mechanical_rerank(
     signals: &FolderSignals,
     db: &MasterDb,
     config: &MechanicalRerankConfig,
+) -> StagedMatchResult {
+    mechanical_rerank_traced(result, signals, db, config, None)
+}
+
+/// [`mechanical_rerank`], recording each candidate's points into `trace`.
+pub fn mechanical_rerank_traced(
+    result: StagedMatchResult,
+    signals: &FolderSignals,
+    db: &MasterDb,
+    config: &MechanicalRerankConfig,
+    trace: Option<&MatchTracer>,
 ) -> StagedMatchResult {
     if !config.enabled || result.status != MatchStatus::NeedsReview {
         return result;
@@ -41,8 +53,16 @@ pub fn mechanical_rerank(
         .candidates_topk
         .iter()
         .map(|c| {
-            let pts = compute_points(c, signals, db, config);
-            (c.entry_id, pts)
+            let breakdown = compute_points_breakdown(c, signals, db, config);
+            if let Some(tracer) = trace {
+                tracer.record_rerank_points(RerankPointsTrace {
+                    entry_id: c.entry_id,
+                    name: c.name.clone(),
+                    points: breakdown.points,
+                    penalties: breakdown.penalties,
+                });
+            }
+            (c.entry_id, breakdown.total())
         })
         .collect();
 
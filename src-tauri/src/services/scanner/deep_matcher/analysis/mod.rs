@@ -1,5 +1,7 @@
+pub mod ai_local_provider;
 pub mod ai_provider;
 pub mod ai_rerank;
+pub mod ai_rerank_cache;
 pub mod content;
 pub mod gamebanana;
 pub mod indexes;
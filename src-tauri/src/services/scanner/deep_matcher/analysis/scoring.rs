@@ -24,6 +24,22 @@ pub fn apply_hash_contribution(
     upsert_hash_reason(state);
 }
 
+pub fn apply_user_correction_contribution(
+    state: &mut ScoreState,
+    pattern: &str,
+    exact: bool,
+    score_delta: f32,
+) {
+    add_score(state, score_delta);
+    push_reason_capped(
+        state,
+        Reason::UserCorrection {
+            pattern: pattern.to_string(),
+            exact,
+        },
+    );
+}
+
 pub fn apply_alias_contribution(state: &mut ScoreState, alias: &str, score_delta: f32) {
     if alias.trim().is_empty() {
         return;
@@ -111,6 +127,7 @@ pub fn has_primary_evidence(reasons: &[Reason]) -> bool {
     reasons.iter().any(|reason| match reason {
         Reason::HashOverlap { overlap, .. } => *overlap >= 1,
         Reason::AliasStrict { .. }
+        | Reason::UserCorrection { .. }
         | Reason::DeepNameToken { .. }
         | Reason::IniSectionToken { .. }
         | Reason::IniContentToken { .. }
@@ -182,7 +199,7 @@ pub(crate) fn cap_reasons(reasons: &mut Vec<Reason>) {
 fn reason_priority(reason: &Reason) -> usize {
     match reason {
         Reason::HashOverlap { .. } => 0,
-        Reason::AliasStrict { .. } => 1,
+        Reason::AliasStrict { .. } | Reason::UserCorrection { .. } => 1,
         Reason::SubstringName { .. } => 2,
         Reason::NegativeEvidence { .. } => 3,
         Reason::TokenOverlap { .. } => 4,
@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+
 use crate::common::normalizer;
-use crate::services::scanner::deep_matcher::{MasterDb, MatchStatus, StagedMatchResult};
+use crate::services::scanner::deep_matcher::analysis::indexes::normalize_hash;
+use crate::services::scanner::deep_matcher::{DbEntry, MasterDb, MatchStatus, StagedMatchResult};
 
 /// Detect skin/variant for a staged match result.
 ///
 /// Checks the best candidate's object against `custom_skins` in the MasterDb.
-/// Returns `(skin_name, skin_folder_name)` if a skin token overlaps the folder name tokens.
+/// The matched resource hashes are tried first against the entry's per-skin
+/// `hash_db`; folder-name tokens are the fallback.
+/// Returns `(skin_name, skin_folder_name)` if either resolves a skin.
 ///
 /// # Covers: Epic 2 §B.3
 pub fn detect_skin_for_staged(
@@ -17,7 +22,7 @@ pub fn detect_skin_for_staged(
             .best
             .as_ref()
             .or_else(|| result.candidates_topk.first()),
-        MatchStatus::NoMatch => None,
+        MatchStatus::NoMatch | MatchStatus::MultiObjectPack => None,
     };
 
     let Some(best_candidate) = best else {
@@ -45,6 +50,15 @@ pub fn detect_skin_for_staged(
         );
     }
 
+    if let Some(resolved) = skin_from_hashes(entry, &result.evidence.matched_hashes) {
+        log::debug!(
+            "Skin resolved via hashes: '{}' -> skin '{}'",
+            folder_name,
+            resolved.0
+        );
+        return (Some(resolved.0), resolved.1);
+    }
+
     for skin in &entry.custom_skins {
         let name_tokens = normalizer::preprocess_text(&skin.name);
         if !name_tokens.is_disjoint(&folder_tokens) {
@@ -67,3 +81,55 @@ pub fn detect_skin_for_staged(
 
     (None, None)
 }
+
+/// The non-default `hash_db` skin sharing the most hashes with the folder.
+///
+/// "Default" is the base body every skin builds on, so it never names a skin.
+/// A tie between two skins resolves nothing.
+fn skin_from_hashes(
+    entry: &DbEntry,
+    matched_hashes: &[String],
+) -> Option<(String, Option<String>)> {
+    if matched_hashes.is_empty() {
+        return None;
+    }
+    let observed: HashSet<&str> = matched_hashes.iter().map(String::as_str).collect();
+
+    let mut best: Option<(&str, usize)> = None;
+    let mut tied = false;
+    for (skin_key, hashes) in &entry.hash_db {
+        if skin_key.eq_ignore_ascii_case("default") {
+            continue;
+        }
+        let overlap = hashes
+            .iter()
+            .filter_map(|raw| normalize_hash(raw))
+            .filter(|hash| observed.contains(hash.as_str()))
+            .count();
+        if overlap == 0 {
+            continue;
+        }
+        match best {
+            Some((_, best_overlap)) if overlap < best_overlap => {}
+            Some((_, best_overlap)) if overlap == best_overlap => tied = true,
+            _ => {
+                best = Some((skin_key, overlap));
+                tied = false;
+            }
+        }
+    }
+    if tied {
+        return None;
+    }
+    let (skin_key, _) = best?;
+
+    // Prefer the schema's spelling and folder alias when the skin is declared.
+    match entry
+        .custom_skins
+        .iter()
+        .find(|skin| skin.name.eq_ignore_ascii_case(skin_key))
+    {
+        Some(skin) => Some((skin.name.clone(), skin.aliases.first().cloned())),
+        None => Some((skin_key.to_string(), None)),
+    }
+}
@@ -0,0 +1,320 @@
+//! Golden corpus test harness for matcher threshold tuning.
+//!
+//! Provides a minimal fixture-driven test system that asserts expected status
+//! and best_entry for deterministic matcher behavior validation.
+//!
+//! Besides the inline [`GoldenCase`] literals, a corpus can live on disk: one
+//! directory per game holding real mod folders and an `expected.json`, so
+//! misclassified folders can be contributed without writing Rust. See
+//! [`load_corpus`] for the layout and [`run_corpus`] for the report.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tempfile::TempDir;
+
+use crate::domain::errors::ScannerError;
+use crate::services::scanner::core::walker::{scan_folder_content, ModCandidate};
+use crate::services::scanner::dedup::hashing::hash_snapshot;
+use crate::services::scanner::dedup::signals::{aggregate_signals, is_reported_score};
+use crate::services::scanner::dedup::snapshot::collect_snapshot;
+
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::IniTokenizationConfig;
+
+use super::{match_folder_full, match_folder_quick};
+
+mod report;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{MatchStatus, StagedMatchResult};
+pub use report::{CaseOutcome, CorpusReport, DuplicateReport, GameReport, ModeReport};
+
+/// Single golden corpus test case.
+pub struct GoldenCase {
+    pub name: &'static str,
+    pub folder_name: &'static str,
+    pub ini_content: Option<&'static str>,
+    pub subfolders: Vec<&'static str>,
+    pub expected_status_quick: MatchStatus,
+    pub expected_best_name_quick: Option<&'static str>,
+    pub expected_status_full: MatchStatus,
+    pub expected_best_name_full: Option<&'static str>,
+}
+
+fn candidate_for(path: PathBuf, display_name: &str) -> ModCandidate {
+    ModCandidate {
+        path,
+        raw_name: display_name.to_string(),
+        display_name: display_name.to_string(),
+        is_disabled: false,
+    }
+}
+
+/// Execute a single golden corpus test case.
+pub fn run_golden_case(case: &GoldenCase, db: &MasterDb) {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join(case.folder_name);
+    std::fs::create_dir_all(&folder).expect("create folder");
+
+    for subfolder in &case.subfolders {
+        std::fs::create_dir_all(folder.join(subfolder)).expect("create subfolder");
+    }
+
+    if let Some(ini) = case.ini_content {
+        std::fs::write(folder.join("mod.ini"), ini).expect("write ini");
+    }
+
+    let content = scan_folder_content(&folder, 3);
+    let candidate = candidate_for(folder.clone(), case.folder_name);
+
+    // Test Quick mode
+    let result_quick = match_folder_quick(
+        &candidate,
+        db,
+        &content,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+    );
+    assert_eq!(
+        result_quick.status, case.expected_status_quick,
+        "Golden case '{}' Quick: expected status {:?}, got {:?}",
+        case.name, case.expected_status_quick, result_quick.status
+    );
+    if let Some(expected_name) = case.expected_best_name_quick {
+        assert!(
+            result_quick.best.is_some(),
+            "Golden case '{}' Quick: expected best candidate",
+            case.name
+        );
+        let best = result_quick.best.unwrap();
+        assert_eq!(
+            best.name, expected_name,
+            "Golden case '{}' Quick: expected best '{}', got '{}'",
+            case.name, expected_name, best.name
+        );
+    } else {
+        assert!(
+            result_quick.best.is_none(),
+            "Golden case '{}' Quick: expected no best candidate",
+            case.name
+        );
+    }
+
+    // Test Full mode
+    let result_full = match_folder_full(
+        &candidate,
+        db,
+        &content,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+        &Default::default(),
+    );
+    assert_eq!(
+        result_full.status, case.expected_status_full,
+        "Golden case '{}' Full: expected status {:?}, got {:?}",
+        case.name, case.expected_status_full, result_full.status
+    );
+    if let Some(expected_name) = case.expected_best_name_full {
+        assert!(
+            result_full.best.is_some(),
+            "Golden case '{}' Full: expected best candidate",
+            case.name
+        );
+        let best = result_full.best.unwrap();
+        assert_eq!(
+            best.name, expected_name,
+            "Golden case '{}' Full: expected best '{}', got '{}'",
+            case.name, expected_name, best.name
+        );
+    } else {
+        assert!(
+            result_full.best.is_none(),
+            "Golden case '{}' Full: expected no best candidate",
+            case.name
+        );
+    }
+}
+
+/// File name of a game corpus's case list.
+pub const EXPECTED_FILE: &str = "expected.json";
+
+/// Bundled MasterDBs, used when a corpus does not name its own.
+const BUNDLED_DATABASES_DIR: &str = "resources/databases";
+
+/// `expected.json` of one game's corpus.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorpusManifest {
+    /// MasterDB to match against, relative to the game directory. Defaults to
+    /// the bundled `resources/databases/<game>.json`.
+    #[serde(default)]
+    pub master_db: Option<PathBuf>,
+    pub cases: Vec<CorpusCase>,
+    /// Folder pairs scored by the duplicate scanner.
+    #[serde(default)]
+    pub duplicates: Vec<CorpusDuplicatePair>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorpusCase {
+    /// Mod folder, relative to the game directory.
+    pub folder: PathBuf,
+    /// Entry name the folder belongs to; `None` for folders that should not
+    /// auto-match anything.
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorpusDuplicatePair {
+    pub left: PathBuf,
+    pub right: PathBuf,
+    pub duplicate: bool,
+}
+
+/// One game's corpus, loaded and ready to run.
+pub struct GameCorpus {
+    pub game: String,
+    pub root: PathBuf,
+    pub db: MasterDb,
+    pub manifest: CorpusManifest,
+}
+
+/// Load every game corpus under `dir`.
+///
+/// Layout: `dir/<game>/expected.json` next to the case folders it names.
+/// Subdirectories without an `expected.json` are skipped, so a corpus can
+/// carry notes or scratch folders.
+pub fn load_corpus(dir: &Path) -> Result<Vec<GameCorpus>, ScannerError> {
+    if !dir.is_dir() {
+        return Err(ScannerError::PathNotFound {
+            path: dir.display().to_string(),
+        });
+    }
+    let mut games: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(EXPECTED_FILE).is_file())
+        .collect();
+    games.sort();
+    games.iter().map(|root| load_game_corpus(root)).collect()
+}
+
+fn load_game_corpus(root: &Path) -> Result<GameCorpus, ScannerError> {
+    let game = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let manifest_path = root.join(EXPECTED_FILE);
+    let manifest: CorpusManifest = serde_json::from_str(&std::fs::read_to_string(&manifest_path)?)
+        .map_err(|error| ScannerError::Parse {
+            what: manifest_path.display().to_string(),
+            detail: error.to_string(),
+        })?;
+
+    let db_path = match &manifest.master_db {
+        Some(path) => root.join(path),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(BUNDLED_DATABASES_DIR)
+            .join(format!("{game}.json")),
+    };
+    if !db_path.is_file() {
+        return Err(ScannerError::PathNotFound {
+            path: db_path.display().to_string(),
+        });
+    }
+    let db = MasterDb::from_json(&std::fs::read_to_string(&db_path)?)?;
+
+    Ok(GameCorpus {
+        game,
+        root: root.to_path_buf(),
+        db,
+        manifest,
+    })
+}
+
+fn candidate_at(root: &Path, folder: &Path) -> ModCandidate {
+    let display_name = folder
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    candidate_for(root.join(folder), &display_name)
+}
+
+/// Match every case of `corpus` in quick and full mode and score the labeled
+/// duplicate pairs.
+pub fn run_game_corpus(corpus: &GameCorpus) -> Result<GameReport, ScannerError> {
+    let ini_filters = IniTokenizationConfig::default().prepare();
+    let mut quick = ModeReport::default();
+    let mut full = ModeReport::default();
+
+    for case in &corpus.manifest.cases {
+        let candidate = candidate_at(&corpus.root, &case.folder);
+        if !candidate.path.is_dir() {
+            return Err(ScannerError::PathNotFound {
+                path: candidate.path.display().to_string(),
+            });
+        }
+        let content = scan_folder_content(&candidate.path, 3);
+        let expected = case.expected.as_deref();
+
+        let result = match_folder_quick(
+            &candidate,
+            &corpus.db,
+            &content,
+            &ini_filters,
+            &AiRerankConfig::default(),
+        );
+        quick.record(&case.folder, expected, &result);
+
+        let result = match_folder_full(
+            &candidate,
+            &corpus.db,
+            &content,
+            &ini_filters,
+            &AiRerankConfig::default(),
+            &Default::default(),
+        );
+        full.record(&case.folder, expected, &result);
+    }
+
+    let mut duplicates = DuplicateReport::default();
+    for pair in &corpus.manifest.duplicates {
+        let left = collect_snapshot(&candidate_at(&corpus.root, &pair.left))?;
+        let right = collect_snapshot(&candidate_at(&corpus.root, &pair.right))?;
+        let (score, _, _) =
+            aggregate_signals(&left, &right, &hash_snapshot(&left), &hash_snapshot(&right));
+        let reported = is_reported_score(score);
+
+        duplicates.pairs += 1;
+        duplicates.labeled += usize::from(pair.duplicate);
+        duplicates.reported += usize::from(reported);
+        if reported == pair.duplicate {
+            duplicates.correct += usize::from(reported);
+        } else {
+            duplicates.misses.push(format!(
+                "{} <> {}: expected duplicate={}, scored {score}",
+                pair.left.display(),
+                pair.right.display(),
+                pair.duplicate,
+            ));
+        }
+    }
+
+    Ok(GameReport {
+        game: corpus.game.clone(),
+        quick,
+        full,
+        duplicates,
+    })
+}
+
+/// Load and run every game corpus under `dir`.
+pub fn run_corpus(dir: &Path) -> Result<CorpusReport, ScannerError> {
+    let games = load_corpus(dir)?
+        .iter()
+        .map(run_game_corpus)
+        .collect::<Result<_, _>>()?;
+    Ok(CorpusReport { games })
+}
+
+#[cfg(test)]
+#[path = "../tests/golden_corpus_tests.rs"]
+mod tests;
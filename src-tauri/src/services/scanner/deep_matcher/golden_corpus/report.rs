@@ -0,0 +1,166 @@
+//! Precision and recall reports for a golden corpus run.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::services::scanner::deep_matcher::{MatchStatus, StagedMatchResult};
+
+/// Counts for one matcher mode over one game's cases.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModeReport {
+    pub cases: usize,
+    /// Cases with an expected entry.
+    pub labeled: usize,
+    pub auto_matched: usize,
+    /// Auto-matches to the expected entry.
+    pub correct: usize,
+    pub needs_review: usize,
+    pub no_match: usize,
+    /// One line per case that auto-matched the wrong entry or missed its own.
+    pub misses: Vec<String>,
+    /// What each case matched, in manifest order.
+    pub outcomes: Vec<CaseOutcome>,
+}
+
+/// The matcher's verdict on one case folder.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CaseOutcome {
+    pub folder: PathBuf,
+    pub status: MatchStatus,
+    pub best: Option<String>,
+}
+
+impl ModeReport {
+    /// Share of auto-matches that were right; `None` with no auto-matches.
+    pub fn precision(&self) -> Option<f64> {
+        ratio(self.correct, self.auto_matched)
+    }
+
+    /// Share of labeled cases auto-matched to their entry.
+    pub fn recall(&self) -> Option<f64> {
+        ratio(self.correct, self.labeled)
+    }
+
+    pub fn needs_review_rate(&self) -> Option<f64> {
+        ratio(self.needs_review, self.cases)
+    }
+
+    pub(super) fn record(
+        &mut self,
+        folder: &Path,
+        expected: Option<&str>,
+        result: &StagedMatchResult,
+    ) {
+        self.cases += 1;
+        self.labeled += usize::from(expected.is_some());
+        let best = result.best.as_ref().map(|best| best.name.as_str());
+        self.outcomes.push(CaseOutcome {
+            folder: folder.to_path_buf(),
+            status: result.status,
+            best: best.map(str::to_string),
+        });
+        match result.status {
+            MatchStatus::AutoMatched => {
+                self.auto_matched += 1;
+                if best.is_some() && best == expected {
+                    self.correct += 1;
+                    return;
+                }
+            }
+            MatchStatus::NeedsReview | MatchStatus::MultiObjectPack => self.needs_review += 1,
+            MatchStatus::NoMatch => self.no_match += 1,
+        }
+        if expected.is_some() || result.status == MatchStatus::AutoMatched {
+            self.misses.push(format!(
+                "{}: expected {}, got {:?} {}",
+                folder.display(),
+                expected.unwrap_or("no auto-match"),
+                result.status,
+                best.unwrap_or("-"),
+            ));
+        }
+    }
+}
+
+/// Counts for the duplicate scanner over one game's labeled pairs.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DuplicateReport {
+    pub pairs: usize,
+    /// Pairs labeled as duplicates.
+    pub labeled: usize,
+    /// Pairs scoring at or above the reporting threshold.
+    pub reported: usize,
+    pub correct: usize,
+    pub misses: Vec<String>,
+}
+
+impl DuplicateReport {
+    pub fn precision(&self) -> Option<f64> {
+        ratio(self.correct, self.reported)
+    }
+
+    pub fn recall(&self) -> Option<f64> {
+        ratio(self.correct, self.labeled)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameReport {
+    pub game: String,
+    pub quick: ModeReport,
+    pub full: ModeReport,
+    pub duplicates: DuplicateReport,
+}
+
+/// Per-game results of a corpus run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CorpusReport {
+    pub games: Vec<GameReport>,
+}
+
+fn ratio(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 / whole as f64)
+}
+
+fn percent(value: Option<f64>) -> String {
+    value
+        .map(|value| format!("{:.1}%", value * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl fmt::Display for CorpusReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for game in &self.games {
+            writeln!(f, "== {} ==", game.game)?;
+            for (mode, report) in [("quick", &game.quick), ("full", &game.full)] {
+                writeln!(
+                    f,
+                    "{mode:<6} cases {:>4}  precision {:>6}  recall {:>6}  needs-review {:>6}",
+                    report.cases,
+                    percent(report.precision()),
+                    percent(report.recall()),
+                    percent(report.needs_review_rate()),
+                )?;
+                for miss in &report.misses {
+                    writeln!(f, "         miss: {miss}")?;
+                }
+            }
+            let dups = &game.duplicates;
+            if dups.pairs > 0 {
+                writeln!(
+                    f,
+                    "dedup  pairs {:>4}  precision {:>6}  recall {:>6}",
+                    dups.pairs,
+                    percent(dups.precision()),
+                    percent(dups.recall()),
+                )?;
+                for miss in &dups.misses {
+                    writeln!(f, "         miss: {miss}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -19,12 +19,15 @@ pub mod golden_corpus;
 mod required_tests;
 
 // Public types and matcher entrypoints used by commands and sync services.
+pub use models::trace::{MatchTrace, MatchTracer};
 pub use models::types::{
     sort_candidates_deterministic, Candidate, Confidence, CustomSkin, DbEntry, Evidence, MatchMode,
-    MatchStatus, Reason, ScoreState, StagedMatchResult, MAX_EVIDENCE_HASHES, MAX_EVIDENCE_SECTIONS,
-    MAX_EVIDENCE_TOKENS, MAX_REASONS_PER_CANDIDATE,
+    MatchStatus, Reason, ScoreState, StagedMatchResult, UserCorrection, MAX_EVIDENCE_HASHES,
+    MAX_EVIDENCE_SECTIONS, MAX_EVIDENCE_TOKENS, MAX_REASONS_PER_CANDIDATE,
+};
+pub use pipeline::full_pipeline::{
+    match_folder_full, match_folder_full_traced, score_forced_candidates,
 };
-pub use pipeline::full_pipeline::{match_folder_full, score_forced_candidates};
 pub use pipeline::quick_pipeline::match_folder_quick;
 pub use state::master_db::MasterDb;
 
@@ -53,6 +56,10 @@ pub fn match_folder_phased<'a>(
 
 /// Same as `match_folder_phased` but accepts an external `SignalCache`
 /// so signals are not recomputed across calls within a scan batch.
+///
+/// A folder laid out as a pack of per-object subfolders is matched per
+/// subfolder too, and reported as a pack when those match several objects,
+/// even when the folder as a whole matched one of them.
 pub fn match_folder_phased_cached<'a>(
     candidate: &ModCandidate,
     db: &MasterDb,
@@ -60,6 +67,30 @@ pub fn match_folder_phased_cached<'a>(
     ini_filters: &PreparedTokenFilters,
     ai_config: &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig<'a>,
     cache: &mut state::signal_cache::SignalCache,
+) -> StagedMatchResult {
+    let result = match_single_object(candidate, db, content, ini_filters, ai_config, cache);
+    if result.status == MatchStatus::MultiObjectPack {
+        return result;
+    }
+    pipeline::pack_split::detect_subfolder_pack(
+        candidate,
+        content,
+        db,
+        ini_filters,
+        ai_config,
+        cache,
+    )
+    .unwrap_or(result)
+}
+
+/// Quick then full scoring for one folder, without looking for packs.
+pub(crate) fn match_single_object<'a>(
+    candidate: &ModCandidate,
+    db: &MasterDb,
+    content: &FolderContent,
+    ini_filters: &PreparedTokenFilters,
+    ai_config: &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig<'a>,
+    cache: &mut state::signal_cache::SignalCache,
 ) -> StagedMatchResult {
     let quick_result = pipeline::quick_pipeline::match_folder_quick_cached(
         candidate,
@@ -70,8 +101,11 @@ pub fn match_folder_phased_cached<'a>(
         cache,
     );
 
-    // Only fall back to full scoring if quick didn't auto-match
-    if quick_result.status == MatchStatus::AutoMatched {
+    // Only fall back to full scoring if quick didn't settle the folder
+    if matches!(
+        quick_result.status,
+        MatchStatus::AutoMatched | MatchStatus::MultiObjectPack
+    ) {
         return quick_result;
     }
 
@@ -8,6 +8,11 @@ use crate::services::scanner::deep_matcher::{
     sort_candidates_deterministic, Candidate, MatchMode, Reason, ScoreState,
 };
 
+#[cfg(feature = "debug_matcher")]
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+#[cfg(feature = "debug_matcher")]
+use log::debug;
+
 const OBJECT_TYPE_MISMATCH_PENALTY: f32 = 2.0;
 const QUICK_NEGATIVE_PENALTY_PER_HIT: f32 = 1.5;
 const QUICK_NEGATIVE_PENALTY_CAP: f32 = 8.0;
@@ -210,7 +215,9 @@ fn has_primary_evidence_for_candidate(
     for reason in &candidate.reasons {
         match reason {
             Reason::HashOverlap { overlap, .. } if *overlap >= 1 => return true,
-            Reason::AliasStrict { .. } | Reason::SubstringName { .. } => return true,
+            Reason::AliasStrict { .. }
+            | Reason::UserCorrection { .. }
+            | Reason::SubstringName { .. } => return true,
             Reason::DeepNameToken { .. }
             | Reason::IniSectionToken { .. }
             | Reason::IniContentToken { .. }
@@ -245,3 +252,40 @@ fn count_bucket_hits(
         .filter(|token| entry_tokens.contains(*token))
         .count()
 }
+
+#[cfg(feature = "debug_matcher")]
+pub(super) fn log_stage_decision(
+    mode: MatchMode,
+    candidates: &[Candidate],
+    primary_flags: &[bool],
+    signals: &FolderSignals,
+    decision: &str,
+) {
+    let best_score = candidates.first().map(|c| c.score).unwrap_or(0.0);
+    let second_score = candidates.get(1).map(|c| c.score).unwrap_or(0.0);
+    let margin = best_score - second_score;
+    let best_has_primary = primary_flags.first().copied().unwrap_or(false);
+    let second_has_primary = primary_flags.get(1).copied().unwrap_or(false);
+    let foreign_hits = candidates
+        .first()
+        .and_then(|c| {
+            c.reasons.iter().find_map(|r| {
+                if let crate::services::scanner::deep_matcher::Reason::NegativeEvidence {
+                    foreign_strong_hits,
+                } = r
+                {
+                    Some(*foreign_strong_hits)
+                } else {
+                    None
+                }
+            })
+        })
+        .unwrap_or(0);
+
+    debug!(
+        "[MATCHER_CALIBRATION] stage_decision: {} | mode={:?} best={:.2} second={:.2} margin={:.2} primary=[{},{}] foreign_hits={} scanned_ini={} scanned_names={}",
+        decision, mode, best_score, second_score, margin,
+        best_has_primary, second_has_primary, foreign_hits,
+        signals.scanned_ini_files, signals.scanned_name_items
+    );
+}
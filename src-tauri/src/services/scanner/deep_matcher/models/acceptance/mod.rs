@@ -1,24 +1,34 @@
 use std::collections::HashMap;
 
 use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
-use crate::services::scanner::deep_matcher::pipeline::quick_pipeline_result::{
-    build_evidence, empty_evidence,
+use crate::services::scanner::deep_matcher::models::trace::{
+    AcceptanceOutcome, AcceptanceTrace, MatchTracer,
 };
+use crate::services::scanner::deep_matcher::pipeline::pack_split::{
+    hash_pack_members, pack_result,
+};
+use crate::services::scanner::deep_matcher::pipeline::quick_pipeline_result::build_evidence;
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{
-    sort_candidates_deterministic, Candidate, Confidence, MatchMode, MatchStatus, ScoreState,
-    StagedMatchResult,
+    Confidence, MatchMode, MatchStatus, ScoreState, StagedMatchResult,
 };
 
 #[cfg(feature = "debug_matcher")]
 use log::debug;
 
-#[path = "acceptance_controls.rs"]
-mod acceptance_controls;
+mod controls;
+mod result_builders;
+mod stage_context;
 
-use acceptance_controls::{
+#[cfg(feature = "debug_matcher")]
+use controls::log_stage_decision;
+use controls::{
     build_ambiguity_snapshot, collect_candidates_with_controls, primary_evidence_flags,
 };
+use result_builders::{
+    build_auto_matched_result, build_review_result, no_match_result, review_or_pack_result,
+};
+pub use stage_context::StageContext;
 
 #[derive(Debug, Clone)]
 pub struct StageAcceptConfig {
@@ -37,65 +47,32 @@ pub struct FinalizeConfig {
     pub top_k: usize,
 }
 
-/// The half of a stage-accept call that never changes within one pipeline run.
-/// Each stage supplies only its own threshold, margin, and winning confidence;
-/// `states` stays a parameter because it is mutated between stages.
-pub struct StageContext<'a> {
-    pub db: &'a MasterDb,
-    pub signals: &'a FolderSignals,
-    pub buckets: &'a crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets,
-    pub mode: MatchMode,
-    pub review_min_score: f32,
-    pub top_k: usize,
-}
-
-impl StageContext<'_> {
-    pub fn accept(
-        &self,
-        states: &HashMap<usize, ScoreState>,
-        threshold: f32,
-        margin: f32,
-        best_confidence: Confidence,
-    ) -> Option<StagedMatchResult> {
-        try_stage_accept(
-            self.db,
-            states,
-            self.signals,
-            self.buckets,
-            None,
-            &StageAcceptConfig {
-                mode: self.mode,
-                threshold,
-                margin,
-                review_min_score: self.review_min_score,
-                top_k: self.top_k,
-                best_confidence,
-            },
-        )
-    }
-
-    pub fn finalize(&self, states: &HashMap<usize, ScoreState>) -> StagedMatchResult {
-        finalize_review(
-            self.db,
-            states,
-            self.signals,
-            self.buckets,
-            None,
-            &FinalizeConfig {
-                mode: self.mode,
-                review_min_score: self.review_min_score,
-                top_k: self.top_k,
-            },
-        )
-    }
+pub fn try_stage_accept(
+    db: &MasterDb,
+    states: &HashMap<usize, ScoreState>,
+    signals: &FolderSignals,
+    observed_buckets: &crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets,
+    object_type_context: Option<&str>,
+    config: &StageAcceptConfig,
+) -> Option<StagedMatchResult> {
+    stage_accept(
+        db,
+        states,
+        signals,
+        observed_buckets,
+        object_type_context,
+        None,
+        config,
+    )
 }
 
-pub fn try_stage_accept(
+fn stage_accept(
     db: &MasterDb,
     states: &HashMap<usize, ScoreState>,
     signals: &FolderSignals,
     observed_buckets: &crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets,
     object_type_context: Option<&str>,
+    trace: Option<&MatchTracer>,
     config: &StageAcceptConfig,
 ) -> Option<StagedMatchResult> {
     let mut candidates = collect_candidates_with_controls(
@@ -105,8 +82,26 @@ pub fn try_stage_accept(
         object_type_context,
         config.mode,
     );
-    let best = candidates.first()?.clone();
+    let record = |outcome, primary_flags: &[bool], pack_multi_entity| {
+        if let Some(tracer) = trace {
+            tracer.record_decision(AcceptanceTrace::new(
+                outcome,
+                &candidates,
+                primary_flags,
+                pack_multi_entity,
+                Some((config.threshold, config.margin)),
+            ));
+        }
+    };
+    let Some(best) = candidates.first().cloned() else {
+        record(AcceptanceOutcome::NoCandidates, &[], false);
+        return None;
+    };
     if best.score < config.threshold {
+        if trace.is_some() {
+            let primary_flags = primary_evidence_flags(db, &candidates, observed_buckets);
+            record(AcceptanceOutcome::ThresholdNotMet, &primary_flags, false);
+        }
         #[cfg(feature = "debug_matcher")]
         debug!(
             "[MATCHER_CALIBRATION] try_stage_accept: threshold_not_met | mode={:?} best_score={:.2} threshold={:.2}",
@@ -117,6 +112,7 @@ pub fn try_stage_accept(
 
     let primary_flags = primary_evidence_flags(db, &candidates, observed_buckets);
     if !primary_flags.first().copied().unwrap_or(false) {
+        record(AcceptanceOutcome::NoPrimaryEvidence, &primary_flags, false);
         #[cfg(feature = "debug_matcher")]
         debug!(
             "[MATCHER_CALIBRATION] try_stage_accept: no_primary_evidence | mode={:?} best_score={:.2}",
@@ -132,6 +128,11 @@ pub fn try_stage_accept(
         Some(config.margin),
     );
     if ambiguity.margin_conflict {
+        record(
+            AcceptanceOutcome::MarginConflictReview,
+            &primary_flags,
+            ambiguity.pack_multi_entity,
+        );
         #[cfg(feature = "debug_matcher")]
         log_stage_decision(
             config.mode,
@@ -140,10 +141,11 @@ pub fn try_stage_accept(
             signals,
             "margin_conflict_review",
         );
-        return Some(build_review_result(
+        return Some(review_or_pack_result(
             db,
             signals,
             &mut candidates,
+            config.review_min_score,
             config.top_k,
         ));
     }
@@ -153,6 +155,11 @@ pub fn try_stage_accept(
         .map(|candidate| candidate.score)
         .unwrap_or(0.0);
     if (best.score - second_score) < config.margin {
+        record(
+            AcceptanceOutcome::MarginInsufficient,
+            &primary_flags,
+            ambiguity.pack_multi_entity,
+        );
         #[cfg(feature = "debug_matcher")]
         debug!(
             "[MATCHER_CALIBRATION] try_stage_accept: margin_insufficient | mode={:?} best_score={:.2} second_score={:.2} margin={:.2}",
@@ -162,6 +169,11 @@ pub fn try_stage_accept(
     }
 
     if ambiguity.ultra_close_primary || ambiguity.ultra_close_any || ambiguity.pack_multi_entity {
+        record(
+            AcceptanceOutcome::AmbiguityForcedReview,
+            &primary_flags,
+            ambiguity.pack_multi_entity,
+        );
         #[cfg(feature = "debug_matcher")]
         log_stage_decision(
             config.mode,
@@ -170,10 +182,11 @@ pub fn try_stage_accept(
             signals,
             "ambiguity_forced_review",
         );
-        return Some(build_review_result(
+        return Some(review_or_pack_result(
             db,
             signals,
             &mut candidates,
+            config.review_min_score,
             config.top_k,
         ));
     }
@@ -186,6 +199,11 @@ pub fn try_stage_accept(
         signals,
         "auto_matched",
     );
+    record(
+        AcceptanceOutcome::AutoMatched,
+        &primary_flags,
+        ambiguity.pack_multi_entity,
+    );
 
     Some(build_auto_matched_result(
         db,
@@ -203,6 +221,26 @@ pub fn finalize_review(
     observed_buckets: &crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets,
     object_type_context: Option<&str>,
     config: &FinalizeConfig,
+) -> StagedMatchResult {
+    finalize(
+        db,
+        states,
+        signals,
+        observed_buckets,
+        object_type_context,
+        None,
+        config,
+    )
+}
+
+fn finalize(
+    db: &MasterDb,
+    states: &HashMap<usize, ScoreState>,
+    signals: &FolderSignals,
+    observed_buckets: &crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets,
+    object_type_context: Option<&str>,
+    trace: Option<&MatchTracer>,
+    config: &FinalizeConfig,
 ) -> StagedMatchResult {
     let mut candidates = collect_candidates_with_controls(
         db,
@@ -211,8 +249,20 @@ pub fn finalize_review(
         object_type_context,
         config.mode,
     );
+    let record = |outcome, primary_flags: &[bool], pack_multi_entity| {
+        if let Some(tracer) = trace {
+            tracer.record_decision(AcceptanceTrace::new(
+                outcome,
+                &candidates,
+                primary_flags,
+                pack_multi_entity,
+                None,
+            ));
+        }
+    };
 
     let Some(best) = candidates.first().cloned() else {
+        record(AcceptanceOutcome::NoCandidates, &[], false);
         #[cfg(feature = "debug_matcher")]
         debug!(
             "[MATCHER_CALIBRATION] finalize_review: no_candidates | mode={:?} scanned_ini={} scanned_names={}",
@@ -236,137 +286,44 @@ pub fn finalize_review(
         );
     }
 
-    if ambiguity.pack_multi_entity || best.score >= config.review_min_score {
-        return build_review_result(db, signals, &mut candidates, config.top_k);
+    let pack_members = hash_pack_members(&candidates, config.review_min_score);
+    if !pack_members.is_empty() {
+        record(
+            AcceptanceOutcome::MultiObjectPack,
+            &primary_flags,
+            ambiguity.pack_multi_entity,
+        );
+        return pack_result(db, signals, pack_members);
     }
 
-    StagedMatchResult {
-        status: MatchStatus::NoMatch,
-        best: None,
-        candidates_topk: Vec::new(),
-        candidates_all: Vec::new(),
-        evidence: build_evidence(db, signals, &best),
+    let review = ambiguity.pack_multi_entity || best.score >= config.review_min_score;
+    let outcome = if review {
+        AcceptanceOutcome::Review
+    } else {
+        AcceptanceOutcome::NoMatch
+    };
+    record(outcome, &primary_flags, ambiguity.pack_multi_entity);
+    if review {
+        return build_review_result(db, signals, &mut candidates, config.top_k);
     }
-}
-
-fn build_review_result(
-    db: &MasterDb,
-    signals: &FolderSignals,
-    candidates: &mut Vec<Candidate>,
-    top_k: usize,
-) -> StagedMatchResult {
-    assemble_ranked_result(
-        db,
-        signals,
-        candidates,
-        top_k,
-        MatchStatus::NeedsReview,
-        None,
-    )
-}
 
-fn no_match_result(signals: &FolderSignals) -> StagedMatchResult {
     StagedMatchResult {
         status: MatchStatus::NoMatch,
         best: None,
         candidates_topk: Vec::new(),
         candidates_all: Vec::new(),
-        evidence: empty_evidence(signals),
-    }
-}
-
-fn build_auto_matched_result(
-    db: &MasterDb,
-    signals: &FolderSignals,
-    candidates: &mut Vec<Candidate>,
-    top_k: usize,
-    best_confidence: &Confidence,
-) -> StagedMatchResult {
-    assemble_ranked_result(
-        db,
-        signals,
-        candidates,
-        top_k,
-        MatchStatus::AutoMatched,
-        Some(best_confidence),
-    )
-}
-
-fn assemble_ranked_result(
-    db: &MasterDb,
-    signals: &FolderSignals,
-    candidates: &mut Vec<Candidate>,
-    top_k: usize,
-    status: MatchStatus,
-    best_confidence: Option<&Confidence>,
-) -> StagedMatchResult {
-    sort_candidates_deterministic(candidates);
-
-    let candidates_all = candidates.clone();
-    candidates.truncate(top_k.max(1));
-
-    if let Some(confidence) = best_confidence {
-        if let Some(best_candidate) = candidates.first_mut() {
-            best_candidate.confidence = std::cmp::max(best_candidate.confidence, *confidence);
-        }
-    }
-
-    let best = candidates.first().cloned();
-    let evidence = best
-        .as_ref()
-        .map(|candidate| build_evidence(db, signals, candidate))
-        .unwrap_or_else(|| empty_evidence(signals));
-
-    StagedMatchResult {
-        status,
-        best,
-        evidence,
-        candidates_topk: candidates.clone(),
-        candidates_all,
+        evidence: build_evidence(db, signals, &best),
     }
 }
 
-#[cfg(feature = "debug_matcher")]
-fn log_stage_decision(
-    mode: MatchMode,
-    candidates: &[Candidate],
-    primary_flags: &[bool],
-    signals: &FolderSignals,
-    decision: &str,
-) {
-    let best_score = candidates.first().map(|c| c.score).unwrap_or(0.0);
-    let second_score = candidates.get(1).map(|c| c.score).unwrap_or(0.0);
-    let margin = best_score - second_score;
-    let best_has_primary = primary_flags.first().copied().unwrap_or(false);
-    let second_has_primary = primary_flags.get(1).copied().unwrap_or(false);
-    let foreign_hits = candidates
-        .first()
-        .and_then(|c| {
-            c.reasons.iter().find_map(|r| {
-                if let crate::services::scanner::deep_matcher::Reason::NegativeEvidence {
-                    foreign_strong_hits,
-                } = r
-                {
-                    Some(*foreign_strong_hits)
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap_or(0);
-
-    debug!(
-        "[MATCHER_CALIBRATION] stage_decision: {} | mode={:?} best={:.2} second={:.2} margin={:.2} primary=[{},{}] foreign_hits={} scanned_ini={} scanned_names={}",
-        decision, mode, best_score, second_score, margin,
-        best_has_primary, second_has_primary, foreign_hits,
-        signals.scanned_ini_files, signals.scanned_name_items
-    );
-}
-
 #[cfg(test)]
-#[path = "../tests/models/acceptance_tests.rs"]
+#[path = "../../tests/models/acceptance_tests.rs"]
 mod acceptance_tests;
 
 #[cfg(test)]
-#[path = "../tests/acceptance_result_tests.rs"]
+#[path = "../../tests/models/pack_acceptance_tests.rs"]
+mod pack_acceptance_tests;
+
+#[cfg(test)]
+#[path = "../../tests/acceptance_result_tests.rs"]
 mod acceptance_result_tests;
@@ -0,0 +1,106 @@
+//! The results a stage or the final check hands back to the pipeline.
+
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::pipeline::pack_split::{
+    hash_pack_members, pack_result,
+};
+use crate::services::scanner::deep_matcher::pipeline::quick_pipeline_result::{
+    build_evidence, empty_evidence,
+};
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{
+    sort_candidates_deterministic, Candidate, Confidence, MatchStatus, StagedMatchResult,
+};
+
+pub(super) fn build_review_result(
+    db: &MasterDb,
+    signals: &FolderSignals,
+    candidates: &mut Vec<Candidate>,
+    top_k: usize,
+) -> StagedMatchResult {
+    assemble_ranked_result(
+        db,
+        signals,
+        candidates,
+        top_k,
+        MatchStatus::NeedsReview,
+        None,
+    )
+}
+
+/// A review of `candidates`, or a pack when hashes unique to several entries
+/// show the folder covers more than one object.
+pub(super) fn review_or_pack_result(
+    db: &MasterDb,
+    signals: &FolderSignals,
+    candidates: &mut Vec<Candidate>,
+    review_min_score: f32,
+    top_k: usize,
+) -> StagedMatchResult {
+    let pack_members = hash_pack_members(candidates, review_min_score);
+    if !pack_members.is_empty() {
+        return pack_result(db, signals, pack_members);
+    }
+    build_review_result(db, signals, candidates, top_k)
+}
+
+pub(super) fn no_match_result(signals: &FolderSignals) -> StagedMatchResult {
+    StagedMatchResult {
+        status: MatchStatus::NoMatch,
+        best: None,
+        candidates_topk: Vec::new(),
+        candidates_all: Vec::new(),
+        evidence: empty_evidence(signals),
+    }
+}
+
+pub(super) fn build_auto_matched_result(
+    db: &MasterDb,
+    signals: &FolderSignals,
+    candidates: &mut Vec<Candidate>,
+    top_k: usize,
+    best_confidence: &Confidence,
+) -> StagedMatchResult {
+    assemble_ranked_result(
+        db,
+        signals,
+        candidates,
+        top_k,
+        MatchStatus::AutoMatched,
+        Some(best_confidence),
+    )
+}
+
+fn assemble_ranked_result(
+    db: &MasterDb,
+    signals: &FolderSignals,
+    candidates: &mut Vec<Candidate>,
+    top_k: usize,
+    status: MatchStatus,
+    best_confidence: Option<&Confidence>,
+) -> StagedMatchResult {
+    sort_candidates_deterministic(candidates);
+
+    let candidates_all = candidates.clone();
+    candidates.truncate(top_k.max(1));
+
+    if let Some(confidence) = best_confidence {
+        if let Some(best_candidate) = candidates.first_mut() {
+            best_candidate.confidence = std::cmp::max(best_candidate.confidence, *confidence);
+        }
+    }
+
+    let best = candidates.first().cloned();
+    let evidence = best
+        .as_ref()
+        .map(|candidate| build_evidence(db, signals, candidate))
+        .unwrap_or_else(|| empty_evidence(signals));
+
+    StagedMatchResult {
+        status,
+        best,
+        evidence,
+        candidates_topk: candidates.clone(),
+        candidates_all,
+    }
+}
@@ -0,0 +1,83 @@
+//! The per-run view of acceptance that the pipeline stages call through.
+
+use std::collections::HashMap;
+
+use super::{finalize, stage_accept, FinalizeConfig, StageAcceptConfig};
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::models::trace::MatchTracer;
+use crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{
+    Confidence, MatchMode, ScoreState, StagedMatchResult,
+};
+
+/// The half of a stage-accept call that never changes within one pipeline run.
+/// Each stage supplies only its own threshold, margin, and winning confidence;
+/// `states` stays a parameter because it is mutated between stages.
+pub struct StageContext<'a> {
+    pub db: &'a MasterDb,
+    pub signals: &'a FolderSignals,
+    pub buckets: &'a ObservedTokenBuckets,
+    pub mode: MatchMode,
+    pub review_min_score: f32,
+    pub top_k: usize,
+    /// Set only when explaining a match; `None` in regular scans.
+    pub trace: Option<&'a MatchTracer>,
+}
+
+impl StageContext<'_> {
+    /// Record what `stage` did to `states`, when tracing.
+    pub fn record_stage(&self, stage: &str, states: &HashMap<usize, ScoreState>) {
+        if let Some(tracer) = self.trace {
+            tracer.record_stage(stage, self.db, states);
+        }
+    }
+
+    /// Like [`Self::record_stage`], for a stage scoring into its own states.
+    pub fn record_isolated_stage(&self, stage: &str, states: &HashMap<usize, ScoreState>) {
+        if let Some(tracer) = self.trace {
+            tracer.record_isolated_stage(stage, self.db, states);
+        }
+    }
+
+    pub fn accept(
+        &self,
+        states: &HashMap<usize, ScoreState>,
+        threshold: f32,
+        margin: f32,
+        best_confidence: Confidence,
+    ) -> Option<StagedMatchResult> {
+        stage_accept(
+            self.db,
+            states,
+            self.signals,
+            self.buckets,
+            None,
+            self.trace,
+            &StageAcceptConfig {
+                mode: self.mode,
+                threshold,
+                margin,
+                review_min_score: self.review_min_score,
+                top_k: self.top_k,
+                best_confidence,
+            },
+        )
+    }
+
+    pub fn finalize(&self, states: &HashMap<usize, ScoreState>) -> StagedMatchResult {
+        finalize(
+            self.db,
+            states,
+            self.signals,
+            self.buckets,
+            None,
+            self.trace,
+            &FinalizeConfig {
+                mode: self.mode,
+                review_min_score: self.review_min_score,
+                top_k: self.top_k,
+            },
+        )
+    }
+}
@@ -1,3 +1,4 @@
 pub mod acceptance;
 pub mod result_summary;
+pub mod trace;
 pub mod types;
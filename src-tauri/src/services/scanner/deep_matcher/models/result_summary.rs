@@ -33,6 +33,9 @@ impl StagedMatchResult {
                 }
             }
             MatchStatus::NoMatch => "No reliable match found".to_string(),
+            MatchStatus::MultiObjectPack => {
+                format!("Pack covering {} objects", self.candidates_topk.len())
+            }
         }
     }
 
@@ -42,6 +45,13 @@ impl StagedMatchResult {
         let candidate = self.best.as_ref().or_else(|| self.candidates_topk.first());
         match self.status {
             MatchStatus::NoMatch => 0,
+            // Every member cleared the review bar; report the weakest one.
+            MatchStatus::MultiObjectPack => self
+                .candidates_topk
+                .iter()
+                .map(score_to_percentage)
+                .min()
+                .unwrap_or(0),
             MatchStatus::NeedsReview => {
                 let Some(c) = candidate else { return 10 };
                 score_to_percentage(c)
@@ -76,7 +86,7 @@ pub(crate) fn score_to_percentage(candidate: &Candidate) -> u8 {
 fn summary_reason_priority(reason: &Reason) -> usize {
     match reason {
         Reason::HashOverlap { .. } => 0,
-        Reason::AliasStrict { .. } => 1,
+        Reason::AliasStrict { .. } | Reason::UserCorrection { .. } => 1,
         Reason::SubstringName { .. } => 2,
         Reason::DeepNameToken { .. } => 3,
         Reason::IniSectionToken { .. } => 4,
@@ -101,6 +111,7 @@ fn format_reason_message(reason: &Reason) -> String {
             }
         }
         Reason::AliasStrict { .. } => "Exact name match".to_string(),
+        Reason::UserCorrection { .. } => "Matched from your earlier correction".to_string(),
         Reason::SubstringName { .. } => "Name pattern detected".to_string(),
         Reason::DirectNameSupport { .. } => "Name match detected".to_string(),
         Reason::TokenOverlap { .. } => "Strong similarity detected".to_string(),
@@ -0,0 +1,234 @@
+//! Structured trace of one match run, for explaining a result after the fact.
+//!
+//! The pipeline writes into a [`MatchTracer`] when one is attached to its
+//! [`StageContext`](super::acceptance::StageContext); without one every hook
+//! is a no-op, so regular scans pay nothing for it.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{Candidate, ScoreState};
+
+/// Everything the tracer saw, in pipeline order.
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+pub struct MatchTrace {
+    pub signals: SignalTrace,
+    pub stages: Vec<StageTrace>,
+    /// Empty when the mechanical reranker did not run.
+    pub mechanical_rerank: Vec<RerankPointsTrace>,
+}
+
+/// The tokens `signal_collector` extracted from the folder.
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+pub struct SignalTrace {
+    pub folder_name_normalized: String,
+    pub folder_tokens: Vec<String>,
+    pub deep_name_tokens: Vec<String>,
+    pub ini_section_tokens: Vec<String>,
+    pub ini_content_tokens: Vec<String>,
+    pub ini_hashes: Vec<String>,
+    pub override_hashes: Vec<String>,
+    pub scanned_ini_files: u32,
+    pub scanned_name_items: u32,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct StageTrace {
+    pub stage: String,
+    /// Entries whose score this stage changed, highest resulting score first.
+    pub contributions: Vec<StageContribution>,
+    /// The acceptance check run after the stage; `None` when the stage ended
+    /// without one.
+    pub decision: Option<AcceptanceTrace>,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct StageContribution {
+    #[specta(type = f64)]
+    pub entry_id: usize,
+    pub name: String,
+    pub delta: f32,
+    pub score: f32,
+}
+
+/// Why an acceptance check accepted, deferred, or stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptanceOutcome {
+    NoCandidates,
+    ThresholdNotMet,
+    NoPrimaryEvidence,
+    MarginInsufficient,
+    MarginConflictReview,
+    AmbiguityForcedReview,
+    AutoMatched,
+    /// Final check: the best candidate is worth a review.
+    Review,
+    /// Final check: nothing scored high enough to show.
+    NoMatch,
+    /// Final check: hashes unique to several entries, so the folder is a pack.
+    MultiObjectPack,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct AcceptanceTrace {
+    pub outcome: AcceptanceOutcome,
+    /// The stage's bar; `None` for the final review check.
+    pub threshold: Option<f32>,
+    pub margin: Option<f32>,
+    pub best: Option<String>,
+    pub best_score: f32,
+    pub second_score: f32,
+    pub best_has_primary_evidence: bool,
+    pub pack_multi_entity: bool,
+}
+
+impl AcceptanceTrace {
+    /// The decision taken on `candidates`, best first. `limits` holds the
+    /// stage's threshold and margin; `None` for the final review check.
+    pub fn new(
+        outcome: AcceptanceOutcome,
+        candidates: &[Candidate],
+        primary_flags: &[bool],
+        pack_multi_entity: bool,
+        limits: Option<(f32, f32)>,
+    ) -> Self {
+        Self {
+            outcome,
+            threshold: limits.map(|(threshold, _)| threshold),
+            margin: limits.map(|(_, margin)| margin),
+            best: candidates.first().map(|candidate| candidate.name.clone()),
+            best_score: candidates.first().map(|c| c.score).unwrap_or(0.0),
+            second_score: candidates.get(1).map(|c| c.score).unwrap_or(0.0),
+            best_has_primary_evidence: primary_flags.first().copied().unwrap_or(false),
+            pack_multi_entity,
+        }
+    }
+}
+
+/// Mechanical reranker points for one candidate.
+#[derive(Debug, Clone, Serialize, specta::Type)]
+pub struct RerankPointsTrace {
+    #[specta(type = f64)]
+    pub entry_id: usize,
+    pub name: String,
+    pub points: f32,
+    /// Sum of the penalties, zero or negative.
+    pub penalties: f32,
+}
+
+/// Sink the pipeline records into. Stage deltas are measured against the
+/// scores seen at the previous stage.
+#[derive(Debug, Default)]
+pub struct MatchTracer {
+    trace: RefCell<MatchTrace>,
+    last_scores: RefCell<HashMap<usize, f32>>,
+}
+
+impl MatchTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_signals(&self, signals: &FolderSignals) {
+        self.trace.borrow_mut().signals = SignalTrace {
+            folder_name_normalized: signals.folder_name_normalized.clone(),
+            folder_tokens: signals.folder_tokens.clone(),
+            deep_name_tokens: signals.deep_name_tokens.clone(),
+            ini_section_tokens: signals.ini_section_tokens.clone(),
+            ini_content_tokens: signals.ini_content_tokens.clone(),
+            ini_hashes: signals.ini_hashes.clone(),
+            override_hashes: signals.override_hashes.clone(),
+            scanned_ini_files: u32::try_from(signals.scanned_ini_files).unwrap_or(u32::MAX),
+            scanned_name_items: u32::try_from(signals.scanned_name_items).unwrap_or(u32::MAX),
+        };
+    }
+
+    /// Record what `stage` added to the shared score states.
+    pub fn record_stage(&self, stage: &str, db: &MasterDb, states: &HashMap<usize, ScoreState>) {
+        let mut last_scores = self.last_scores.borrow_mut();
+        let contributions = contributions(db, states, &last_scores);
+        *last_scores = states
+            .iter()
+            .map(|(entry_id, state)| (*entry_id, state.score))
+            .collect();
+        self.push_stage(stage, contributions);
+    }
+
+    /// Record a stage that scores into its own states, leaving the shared
+    /// baseline alone.
+    pub fn record_isolated_stage(
+        &self,
+        stage: &str,
+        db: &MasterDb,
+        states: &HashMap<usize, ScoreState>,
+    ) {
+        self.push_stage(stage, contributions(db, states, &HashMap::new()));
+    }
+
+    /// Attach an acceptance decision to the latest stage.
+    pub fn record_decision(&self, decision: AcceptanceTrace) {
+        let mut trace = self.trace.borrow_mut();
+        match trace.stages.last_mut() {
+            Some(stage) if stage.decision.is_none() => stage.decision = Some(decision),
+            _ => trace.stages.push(StageTrace {
+                stage: "acceptance".to_string(),
+                contributions: Vec::new(),
+                decision: Some(decision),
+            }),
+        }
+    }
+
+    pub fn record_rerank_points(&self, points: RerankPointsTrace) {
+        self.trace.borrow_mut().mechanical_rerank.push(points);
+    }
+
+    pub fn into_trace(self) -> MatchTrace {
+        self.trace.into_inner()
+    }
+
+    fn push_stage(&self, stage: &str, contributions: Vec<StageContribution>) {
+        self.trace.borrow_mut().stages.push(StageTrace {
+            stage: stage.to_string(),
+            contributions,
+            decision: None,
+        });
+    }
+}
+
+fn contributions(
+    db: &MasterDb,
+    states: &HashMap<usize, ScoreState>,
+    baseline: &HashMap<usize, f32>,
+) -> Vec<StageContribution> {
+    let mut contributions: Vec<StageContribution> = states
+        .iter()
+        .filter_map(|(entry_id, state)| {
+            let delta = state.score - baseline.get(entry_id).copied().unwrap_or(0.0);
+            if delta.abs() < f32::EPSILON {
+                return None;
+            }
+            Some(StageContribution {
+                entry_id: *entry_id,
+                name: db.entries.get(*entry_id)?.name.clone(),
+                delta,
+                score: state.score,
+            })
+        })
+        .collect();
+    contributions.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    contributions
+}
+
+#[cfg(test)]
+#[path = "../tests/models/trace_tests.rs"]
+mod trace_tests;
@@ -1,7 +1,7 @@
 //! Domain types for the staged matcher pipeline.
 //!
 //! Contains: MatchMode, MatchStatus, Candidate, Evidence, Reason,
-//! StagedMatchResult, ScoreState, Confidence, CustomSkin, DbEntry.
+//! StagedMatchResult, ScoreState, Confidence, CustomSkin, DbEntry, UserCorrection.
 
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +34,9 @@ pub enum MatchStatus {
     NeedsReview,
     /// No viable match found.
     NoMatch,
+    /// The folder covers several objects: `candidates_topk` holds one
+    /// candidate per object and `best` is `None`.
+    MultiObjectPack,
 }
 
 impl std::fmt::Display for MatchStatus {
@@ -42,6 +45,7 @@ impl std::fmt::Display for MatchStatus {
             MatchStatus::AutoMatched => write!(f, "AutoMatched"),
             MatchStatus::NeedsReview => write!(f, "NeedsReview"),
             MatchStatus::NoMatch => write!(f, "NoMatch"),
+            MatchStatus::MultiObjectPack => write!(f, "MultiObjectPack"),
         }
     }
 }
@@ -133,6 +137,9 @@ pub enum Reason {
         matched_term: String,
         similarity: f32,
     },
+    /// The user previously assigned a folder with this name pattern to the
+    /// candidate. `exact` is false when only the pattern's words matched.
+    UserCorrection { pattern: String, exact: bool },
 }
 
 /// Result contract for staged matcher (new implementation).
@@ -270,3 +277,13 @@ pub struct DbEntry {
     #[serde(default)]
     pub hash_db: std::collections::HashMap<String, Vec<String>>,
 }
+
+/// A folder name the user reassigned to an entry, fed to `MasterDb` so the
+/// matcher learns the correction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserCorrection {
+    /// Folder name as the user saw it; normalized when the index is built.
+    pub folder_name: String,
+    /// Name of the entry the folder belongs to.
+    pub entry_name: String,
+}
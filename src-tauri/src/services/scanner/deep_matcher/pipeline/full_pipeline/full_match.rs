@@ -2,17 +2,20 @@
 
 use crate::services::scanner::core::walker::{FolderContent, ModCandidate};
 use crate::services::scanner::deep_matcher::analysis::ai_rerank::maybe_apply_ai_rerank;
-use crate::services::scanner::deep_matcher::analysis::content::PreparedTokenFilters;
+use crate::services::scanner::deep_matcher::analysis::content::{
+    FolderSignals, PreparedTokenFilters,
+};
 use crate::services::scanner::deep_matcher::analysis::gamebanana::{self, GameBananaConfig};
 use crate::services::scanner::deep_matcher::analysis::mechanical_rerank::{
     self, MechanicalRerankConfig,
 };
 use crate::services::scanner::deep_matcher::models::acceptance::StageContext;
-use crate::services::scanner::deep_matcher::pipeline::name_rescue;
+use crate::services::scanner::deep_matcher::models::trace::MatchTracer;
 use crate::services::scanner::deep_matcher::pipeline::stages::{
-    apply_direct_name_support_stage, replenish_candidates_if_needed, seed_candidates,
-    ObservedTokenBuckets, DEFAULT_MIN_POOL, DEFAULT_SEED_CAP,
+    apply_direct_name_support_stage, apply_user_correction_stage, replenish_candidates_if_needed,
+    seed_candidates, ObservedTokenBuckets, DEFAULT_MIN_POOL, DEFAULT_SEED_CAP,
 };
+use crate::services::scanner::deep_matcher::pipeline::{hash_overlap, name_rescue};
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{
     Confidence, MatchMode, MatchStatus, ScoreState, StagedMatchResult,
@@ -25,6 +28,8 @@ use super::scoring_stages::{
 
 const FULL_TOP_K: usize = 5;
 const REVIEW_MIN_SCORE_FULL: f32 = 12.0;
+const T_CORRECTION_FULL: f32 = 20.0;
+const M_CORRECTION_FULL: f32 = 8.0;
 const T_HASH_FULL: f32 = 10.0;
 const M_HASH_FULL: f32 = 4.0;
 const T_ALIAS_FULL: f32 = 12.0;
@@ -72,7 +77,42 @@ pub fn match_folder_full_cached(
             ini_filters,
         )
         .clone();
-    let observed_buckets = ObservedTokenBuckets::from_signals(&signals);
+    full_match_with_signals(&signals, db, ai_config, gb_config, None)
+}
+
+/// [`match_folder_full`] with every stage, acceptance check and rerank score
+/// recorded into `tracer`.
+pub fn match_folder_full_traced(
+    candidate: &ModCandidate,
+    db: &MasterDb,
+    content: &FolderContent,
+    ini_filters: &PreparedTokenFilters,
+    ai_config: &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig<'_>,
+    gb_config: &GameBananaConfig,
+    tracer: &MatchTracer,
+) -> StagedMatchResult {
+    let mut local_cache =
+        crate::services::scanner::deep_matcher::state::signal_cache::SignalCache::new();
+    let signals = local_cache
+        .get_or_compute(
+            &candidate.path,
+            content,
+            MatchMode::FullScoring,
+            ini_filters,
+        )
+        .clone();
+    tracer.record_signals(&signals);
+    full_match_with_signals(&signals, db, ai_config, gb_config, Some(tracer))
+}
+
+fn full_match_with_signals(
+    signals: &FolderSignals,
+    db: &MasterDb,
+    ai_config: &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig<'_>,
+    gb_config: &GameBananaConfig,
+    trace: Option<&MatchTracer>,
+) -> StagedMatchResult {
+    let observed_buckets = ObservedTokenBuckets::from_signals(signals);
     let observed_tokens: HashSet<String> = observed_buckets.observed_tokens().into_iter().collect();
     let seeded = seed_candidates(
         &db.indexes,
@@ -95,14 +135,39 @@ pub fn match_folder_full_cached(
 
     let stage = StageContext {
         db,
-        signals: &signals,
+        signals,
         buckets: &observed_buckets,
         mode: MatchMode::FullScoring,
         review_min_score: REVIEW_MIN_SCORE_FULL,
         top_k: FULL_TOP_K,
+        trace,
     };
 
+    // ★ F0a: UserCorrection — folder names the user already reassigned
+    apply_user_correction_stage(
+        db,
+        &signals.folder_name_normalized,
+        &observed_buckets.folder_tokens,
+        &mut states,
+    );
+    stage.record_stage("user_correction", &states);
+    if let Some(accepted) = stage.accept(
+        &states,
+        T_CORRECTION_FULL,
+        M_CORRECTION_FULL,
+        Confidence::High,
+    ) {
+        return accepted;
+    }
+
+    // ★ F0b: HashOverlap — decisive TextureOverride identity, before any fuzzy stage
+    if let Some(accepted) = hash_overlap::try_accept_hash_overlap(&stage, &signals.override_hashes)
+    {
+        return accepted;
+    }
+
     apply_hash_stage(db, &signals.ini_hashes, &mut states);
+    stage.record_stage("hash", &states);
     if let Some(accepted) = stage.accept(&states, T_HASH_FULL, M_HASH_FULL, Confidence::High) {
         return accepted;
     }
@@ -112,12 +177,14 @@ pub fn match_folder_full_cached(
         &observed_buckets.folder_tokens,
         &mut states,
     );
+    stage.record_stage("alias", &states);
     if let Some(accepted) = stage.accept(&states, T_ALIAS_FULL, M_ALIAS_FULL, Confidence::High) {
         return accepted;
     }
 
     // ★ F3A: SubstringNameDeep Pass A — check file/subfolder names via substring matching
-    name_rescue::apply_substring_name_pass_a(db, &signals, &mut states);
+    name_rescue::apply_substring_name_pass_a(db, signals, &mut states);
+    stage.record_stage("substring_name_pass_a", &states);
     if let Some(accepted) = stage.accept(&states, T_DEEP_FULL, M_DEEP_FULL, Confidence::High) {
         return accepted;
     }
@@ -128,22 +195,26 @@ pub fn match_folder_full_cached(
         &observed_buckets,
         &mut states,
     );
+    stage.record_stage("deep_tokens", &states);
     if let Some(accepted) = stage.accept(&states, T_DEEP_FULL, M_DEEP_FULL, Confidence::Medium) {
         return accepted;
     }
 
     // ★ F3B: SubstringNameDeep Pass B — INI-derived strings (section headers + path stems)
-    name_rescue::apply_substring_name_pass_b(db, &signals, &mut states);
+    name_rescue::apply_substring_name_pass_b(db, signals, &mut states);
+    stage.record_stage("substring_name_pass_b", &states);
     if let Some(accepted) = stage.accept(&states, T_DEEP_FULL, M_DEEP_FULL, Confidence::High) {
         return accepted;
     }
 
     apply_alias_recheck_stage(db, &observed_tokens, &mut states);
+    stage.record_stage("alias_recheck", &states);
     if let Some(accepted) = stage.accept(&states, T_ALIAS_FULL, M_ALIAS_FULL, Confidence::High) {
         return accepted;
     }
 
     apply_weighted_token_overlap_stage(db, &observed_buckets.folder_tokens, &mut states);
+    stage.record_stage("weighted_token_overlap", &states);
     if let Some(accepted) = stage.accept(&states, T_TOKEN_FULL, M_TOKEN_FULL, Confidence::Medium) {
         return accepted;
     }
@@ -157,14 +228,15 @@ pub fn match_folder_full_cached(
         6.0,
         4.0,
     );
+    stage.record_stage("direct_name_support", &states);
     let result = stage.finalize(&states);
 
-    let result = maybe_apply_ai_rerank(result, &signals, db, MatchMode::FullScoring, ai_config);
+    let result = maybe_apply_ai_rerank(result, signals, db, MatchMode::FullScoring, ai_config);
 
     // ★ GameBanana enrichment + mechanical rerank (independent of trait-based AI)
     let result = if result.status == MatchStatus::NeedsReview {
         let gb_result = if gb_config.enabled {
-            let refs = gamebanana::detect_gamebanana_ids(&signals);
+            let refs = gamebanana::detect_gamebanana_ids(signals);
             if !refs.is_empty() {
                 gamebanana::fetch_gamebanana_metadata(&refs, gb_config)
             } else {
@@ -180,14 +252,14 @@ pub fn match_folder_full_cached(
             gb_description_keywords: gb_result.description_keywords,
             ..MechanicalRerankConfig::default()
         };
-        mechanical_rerank::mechanical_rerank(result, &signals, db, &mech_config)
+        mechanical_rerank::mechanical_rerank_traced(result, signals, db, &mech_config, trace)
     } else {
         result
     };
 
     // ★ F9: Root folder rescue — last resort when everything else fails
     if result.status == MatchStatus::NoMatch {
-        return name_rescue::apply_root_folder_rescue(db, &signals);
+        return name_rescue::apply_root_folder_rescue(db, signals);
     }
 
     result
@@ -0,0 +1,89 @@
+//! F0b: hash-overlap stage — deterministic identity from `TextureOverride` hashes.
+//!
+//! Runs ahead of every token and fuzzy stage in both pipelines. It scores the
+//! whole hash index rather than the seeded pool, weighting each hit by its
+//! rarity, and only ever returns an auto-match: anything less decisive falls
+//! through to the regular stages untouched.
+
+use std::collections::HashMap;
+
+use crate::services::scanner::deep_matcher::analysis::scoring::apply_hash_contribution;
+use crate::services::scanner::deep_matcher::models::acceptance::StageContext;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{
+    Confidence, MatchStatus, ScoreState, StagedMatchResult,
+};
+
+const T_HASH_OVERLAP: f32 = 20.0;
+const M_HASH_OVERLAP: f32 = 10.0;
+
+/// Per-hit weight, multiplied by the hash IDF.
+const HASH_OVERLAP_WEIGHT: f32 = 4.0;
+/// Flat bonus for a hash that belongs to exactly one entry.
+const UNIQUE_HASH_BONUS: f32 = 6.0;
+/// Hashes shared by more entries than this are generic (shared bodies, common
+/// UI textures) and say nothing about identity.
+const MAX_SHARED_DF: usize = 8;
+/// A single unique hash can be a copied texture; two cannot be a coincidence.
+const MIN_UNIQUE_HASHES: u32 = 2;
+
+/// Score every entry that owns at least one of `override_hashes`.
+pub fn score_hash_overlap(db: &MasterDb, override_hashes: &[String]) -> HashMap<usize, ScoreState> {
+    let mut states: HashMap<usize, ScoreState> = HashMap::new();
+    for hash in override_hashes {
+        let Some(posting) = db.indexes.hash_index.get(hash) else {
+            continue;
+        };
+        let df = db
+            .indexes
+            .hash_df
+            .get(hash)
+            .copied()
+            .unwrap_or(posting.len());
+        if df > MAX_SHARED_DF {
+            continue;
+        }
+        let unique_overlap = if df == 1 { 1 } else { 0 };
+        let score_delta =
+            HASH_OVERLAP_WEIGHT * db.hash_idf(hash) + if df == 1 { UNIQUE_HASH_BONUS } else { 0.0 };
+
+        for entry_id in posting {
+            let state = states.entry(*entry_id).or_default();
+            apply_hash_contribution(state, 1, unique_overlap, score_delta);
+        }
+    }
+    states
+}
+
+/// Auto-accept on hash identity alone, or return `None` and leave the folder to
+/// the later stages.
+///
+/// Decisive means one entry owns at least [`MIN_UNIQUE_HASHES`] of the folder's
+/// unique hashes and no other entry owns any. A folder carrying two objects'
+/// own hashes is a pack or a merge, which the ambiguity controls handle better.
+pub fn try_accept_hash_overlap(
+    stage: &StageContext<'_>,
+    override_hashes: &[String],
+) -> Option<StagedMatchResult> {
+    let states = score_hash_overlap(stage.db, override_hashes);
+    stage.record_isolated_stage("hash_overlap", &states);
+
+    let mut owners = states.values().filter(|state| state.unique_overlap > 0);
+    let owner = owners.next()?;
+    if owners.next().is_some() || owner.unique_overlap < MIN_UNIQUE_HASHES {
+        return None;
+    }
+
+    stage
+        .accept(
+            &states,
+            T_HASH_OVERLAP,
+            M_HASH_OVERLAP,
+            Confidence::Excellent,
+        )
+        .filter(|result| result.status == MatchStatus::AutoMatched)
+}
+
+#[cfg(test)]
+#[path = "../tests/pipeline/hash_overlap_tests.rs"]
+mod hash_overlap_tests;
@@ -1,5 +1,7 @@
 pub mod full_pipeline;
+pub mod hash_overlap;
 pub mod name_rescue;
+pub mod pack_split;
 pub mod quick_pipeline;
 pub mod quick_pipeline_result;
 pub mod stages;
@@ -0,0 +1,251 @@
+//! Multi-object packs: one download touching several objects.
+//!
+//! A pack shows up two ways. Its INIs carry hashes unique to several entries,
+//! which the acceptance controls see as hash evidence spanning entries; or it
+//! holds one subfolder per object, each of which matches on its own. Either
+//! way the folder is reported as [`MatchStatus::MultiObjectPack`] with one
+//! candidate per object instead of a forced single best.
+
+use std::collections::BTreeSet;
+
+use crate::common::normalizer;
+use crate::services::scanner::core::walker::{self, FolderContent, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::{
+    FolderSignals, PreparedTokenFilters,
+};
+use crate::services::scanner::deep_matcher::pipeline::quick_pipeline_result::{
+    build_evidence, empty_evidence,
+};
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::state::signal_cache::SignalCache;
+use crate::services::scanner::deep_matcher::{
+    Candidate, Evidence, MatchStatus, Reason, StagedMatchResult, MAX_EVIDENCE_HASHES,
+    MAX_EVIDENCE_SECTIONS, MAX_EVIDENCE_TOKENS,
+};
+
+/// Fewer objects than this is a single mod, not a pack.
+pub const MIN_PACK_MEMBERS: usize = 2;
+/// A folder with more subfolders than this is a library, not a pack; matching
+/// each one would cost more than the review it saves.
+const MAX_PACK_SUBFOLDERS: usize = 16;
+
+/// One immediate subfolder of a pack and how it matched on its own.
+#[derive(Debug, Clone)]
+pub struct PackPart {
+    pub folder: ModCandidate,
+    pub result: StagedMatchResult,
+}
+
+impl PackPart {
+    /// The object this part belongs to, when it matched one outright.
+    pub fn matched(&self) -> Option<&Candidate> {
+        (self.result.status == MatchStatus::AutoMatched)
+            .then_some(self.result.best.as_ref())
+            .flatten()
+    }
+}
+
+/// Candidates carrying hashes no other entry owns, when there are enough of
+/// them to make a pack. `candidates` are ranked, one per entry.
+pub(crate) fn hash_pack_members(candidates: &[Candidate], review_min_score: f32) -> Vec<Candidate> {
+    let members: Vec<Candidate> = candidates
+        .iter()
+        .filter(|candidate| candidate.score >= review_min_score)
+        .filter(|candidate| {
+            candidate.reasons.iter().any(|reason| {
+                matches!(reason, Reason::HashOverlap { unique_overlap, .. } if *unique_overlap >= 1)
+            })
+        })
+        .cloned()
+        .collect();
+    if members.len() < MIN_PACK_MEMBERS {
+        return Vec::new();
+    }
+    members
+}
+
+/// A pack result listing `members`, with their evidence merged.
+pub(crate) fn pack_result(
+    db: &MasterDb,
+    signals: &FolderSignals,
+    members: Vec<Candidate>,
+) -> StagedMatchResult {
+    let mut evidence = empty_evidence(signals);
+    for member in &members {
+        merge_evidence(&mut evidence, build_evidence(db, signals, member));
+    }
+    StagedMatchResult {
+        status: MatchStatus::MultiObjectPack,
+        best: None,
+        candidates_topk: members.clone(),
+        candidates_all: members,
+        evidence,
+    }
+}
+
+/// Union the matched lists, keeping them sorted and within the usual caps.
+/// Scan counts are left to the caller.
+fn merge_evidence(into: &mut Evidence, from: Evidence) {
+    for (target, source, cap) in [
+        (
+            &mut into.matched_hashes,
+            from.matched_hashes,
+            MAX_EVIDENCE_HASHES,
+        ),
+        (
+            &mut into.matched_tokens,
+            from.matched_tokens,
+            MAX_EVIDENCE_TOKENS,
+        ),
+        (
+            &mut into.matched_sections,
+            from.matched_sections,
+            MAX_EVIDENCE_SECTIONS,
+        ),
+    ] {
+        let merged: BTreeSet<String> = target.drain(..).chain(source).collect();
+        *target = merged.into_iter().take(cap).collect();
+    }
+}
+
+/// The immediate subfolders of `folder` that hold at least one INI, each with
+/// its content. Hidden folders are skipped, as the mods walk skips them.
+fn pack_subfolders(folder: &ModCandidate) -> Vec<(ModCandidate, FolderContent)> {
+    let Ok(entries) = std::fs::read_dir(&folder.path) else {
+        return Vec::new();
+    };
+    let mut subfolders: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| {
+            let raw_name = entry.file_name().to_string_lossy().into_owned();
+            if raw_name.starts_with('.') {
+                return None;
+            }
+            let path = entry.path();
+            let content = walker::scan_folder_content(&path, 3);
+            if content.ini_files.is_empty() {
+                return None;
+            }
+            let candidate = ModCandidate {
+                display_name: normalizer::normalize_display_name(&raw_name).into_owned(),
+                is_disabled: folder.is_disabled || normalizer::is_disabled_folder(&raw_name),
+                raw_name,
+                path,
+            };
+            Some((candidate, content))
+        })
+        .collect();
+    subfolders.sort_by(|left, right| left.0.path.cmp(&right.0.path));
+    subfolders
+}
+
+/// Cheap layout check run before any subfolder is read or matched: a pack
+/// keeps its INIs in at least [`MIN_PACK_MEMBERS`] and at most
+/// [`MAX_PACK_SUBFOLDERS`] immediate subfolders, and none at its root. A root
+/// INI drives the whole folder, as in a mod with toggleable variants.
+pub fn looks_like_pack(folder: &ModCandidate, content: &FolderContent) -> bool {
+    let mut subfolders = BTreeSet::new();
+    for ini in &content.ini_files {
+        let Ok(relative) = ini.strip_prefix(&folder.path) else {
+            continue;
+        };
+        let mut components = relative.components();
+        let Some(first) = components.next() else {
+            continue;
+        };
+        if components.next().is_none() {
+            return false;
+        }
+        let name = first.as_os_str().to_string_lossy();
+        if !name.starts_with('.') {
+            subfolders.insert(name.into_owned());
+        }
+    }
+    (MIN_PACK_MEMBERS..=MAX_PACK_SUBFOLDERS).contains(&subfolders.len())
+}
+
+/// Match each INI-bearing subfolder of `folder` on its own. Empty when the
+/// folder does not look like a pack, see [`looks_like_pack`].
+pub fn split_pack(
+    folder: &ModCandidate,
+    content: &FolderContent,
+    db: &MasterDb,
+    ini_filters: &PreparedTokenFilters,
+    ai_config: &AiRerankConfig<'_>,
+    cache: &mut SignalCache,
+) -> Vec<PackPart> {
+    if !looks_like_pack(folder, content) {
+        return Vec::new();
+    }
+    let subfolders = pack_subfolders(folder);
+    if subfolders.len() < MIN_PACK_MEMBERS || subfolders.len() > MAX_PACK_SUBFOLDERS {
+        return Vec::new();
+    }
+    subfolders
+        .into_iter()
+        .map(|(subfolder, content)| {
+            let result = crate::services::scanner::deep_matcher::match_single_object(
+                &subfolder,
+                db,
+                &content,
+                ini_filters,
+                ai_config,
+                cache,
+            );
+            PackPart {
+                folder: subfolder,
+                result,
+            }
+        })
+        .collect()
+}
+
+/// The objects `parts` matched outright, one candidate per entry.
+pub fn pack_members(parts: &[PackPart]) -> Vec<Candidate> {
+    let mut seen = BTreeSet::new();
+    parts
+        .iter()
+        .filter_map(PackPart::matched)
+        .filter(|candidate| seen.insert(candidate.entry_id))
+        .cloned()
+        .collect()
+}
+
+/// Report `folder` as a pack when its subfolders match at least
+/// [`MIN_PACK_MEMBERS`] different objects on their own.
+pub fn detect_subfolder_pack(
+    folder: &ModCandidate,
+    content: &FolderContent,
+    db: &MasterDb,
+    ini_filters: &PreparedTokenFilters,
+    ai_config: &AiRerankConfig<'_>,
+    cache: &mut SignalCache,
+) -> Option<StagedMatchResult> {
+    let parts = split_pack(folder, content, db, ini_filters, ai_config, cache);
+    let members = pack_members(&parts);
+    if members.len() < MIN_PACK_MEMBERS {
+        return None;
+    }
+
+    let mut evidence = Evidence::new();
+    for part in &parts {
+        evidence.scanned_ini_files += part.result.evidence.scanned_ini_files;
+        evidence.scanned_name_items += part.result.evidence.scanned_name_items;
+        if part.matched().is_some() {
+            merge_evidence(&mut evidence, part.result.evidence.clone());
+        }
+    }
+    Some(StagedMatchResult {
+        status: MatchStatus::MultiObjectPack,
+        best: None,
+        candidates_topk: members.clone(),
+        candidates_all: members,
+        evidence,
+    })
+}
+
+#[cfg(test)]
+#[path = "../tests/pipeline/pack_split_tests.rs"]
+mod pack_split_tests;
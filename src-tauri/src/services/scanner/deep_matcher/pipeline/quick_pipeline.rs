@@ -8,11 +8,12 @@ use crate::services::scanner::deep_matcher::analysis::scoring::{
     apply_hash_contribution, apply_token_overlap_contribution,
 };
 use crate::services::scanner::deep_matcher::models::acceptance::StageContext;
-use crate::services::scanner::deep_matcher::pipeline::name_rescue;
 use crate::services::scanner::deep_matcher::pipeline::stages::{
-    apply_direct_name_support_stage, entry_tokens, replenish_candidates_if_needed, seed_candidates,
-    ObservedTokenBuckets, DEFAULT_MIN_POOL, DEFAULT_SEED_CAP,
+    apply_direct_name_support_stage, apply_user_correction_stage, entry_tokens,
+    replenish_candidates_if_needed, seed_candidates, ObservedTokenBuckets, DEFAULT_MIN_POOL,
+    DEFAULT_SEED_CAP,
 };
+use crate::services::scanner::deep_matcher::pipeline::{hash_overlap, name_rescue};
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::{
     Confidence, MatchMode, ScoreState, StagedMatchResult,
@@ -21,6 +22,8 @@ use crate::services::scanner::deep_matcher::{
 const QUICK_TOP_K: usize = 5;
 const REVIEW_MIN_SCORE_QUICK: f32 = 10.0;
 
+const T_CORRECTION_QUICK: f32 = 20.0;
+const M_CORRECTION_QUICK: f32 = 8.0;
 const T_HASH_QUICK: f32 = 10.0;
 const M_HASH_QUICK: f32 = 6.0;
 const T_ALIAS_QUICK: f32 = 12.0;
@@ -90,8 +93,31 @@ pub fn match_folder_quick_cached(
         mode: MatchMode::Quick,
         review_min_score: REVIEW_MIN_SCORE_QUICK,
         top_k: QUICK_TOP_K,
+        trace: None,
     };
 
+    // ★ F0a: UserCorrection — folder names the user already reassigned
+    apply_user_correction_stage(
+        db,
+        &signals.folder_name_normalized,
+        &observed_buckets.folder_tokens,
+        &mut states,
+    );
+    if let Some(accepted) = stage.accept(
+        &states,
+        T_CORRECTION_QUICK,
+        M_CORRECTION_QUICK,
+        Confidence::High,
+    ) {
+        return accepted;
+    }
+
+    // ★ F0b: HashOverlap — decisive TextureOverride identity, before any fuzzy stage
+    if let Some(accepted) = hash_overlap::try_accept_hash_overlap(&stage, &signals.override_hashes)
+    {
+        return accepted;
+    }
+
     apply_hash_stage(db, &signals.ini_hashes, &mut states);
     if let Some(accepted) = stage.accept(&states, T_HASH_QUICK, M_HASH_QUICK, Confidence::High) {
         return accepted;
@@ -7,6 +7,7 @@ use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
 use crate::services::scanner::deep_matcher::analysis::indexes::MatcherIndexes;
 use crate::services::scanner::deep_matcher::analysis::scoring::{
     apply_alias_contribution, apply_deep_token_contribution, apply_ini_token_contribution,
+    apply_user_correction_contribution,
 };
 use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
 use crate::services::scanner::deep_matcher::ScoreState;
@@ -248,6 +249,33 @@ pub(super) fn apply_alias_stage(
     }
 }
 
+/// Folders the user already reassigned. An exact pattern match is decisive on
+/// its own; a folder carrying every word of a corrected name scores like a
+/// strict alias. Corrected entries join the pool even when seeding missed them.
+pub(super) fn apply_user_correction_stage(
+    db: &MasterDb,
+    folder_name_normalized: &str,
+    folder_tokens: &BTreeSet<String>,
+    states: &mut HashMap<usize, ScoreState>,
+) {
+    let corrections = &db.indexes;
+    if let Some(&entry_id) = corrections.correction_patterns.get(folder_name_normalized) {
+        let state = states.entry(entry_id).or_default();
+        apply_user_correction_contribution(state, folder_name_normalized, true, 24.0);
+        return;
+    }
+
+    let mut hinted: HashSet<usize> = HashSet::new();
+    for hint in &corrections.correction_hints {
+        if hinted.contains(&hint.entry_id) || !hint.tokens.is_subset(folder_tokens) {
+            continue;
+        }
+        hinted.insert(hint.entry_id);
+        let state = states.entry(hint.entry_id).or_default();
+        apply_user_correction_contribution(state, &hint.pattern, false, 12.0);
+    }
+}
+
 pub(super) fn apply_deep_stage(
     db: &MasterDb,
     buckets: &ObservedTokenBuckets,
@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
-use crate::common::normalizer;
+use crate::common::{normalizer, path_key};
 use crate::domain::errors::ScannerError;
 use crate::services::scanner::deep_matcher::analysis::indexes::MatcherIndexes;
-use crate::services::scanner::deep_matcher::models::types::DbEntry;
+use crate::services::scanner::deep_matcher::models::types::{DbEntry, UserCorrection};
 
 /// The Master DB containing all known objects for matching.
 #[derive(Debug, Clone)]
@@ -18,6 +18,11 @@ pub struct MasterDb {
 impl MasterDb {
     /// Build a MasterDb from raw entries, pre-computing keyword sets.
     pub fn new(entries: Vec<DbEntry>) -> Self {
+        Self::new_with_corrections(entries, &[])
+    }
+
+    /// Build a MasterDb whose indexes also carry the user's corrections.
+    pub fn new_with_corrections(entries: Vec<DbEntry>, corrections: &[UserCorrection]) -> Self {
         let keywords: Vec<(usize, HashSet<String>)> = entries
             .iter()
             .enumerate()
@@ -30,7 +35,8 @@ impl MasterDb {
             })
             .collect();
 
-        let indexes = MatcherIndexes::build(&entries, &keywords);
+        let mut indexes = MatcherIndexes::build(&entries, &keywords);
+        indexes.attach_corrections(&entries, corrections);
 
         Self {
             entries,
@@ -39,11 +45,33 @@ impl MasterDb {
         }
     }
 
+    /// Digest of every entry's name and tags in order. Entry ids are
+    /// positions, so anything that stores scores by entry id (the AI rerank
+    /// cache) is only valid for the database it was computed against.
+    pub fn content_version(&self) -> String {
+        let mut hasher = blake3::Hasher::new();
+        for entry in &self.entries {
+            hasher.update(entry.name.as_bytes());
+            for tag in &entry.tags {
+                hasher.update(b"\x1f");
+                hasher.update(tag.as_bytes());
+            }
+            hasher.update(b"\x1e");
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
     /// Load from JSON string.
     /// Supports both legacy array format `[{entry1}, {entry2}]`
     /// and new object format `{"entries": [...], "hash_db": {...}}`.
     /// When hash_db is present, merges hashes into matching entries by name.
     pub fn from_json(json: &str) -> Result<Self, ScannerError> {
+        Ok(Self::new(Self::entries_from_json(json)?))
+    }
+
+    /// Parse the entries `from_json` would build from, for callers that add
+    /// corrections before the indexes are built.
+    pub fn entries_from_json(json: &str) -> Result<Vec<DbEntry>, ScannerError> {
         let value: serde_json::Value = serde_json::from_str(json)?;
 
         let (mut entries, hash_db) = match value {
@@ -81,7 +109,25 @@ impl MasterDb {
             }
         }
 
-        Ok(Self::new(entries))
+        Ok(entries)
+    }
+
+    /// Fold the user's MasterDB overlay into `entries`, before the indexes
+    /// are built.
+    ///
+    /// An overlay record naming an existing entry adds its tags, skins,
+    /// aliases, and hashes to it; what the entry already has wins. Any other
+    /// record is a new entry.
+    pub fn merge_overlay(entries: &mut Vec<DbEntry>, overlay: &[DbEntry]) {
+        for addition in overlay {
+            match entries
+                .iter_mut()
+                .find(|entry| path_key::names_equal_by_key(&entry.name, &addition.name))
+            {
+                Some(entry) => merge_overlay_entry(entry, addition),
+                None => entries.push(addition.clone()),
+            }
+        }
     }
 
     pub fn token_idf(&self, token: &str) -> f32 {
@@ -92,3 +138,46 @@ impl MasterDb {
         self.indexes.hash_idf(hash, self.entries.len())
     }
 }
+
+/// Push the `additions` `values` lacks, compared case-insensitively.
+fn extend_unique(values: &mut Vec<String>, additions: &[String]) {
+    for addition in additions {
+        let trimmed = addition.trim();
+        if trimmed.is_empty()
+            || values
+                .iter()
+                .any(|value| value.trim().eq_ignore_ascii_case(trimmed))
+        {
+            continue;
+        }
+        values.push(trimmed.to_string());
+    }
+}
+
+/// Add what `addition` carries beyond `entry`. Fields `entry` already set are
+/// kept.
+pub(crate) fn merge_overlay_entry(entry: &mut DbEntry, addition: &DbEntry) {
+    extend_unique(&mut entry.tags, &addition.tags);
+    for skin in &addition.custom_skins {
+        match entry
+            .custom_skins
+            .iter_mut()
+            .find(|known| known.name.trim().eq_ignore_ascii_case(skin.name.trim()))
+        {
+            Some(known) => extend_unique(&mut known.aliases, &skin.aliases),
+            None => entry.custom_skins.push(skin.clone()),
+        }
+    }
+    for (variant, hashes) in &addition.hash_db {
+        extend_unique(entry.hash_db.entry(variant.clone()).or_default(), hashes);
+    }
+    if entry.object_type.is_empty() {
+        entry.object_type = addition.object_type.clone();
+    }
+    if entry.thumbnail_path.is_none() {
+        entry.thumbnail_path = addition.thumbnail_path.clone();
+    }
+    if entry.metadata.is_none() {
+        entry.metadata = addition.metadata.clone();
+    }
+}
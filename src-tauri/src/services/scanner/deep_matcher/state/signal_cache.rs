@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 
 use crate::services::scanner::core::walker::FolderContent;
 use crate::services::scanner::deep_matcher::analysis::content::{
@@ -7,20 +8,25 @@ use crate::services::scanner::deep_matcher::analysis::content::{
 };
 use crate::services::scanner::deep_matcher::MatchMode;
 
+/// Bumped when `FolderSignals` or the signal collector changes, so signals
+/// persisted by an older build are recomputed rather than trusted.
+pub const SIGNAL_CACHE_VERSION: u32 = 1;
+
 /// In-memory cache for `FolderSignals` keyed by `(folder_path, mode)`.
 ///
 /// Avoids re-reading and re-tokenizing INI files when `match_folder_phased`
 /// falls through from Quick → Full for the same folder within one scan batch.
+/// The scan preview also seeds it from signals persisted by an earlier scan.
 #[derive(Debug, Default)]
 pub struct SignalCache {
     store: HashMap<(String, u8), FolderSignals>,
+    /// Keys computed here rather than seeded, in computation order.
+    computed: Vec<(String, u8)>,
 }
 
 impl SignalCache {
     pub fn new() -> Self {
-        Self {
-            store: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// Get cached signals or compute + cache them.
@@ -33,11 +39,30 @@ impl SignalCache {
     ) -> &FolderSignals {
         let key = (folder.to_string_lossy().to_string(), mode as u8);
 
+        if !self.store.contains_key(&key) {
+            self.computed.push(key.clone());
+        }
         self.store
             .entry(key)
             .or_insert_with_key(|_| collect_deep_signals(folder, content, mode, ini_filters))
     }
 
+    /// Store signals computed earlier, e.g. loaded from the persisted cache.
+    pub fn seed(&mut self, folder_path: String, mode: u8, signals: FolderSignals) {
+        self.store.insert((folder_path, mode), signals);
+    }
+
+    /// Signals computed since the last call, as `(folder_path, mode, signals)`.
+    pub fn take_computed(&mut self) -> Vec<(String, u8, FolderSignals)> {
+        std::mem::take(&mut self.computed)
+            .into_iter()
+            .filter_map(|key| {
+                let signals = self.store.get(&key)?.clone();
+                Some((key.0, key.1, signals))
+            })
+            .collect()
+    }
+
     /// Number of cached entries (for diagnostics).
     pub fn len(&self) -> usize {
         self.store.len()
@@ -47,3 +72,75 @@ impl SignalCache {
         self.len() == 0
     }
 }
+
+/// Cheap fingerprint of what `collect_deep_signals` reads from a folder: the
+/// subfolder and file names the walk found, the size and mtime of each INI,
+/// and the token filters. Equal fingerprints mean the persisted signals still
+/// hold, without opening a single INI.
+pub fn folder_fingerprint(
+    folder: &Path,
+    content: &FolderContent,
+    ini_filters: &PreparedTokenFilters,
+) -> String {
+    let relative = |path: &Path| {
+        path.strip_prefix(folder)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&SIGNAL_CACHE_VERSION.to_le_bytes());
+    // Signals collected under one set of filters do not hold under another.
+    for (label, set) in ini_filters.labeled_sets() {
+        hasher.update(label.as_bytes());
+        for value in set {
+            hasher.update(value.as_bytes());
+            hasher.update(&[0]);
+        }
+    }
+
+    let mut names: Vec<&str> = content.subfolder_names.iter().map(String::as_str).collect();
+    names.sort_unstable();
+    hasher.update(b"subfolders");
+    for name in names {
+        hasher.update(name.as_bytes());
+        hasher.update(&[0]);
+    }
+
+    let mut files: Vec<String> = content
+        .files
+        .iter()
+        .map(|file| relative(&file.path))
+        .collect();
+    files.sort_unstable();
+    hasher.update(b"files");
+    for file in files {
+        hasher.update(file.as_bytes());
+        hasher.update(&[0]);
+    }
+
+    let mut ini_files: Vec<(String, u64, u128)> = content
+        .ini_files
+        .iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(path).ok();
+            let len = metadata.as_ref().map_or(0, |metadata| metadata.len());
+            let modified = metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |elapsed| elapsed.as_nanos());
+            (relative(path), len, modified)
+        })
+        .collect();
+    ini_files.sort_unstable();
+    hasher.update(b"ini");
+    for (path, len, modified) in ini_files {
+        hasher.update(path.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&len.to_le_bytes());
+        hasher.update(&modified.to_le_bytes());
+    }
+
+    hasher.finalize().to_hex().to_string()
+}
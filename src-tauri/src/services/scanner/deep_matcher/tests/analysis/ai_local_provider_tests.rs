@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use super::{LocalAiProtocol, LocalAiRerankProvider, LocalAiSettings};
+use crate::domain::errors::ScannerError;
+use crate::services::config::models::{AiConfig, AiProviderKind};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::{
+    maybe_apply_ai_rerank, AiRerankCache, AiRerankCacheKey, AiRerankConfig, AiRerankProvider,
+    AiRerankRequest,
+};
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::models::types::{
+    Candidate, Confidence, MatchMode, MatchStatus, StagedMatchResult,
+};
+use crate::services::scanner::deep_matcher::{DbEntry, MasterDb};
+
+struct CapturedRequest {
+    path: String,
+    body: serde_json::Value,
+}
+
+/// Serve one HTTP request on a loopback port, answering `reply` as JSON after
+/// `delay`. Returns the base URL and the captured request.
+fn serve_once(
+    reply: serde_json::Value,
+    delay: Duration,
+) -> (String, mpsc::Receiver<CapturedRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub server");
+    let base = format!("http://{}", listener.local_addr().expect("stub address"));
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("accept");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).expect("request line");
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or_default()
+            .to_string();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).expect("header");
+            let header = header.trim();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().expect("content length");
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).expect("body");
+        let _ = sender.send(CapturedRequest {
+            path,
+            body: serde_json::from_slice(&body).expect("json body"),
+        });
+
+        thread::sleep(delay);
+        let payload = reply.to_string();
+        let mut stream = stream;
+        let _ = write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{payload}",
+            payload.len()
+        );
+    });
+
+    (base, receiver)
+}
+
+fn entry(name: &str) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins: vec![],
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::new(),
+    }
+}
+
+fn db() -> MasterDb {
+    MasterDb::new(vec![entry("Raiden Shogun"), entry("Yae Miko")])
+}
+
+fn signals() -> FolderSignals {
+    FolderSignals {
+        folder_tokens: vec!["purple".to_string(), "lady".to_string()],
+        ..FolderSignals::default()
+    }
+}
+
+fn settings(protocol: LocalAiProtocol, url: String) -> LocalAiSettings {
+    LocalAiSettings {
+        protocol,
+        url: Some(url),
+        model: None,
+        prompt_template: None,
+        timeout: Duration::from_secs(5),
+        max_candidates: 8,
+    }
+}
+
+fn request() -> AiRerankRequest {
+    AiRerankRequest {
+        mode: MatchMode::Quick,
+        cache_key: AiRerankCacheKey {
+            provider: "local".to_string(),
+            signals_hash: "signals".to_string(),
+            db_version: "db-v1".to_string(),
+        },
+        candidate_entry_ids: vec![0, 1],
+    }
+}
+
+fn candidate(entry_id: usize, name: &str) -> Candidate {
+    Candidate {
+        entry_id,
+        name: name.to_string(),
+        object_type: "Character".to_string(),
+        score: 14.0,
+        confidence: Confidence::Low,
+        reasons: vec![],
+    }
+}
+
+fn review_result() -> StagedMatchResult {
+    StagedMatchResult {
+        status: MatchStatus::NeedsReview,
+        best: Some(candidate(0, "Raiden Shogun")),
+        candidates_topk: vec![candidate(0, "Raiden Shogun"), candidate(1, "Yae Miko")],
+        candidates_all: Vec::new(),
+        evidence: Default::default(),
+    }
+}
+
+#[test]
+fn test_ollama_provider_posts_generate_request_and_reads_wrapped_reply() {
+    let (base, captured) = serve_once(
+        serde_json::json!({ "response": "Sure! ```json\n{\"0\": 0.9, \"1\": 1.4}\n```" }),
+        Duration::ZERO,
+    );
+    let mut settings = settings(LocalAiProtocol::Ollama, format!("{base}/api/generate"));
+    settings.model = Some("qwen2.5".to_string());
+    let provider = LocalAiRerankProvider::new(settings).expect("provider");
+
+    let scores = provider
+        .rerank(&request(), &signals(), &db())
+        .expect("rerank");
+
+    assert_eq!(scores, HashMap::from([(0, 0.9), (1, 1.0)]));
+    let captured = captured.recv().expect("captured request");
+    assert_eq!(captured.path, "/api/generate");
+    assert_eq!(captured.body["model"], "qwen2.5");
+    assert_eq!(captured.body["stream"], false);
+    assert_eq!(captured.body["format"], "json");
+    let prompt = captured.body["prompt"].as_str().expect("prompt");
+    assert!(prompt.contains("- ID: 1, Name: Yae Miko"));
+    assert!(prompt.contains(r#"["purple", "lady"]"#));
+}
+
+#[test]
+fn test_llama_cpp_provider_renders_template_and_caps_candidates() {
+    let (base, captured) = serve_once(
+        serde_json::json!({ "content": "{\"0\": 0.8, \"1\": 0.7}" }),
+        Duration::ZERO,
+    );
+    let mut settings = settings(LocalAiProtocol::LlamaCpp, format!("{base}/completion"));
+    settings.prompt_template = Some("Folder {folder_tokens}\n{candidates}".to_string());
+    settings.max_candidates = 1;
+    let provider = LocalAiRerankProvider::new(settings).expect("provider");
+
+    let scores = provider
+        .rerank(&request(), &signals(), &db())
+        .expect("rerank");
+
+    // Entry 1 was never offered, so its score in the reply is ignored.
+    assert_eq!(scores, HashMap::from([(0, 0.8)]));
+    let captured = captured.recv().expect("captured request");
+    assert_eq!(captured.path, "/completion");
+    assert!(captured.body.get("model").is_none());
+    assert_eq!(
+        captured.body["prompt"],
+        "Folder [\"purple\", \"lady\"]\n- ID: 0, Name: Raiden Shogun, Tags: []"
+    );
+}
+
+#[test]
+fn test_local_provider_times_out_on_a_slow_server() {
+    let (base, _captured) = serve_once(
+        serde_json::json!({ "response": "{}" }),
+        Duration::from_secs(2),
+    );
+    let mut settings = settings(LocalAiProtocol::Ollama, base);
+    settings.timeout = Duration::from_millis(200);
+    let provider = LocalAiRerankProvider::new(settings).expect("provider");
+
+    let error = provider
+        .rerank(&request(), &signals(), &db())
+        .expect_err("slow server should time out");
+
+    assert!(matches!(error, ScannerError::Network(_)));
+}
+
+#[test]
+fn test_local_settings_come_from_ai_config_only_for_local_providers() {
+    let mut config = AiConfig {
+        enabled: true,
+        base_url: Some("  ".to_string()),
+        timeout_secs: Some(0),
+        max_candidates: Some(3),
+        ..AiConfig::default()
+    };
+    assert!(LocalAiSettings::from_config(&config).is_none());
+
+    config.provider = AiProviderKind::LlamaCpp;
+    let settings = LocalAiSettings::from_config(&config).expect("local settings");
+    assert_eq!(settings.protocol, LocalAiProtocol::LlamaCpp);
+    assert_eq!(settings.url, None);
+    assert_eq!(settings.timeout, Duration::from_secs(30));
+    assert_eq!(settings.max_candidates, 3);
+}
+
+#[test]
+fn test_local_cache_identity_follows_model_prompt_and_candidate_window() {
+    let base = || settings(LocalAiProtocol::Ollama, "http://127.0.0.1:1".to_string());
+    let identity = |settings| {
+        LocalAiRerankProvider::new(settings)
+            .expect("provider")
+            .cache_identity()
+    };
+
+    let default = identity(base());
+    assert_eq!(default, identity(base()));
+    assert_ne!(
+        default,
+        identity(LocalAiSettings {
+            model: Some("qwen2.5".to_string()),
+            ..base()
+        })
+    );
+    assert_ne!(
+        default,
+        identity(LocalAiSettings {
+            prompt_template: Some("Score {candidates}".to_string()),
+            ..base()
+        })
+    );
+    assert_ne!(
+        default,
+        identity(LocalAiSettings {
+            max_candidates: 3,
+            ..base()
+        })
+    );
+}
+
+/// Scores cached on disk answer the same folder after a restart, without the
+/// server being up.
+#[test]
+fn test_persistent_cache_survives_restart_without_the_server() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let cache_path = dir.path().join("ai_rerank_cache.jsonl");
+    let db = db();
+
+    let (base, _captured) = serve_once(
+        serde_json::json!({ "response": "{\"0\": 0.2, \"1\": 0.9}" }),
+        Duration::ZERO,
+    );
+    let provider =
+        LocalAiRerankProvider::new(settings(LocalAiProtocol::Ollama, base)).expect("provider");
+    let cache = AiRerankCache::open(&cache_path);
+    let first = maybe_apply_ai_rerank(
+        review_result(),
+        &signals(),
+        &db,
+        MatchMode::Quick,
+        &AiRerankConfig {
+            ai_enabled: true,
+            db_version: Some("db-v1"),
+            provider: Some(&provider),
+            cache: Some(&cache),
+        },
+    );
+    assert_eq!(first.status, MatchStatus::AutoMatched);
+    drop(cache);
+
+    let offline = {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        format!("http://{}", listener.local_addr().expect("address"))
+    };
+    let provider =
+        LocalAiRerankProvider::new(settings(LocalAiProtocol::Ollama, offline)).expect("provider");
+    let reopened = AiRerankCache::open(&cache_path);
+    assert_eq!(reopened.len(), 1);
+    let second = maybe_apply_ai_rerank(
+        review_result(),
+        &signals(),
+        &db,
+        MatchMode::Quick,
+        &AiRerankConfig {
+            ai_enabled: true,
+            db_version: Some("db-v1"),
+            provider: Some(&provider),
+            cache: Some(&reopened),
+        },
+    );
+
+    assert_eq!(second.status, MatchStatus::AutoMatched);
+    assert_eq!(
+        second.best.as_ref().map(|candidate| candidate.entry_id),
+        Some(1)
+    );
+}
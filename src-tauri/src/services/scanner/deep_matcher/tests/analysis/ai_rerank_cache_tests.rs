@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use super::{AiRerankCache, AiRerankCacheKey};
+
+fn key(provider: &str, signals_hash: &str) -> AiRerankCacheKey {
+    AiRerankCacheKey {
+        provider: provider.to_string(),
+        signals_hash: signals_hash.to_string(),
+        db_version: "db-v1".to_string(),
+    }
+}
+
+fn line_count(path: &std::path::Path) -> usize {
+    std::fs::read_to_string(path)
+        .expect("read cache")
+        .lines()
+        .count()
+}
+
+#[test]
+fn inserts_append_a_line_each_and_reload_after_a_restart() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let path = dir.path().join("ai_rerank_cache.jsonl");
+
+    let cache = AiRerankCache::open(&path);
+    cache.insert(key("ollama", "a"), HashMap::from([(0, 0.9)]));
+    cache.insert(key("ollama", "b"), HashMap::from([(1, 0.4)]));
+    // Same signals, other model: a separate entry.
+    cache.insert(key("openai", "a"), HashMap::from([(0, 0.1)]));
+    drop(cache);
+
+    assert_eq!(line_count(&path), 4);
+    let reopened = AiRerankCache::open(&path);
+    assert_eq!(reopened.len(), 3);
+    assert_eq!(
+        reopened.get(&key("ollama", "a")),
+        Some(HashMap::from([(0, 0.9)]))
+    );
+    assert_eq!(
+        reopened.get(&key("openai", "a")),
+        Some(HashMap::from([(0, 0.1)]))
+    );
+}
+
+#[test]
+fn superseded_and_torn_lines_are_compacted_on_open() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let path = dir.path().join("ai_rerank_cache.jsonl");
+
+    let cache = AiRerankCache::open(&path);
+    cache.insert(key("ollama", "a"), HashMap::from([(0, 0.2)]));
+    cache.insert(key("ollama", "a"), HashMap::from([(0, 0.8)]));
+    drop(cache);
+    let mut text = std::fs::read_to_string(&path).expect("read cache");
+    text.push_str("{\"provider\":\"oll");
+    std::fs::write(&path, text).expect("tear last line");
+
+    let reopened = AiRerankCache::open(&path);
+
+    assert_eq!(
+        reopened.get(&key("ollama", "a")),
+        Some(HashMap::from([(0, 0.8)]))
+    );
+    assert_eq!(line_count(&path), 2);
+}
+
+#[test]
+fn a_file_from_an_older_layout_starts_empty() {
+    let dir = tempfile::TempDir::new().expect("temp dir");
+    let path = dir.path().join("ai_rerank_cache.jsonl");
+    std::fs::write(&path, "{\"version\":1,\"entries\":[]}").expect("write old cache");
+
+    let cache = AiRerankCache::open(&path);
+
+    assert!(cache.is_empty());
+    assert_eq!(line_count(&path), 1);
+}
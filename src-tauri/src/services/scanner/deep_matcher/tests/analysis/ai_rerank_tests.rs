@@ -22,6 +22,10 @@ impl CountingProvider {
 }
 
 impl AiRerankProvider for CountingProvider {
+    fn cache_identity(&self) -> String {
+        "counting".to_string()
+    }
+
     fn rerank(
         &self,
         _request: &AiRerankRequest,
@@ -34,6 +38,29 @@ impl AiRerankProvider for CountingProvider {
     }
 }
 
+/// Fails like a provider that timed out.
+struct FailingProvider {
+    calls: Arc<Mutex<usize>>,
+}
+
+impl AiRerankProvider for FailingProvider {
+    fn cache_identity(&self) -> String {
+        "failing".to_string()
+    }
+
+    fn rerank(
+        &self,
+        _request: &AiRerankRequest,
+        _signals: &FolderSignals,
+        _db: &crate::services::scanner::deep_matcher::MasterDb,
+    ) -> Result<HashMap<usize, f32>, crate::domain::errors::ScannerError> {
+        *self.calls.lock().expect("lock call counter") += 1;
+        Err(crate::domain::errors::ScannerError::Io(
+            "request timed out".to_string(),
+        ))
+    }
+}
+
 fn review_result() -> StagedMatchResult {
     StagedMatchResult {
         status: MatchStatus::NeedsReview,
@@ -78,6 +105,7 @@ fn signals(seed: &str) -> FolderSignals {
         ini_section_tokens: vec![format!("section-{seed}")],
         ini_content_tokens: vec![format!("content-{seed}")],
         ini_hashes: vec![format!("hash{seed}")],
+        override_hashes: Vec::new(),
         scanned_ini_files: 1,
         scanned_name_items: 1,
         scanned_ini_bytes: 12,
@@ -123,12 +151,14 @@ fn test_ai_rerank_disabled_does_not_call_provider() {
 // Covers: TC-2.2-Task14-02
 #[test]
 fn test_ai_rerank_cache_key_includes_signals_hash_and_db_version() {
-    let key_a = build_ai_cache_key(&signals("a"), MatchMode::FullScoring, "db-v42");
-    let key_b = build_ai_cache_key(&signals("b"), MatchMode::FullScoring, "db-v42");
+    let key_a = build_ai_cache_key(&signals("a"), MatchMode::FullScoring, "db-v42", "p");
+    let key_b = build_ai_cache_key(&signals("b"), MatchMode::FullScoring, "db-v42", "p");
+    let other_model = build_ai_cache_key(&signals("a"), MatchMode::FullScoring, "db-v42", "q");
 
     assert_eq!(key_a.db_version, "db-v42");
     assert!(!key_a.signals_hash.is_empty());
     assert_ne!(key_a.signals_hash, key_b.signals_hash);
+    assert_ne!(key_a, other_model);
 }
 
 // Covers: TC-2.2-Task14-03
@@ -236,3 +266,34 @@ fn test_ai_rerank_cache_reuses_cached_result_deterministically() {
         Some(1)
     );
 }
+
+#[test]
+fn test_ai_rerank_failure_is_not_cached() {
+    let calls = Arc::new(Mutex::new(0_usize));
+    let provider = FailingProvider {
+        calls: calls.clone(),
+    };
+    let cache = AiRerankCache::default();
+    let config = AiRerankConfig {
+        ai_enabled: true,
+        db_version: Some("db-v3"),
+        provider: Some(&provider),
+        cache: Some(&cache),
+    };
+    let db = crate::services::scanner::deep_matcher::MasterDb::new(vec![]);
+
+    for _ in 0..2 {
+        let result = maybe_apply_ai_rerank(
+            review_result(),
+            &signals("timeout"),
+            &db,
+            MatchMode::Quick,
+            &config,
+        );
+        assert_eq!(result.status, MatchStatus::NeedsReview);
+    }
+
+    // The second scan asks again instead of reading back empty scores.
+    assert_eq!(*calls.lock().expect("lock call counter"), 2);
+    assert!(cache.is_empty());
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::analysis::mechanical_rerank::penalties::PENALTY_GB_CATEGORY_MISMATCH;
 use crate::services::scanner::deep_matcher::models::types::Confidence;
 use crate::services::scanner::deep_matcher::models::types::DbEntry;
 use crate::services::scanner::deep_matcher::{Candidate, MasterDb};
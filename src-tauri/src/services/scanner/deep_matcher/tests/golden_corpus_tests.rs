@@ -1,5 +1,7 @@
 use super::*;
-use crate::services::scanner::deep_matcher::{CustomSkin, DbEntry, MatchStatus};
+use crate::services::scanner::deep_matcher::{
+    Candidate, Confidence, CustomSkin, DbEntry, MatchStatus,
+};
 
 fn test_db() -> MasterDb {
     MasterDb::new(vec![
@@ -141,3 +143,156 @@ fn test_golden_no_match_insufficient_signals() {
     };
     run_golden_case(&case, &test_db());
 }
+
+fn fixture_corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden_corpus")
+}
+
+fn result(status: MatchStatus, best: Option<&str>) -> StagedMatchResult {
+    let best = best.map(|name| Candidate {
+        entry_id: 0,
+        name: name.to_string(),
+        object_type: "Character".to_string(),
+        score: 10.0,
+        confidence: Confidence::High,
+        reasons: vec![],
+    });
+    StagedMatchResult {
+        status,
+        best: best.clone(),
+        candidates_topk: best.into_iter().collect(),
+        candidates_all: Vec::new(),
+        evidence: Default::default(),
+    }
+}
+
+#[test]
+fn test_mode_report_precision_recall_and_review_rate() {
+    let mut report = ModeReport::default();
+    let folder = Path::new("case");
+    report.record(
+        folder,
+        Some("Ayaka"),
+        &result(MatchStatus::AutoMatched, Some("Ayaka")),
+    );
+    report.record(
+        folder,
+        Some("Ayaka"),
+        &result(MatchStatus::AutoMatched, Some("Nahida")),
+    );
+    report.record(
+        folder,
+        Some("Nahida"),
+        &result(MatchStatus::NeedsReview, Some("Nahida")),
+    );
+    report.record(folder, None, &result(MatchStatus::NoMatch, None));
+
+    assert_eq!(report.precision(), Some(0.5));
+    assert_eq!(report.recall(), Some(1.0 / 3.0));
+    assert_eq!(report.needs_review_rate(), Some(0.25));
+    // The wrong auto-match and the review of a labeled case are misses; the
+    // unlabeled folder that matched nothing is not.
+    assert_eq!(report.misses.len(), 2);
+}
+
+#[test]
+fn test_fixture_corpus_reports_each_game_in_both_modes() {
+    let report = run_corpus(&fixture_corpus_dir()).expect("run corpus");
+
+    // The rendered report is shown only when the shape is off.
+    assert_eq!(report.games.len(), 1, "{report}");
+    let game = &report.games[0];
+    assert_eq!(game.game, "gimi");
+    for mode in [&game.quick, &game.full] {
+        assert_eq!(mode.cases, 3);
+        assert_eq!(mode.labeled, 2);
+        let outcomes: Vec<(&str, MatchStatus, Option<&str>)> = mode
+            .outcomes
+            .iter()
+            .map(|outcome| {
+                (
+                    outcome.folder.to_str().expect("utf-8 folder"),
+                    outcome.status,
+                    outcome.best.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (
+                    "Raiden Hash Mod",
+                    MatchStatus::AutoMatched,
+                    Some("Raiden Shogun")
+                ),
+                (
+                    "Nahida Dendro Pack",
+                    MatchStatus::AutoMatched,
+                    Some("Nahida")
+                ),
+                ("Shader Fix", MatchStatus::NoMatch, None),
+            ],
+            "{report}"
+        );
+        assert!(mode.misses.is_empty(), "{report}");
+    }
+
+    assert_eq!(game.duplicates.pairs, 2);
+    assert_eq!(game.duplicates.labeled, 1);
+    assert_eq!(game.duplicates.recall(), Some(1.0));
+}
+
+#[test]
+fn test_corpus_loader_skips_directories_without_manifest() {
+    let temp = TempDir::new().expect("temp dir");
+    std::fs::create_dir_all(temp.path().join("notes")).expect("notes dir");
+    let game = temp.path().join("srmi");
+    std::fs::create_dir_all(game.join("Some Mod")).expect("case dir");
+    std::fs::write(game.join("db.json"), "[]").expect("db");
+    std::fs::write(
+        game.join(EXPECTED_FILE),
+        r#"{ "master_db": "db.json", "cases": [{ "folder": "Some Mod", "expected": null }] }"#,
+    )
+    .expect("manifest");
+
+    let games = load_corpus(temp.path()).expect("load corpus");
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].game, "srmi");
+    assert_eq!(games[0].manifest.cases.len(), 1);
+    assert!(games[0].manifest.duplicates.is_empty());
+}
+
+#[test]
+fn test_corpus_case_folder_must_exist() {
+    let temp = TempDir::new().expect("temp dir");
+    let game = temp.path().join("gimi");
+    std::fs::create_dir_all(&game).expect("game dir");
+    std::fs::write(game.join("db.json"), "[]").expect("db");
+    std::fs::write(
+        game.join(EXPECTED_FILE),
+        r#"{ "master_db": "db.json", "cases": [{ "folder": "Missing", "expected": "Ayaka" }] }"#,
+    )
+    .expect("manifest");
+
+    let error = run_corpus(temp.path()).expect_err("missing folder");
+    assert!(matches!(error, ScannerError::PathNotFound { .. }));
+}
+
+/// Runs a corpus kept outside the repo and prints its report:
+///
+/// `GOLDEN_CORPUS_DIR=/path/to/corpus cargo test golden_corpus_report -- --ignored --nocapture`
+///
+/// Set `GOLDEN_CORPUS_REPORT` to also write the report as JSON, for diffing
+/// two weight settings.
+#[test]
+#[ignore = "needs GOLDEN_CORPUS_DIR"]
+fn test_external_golden_corpus_report() {
+    let dir = std::env::var("GOLDEN_CORPUS_DIR").expect("GOLDEN_CORPUS_DIR");
+    let report = run_corpus(Path::new(&dir)).expect("run corpus");
+    println!("{report}");
+
+    if let Ok(path) = std::env::var("GOLDEN_CORPUS_REPORT") {
+        let json = serde_json::to_string_pretty(&report).expect("serialize report");
+        std::fs::write(path, json).expect("write report");
+    }
+}
@@ -8,7 +8,7 @@ use crate::services::scanner::deep_matcher::{
     Confidence, DbEntry, MatchMode, MatchStatus, Reason, ScoreState,
 };
 
-fn db_entry(name: &str, tags: &[&str], object_type: &str) -> DbEntry {
+pub(super) fn db_entry(name: &str, tags: &[&str], object_type: &str) -> DbEntry {
     DbEntry {
         name: name.to_string(),
         tags: tags.iter().map(|tag| tag.to_string()).collect(),
@@ -20,7 +20,7 @@ fn db_entry(name: &str, tags: &[&str], object_type: &str) -> DbEntry {
     }
 }
 
-fn score_state(score: f32, reasons: Vec<Reason>) -> ScoreState {
+pub(super) fn score_state(score: f32, reasons: Vec<Reason>) -> ScoreState {
     ScoreState {
         score,
         reasons,
@@ -44,7 +44,7 @@ fn buckets(
     }
 }
 
-fn empty_signals() -> FolderSignals {
+pub(super) fn empty_signals() -> FolderSignals {
     FolderSignals::default()
 }
 
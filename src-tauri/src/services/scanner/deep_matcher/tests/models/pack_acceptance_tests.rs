@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use super::acceptance_tests::{db_entry, empty_signals, score_state};
+use super::{finalize_review, FinalizeConfig};
+use crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{MatchMode, MatchStatus, Reason, ScoreState};
+
+#[test]
+fn test_finalize_reports_unique_hashes_of_several_entries_as_a_pack() {
+    let db = MasterDb::new(vec![
+        db_entry("Alpha Hero", &["alpha"], "Character"),
+        db_entry("Beta Hero", &["beta"], "Character"),
+        db_entry("Gamma Hero", &["gamma"], "Character"),
+    ]);
+    let unique_hashes = |unique_overlap| {
+        vec![Reason::HashOverlap {
+            overlap: 2,
+            unique_overlap,
+        }]
+    };
+    let states: HashMap<usize, ScoreState> = [
+        (0, score_state(24.0, unique_hashes(2))),
+        (1, score_state(12.0, unique_hashes(1))),
+        // Shared hashes alone do not make an entry part of the pack.
+        (2, score_state(11.0, unique_hashes(0))),
+    ]
+    .into_iter()
+    .collect();
+
+    let finalized = finalize_review(
+        &db,
+        &states,
+        &empty_signals(),
+        &ObservedTokenBuckets::default(),
+        None,
+        &FinalizeConfig {
+            mode: MatchMode::Quick,
+            review_min_score: 10.0,
+            top_k: 5,
+        },
+    );
+
+    assert_eq!(finalized.status, MatchStatus::MultiObjectPack);
+    assert!(finalized.best.is_none());
+    let members: Vec<&str> = finalized
+        .candidates_topk
+        .iter()
+        .map(|candidate| candidate.name.as_str())
+        .collect();
+    assert_eq!(members, vec!["Alpha Hero", "Beta Hero"]);
+    assert_eq!(finalized.summary(), "Pack covering 2 objects");
+}
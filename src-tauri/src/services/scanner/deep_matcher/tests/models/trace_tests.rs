@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use super::{AcceptanceOutcome, MatchTracer};
+use crate::services::scanner::core::walker::{scan_folder_content, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::{
+    FolderSignals, IniTokenizationConfig,
+};
+use crate::services::scanner::deep_matcher::analysis::mechanical_rerank::{
+    mechanical_rerank_traced, MechanicalRerankConfig,
+};
+use crate::services::scanner::deep_matcher::{
+    match_folder_full, match_folder_full_traced, Candidate, Confidence, CustomSkin, DbEntry,
+    MasterDb, MatchStatus, ScoreState, StagedMatchResult,
+};
+
+fn entry(name: &str, custom_skins: Vec<CustomSkin>) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins,
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::new(),
+    }
+}
+
+fn candidate_for(path: PathBuf, display_name: &str) -> ModCandidate {
+    ModCandidate {
+        path,
+        raw_name: display_name.to_string(),
+        display_name: display_name.to_string(),
+        is_disabled: false,
+    }
+}
+
+fn scored(score: f32) -> ScoreState {
+    ScoreState {
+        score,
+        ..ScoreState::new()
+    }
+}
+
+fn review_candidate(entry_id: usize, name: &str) -> Candidate {
+    Candidate {
+        entry_id,
+        name: name.to_string(),
+        object_type: "Character".to_string(),
+        score: 13.0,
+        confidence: Confidence::Low,
+        reasons: vec![],
+    }
+}
+
+#[test]
+fn test_tracing_records_stages_and_keeps_the_result_unchanged() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("target_mystery_mod_pack");
+    std::fs::create_dir_all(&folder).expect("create folder");
+    std::fs::write(
+        folder.join("mod.ini"),
+        "[TextureOverrideX]\nfilename = Textures/radiantshadow/body_diffuse.dds\n",
+    )
+    .expect("write ini");
+
+    let content = scan_folder_content(&folder, 3);
+    let candidate = candidate_for(folder.clone(), "Mystery Pack");
+    let db = MasterDb::new(vec![
+        entry(
+            "Target Hero",
+            vec![CustomSkin {
+                name: "Radiant Shadow".to_string(),
+                aliases: vec!["radiantshadow".to_string()],
+                thumbnail_skin_path: None,
+                rarity: None,
+            }],
+        ),
+        entry("Control Hero", vec![]),
+    ]);
+    let filters = IniTokenizationConfig::default().prepare();
+
+    let plain = match_folder_full(
+        &candidate,
+        &db,
+        &content,
+        &filters,
+        &AiRerankConfig::default(),
+        &Default::default(),
+    );
+    let tracer = MatchTracer::new();
+    let traced = match_folder_full_traced(
+        &candidate,
+        &db,
+        &content,
+        &filters,
+        &AiRerankConfig::default(),
+        &Default::default(),
+        &tracer,
+    );
+    let trace = tracer.into_trace();
+
+    assert_eq!(traced.status, MatchStatus::AutoMatched);
+    assert_eq!(traced.status, plain.status);
+    assert_eq!(
+        traced.best.as_ref().map(|best| &best.name),
+        plain.best.as_ref().map(|best| &best.name)
+    );
+
+    assert_eq!(trace.signals.scanned_ini_files, 1);
+    assert_eq!(trace.stages[0].stage, "user_correction");
+    let accepted = trace.stages.last().expect("accepting stage");
+    let decision = accepted.decision.as_ref().expect("decision");
+    assert_eq!(decision.outcome, AcceptanceOutcome::AutoMatched);
+    assert_eq!(decision.best.as_deref(), Some("Target Hero"));
+    assert!(trace.stages.iter().any(|stage| stage
+        .contributions
+        .iter()
+        .any(|contribution| contribution.name == "Target Hero" && contribution.delta > 0.0)));
+    assert!(trace.mechanical_rerank.is_empty());
+}
+
+#[test]
+fn test_stage_contributions_are_deltas_against_the_previous_stage() {
+    let db = MasterDb::new(vec![entry("Alpha", vec![]), entry("Beta", vec![])]);
+    let tracer = MatchTracer::new();
+
+    let mut states = HashMap::from([(0, scored(4.0)), (1, scored(0.0))]);
+    tracer.record_stage("first", &db, &states);
+    states.insert(0, scored(10.0));
+    tracer.record_stage("second", &db, &states);
+    tracer.record_stage("third", &db, &states);
+
+    let trace = tracer.into_trace();
+    let deltas: Vec<Vec<(String, f32)>> = trace
+        .stages
+        .iter()
+        .map(|stage| {
+            stage
+                .contributions
+                .iter()
+                .map(|contribution| (contribution.name.clone(), contribution.delta))
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        deltas,
+        vec![
+            vec![("Alpha".to_string(), 4.0)],
+            vec![("Alpha".to_string(), 6.0)],
+            vec![]
+        ]
+    );
+}
+
+#[test]
+fn test_mechanical_rerank_records_points_per_candidate() {
+    let db = MasterDb::new(vec![entry("Alpha", vec![]), entry("Beta", vec![])]);
+    let result = StagedMatchResult {
+        status: MatchStatus::NeedsReview,
+        best: Some(review_candidate(0, "Alpha")),
+        candidates_topk: vec![review_candidate(0, "Alpha"), review_candidate(1, "Beta")],
+        candidates_all: Vec::new(),
+        evidence: Default::default(),
+    };
+    let signals = FolderSignals {
+        deep_name_strings: vec!["alpha".to_string()],
+        ..FolderSignals::default()
+    };
+    let tracer = MatchTracer::new();
+
+    mechanical_rerank_traced(
+        result,
+        &signals,
+        &db,
+        &MechanicalRerankConfig::default(),
+        Some(&tracer),
+    );
+
+    let points = tracer.into_trace().mechanical_rerank;
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0].name, "Alpha");
+    assert!(points[0].points > points[1].points);
+    assert!(points.iter().all(|entry| entry.penalties <= 0.0));
+}
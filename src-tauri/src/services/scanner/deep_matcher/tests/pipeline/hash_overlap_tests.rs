@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use crate::services::scanner::core::walker::{scan_folder_content, ModCandidate};
+
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::{
+    collect_deep_signals, FolderSignals, IniTokenizationConfig,
+};
+use crate::services::scanner::deep_matcher::analysis::skin_resolver::detect_skin_for_staged;
+use crate::services::scanner::deep_matcher::models::acceptance::StageContext;
+use crate::services::scanner::deep_matcher::pipeline::stages::ObservedTokenBuckets;
+
+use super::{score_hash_overlap, try_accept_hash_overlap};
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::{
+    match_folder_quick, Confidence, CustomSkin, DbEntry, MatchMode, MatchStatus,
+};
+
+fn entry(name: &str, hash_db: &[(&str, &[&str])], custom_skins: Vec<CustomSkin>) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins,
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: hash_db
+            .iter()
+            .map(|(skin, hashes)| {
+                (
+                    skin.to_string(),
+                    hashes.iter().map(|hash| hash.to_string()).collect(),
+                )
+            })
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+fn hash_test_db() -> MasterDb {
+    MasterDb::new(vec![
+        entry(
+            "Raiden Shogun",
+            &[
+                ("Default", &["d94c8962", "aa11bb22"]),
+                ("Wish", &["0c0ffee1", "0c0ffee2"]),
+            ],
+            vec![CustomSkin {
+                name: "Wish".to_string(),
+                aliases: vec!["raidenwish".to_string()],
+                thumbnail_skin_path: None,
+                rarity: None,
+            }],
+        ),
+        entry("Ayaka", &[("Default", &["5eed0001", "5eed0002"])], vec![]),
+        entry("Ganyu", &[("Default", &["feed0001"])], vec![]),
+    ])
+}
+
+fn candidate_for(path: PathBuf, display_name: &str) -> ModCandidate {
+    ModCandidate {
+        path,
+        raw_name: display_name.to_string(),
+        display_name: display_name.to_string(),
+        is_disabled: false,
+    }
+}
+
+fn owned(hashes: &[&str]) -> Vec<String> {
+    hashes.iter().map(|hash| hash.to_string()).collect()
+}
+
+fn accept_with(db: &MasterDb, hashes: &[&str]) -> Option<MatchStatus> {
+    let signals = FolderSignals::default();
+    let buckets = ObservedTokenBuckets::default();
+    let stage = StageContext {
+        db,
+        signals: &signals,
+        buckets: &buckets,
+        mode: MatchMode::Quick,
+        review_min_score: 10.0,
+        top_k: 5,
+        trace: None,
+    };
+    try_accept_hash_overlap(&stage, &owned(hashes)).map(|result| result.status)
+}
+
+#[test]
+fn test_hash_overlap_weighs_unique_hashes_above_shared_ones() {
+    let db = MasterDb::new(vec![
+        entry("Alpha", &[("Default", &["11111111", "cafe0000"])], vec![]),
+        entry("Beta", &[("Default", &["22222222", "cafe0000"])], vec![]),
+    ]);
+
+    let states = score_hash_overlap(&db, &owned(&["11111111", "cafe0000"]));
+
+    assert_eq!(states[&0].unique_overlap, 1);
+    assert_eq!(states[&0].overlap, 2);
+    assert_eq!(states[&1].unique_overlap, 0);
+    assert!(states[&0].score > states[&1].score);
+}
+
+#[test]
+fn test_hash_overlap_auto_accepts_on_unique_hashes_alone() {
+    let db = hash_test_db();
+    assert_eq!(
+        accept_with(&db, &["d94c8962", "aa11bb22"]),
+        Some(MatchStatus::AutoMatched)
+    );
+}
+
+#[test]
+fn test_hash_overlap_declines_a_single_unique_hash() {
+    let db = hash_test_db();
+    assert_eq!(accept_with(&db, &["feed0001"]), None);
+}
+
+#[test]
+fn test_hash_overlap_declines_when_two_entries_own_hashes() {
+    let db = hash_test_db();
+    assert_eq!(
+        accept_with(&db, &["d94c8962", "aa11bb22", "5eed0001"]),
+        None
+    );
+}
+
+#[test]
+fn test_override_hashes_skip_shader_and_denylisted_sections() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("mod");
+    std::fs::create_dir_all(&folder).expect("create folder");
+    std::fs::write(
+        folder.join("mod.ini"),
+        "[ShaderOverrideGlow]\nhash = 0123456789abcdef\n\
+         [TextureOverrideUIIcon]\nhash = 0badf00d\n\
+         [TextureOverrideBody]\nhash = d94c8962\n",
+    )
+    .expect("write ini");
+
+    let content = scan_folder_content(&folder, 3);
+    let signals = collect_deep_signals(
+        &folder,
+        &content,
+        MatchMode::Quick,
+        &IniTokenizationConfig::default().prepare(),
+    );
+
+    assert_eq!(signals.override_hashes, vec!["d94c8962".to_string()]);
+    assert!(signals.ini_hashes.contains(&"89abcdef".to_string()));
+}
+
+#[test]
+fn test_quick_pipeline_accepts_on_hashes_and_resolves_skin_from_hash_db() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("mystery_mod");
+    std::fs::create_dir_all(&folder).expect("create folder");
+    std::fs::write(
+        folder.join("mod.ini"),
+        "[TextureOverrideBody]\nhash = d94c8962\n\
+         [TextureOverrideDress]\nhash = 0c0ffee1\n\
+         [TextureOverrideSleeves]\nhash = 0c0ffee2\n",
+    )
+    .expect("write ini");
+
+    let content = scan_folder_content(&folder, 3);
+    let candidate = candidate_for(folder.clone(), "mystery_mod");
+    let db = hash_test_db();
+
+    let result = match_folder_quick(
+        &candidate,
+        &db,
+        &content,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+    );
+
+    assert_eq!(result.status, MatchStatus::AutoMatched);
+    let best = result.best.as_ref().expect("best candidate");
+    assert_eq!(best.name, "Raiden Shogun");
+    assert_eq!(best.confidence, Confidence::Excellent);
+
+    let (skin, skin_folder) = detect_skin_for_staged(&result, &candidate.raw_name, &db);
+    assert_eq!(skin.as_deref(), Some("Wish"));
+    assert_eq!(skin_folder.as_deref(), Some("raidenwish"));
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+
+use super::{detect_subfolder_pack, looks_like_pack, pack_members, split_pack};
+use crate::services::scanner::core::walker::{scan_folder_content, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::IniTokenizationConfig;
+use crate::services::scanner::deep_matcher::state::master_db::MasterDb;
+use crate::services::scanner::deep_matcher::state::signal_cache::SignalCache;
+use crate::services::scanner::deep_matcher::{
+    match_folder_phased, DbEntry, MatchStatus, StagedMatchResult,
+};
+
+const RAIDEN_INI: &str = "[TextureOverrideRaidenBody]\nhash = d94c8962\n\
+                          [TextureOverrideRaidenDress]\nhash = aa11bb22\n";
+const AYAKA_INI: &str = "[TextureOverrideAyakaBody]\nhash = 5eed0001\n\
+                         [TextureOverrideAyakaDress]\nhash = 5eed0002\n";
+
+fn entry(name: &str, hashes: &[&str]) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins: vec![],
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::from([(
+            "Default".to_string(),
+            hashes.iter().map(|hash| hash.to_string()).collect(),
+        )]),
+    }
+}
+
+fn pack_db() -> MasterDb {
+    MasterDb::new(vec![
+        entry("Raiden Shogun", &["d94c8962", "aa11bb22"]),
+        entry("Ayaka", &["5eed0001", "5eed0002"]),
+        entry("Ganyu", &["feed0001"]),
+    ])
+}
+
+fn write_ini(folder: &Path, ini: &str) {
+    std::fs::create_dir_all(folder).expect("create folder");
+    std::fs::write(folder.join("mod.ini"), ini).expect("write ini");
+}
+
+fn candidate_for(path: PathBuf) -> ModCandidate {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    ModCandidate {
+        path,
+        raw_name: name.clone(),
+        display_name: name,
+        is_disabled: false,
+    }
+}
+
+fn phased(candidate: &ModCandidate, db: &MasterDb) -> StagedMatchResult {
+    match_folder_phased(
+        candidate,
+        db,
+        &scan_folder_content(&candidate.path, 3),
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+    )
+}
+
+fn member_names(result: &StagedMatchResult) -> Vec<String> {
+    let mut names: Vec<String> = result
+        .candidates_topk
+        .iter()
+        .map(|candidate| candidate.name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_ini_overriding_several_objects_is_reported_as_a_pack() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("Inazuma Duo");
+    write_ini(&folder, &format!("{RAIDEN_INI}{AYAKA_INI}"));
+    let db = pack_db();
+    let candidate = candidate_for(folder);
+
+    let result = phased(&candidate, &db);
+    eprintln!(
+        "DBG1 {:?} {:#?}",
+        result.status,
+        result
+            .candidates_all
+            .iter()
+            .map(|c| (c.name.clone(), c.score, c.reasons.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(result.status, MatchStatus::MultiObjectPack);
+    assert!(result.best.is_none());
+    assert_eq!(member_names(&result), vec!["Ayaka", "Raiden Shogun"]);
+    assert_eq!(result.evidence.matched_hashes.len(), 4);
+    // One INI cannot be split by folder.
+    let parts = split_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3),
+        &db,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+        &mut SignalCache::new(),
+    );
+    assert!(parts.is_empty());
+}
+
+#[test]
+fn test_subfolders_matching_different_objects_split_into_parts() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("Summer Pack");
+    write_ini(&folder.join("Raiden"), RAIDEN_INI);
+    write_ini(&folder.join("Ayaka"), AYAKA_INI);
+    std::fs::create_dir_all(folder.join("previews")).expect("create folder");
+    write_ini(&folder.join(".backup"), RAIDEN_INI);
+    let db = pack_db();
+    let candidate = candidate_for(folder);
+    let ini_filters = IniTokenizationConfig::default().prepare();
+    let mut cache = SignalCache::new();
+
+    let parts = split_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3),
+        &db,
+        &ini_filters,
+        &AiRerankConfig::default(),
+        &mut cache,
+    );
+
+    // Folders without an INI and hidden folders are not parts.
+    let matched: Vec<(String, Option<String>)> = parts
+        .iter()
+        .map(|part| {
+            (
+                part.folder.raw_name.clone(),
+                part.matched().map(|candidate| candidate.name.clone()),
+            )
+        })
+        .collect();
+    assert_eq!(
+        matched,
+        vec![
+            ("Ayaka".to_string(), Some("Ayaka".to_string())),
+            ("Raiden".to_string(), Some("Raiden Shogun".to_string())),
+        ]
+    );
+    assert_eq!(pack_members(&parts).len(), 2);
+
+    let pack = detect_subfolder_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3),
+        &db,
+        &ini_filters,
+        &AiRerankConfig::default(),
+        &mut cache,
+    )
+    .expect("subfolder pack");
+    assert_eq!(pack.status, MatchStatus::MultiObjectPack);
+    assert_eq!(member_names(&pack), vec!["Ayaka", "Raiden Shogun"]);
+    let r = phased(&candidate, &db);
+    eprintln!(
+        "DBG2 {:?} {:#?}",
+        r.status,
+        r.candidates_all
+            .iter()
+            .map(|c| (c.name.clone(), c.score, c.reasons.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(r.status, MatchStatus::MultiObjectPack);
+}
+
+#[test]
+fn test_variant_subfolders_of_one_object_are_not_a_pack() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("Raiden Variants");
+    write_ini(&folder.join("Red"), RAIDEN_INI);
+    write_ini(&folder.join("Blue"), RAIDEN_INI);
+    let db = pack_db();
+    let candidate = candidate_for(folder);
+
+    let pack = detect_subfolder_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3),
+        &db,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+        &mut SignalCache::new(),
+    );
+
+    assert!(pack.is_none());
+    let result = phased(&candidate, &db);
+    assert_eq!(result.status, MatchStatus::AutoMatched);
+    assert_eq!(result.best.expect("best").name, "Raiden Shogun");
+}
+
+#[test]
+fn test_root_ini_keeps_subfolders_from_being_split() {
+    let temp = TempDir::new().expect("temp dir");
+    let folder = temp.path().join("Raiden Toggle");
+    write_ini(&folder, RAIDEN_INI);
+    write_ini(&folder.join("Raiden"), RAIDEN_INI);
+    write_ini(&folder.join("Ayaka"), AYAKA_INI);
+    let candidate = candidate_for(folder);
+
+    assert!(!looks_like_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3)
+    ));
+    std::fs::remove_file(candidate.path.join("mod.ini")).expect("remove root ini");
+    assert!(looks_like_pack(
+        &candidate,
+        &scan_folder_content(&candidate.path, 3)
+    ));
+}
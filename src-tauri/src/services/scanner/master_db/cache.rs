@@ -1,18 +1,23 @@
-//! The parsed MasterDB, cached per game type, with the user's own aliases
-//! folded into the bundled entries.
+//! The parsed MasterDB, cached per game type, with the user's overlay and own
+//! aliases folded into the bundled entries. A game's matcher corrections are
+//! folded into its indexes in a copy cached per game.
 
 use crate::domain::errors::ScannerError;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::services::scanner::deep_matcher;
+use crate::services::scanner::deep_matcher::analysis::ai_provider::build_rerank_provider;
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::{AiRerankCache, AiRerankContext};
 
-/// Parsed MasterDB per game type, so a 5 MB JSON is read and parsed once.
+/// Parsed MasterDB per game type, so a 5 MB JSON is read and parsed once, and
+/// per game for the ones built with that game's corrections.
 #[derive(Default)]
 pub struct MasterDbCache(tokio::sync::RwLock<HashMap<String, Arc<deep_matcher::MasterDb>>>);
 
 impl MasterDbCache {
-    /// Drop every parsed database so the next scan picks up edited user aliases.
+    /// Drop every parsed database so the next scan picks up edited user aliases,
+    /// corrections, and overlay entries.
     ///
     /// Clears all game types rather than one: the caller that edits an object
     /// knows its game id, not its game type, and re-parsing costs one JSON read
@@ -23,7 +28,8 @@ impl MasterDbCache {
     }
 }
 
-/// The parsed MasterDB for a game type, loading it on first use.
+/// The parsed MasterDB for a game type, loading it on first use. It carries
+/// no matcher corrections, which belong to a game; see `get_cached_for_game`.
 ///
 /// Returns `None` when the game has no bundled database. This used to be a
 /// `db_json: String` parameter: the frontend fetched the whole database, held
@@ -32,13 +38,74 @@ impl MasterDbCache {
 pub async fn get_cached(
     app: &tauri::AppHandle,
     game_type: i32,
+) -> Result<Option<Arc<deep_matcher::MasterDb>>, ScannerError> {
+    load_cached(app, game_type, None).await
+}
+
+/// `get_cached` with `game_id`'s matcher corrections folded in.
+pub async fn get_cached_for_game(
+    app: &tauri::AppHandle,
+    game_type: i32,
+    game_id: &str,
+) -> Result<Option<Arc<deep_matcher::MasterDb>>, ScannerError> {
+    load_cached(app, game_type, Some(game_id)).await
+}
+
+/// The AI rerank setup for a scan against `master_db`: the provider the
+/// settings select, the persisted score cache, and the database version its
+/// scores are keyed by. Off when AI rerank is disabled or unusable.
+pub async fn ai_rerank_context(
+    app: &tauri::AppHandle,
+    master_db: &deep_matcher::MasterDb,
+) -> AiRerankContext {
+    use tauri::Manager;
+
+    let ai = app
+        .state::<crate::services::config::ConfigService>()
+        .get_settings()
+        .ai;
+    if !ai.enabled {
+        return AiRerankContext::default();
+    }
+    // The providers hold a blocking HTTP client, which must not be built on
+    // an async worker.
+    let provider =
+        match tauri::async_runtime::spawn_blocking(move || build_rerank_provider(&ai)).await {
+            Ok(Ok(Some(provider))) => Arc::from(provider),
+            Ok(Ok(None)) => return AiRerankContext::default(),
+            Ok(Err(error)) => {
+                log::warn!("AI rerank unavailable, matching without it: {error}");
+                return AiRerankContext::default();
+            }
+            Err(error) => {
+                log::warn!("AI rerank provider setup failed: {error}");
+                return AiRerankContext::default();
+            }
+        };
+    AiRerankContext {
+        provider: Some(provider),
+        cache: app
+            .try_state::<Arc<AiRerankCache>>()
+            .map(|cache| Arc::clone(&cache)),
+        db_version: Some(master_db.content_version()),
+    }
+}
+
+async fn load_cached(
+    app: &tauri::AppHandle,
+    game_type: i32,
+    game_id: Option<&str>,
 ) -> Result<Option<Arc<deep_matcher::MasterDb>>, ScannerError> {
     use tauri::Manager;
 
     let canonical = crate::services::game::schema_loader::normalize_game_type(game_type);
+    let key = match game_id {
+        Some(game_id) => format!("{canonical}/{game_id}"),
+        None => canonical,
+    };
     let cache = app.state::<MasterDbCache>();
 
-    if let Some(hit) = cache.0.read().await.get(&canonical).cloned() {
+    if let Some(hit) = cache.0.read().await.get(&key).cloned() {
         return Ok(Some(hit));
     }
 
@@ -46,6 +113,25 @@ pub async fn get_cached(
         .path()
         .resource_dir()
         .map_err(|error| ScannerError::Io(format!("failed to resolve resource dir: {error}")))?;
+    let pool = app.state::<sqlx::SqlitePool>();
+    let Some(db) = load_master_db(&pool, &resource_dir, game_type, game_id).await? else {
+        return Ok(None);
+    };
+
+    let parsed = Arc::new(db);
+    cache.0.write().await.insert(key, Arc::clone(&parsed));
+    Ok(Some(parsed))
+}
+
+/// The MasterDB for `game_type` as scans see it, read from `resource_dir`
+/// without the cache. `None` when the game has no bundled database.
+pub(crate) async fn load_master_db(
+    pool: &sqlx::SqlitePool,
+    resource_dir: &std::path::Path,
+    game_type: i32,
+    game_id: Option<&str>,
+) -> Result<Option<deep_matcher::MasterDb>, ScannerError> {
+    let canonical = crate::services::game::schema_loader::normalize_game_type(game_type);
     let db_path = resource_dir
         .join("databases")
         .join(format!("{canonical}.json"));
@@ -54,15 +140,18 @@ pub async fn get_cached(
     }
 
     let json = std::fs::read_to_string(&db_path)?;
-    let mut db = deep_matcher::MasterDb::from_json(&json)?;
-    attach_user_aliases(
-        &mut db,
-        &load_user_aliases(&app.state::<sqlx::SqlitePool>()).await,
+    let mut entries = deep_matcher::MasterDb::entries_from_json(&json)?;
+    deep_matcher::MasterDb::merge_overlay(
+        &mut entries,
+        &super::overlay::load_overlay(pool, game_type).await,
     );
-
-    let parsed = Arc::new(db);
-    cache.0.write().await.insert(canonical, Arc::clone(&parsed));
-    Ok(Some(parsed))
+    let corrections = match game_id {
+        Some(game_id) => super::corrections::load_user_corrections(pool, game_id, &entries).await,
+        None => Vec::new(),
+    };
+    let mut db = deep_matcher::MasterDb::new_with_corrections(entries, &corrections);
+    attach_user_aliases(&mut db, &load_user_aliases(pool).await);
+    Ok(Some(db))
 }
 
 /// Aliases the user typed on their own objects, grouped by matched entry key.
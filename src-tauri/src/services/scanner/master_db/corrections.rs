@@ -0,0 +1,238 @@
+//! Folder-name corrections the matcher learns from.
+//!
+//! Recorded when the user reassigns a folder to a different entry, folded into
+//! the MasterDB indexes when the cache loads it, and shareable as a JSON file
+//! so a team can hand its corrections around.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::common::normalizer;
+use crate::domain::errors::AppError;
+use crate::repo::correction_repo::{self, CorrectionRow};
+use crate::services::scanner::deep_matcher::{DbEntry, UserCorrection};
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+use crate::services::scanner::sync::ConfirmedScanItem;
+
+/// `matched_reason` the scan review sends for a row the user re-pointed.
+pub const MANUAL_OVERRIDE_REASON: &str = "Manual override";
+
+/// `matched_source` of a match the user picked by hand.
+pub const MANUAL_MATCH_SOURCE: &str = "manual_match";
+
+/// Bumped when the export layout changes incompatibly.
+const EXPORT_VERSION: u32 = 1;
+
+/// One learned correction, as listed and exported.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MatcherCorrection {
+    /// Normalized folder name the correction keys on. Recomputed on import.
+    #[serde(default)]
+    pub folder_pattern: String,
+    pub folder_name: String,
+    pub entry_key: String,
+    /// How often the user made this same correction.
+    #[serde(default = "one")]
+    pub hits: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CorrectionExport {
+    version: u32,
+    corrections: Vec<MatcherCorrection>,
+}
+
+/// The stored row for a folder reassigned to `entry_key`, or `None` when the
+/// folder name normalizes to nothing the matcher could key on.
+fn correction_row(folder_name: &str, entry_key: &str, hits: u32) -> Option<CorrectionRow> {
+    let folder_name = normalizer::normalize_display_name(folder_name);
+    let folder_pattern = normalizer::normalize_for_matching_default(&folder_name);
+    if folder_pattern.is_empty() || entry_key.trim().is_empty() {
+        return None;
+    }
+    Some(CorrectionRow {
+        folder_pattern,
+        entry_key: entry_key.to_string(),
+        folder_name: folder_name.into_owned(),
+        hits: i64::from(hits.max(1)),
+    })
+}
+
+fn folder_name_of(folder_path: &str) -> Option<&str> {
+    Path::new(folder_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+}
+
+/// Records `(folder_name, entry_key)` corrections for `game_id`. Returns how
+/// many were kept.
+pub async fn record_corrections(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    corrections: &[(String, String)],
+) -> Result<usize, AppError> {
+    let rows: Vec<CorrectionRow> = corrections
+        .iter()
+        .filter_map(|(folder_name, entry_key)| correction_row(folder_name, entry_key, 1))
+        .collect();
+    if rows.is_empty() {
+        return Ok(0);
+    }
+    correction_repo::upsert_corrections(pool, game_id, &rows).await?;
+    Ok(rows.len())
+}
+
+/// The scan review rows the user re-pointed at another entry, as
+/// `(folder_name, entry_key)` pairs.
+pub fn scan_overrides(items: &[ConfirmedScanItem]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .filter(|item| !item.skip)
+        .filter(|item| item.matched_reason.as_deref() == Some(MANUAL_OVERRIDE_REASON))
+        .filter_map(|item| {
+            let entry_key = item.matched_entry_key.clone()?;
+            let folder_name = folder_name_of(&item.folder_path)?;
+            Some((folder_name.to_string(), entry_key))
+        })
+        .collect()
+}
+
+/// The correction a manual object match teaches, if it changed anything.
+///
+/// `folder_path` is the mod folder the match was made from, when there was
+/// one; otherwise the object's own folder names the pattern. Re-applying the
+/// entry the object already had teaches nothing.
+pub fn manual_match_correction(
+    folder_path: Option<&str>,
+    object_folder: &str,
+    previous_entry_key: Option<&str>,
+    entry_key: &str,
+) -> Option<(String, String)> {
+    if previous_entry_key == Some(entry_key) {
+        return None;
+    }
+    let folder_name = folder_name_of(folder_path.unwrap_or(object_folder))?;
+    Some((folder_name.to_string(), entry_key.to_string()))
+}
+
+/// Every correction stored for `game_id`, ordered by pattern.
+pub async fn list_corrections(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+) -> Result<Vec<MatcherCorrection>, AppError> {
+    let rows = correction_repo::list_corrections(pool, game_id).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| MatcherCorrection {
+            folder_pattern: row.folder_pattern,
+            folder_name: row.folder_name,
+            entry_key: row.entry_key,
+            hits: u32::try_from(row.hits).unwrap_or(u32::MAX),
+        })
+        .collect())
+}
+
+/// Forgets one of `game_id`'s corrections.
+pub async fn delete_correction(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    folder_pattern: &str,
+) -> Result<(), AppError> {
+    if !correction_repo::delete_correction(pool, game_id, folder_pattern).await? {
+        return Err(AppError::NotFound(format!(
+            "No matcher correction for '{folder_pattern}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Writes every correction stored for `game_id` to `path`. Returns how many
+/// were written.
+pub async fn export_corrections(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    path: &Path,
+) -> Result<usize, AppError> {
+    let export = CorrectionExport {
+        version: EXPORT_VERSION,
+        corrections: list_corrections(pool, game_id).await?,
+    };
+    let json = serde_json::to_string_pretty(&export)?;
+    tokio::fs::write(path, json).await?;
+    Ok(export.corrections.len())
+}
+
+/// Merges the corrections in `path` into `game_id`'s. Returns how many were
+/// kept.
+///
+/// An imported pattern overrides a local one pointing elsewhere, the same way
+/// a fresh correction would.
+pub async fn import_corrections(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    path: &Path,
+) -> Result<usize, AppError> {
+    let json = tokio::fs::read_to_string(path).await?;
+    let export: CorrectionExport = serde_json::from_str(&json)?;
+    if export.version > EXPORT_VERSION {
+        return Err(AppError::Validation(format!(
+            "Matcher corrections file version {} is newer than supported version {EXPORT_VERSION}",
+            export.version
+        )));
+    }
+
+    let rows: Vec<CorrectionRow> = export
+        .corrections
+        .iter()
+        .filter_map(|correction| {
+            correction_row(
+                &correction.folder_name,
+                &correction.entry_key,
+                correction.hits,
+            )
+        })
+        .collect();
+    correction_repo::upsert_corrections(pool, game_id, &rows).await?;
+    Ok(rows.len())
+}
+
+/// `game_id`'s stored corrections resolved to entry names, for `MasterDb::new_with_corrections`.
+///
+/// Like user aliases, a correction whose entry key names nothing bundled is
+/// dropped: the matcher can only ever return a bundled entry.
+pub(super) async fn load_user_corrections(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    entries: &[DbEntry],
+) -> Vec<UserCorrection> {
+    let rows = match correction_repo::list_corrections(pool, game_id).await {
+        Ok(rows) => rows,
+        Err(error) => {
+            log::warn!("matcher corrections unavailable, matching without them: {error}");
+            return Vec::new();
+        }
+    };
+
+    let names_by_key: HashMap<String, &str> = entries
+        .iter()
+        .map(|entry| (canonical_entry_key(&entry.name), entry.name.as_str()))
+        .collect();
+
+    rows.into_iter()
+        .filter_map(|row| {
+            let entry_name = names_by_key.get(&row.entry_key)?;
+            Some(UserCorrection {
+                folder_name: row.folder_name,
+                entry_name: entry_name.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "../tests/matcher_corrections_tests.rs"]
+mod matcher_corrections_tests;
@@ -0,0 +1,63 @@
+//! Traced matching of a single folder, for explaining a scan result.
+
+use std::path::Path;
+
+use crate::services::scanner::core::types;
+use crate::services::scanner::core::walker::ModCandidate;
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::PreparedTokenFilters;
+use crate::services::scanner::deep_matcher::analysis::gamebanana::GameBananaConfig;
+use crate::services::scanner::deep_matcher::{self, MasterDb, MatchTrace, MatchTracer};
+
+/// A full-scoring match of one folder, with the trace of how it was reached.
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct MatchExplanation {
+    pub matched_name: Option<String>,
+    pub match_level: String,
+    pub match_confidence: String,
+    pub match_detail: String,
+    pub trace: MatchTrace,
+}
+
+/// Run the full-scoring pipeline on `folder` with a tracer attached.
+///
+/// Always full scoring, even when Quick would have accepted: the point is to
+/// see every stage. AI and GameBanana lookups stay off so explaining a folder
+/// never leaves the machine.
+pub fn explain_folder_match(
+    folder: &Path,
+    db: &MasterDb,
+    ini_filters: &PreparedTokenFilters,
+) -> MatchExplanation {
+    let raw_name = folder
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let candidate = ModCandidate {
+        path: folder.to_path_buf(),
+        display_name: crate::common::normalizer::normalize_display_name(&raw_name).into_owned(),
+        is_disabled: crate::common::normalizer::is_disabled_folder(&raw_name),
+        raw_name,
+    };
+    let content = crate::services::scanner::core::walker::scan_folder_content(folder, 3);
+
+    let tracer = MatchTracer::new();
+    let result = deep_matcher::match_folder_full_traced(
+        &candidate,
+        db,
+        &content,
+        ini_filters,
+        &AiRerankConfig::default(),
+        &GameBananaConfig::default(),
+        &tracer,
+    );
+
+    MatchExplanation {
+        matched_name: result.best.as_ref().map(|best| best.name.clone()),
+        match_level: types::match_status_label(&result.status).to_string(),
+        match_confidence: types::staged_confidence_label(&result).to_string(),
+        match_detail: types::staged_match_detail(&result),
+        trace: tracer.into_trace(),
+    }
+}
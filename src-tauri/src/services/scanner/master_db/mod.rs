@@ -10,13 +10,19 @@ use std::path::Path;
 use crate::services::game::schema_loader;
 use crate::services::scanner::core::types;
 use crate::services::scanner::core::walker::{FolderContent, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
 use crate::services::scanner::deep_matcher::analysis::content::{
     IniTokenizationConfig, PreparedTokenFilters,
 };
 use crate::services::scanner::deep_matcher::{self, DbEntry, MasterDb, StagedMatchResult};
 
-/// Load and parse the MasterDB JSON for a given game type from `resource_dir`.
-pub fn load_master_db_json(resource_dir: &Path, game_type: i32) -> Result<String, ScannerError> {
+/// Load and parse the MasterDB JSON for a given game type from `resource_dir`,
+/// with the user's `overlay` merged in.
+pub fn load_master_db_json(
+    resource_dir: &Path,
+    game_type: i32,
+    overlay: &[DbEntry],
+) -> Result<String, ScannerError> {
     let canonical = schema_loader::normalize_game_type(game_type);
     let db_path = resource_dir
         .join("databases")
@@ -48,6 +54,7 @@ pub fn load_master_db_json(resource_dir: &Path, game_type: i32) -> Result<String
         }
     };
 
+    overlay::merge_overlay_json(&mut entries, overlay);
     resolve_entry_thumbnails(&mut entries, resource_dir);
     Ok(serde_json::to_string(&entries)?)
 }
@@ -136,7 +143,7 @@ pub fn match_object_with_staged_pipeline(db: &MasterDb, object_name: &str) -> St
         db,
         &content,
         &ini_filters,
-        &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig::default(),
+        &AiRerankConfig::default(),
     )
 }
 
@@ -182,28 +189,11 @@ pub fn build_matched_db_entry_from_staged(
 
 pub fn match_object_with_db_service(
     resource_dir: &Path,
-    game_type: i32,
+    db: &MasterDb,
     object_name: &str,
-) -> Result<Option<MatchedDbEntry>, ScannerError> {
-    let canonical = schema_loader::normalize_game_type(game_type);
-    let db_path = resource_dir
-        .join("databases")
-        .join(format!("{}.json", canonical));
-
-    if !db_path.exists() {
-        return Ok(None);
-    }
-
-    let json = std::fs::read_to_string(&db_path)?;
-
-    let db = MasterDb::from_json(&json)?;
-
-    let match_result = match_object_with_staged_pipeline(&db, object_name);
-    Ok(build_matched_db_entry_from_staged(
-        resource_dir,
-        &db,
-        &match_result,
-    ))
+) -> Option<MatchedDbEntry> {
+    let match_result = match_object_with_staged_pipeline(db, object_name);
+    build_matched_db_entry_from_staged(resource_dir, db, &match_result)
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -341,4 +331,9 @@ pub fn search_master_db_service(
 }
 
 mod cache;
-pub use cache::{get_cached, MasterDbCache};
+pub mod corrections;
+mod explain;
+pub mod overlay;
+pub mod rematch;
+pub use cache::{ai_rerank_context, get_cached, get_cached_for_game, MasterDbCache};
+pub use explain::{explain_folder_match, MatchExplanation};
@@ -0,0 +1,199 @@
+//! The user's MasterDB overlay.
+//!
+//! The bundled `resources/databases/*.json` is read-only and only changes with
+//! an app update. The overlay keeps, per game type, entries the bundled
+//! database lacks (new characters, NPCs, UI elements) and extra tags, skins,
+//! aliases, and hashes for entries it has. The cache merges it over the
+//! bundled database on load, and it is shared as a file in the bundled
+//! database's own `{"entries", "hash_db"}` layout.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::common::path_key;
+use crate::domain::errors::AppError;
+use crate::repo::overlay_repo::{self, OverlayRow};
+use crate::services::game::schema_loader;
+use crate::services::scanner::deep_matcher::state::master_db::merge_overlay_entry;
+use crate::services::scanner::deep_matcher::{DbEntry, MasterDb};
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+
+/// The `hash_db` variant the file layout's top-level map feeds.
+const DEFAULT_VARIANT: &str = "Default";
+
+/// An overlay file: the bundled database layout, with default-variant hashes
+/// in the top-level `hash_db` keyed by entry name.
+#[derive(Debug, serde::Serialize)]
+struct OverlayExport {
+    entries: Vec<DbEntry>,
+    hash_db: BTreeMap<String, Vec<String>>,
+}
+
+/// `entry` trimmed and checked, keyed for storage.
+fn overlay_row(mut entry: DbEntry) -> Result<OverlayRow, AppError> {
+    entry.name = entry.name.trim().to_string();
+    let entry_key = canonical_entry_key(&entry.name);
+    if entry_key.is_empty() {
+        return Err(AppError::Validation(
+            "MasterDB overlay entry needs a name".to_string(),
+        ));
+    }
+    Ok(OverlayRow {
+        entry_key,
+        entry_json: serde_json::to_string(&entry)?,
+    })
+}
+
+/// Every overlay record for `game_type`, ordered by entry key.
+pub async fn list_overlay(
+    pool: &sqlx::SqlitePool,
+    game_type: i32,
+) -> Result<Vec<DbEntry>, AppError> {
+    let rows =
+        overlay_repo::list_overlay(pool, &schema_loader::normalize_game_type(game_type)).await?;
+    rows.into_iter()
+        .map(|row| Ok(serde_json::from_str(&row.entry_json)?))
+        .collect()
+}
+
+/// Store `entry` as the overlay record for its name, replacing any earlier one.
+pub async fn save_overlay_entry(
+    pool: &sqlx::SqlitePool,
+    game_type: i32,
+    entry: DbEntry,
+) -> Result<(), AppError> {
+    let row = overlay_row(entry)?;
+    overlay_repo::upsert_overlay(pool, &schema_loader::normalize_game_type(game_type), &[row])
+        .await?;
+    Ok(())
+}
+
+/// Drops the overlay record for `entry_name`.
+pub async fn delete_overlay_entry(
+    pool: &sqlx::SqlitePool,
+    game_type: i32,
+    entry_name: &str,
+) -> Result<(), AppError> {
+    let game_type = schema_loader::normalize_game_type(game_type);
+    if !overlay_repo::delete_overlay(pool, &game_type, &canonical_entry_key(entry_name)).await? {
+        return Err(AppError::NotFound(format!(
+            "No MasterDB overlay entry '{entry_name}' for {game_type}"
+        )));
+    }
+    Ok(())
+}
+
+/// Writes the overlay for `game_type` to `path`. Returns how many entries were
+/// written.
+pub async fn export_overlay(
+    pool: &sqlx::SqlitePool,
+    game_type: i32,
+    path: &Path,
+) -> Result<usize, AppError> {
+    let mut export = OverlayExport {
+        entries: list_overlay(pool, game_type).await?,
+        hash_db: BTreeMap::new(),
+    };
+    for entry in &mut export.entries {
+        if let Some(hashes) = entry.hash_db.remove(DEFAULT_VARIANT) {
+            export.hash_db.insert(entry.name.clone(), hashes);
+        }
+    }
+    let json = serde_json::to_string_pretty(&export)?;
+    tokio::fs::write(path, json).await?;
+    Ok(export.entries.len())
+}
+
+/// Merges the overlay file at `path` into the stored overlay. Returns how many
+/// entries it carried.
+///
+/// Accepts anything the bundled database loader does, so an upstream database
+/// file or a fragment of one imports as-is. An imported entry already in the
+/// overlay adds to it rather than replacing it.
+pub async fn import_overlay(
+    pool: &sqlx::SqlitePool,
+    game_type: i32,
+    path: &Path,
+) -> Result<usize, AppError> {
+    let json = tokio::fs::read_to_string(path).await?;
+    let imported = MasterDb::entries_from_json(&json)?;
+
+    let mut merged = list_overlay(pool, game_type).await?;
+    MasterDb::merge_overlay(&mut merged, &imported);
+    let rows = merged
+        .into_iter()
+        .filter(|entry| {
+            imported
+                .iter()
+                .any(|addition| path_key::names_equal_by_key(&addition.name, &entry.name))
+        })
+        .map(overlay_row)
+        .collect::<Result<Vec<_>, _>>()?;
+    overlay_repo::upsert_overlay(pool, &schema_loader::normalize_game_type(game_type), &rows)
+        .await?;
+    Ok(imported.len())
+}
+
+/// The overlay for `game_type`, for merging into a freshly loaded database.
+///
+/// Unreadable records are skipped: a broken overlay should cost the user
+/// their additions, not matching altogether.
+pub async fn load_overlay(pool: &sqlx::SqlitePool, game_type: i32) -> Vec<DbEntry> {
+    let game_type = schema_loader::normalize_game_type(game_type);
+    let rows = match overlay_repo::list_overlay(pool, &game_type).await {
+        Ok(rows) => rows,
+        Err(error) => {
+            log::warn!("MasterDB overlay unavailable for {game_type}, using bundled only: {error}");
+            return Vec::new();
+        }
+    };
+    rows.into_iter()
+        .filter_map(
+            |row| match serde_json::from_str::<DbEntry>(&row.entry_json) {
+                Ok(entry) => Some(entry),
+                Err(error) => {
+                    log::warn!(
+                        "skipping unreadable overlay entry_key={} for {game_type}: {error}",
+                        row.entry_key
+                    );
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// [`MasterDb::merge_overlay`] over the raw JSON entries the frontend gets,
+/// keeping any field the bundled file has that `DbEntry` does not model.
+pub fn merge_overlay_json(entries: &mut Vec<Value>, overlay: &[DbEntry]) {
+    for addition in overlay {
+        let existing = entries.iter_mut().find(|value| {
+            value
+                .get("name")
+                .and_then(Value::as_str)
+                .is_some_and(|name| path_key::names_equal_by_key(name, &addition.name))
+        });
+        let Some(value) = existing else {
+            match serde_json::to_value(addition) {
+                Ok(added) => entries.push(added),
+                Err(error) => log::warn!("skipping overlay entry '{}': {error}", addition.name),
+            }
+            continue;
+        };
+        let Ok(mut entry) = serde_json::from_value::<DbEntry>(value.clone()) else {
+            continue;
+        };
+        merge_overlay_entry(&mut entry, addition);
+        if let (Value::Object(fields), Ok(Value::Object(merged))) =
+            (value, serde_json::to_value(&entry))
+        {
+            fields.extend(merged);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "../tests/master_db_overlay_tests.rs"]
+mod master_db_overlay_tests;
@@ -0,0 +1,178 @@
+//! What a re-match diffs against: every MasterDB entry fingerprinted, and
+//! the per-game baseline those fingerprints are stored as.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::domain::errors::AppError;
+use crate::repo::{game_repo, settings_repo};
+use crate::services::scanner::deep_matcher::{DbEntry, MasterDb};
+use crate::services::scanner::master_db::cache::load_master_db;
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+
+/// `app_meta` key prefix of the per-game fingerprint the last review saw.
+const BASELINE_META_PREFIX: &str = "master_db_rematch_baseline:";
+
+/// The parts of one entry a re-match cares about, each as a digest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct EntryFingerprint {
+    aliases: String,
+    skins: String,
+    hashes: String,
+    tags: String,
+}
+
+/// Every entry of a MasterDB, fingerprinted and keyed by entry key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct MasterDbFingerprint(BTreeMap<String, EntryFingerprint>);
+
+fn digest<'a>(values: impl IntoIterator<Item = &'a str>) -> String {
+    let sorted: BTreeSet<String> = values
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect();
+    let mut hasher = blake3::Hasher::new();
+    for value in sorted {
+        hasher.update(value.as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+impl MasterDbFingerprint {
+    pub fn of(entries: &[DbEntry]) -> Self {
+        Self(
+            entries
+                .iter()
+                .map(|entry| {
+                    let fingerprint = EntryFingerprint {
+                        // Tags are also the entry's own alternate and
+                        // localized names: the matcher indexes them with the
+                        // name, as it reads skin aliases at match time.
+                        aliases: digest(
+                            entry.tags.iter().map(String::as_str).chain(
+                                entry
+                                    .custom_skins
+                                    .iter()
+                                    .flat_map(|skin| skin.aliases.iter().map(String::as_str)),
+                            ),
+                        ),
+                        skins: digest(entry.custom_skins.iter().map(|skin| skin.name.as_str())),
+                        hashes: digest(entry.hash_db.values().flatten().map(String::as_str)),
+                        tags: digest(entry.tags.iter().map(String::as_str)),
+                    };
+                    (canonical_entry_key(&entry.name), fingerprint)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// How one entry differs between two databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryChange {
+    Added,
+    Removed,
+    AliasesChanged,
+    SkinsChanged,
+    HashesChanged,
+    TagsChanged,
+}
+
+/// Entry key → what changed, for every entry that differs.
+pub fn diff_fingerprints(
+    old: &MasterDbFingerprint,
+    new: &MasterDbFingerprint,
+) -> BTreeMap<String, Vec<EntryChange>> {
+    let mut diff = BTreeMap::new();
+    for (key, before) in &old.0 {
+        let Some(after) = new.0.get(key) else {
+            diff.insert(key.clone(), vec![EntryChange::Removed]);
+            continue;
+        };
+        let changes: Vec<EntryChange> = [
+            (before.aliases != after.aliases, EntryChange::AliasesChanged),
+            (before.skins != after.skins, EntryChange::SkinsChanged),
+            (before.hashes != after.hashes, EntryChange::HashesChanged),
+            (before.tags != after.tags, EntryChange::TagsChanged),
+        ]
+        .into_iter()
+        .filter_map(|(changed, change)| changed.then_some(change))
+        .collect();
+        if !changes.is_empty() {
+            diff.insert(key.clone(), changes);
+        }
+    }
+    for key in new.0.keys() {
+        if !old.0.contains_key(key) {
+            diff.insert(key.clone(), vec![EntryChange::Added]);
+        }
+    }
+    diff
+}
+
+fn baseline_key(game_id: &str) -> String {
+    format!("{BASELINE_META_PREFIX}{game_id}")
+}
+
+/// The fingerprint stored by the last review, or `None` when there is none or
+/// it cannot be read.
+pub async fn load_baseline(pool: &sqlx::SqlitePool, game_id: &str) -> Option<MasterDbFingerprint> {
+    let json = settings_repo::get_app_meta(pool, &baseline_key(game_id)).await?;
+    match serde_json::from_str(&json) {
+        Ok(baseline) => Some(baseline),
+        Err(error) => {
+            log::warn!("rematch: unreadable baseline for game_id={game_id}, ignoring: {error}");
+            None
+        }
+    }
+}
+
+/// Record `db` as the database the user has reviewed re-matches against.
+pub async fn store_baseline(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    db: &MasterDb,
+) -> Result<(), AppError> {
+    store_fingerprint(pool, game_id, &MasterDbFingerprint::of(&db.entries)).await
+}
+
+async fn store_fingerprint(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    fingerprint: &MasterDbFingerprint,
+) -> Result<(), AppError> {
+    let json = serde_json::to_string(fingerprint)?;
+    settings_repo::try_set_app_meta(pool, &baseline_key(game_id), &json).await?;
+    Ok(())
+}
+
+/// Record the MasterDB each game matches against now as its baseline, for
+/// every game without one. Run before an update replaces the database, so the
+/// first re-match after it diffs against what the objects were matched with.
+/// A game that has a baseline keeps it: the diff from its last review already
+/// covers this update.
+pub async fn snapshot_outgoing_baselines(
+    pool: &sqlx::SqlitePool,
+    resource_dir: &Path,
+) -> Result<(), AppError> {
+    for game in game_repo::get_all_games(pool).await? {
+        if load_baseline(pool, &game.id).await.is_some() {
+            continue;
+        }
+        let game_type = game.game_type as i32;
+        let Some(db) = load_master_db(pool, resource_dir, game_type, None).await? else {
+            continue;
+        };
+        store_baseline(pool, &game.id, &db).await?;
+        log::info!(
+            "rematch: baseline snapshot | game_id={} entries={}",
+            game.id,
+            db.entries.len()
+        );
+    }
+    Ok(())
+}
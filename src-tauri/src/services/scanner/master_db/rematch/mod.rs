@@ -0,0 +1,248 @@
+//! Re-matching existing objects after a MasterDB update.
+//!
+//! Objects keep the `matched_entry_key` they were given at import, so a newer
+//! database never reaches them. This job fingerprints every entry as of the
+//! last reviewed re-match, diffs that against the current database, and re-runs
+//! the matcher only on objects whose entry changed and on objects that never
+//! matched well. It returns a change list and writes nothing: the user applies
+//! the changes they accept through `apply_object_match_cmd`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::domain::errors::AppError;
+use crate::repo::object_repo::{self, ObjectMatchState};
+use crate::services::scanner::core::walker::{self, FolderContent, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::PreparedTokenFilters;
+use crate::services::scanner::deep_matcher::{self, MasterDb, MatchStatus};
+use crate::services::scanner::master_db::corrections::MANUAL_MATCH_SOURCE;
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+
+mod baseline;
+pub use baseline::{
+    diff_fingerprints, load_baseline, snapshot_outgoing_baselines, store_baseline, EntryChange,
+    MasterDbFingerprint,
+};
+
+/// `matched_source` of a match accepted from a re-match change list.
+pub const REMATCH_SOURCE: &str = "master_db_rematch";
+
+/// Objects matched below this confidence are re-matched even when their entry
+/// did not change.
+const LOW_CONFIDENCE: f64 = 0.6;
+
+/// A same-entry re-match only refreshes the stored confidence when it gains
+/// at least this much.
+const CONFIDENCE_GAIN: f64 = 0.05;
+
+/// Why an object was re-matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RematchCause {
+    /// Its entry gained or lost aliases, skins, hashes, or tags.
+    EntryChanged,
+    /// Its entry is gone from the database.
+    EntryRemoved,
+    Unmatched,
+    LowConfidence,
+}
+
+/// One proposed match update, shaped for `apply_object_match_cmd`.
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct RematchChange {
+    pub object_id: String,
+    pub object_name: String,
+    pub cause: RematchCause,
+    /// What changed in the previous entry; empty unless `cause` is
+    /// `EntryChanged`.
+    pub entry_changes: Vec<EntryChange>,
+    pub previous_entry_key: Option<String>,
+    pub previous_confidence: Option<f64>,
+    /// `None` clears the match: the previous entry was removed and nothing
+    /// else matched.
+    pub matched_entry_key: Option<String>,
+    pub matched_alias_name: Option<String>,
+    pub matched_confidence: Option<f64>,
+    pub matched_reason: Option<String>,
+    pub matched_source: String,
+}
+
+/// The reviewable outcome of a re-match run.
+#[derive(Debug, Clone, Default, serde::Serialize, specta::Type)]
+pub struct RematchPlan {
+    /// False on the first run for a game: there was no earlier database to
+    /// diff against, so only unmatched and low-confidence objects were tried.
+    pub has_baseline: bool,
+    /// Entries added, removed, or changed since the last review.
+    pub changed_entries: u32,
+    /// Objects the matcher was re-run on.
+    pub examined: u32,
+    pub changes: Vec<RematchChange>,
+}
+
+/// Why `object` should be re-matched, or `None` to leave it alone. A manual
+/// match is the user's call and is only revisited when its entry is gone.
+fn rematch_cause(
+    object: &ObjectMatchState,
+    diff: &BTreeMap<String, Vec<EntryChange>>,
+) -> Option<(RematchCause, Vec<EntryChange>)> {
+    let Some(entry_key) = object.matched_entry_key.as_deref() else {
+        return Some((RematchCause::Unmatched, Vec::new()));
+    };
+    let entry_changes = diff.get(entry_key);
+    if entry_changes.is_some_and(|changes| changes.contains(&EntryChange::Removed)) {
+        return Some((RematchCause::EntryRemoved, Vec::new()));
+    }
+    if object.matched_source.as_deref() == Some(MANUAL_MATCH_SOURCE) {
+        return None;
+    }
+    if let Some(changes) = entry_changes {
+        return Some((RematchCause::EntryChanged, changes.clone()));
+    }
+    (object.matched_confidence.unwrap_or(0.0) < LOW_CONFIDENCE)
+        .then_some((RematchCause::LowConfidence, Vec::new()))
+}
+
+/// Match one object by its folder, or by its name alone when the folder is
+/// not on disk.
+fn match_object(
+    folder: &Path,
+    object_name: &str,
+    db: &MasterDb,
+    ini_filters: &PreparedTokenFilters,
+) -> deep_matcher::StagedMatchResult {
+    let content = if folder.is_dir() {
+        walker::scan_folder_content(folder, 3)
+    } else {
+        FolderContent {
+            subfolder_names: Vec::new(),
+            files: Vec::new(),
+            ini_files: Vec::new(),
+        }
+    };
+    let candidate = ModCandidate {
+        path: folder.to_path_buf(),
+        raw_name: object_name.to_string(),
+        display_name: object_name.to_string(),
+        is_disabled: false,
+    };
+    deep_matcher::match_folder_phased(
+        &candidate,
+        db,
+        &content,
+        ini_filters,
+        &AiRerankConfig::default(),
+    )
+}
+
+/// The change re-matching `object` proposes, if any.
+fn propose_change(
+    object: ObjectMatchState,
+    cause: RematchCause,
+    entry_changes: Vec<EntryChange>,
+    result: &deep_matcher::StagedMatchResult,
+) -> Option<RematchChange> {
+    let matched = (result.status == MatchStatus::AutoMatched)
+        .then_some(result.best.as_ref())
+        .flatten();
+    let (entry_key, alias_name, confidence, reason) = match matched {
+        Some(best) => {
+            let entry_key = canonical_entry_key(&best.name);
+            let confidence = f64::from(result.confidence_score()) / 100.0;
+            let same_entry = object.matched_entry_key.as_deref() == Some(entry_key.as_str());
+            let gained = confidence >= object.matched_confidence.unwrap_or(0.0) + CONFIDENCE_GAIN;
+            if same_entry && !gained {
+                return None;
+            }
+            (
+                Some(entry_key),
+                Some(best.name.clone()),
+                Some(confidence),
+                Some(result.summary()),
+            )
+        }
+        None if cause == RematchCause::EntryRemoved => (
+            None,
+            None,
+            None,
+            Some("Entry removed from MasterDB".to_string()),
+        ),
+        None => return None,
+    };
+
+    Some(RematchChange {
+        object_id: object.id,
+        object_name: object.name,
+        cause,
+        entry_changes,
+        previous_entry_key: object.matched_entry_key,
+        previous_confidence: object.matched_confidence,
+        matched_entry_key: entry_key,
+        matched_alias_name: alias_name,
+        matched_confidence: confidence,
+        matched_reason: reason,
+        matched_source: REMATCH_SOURCE.to_string(),
+    })
+}
+
+/// Build the re-match change list for one game against `db`.
+///
+/// Entries are diffed against the game's baseline, which metadata sync
+/// snapshots before an update and a review moves forward. Without one, only
+/// unmatched and low-confidence objects are tried.
+pub async fn plan_rematch(
+    pool: &sqlx::SqlitePool,
+    game_id: &str,
+    db: Arc<MasterDb>,
+    ini_filters: PreparedTokenFilters,
+) -> Result<RematchPlan, AppError> {
+    let baseline = load_baseline(pool, game_id).await;
+    let has_baseline = baseline.is_some();
+    let diff = baseline
+        .map(|baseline| diff_fingerprints(&baseline, &MasterDbFingerprint::of(&db.entries)))
+        .unwrap_or_default();
+
+    let mods_root = object_repo::load_game_mods_path(pool, game_id)
+        .await?
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let targets: Vec<_> = object_repo::get_match_states_by_game(pool, game_id)
+        .await?
+        .into_iter()
+        .filter_map(|object| {
+            let (cause, entry_changes) = rematch_cause(&object, &diff)?;
+            Some((object, cause, entry_changes))
+        })
+        .collect();
+    let examined = targets.len();
+
+    let changes = tokio::task::spawn_blocking(move || {
+        targets
+            .into_iter()
+            .filter_map(|(object, cause, entry_changes)| {
+                let folder = mods_root.join(&object.folder_path);
+                let result = match_object(&folder, &object.name, &db, &ini_filters);
+                propose_change(object, cause, entry_changes, &result)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    log::info!(
+        "rematch: planned | game_id={game_id} changed_entries={} examined={examined} changes={}",
+        diff.len(),
+        changes.len()
+    );
+    Ok(RematchPlan {
+        has_baseline,
+        changed_entries: u32::try_from(diff.len()).unwrap_or(u32::MAX),
+        examined: u32::try_from(examined).unwrap_or(u32::MAX),
+        changes,
+    })
+}
+
+#[cfg(test)]
+#[path = "../../tests/master_db_rematch_tests.rs"]
+mod master_db_rematch_tests;
@@ -36,34 +36,50 @@ pub async fn commit_scan_results(
     ensure_game_exists(&mut tx, game_id, game_name, game_type, mods_path).await?;
 
     let mut new_objects_count = 0;
-    let (disk_entries, collisions) =
+    let ((disk_entries, collisions), moves) =
         prepare_disk_entries(&mut tx, &ctx, items, &mut new_objects_count).await?;
 
     let total = disk_entries.len();
 
-    // Fetch snapshot of DB state
-    let db_mods = mod_repo::get_all_mods_sync_info_tx(&mut tx, game_id).await?;
+    // Folders already moved go back if the database side fails.
+    let written = async {
+        // Fetch snapshot of DB state
+        let db_mods = mod_repo::get_all_mods_sync_info_tx(&mut tx, game_id).await?;
 
-    let disk_to_db = link_disk_to_db(&disk_entries, &db_mods, std::path::Path::new(ctx.mods_path));
+        let disk_to_db =
+            link_disk_to_db(&disk_entries, &db_mods, std::path::Path::new(ctx.mods_path));
 
-    let (new_mods_count, updated_mods_count) = execute_entries(
-        &mut tx,
-        &ctx,
-        disk_entries,
-        &db_mods,
-        &disk_to_db,
-        &mut new_objects_count,
-    )
-    .await?;
+        let counts = execute_entries(
+            &mut tx,
+            &ctx,
+            disk_entries,
+            &db_mods,
+            &disk_to_db,
+            &mut new_objects_count,
+        )
+        .await?;
+
+        crate::repo::object_repo::delete_ghost_objects_gc(&mut tx, game_id).await?;
+
+        tx.commit().await?;
+        Ok::<_, ScannerError>(counts)
+    }
+    .await;
+    let (new_mods_count, updated_mods_count) = match written {
+        Ok(counts) => {
+            moves.finish();
+            counts
+        }
+        Err(error) => {
+            moves.undo();
+            return Err(error);
+        }
+    };
 
     // Scan commit is an explicit import/scan commit, not passive filesystem repair.
     // Disk Reconcile owns cleanup of DB rows whose folders disappeared outside this flow.
     let deleted_mods_count = 0;
 
-    crate::repo::object_repo::delete_ghost_objects_gc(&mut tx, game_id).await?;
-
-    tx.commit().await?;
-
     let temp_dir_path = std::path::Path::new(mods_path).join(".emmm_temp");
     if temp_dir_path.exists() {
         let _ = std::fs::remove_dir(&temp_dir_path);
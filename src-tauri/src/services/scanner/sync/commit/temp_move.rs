@@ -1,10 +1,19 @@
 //! Phase 0: resolve `move_from_temp` items to their final on-disk location.
+//!
+//! A multi-object pack confirmed with `pack_parts` is split here: each part is
+//! moved into its own object folder like a staged temp folder, and the pack
+//! folder itself is not committed. Parts the user skipped, and subfolders that
+//! matched nothing, stay in the pack folder; one left empty is removed.
+//!
+//! Every completed move is recorded, so a commit that fails later can put the
+//! folders back where the scan found them.
 
 use crate::domain::errors::ScannerError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::repo::mod_repo;
 use crate::repo::stable_ids::generate_stable_id;
+use crate::services::fs_utils::guard::validate_path_in_root;
 use crate::services::scanner::core::types::CollisionInfo;
 use crate::services::scanner::sync::helpers::{
     resolve_or_create_object_target_for_match, ResolveObjectTargetInput,
@@ -13,6 +22,41 @@ use crate::services::scanner::sync::types::ConfirmedScanItem;
 
 use super::request::CommitCtx;
 
+/// Folders moved by [`prepare_disk_entries`], and the packs they came from.
+#[derive(Debug, Default)]
+pub(super) struct DiskMoves {
+    /// `(source, target)` in the order the moves happened.
+    moved: Vec<(PathBuf, PathBuf)>,
+    packs: Vec<PathBuf>,
+}
+
+impl DiskMoves {
+    /// Move every folder back, newest first.
+    pub(super) fn undo(&self) {
+        for (source, target) in self.moved.iter().rev() {
+            if let Err(error) = std::fs::rename(target, source) {
+                log::warn!(
+                    "commit_scan: could not move {} back to {}: {error}",
+                    target.display(),
+                    source.display()
+                );
+            }
+        }
+    }
+
+    /// Remove the pack folders the split left empty. A pack still holding
+    /// skipped parts or loose files stays.
+    pub(super) fn finish(&self) {
+        for pack in &self.packs {
+            if std::fs::remove_dir(pack).is_ok() {
+                log::info!("commit_scan: removed emptied pack {}", pack.display());
+            }
+        }
+    }
+}
+
+type PreparedEntries = (Vec<(ConfirmedScanItem, String)>, Vec<CollisionInfo>);
+
 async fn resolve_temp_target_object_folder(
     conn: &mut sqlx::SqliteConnection,
     input: ResolveObjectTargetInput<'_>,
@@ -30,13 +74,71 @@ async fn resolve_temp_target_object_folder(
         .unwrap_or_else(|| "Other".to_string()))
 }
 
+/// Replaces every confirmed pack with its parts, each flagged to move into its
+/// own object folder, and returns the pack folders. Fails before anything
+/// moves if a part is outside `mods_root` or not a direct child of its pack.
+fn expand_pack_parts(
+    items: Vec<ConfirmedScanItem>,
+    mods_root: &Path,
+) -> Result<(Vec<ConfirmedScanItem>, Vec<PathBuf>), ScannerError> {
+    let mut expanded = Vec::with_capacity(items.len());
+    let mut packs = Vec::new();
+    for mut item in items {
+        if item.skip || item.pack_parts.is_empty() {
+            expanded.push(item);
+            continue;
+        }
+        packs.push(PathBuf::from(&item.folder_path));
+        for mut part in std::mem::take(&mut item.pack_parts) {
+            validate_path_in_root(mods_root, &part.folder_path).map_err(|_| {
+                ScannerError::PathEscape {
+                    path: part.folder_path.clone(),
+                }
+            })?;
+            if Path::new(&part.folder_path).parent() != Some(Path::new(&item.folder_path)) {
+                return Err(ScannerError::Validation(format!(
+                    "Pack part {} is not a subfolder of {}",
+                    part.folder_path, item.folder_path
+                )));
+            }
+            part.move_from_temp = true;
+            part.pack_parts.clear();
+            expanded.push(part);
+        }
+    }
+    Ok((expanded, packs))
+}
+
 /// Moves staged temp folders into their object directory, reporting collisions.
+/// On error every folder already moved is moved back; on success the caller
+/// gets the moves to undo or finish once the commit settles.
 pub(super) async fn prepare_disk_entries(
     tx: &mut sqlx::SqliteConnection,
     ctx: &CommitCtx<'_>,
     items: Vec<ConfirmedScanItem>,
     new_objects_count: &mut usize,
-) -> Result<(Vec<(ConfirmedScanItem, String)>, Vec<CollisionInfo>), ScannerError> {
+) -> Result<(PreparedEntries, DiskMoves), ScannerError> {
+    let (items, packs) = expand_pack_parts(items, Path::new(ctx.mods_path))?;
+    let mut moves = DiskMoves {
+        moved: Vec::new(),
+        packs,
+    };
+    match move_entries(tx, ctx, items, new_objects_count, &mut moves.moved).await {
+        Ok(prepared) => Ok((prepared, moves)),
+        Err(error) => {
+            moves.undo();
+            Err(error)
+        }
+    }
+}
+
+async fn move_entries(
+    tx: &mut sqlx::SqliteConnection,
+    ctx: &CommitCtx<'_>,
+    items: Vec<ConfirmedScanItem>,
+    new_objects_count: &mut usize,
+    moved: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<PreparedEntries, ScannerError> {
     let game_id = ctx.game_id;
     let mods_path = ctx.mods_path;
     let mut collisions = Vec::new();
@@ -107,6 +209,7 @@ pub(super) async fn prepare_disk_entries(
                         )));
                     } else {
                         actual_folder_path = target_path.to_string_lossy().into_owned();
+                        moved.push((source_path.to_path_buf(), target_path));
                     }
                 } else {
                     return Err(ScannerError::Validation(format!(
@@ -2,14 +2,20 @@ pub mod commit;
 pub mod helpers;
 pub mod preview;
 pub mod scoring;
+pub mod signal_store;
 pub mod types;
 
 pub use commit::*;
 pub use helpers::*;
 pub use preview::*;
 pub use scoring::*;
+pub use signal_store::*;
 pub use types::*;
 
 #[cfg(test)]
 #[path = "../tests/sync_tests.rs"]
 mod sync_tests;
+
+#[cfg(test)]
+#[path = "../tests/pack_split_commit_tests.rs"]
+mod pack_split_commit_tests;
@@ -0,0 +1,63 @@
+//! MasterDB entry fields as preview and commit items carry them.
+
+use std::path::Path;
+
+use crate::services::scanner::deep_matcher::models::types;
+
+/// The MasterDB entry fields a preview or commit item carries, serialized.
+#[derive(Default)]
+pub(super) struct EntryJson {
+    pub(super) thumbnail: Option<String>,
+    pub(super) tags: Option<String>,
+    pub(super) metadata: Option<String>,
+    pub(super) hash_db: Option<String>,
+    pub(super) custom_skins: Option<String>,
+}
+
+impl EntryJson {
+    pub(super) fn new(db_entry: Option<&types::DbEntry>) -> Self {
+        let Some(entry) = db_entry else {
+            return Self::default();
+        };
+        Self {
+            thumbnail: entry.thumbnail_path.clone(),
+            tags: Some(serde_json::to_string(&entry.tags).unwrap_or_else(|_| "[]".to_string())),
+            metadata: entry
+                .metadata
+                .as_ref()
+                .map(|m| serde_json::to_string(m).unwrap_or_else(|_| "{}".to_string())),
+            hash_db: Some(
+                serde_json::to_string(&entry.hash_db).unwrap_or_else(|_| "{}".to_string()),
+            ),
+            custom_skins: Some(
+                serde_json::to_string(&entry.custom_skins).unwrap_or_else(|_| "[]".to_string()),
+            ),
+        }
+    }
+}
+
+pub(super) fn resolve_thumbnail(
+    mods_path: &Path,
+    db_entry: Option<&types::DbEntry>,
+    resource_dir: Option<&Path>,
+) -> Option<String> {
+    let entry = db_entry?;
+    let r = entry.thumbnail_path.clone()?;
+
+    if let Some(res_dir) = resource_dir {
+        let abs = res_dir.join(&r);
+        if abs.exists() {
+            Some(abs.to_string_lossy().to_string())
+        } else {
+            None
+        }
+    } else {
+        // No resource_dir: use mods_path as fallback base for absolute path resolution.
+        let abs = mods_path.join(r);
+        if abs.exists() {
+            Some(abs.to_string_lossy().to_string())
+        } else {
+            None
+        }
+    }
+}
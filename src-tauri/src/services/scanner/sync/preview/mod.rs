@@ -8,15 +8,30 @@ use std::sync::Arc;
 use tauri::ipc::Channel;
 
 use super::helpers::{auto_matched_candidate, canonical_entry_key};
+use super::signal_store::{
+    load_persisted_signals, store_signals, ComputedSignals, PersistedSignals,
+};
 use super::types::{ScanPreviewItem, ScoredCandidate};
 use crate::services::scanner::core::types::{
     match_status_label, staged_confidence_label, ScanEvent,
 };
 use crate::services::scanner::core::walker;
 use crate::services::scanner::deep_matcher;
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankContext;
 use crate::services::scanner::deep_matcher::analysis::content::PreparedTokenFilters;
+use crate::services::scanner::deep_matcher::analysis::skin_resolver;
 use crate::services::scanner::deep_matcher::models::result_summary::score_to_percentage;
 use crate::services::scanner::deep_matcher::models::types;
+use crate::services::scanner::deep_matcher::state::signal_cache::{
+    folder_fingerprint, SignalCache,
+};
+
+mod entry_json;
+mod pack_parts;
+mod progress;
+
+use entry_json::{resolve_thumbnail, EntryJson};
+use progress::PreviewProgress;
 
 type ExistingMod = (String, Option<String>);
 
@@ -27,6 +42,8 @@ struct PreviewWorker {
     resource_dir: Option<PathBuf>,
     existing_by_path: HashMap<String, ExistingMod>,
     matched_key_by_object: HashMap<String, String>,
+    persisted_signals: PersistedSignals,
+    ai: AiRerankContext,
 }
 
 struct PreviewWorkerInput<'a> {
@@ -36,12 +53,7 @@ struct PreviewWorkerInput<'a> {
     master_db: Arc<deep_matcher::MasterDb>,
     ini_filters: PreparedTokenFilters,
     resource_dir: Option<&'a Path>,
-}
-
-struct PreviewProgress {
-    channel: Option<Channel<ScanEvent>>,
-    total: usize,
-    started: std::time::Instant,
+    ai: AiRerankContext,
 }
 
 pub struct ScanPreviewRequest<'a> {
@@ -53,6 +65,8 @@ pub struct ScanPreviewRequest<'a> {
     pub resource_dir: Option<&'a Path>,
     pub on_progress: Option<Channel<ScanEvent>>,
     pub specific_paths: Option<Vec<PathBuf>>,
+    /// AI rerank for folders the deterministic stages leave in review.
+    pub ai: AiRerankContext,
 }
 
 /// Phase 1: Scan folders and run the Deep Match Scanner preview without writing to DB.
@@ -68,6 +82,7 @@ pub async fn scan_preview(
         resource_dir,
         on_progress,
         specific_paths,
+        ai,
     } = request;
     let candidates = if let Some(paths) = specific_paths {
         walker::scan_specific_folders(&paths)?
@@ -95,6 +110,7 @@ pub async fn scan_preview(
         master_db,
         ini_filters: ini_filters.clone(),
         resource_dir,
+        ai,
     })
     .await?;
     let completed = Arc::new(AtomicUsize::new(0));
@@ -104,26 +120,40 @@ pub async fn scan_preview(
         started,
     });
     let worker_progress = Arc::clone(&progress);
-    let items = tauri::async_runtime::spawn_blocking(move || {
+    let (items, computed): (Vec<_>, Vec<_>) = tauri::async_runtime::spawn_blocking(move || {
         candidates
             .into_par_iter()
             .map(|candidate| {
-                let preview_item = worker.preview_item(&candidate);
+                let (preview_item, computed) = worker.preview_item(&candidate);
                 let current = completed.fetch_add(1, Ordering::Relaxed) + 1;
                 worker_progress.send_item(&preview_item, current);
-                preview_item
+                (preview_item, computed)
             })
-            .collect::<Vec<_>>()
+            .unzip()
     })
     .await
     .map_err(|error| ScannerError::Io(format!("preview worker failed: {error}")))?;
 
+    // A folder that computed nothing was matched entirely from persisted signals.
+    let reused = computed.iter().filter(|signals| signals.is_empty()).count();
+    let computed: Vec<ComputedSignals> = computed.into_iter().flatten().collect();
+    log::info!(
+        "scan_preview: signals | game_id={game_id} reused_folders={reused} computed={}",
+        computed.len()
+    );
+    store_signals(pool, game_id, &computed).await;
+
     progress.finish(game_id, &items);
     Ok(items)
 }
 
 impl PreviewWorker {
-    fn preview_item(&self, candidate: &walker::ModCandidate) -> ScanPreviewItem {
+    /// The preview row for one folder, plus the signals computed for it that
+    /// the persisted cache did not already hold.
+    fn preview_item(
+        &self,
+        candidate: &walker::ModCandidate,
+    ) -> (ScanPreviewItem, Vec<ComputedSignals>) {
         let folder_path = candidate.path.to_string_lossy().to_string();
         let path_key = crate::common::path_key::folder_path_key(
             &folder_path,
@@ -131,13 +161,24 @@ impl PreviewWorker {
         );
         let existing = self.existing_by_path.get(&path_key);
         let content = walker::scan_folder_content(&candidate.path, 3);
-        let match_result = deep_matcher::match_folder_phased(
+        let fingerprint = folder_fingerprint(&candidate.path, &content, &self.ini_filters);
+        let mut signal_cache = SignalCache::new();
+        self.persisted_signals
+            .seed(&mut signal_cache, &folder_path, &fingerprint);
+        let match_result = deep_matcher::match_folder_phased_cached(
             candidate,
             &self.master_db,
             &content,
             &self.ini_filters,
-            &crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig::default(),
+            &self.ai.config(),
+            &mut signal_cache,
         );
+        let pack_parts = if match_result.status == types::MatchStatus::MultiObjectPack {
+            self.pack_part_items(candidate, &content, &mut signal_cache)
+        } else {
+            Vec::new()
+        };
+        let computed = ComputedSignals::drain(&mut signal_cache, &folder_path, &fingerprint);
         let auto_candidate = auto_matched_candidate(&match_result);
 
         let matched_alias_name = auto_candidate.map(|c| c.name.clone());
@@ -145,6 +186,16 @@ impl PreviewWorker {
             .as_ref()
             .map(|name| canonical_entry_key(name));
         let object_type = auto_candidate.map(|c| c.object_type.clone());
+        // The resolver also accepts review results; the preview only names a
+        // skin when it names the object.
+        let detected_skin = auto_candidate.and_then(|_| {
+            skin_resolver::detect_skin_for_staged(
+                &match_result,
+                &candidate.raw_name,
+                &self.master_db,
+            )
+            .0
+        });
         let match_level = match_status_label(&match_result.status).to_string();
         let confidence = staged_confidence_label(&match_result).to_string();
         let match_detail = Some(match_result.summary());
@@ -165,18 +216,9 @@ impl PreviewWorker {
                 .find(|entry| &entry.name == name)
         });
 
-        let raw_thumbnail = db_entry.and_then(|e| e.thumbnail_path.clone());
+        let entry_json = EntryJson::new(db_entry);
         let db_thumbnail =
             resolve_thumbnail(&self.mods_path, db_entry, self.resource_dir.as_deref());
-        let tags_json =
-            db_entry.map(|e| serde_json::to_string(&e.tags).unwrap_or_else(|_| "[]".to_string()));
-        let metadata_json = db_entry
-            .and_then(|e| e.metadata.as_ref())
-            .map(|m| serde_json::to_string(m).unwrap_or_else(|_| "{}".to_string()));
-        let hash_db_json = db_entry
-            .map(|e| serde_json::to_string(&e.hash_db).unwrap_or_else(|_| "{}".to_string()));
-        let custom_skins_json = db_entry
-            .map(|e| serde_json::to_string(&e.custom_skins).unwrap_or_else(|_| "[]".to_string()));
 
         let scored_candidates: Vec<ScoredCandidate> = match_result
             .candidates_topk
@@ -188,7 +230,7 @@ impl PreviewWorker {
             })
             .collect();
 
-        ScanPreviewItem {
+        let item = ScanPreviewItem {
             folder_path,
             display_name: candidate.display_name.clone(),
             is_disabled: candidate.is_disabled,
@@ -198,18 +240,20 @@ impl PreviewWorker {
             confidence,
             confidence_score: match_result.confidence_score(),
             match_detail,
-            detected_skin: None,
+            detected_skin,
             object_type,
             thumbnail_path: db_thumbnail,
-            tags_json,
-            metadata_json,
-            hash_db_json,
-            custom_skins_json,
-            db_thumbnail: raw_thumbnail,
+            tags_json: entry_json.tags,
+            metadata_json: entry_json.metadata,
+            hash_db_json: entry_json.hash_db,
+            custom_skins_json: entry_json.custom_skins,
+            db_thumbnail: entry_json.thumbnail,
             already_in_db,
             already_matched,
             scored_candidates,
-        }
+            pack_parts,
+        };
+        (item, computed)
     }
 
     fn is_already_matched(
@@ -245,6 +289,7 @@ async fn build_preview_worker(
         .collect();
     let matched_key_by_object =
         crate::repo::object_repo::get_matched_entry_keys_by_game(input.pool, input.game_id).await?;
+    let persisted_signals = load_persisted_signals(input.pool, input.game_id).await;
 
     Ok(PreviewWorker {
         mods_path: input.mods_path.to_path_buf(),
@@ -253,70 +298,7 @@ async fn build_preview_worker(
         resource_dir: input.resource_dir.map(Path::to_path_buf),
         existing_by_path,
         matched_key_by_object,
+        persisted_signals,
+        ai: input.ai,
     })
 }
-
-impl PreviewProgress {
-    fn send_item(&self, preview_item: &ScanPreviewItem, current: usize) {
-        let Some(channel) = &self.channel else { return };
-        let elapsed_ms = self.started.elapsed().as_millis() as u64;
-        let _ = channel.send(ScanEvent::Progress {
-            current,
-            total: self.total,
-            folder_name: preview_item.display_name.clone(),
-            elapsed_ms,
-            eta_ms: elapsed_ms * (self.total - current) as u64 / current.max(1) as u64,
-        });
-        if let Some(object_name) = &preview_item.matched_alias_name {
-            let _ = channel.send(ScanEvent::Matched {
-                folder_name: preview_item.display_name.clone(),
-                object_name: object_name.clone(),
-                confidence: preview_item.confidence.clone(),
-            });
-        }
-    }
-
-    fn finish(&self, game_id: &str, items: &[ScanPreviewItem]) {
-        let matched = items
-            .iter()
-            .filter(|preview_item| preview_item.matched_entry_key.is_some())
-            .count();
-        log::info!(
-            "scan_preview: done | game_id={game_id} folders={} matched={matched} unmatched={} elapsed_ms={}",
-            self.total,
-            self.total - matched,
-            self.started.elapsed().as_millis()
-        );
-        let Some(channel) = &self.channel else { return };
-        let _ = channel.send(ScanEvent::Finished {
-            matched,
-            unmatched: self.total - matched,
-        });
-    }
-}
-
-fn resolve_thumbnail(
-    mods_path: &Path,
-    db_entry: Option<&types::DbEntry>,
-    resource_dir: Option<&Path>,
-) -> Option<String> {
-    let entry = db_entry?;
-    let r = entry.thumbnail_path.clone()?;
-
-    if let Some(res_dir) = resource_dir {
-        let abs = res_dir.join(&r);
-        if abs.exists() {
-            Some(abs.to_string_lossy().to_string())
-        } else {
-            None
-        }
-    } else {
-        // No resource_dir: use mods_path as fallback base for absolute path resolution.
-        let abs = mods_path.join(r);
-        if abs.exists() {
-            Some(abs.to_string_lossy().to_string())
-        } else {
-            None
-        }
-    }
-}
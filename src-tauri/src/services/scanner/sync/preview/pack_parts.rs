@@ -0,0 +1,68 @@
+//! Commit items for the parts of a multi-object pack.
+
+use super::entry_json::{resolve_thumbnail, EntryJson};
+use super::PreviewWorker;
+use crate::services::scanner::core::walker;
+use crate::services::scanner::deep_matcher::pipeline::pack_split::{
+    self, PackPart, MIN_PACK_MEMBERS,
+};
+use crate::services::scanner::deep_matcher::state::signal_cache::SignalCache;
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+use crate::services::scanner::sync::types::ConfirmedScanItem;
+
+impl PreviewWorker {
+    /// Prefilled commit items for the subfolders of a pack that matched an
+    /// object. Empty for a pack that cannot be split by folder, such as one
+    /// INI overriding several objects.
+    pub(super) fn pack_part_items(
+        &self,
+        candidate: &walker::ModCandidate,
+        content: &walker::FolderContent,
+        signal_cache: &mut SignalCache,
+    ) -> Vec<ConfirmedScanItem> {
+        let parts = pack_split::split_pack(
+            candidate,
+            content,
+            &self.master_db,
+            &self.ini_filters,
+            &self.ai.config(),
+            signal_cache,
+        );
+        if pack_split::pack_members(&parts).len() < MIN_PACK_MEMBERS {
+            return Vec::new();
+        }
+        parts
+            .iter()
+            .filter_map(|part| self.pack_part_item(part))
+            .collect()
+    }
+
+    fn pack_part_item(&self, part: &PackPart) -> Option<ConfirmedScanItem> {
+        let matched = part.matched()?;
+        let db_entry = self.master_db.entries.get(matched.entry_id);
+        let entry_json = EntryJson::new(db_entry);
+        Some(ConfirmedScanItem {
+            folder_path: part.folder.path.to_string_lossy().to_string(),
+            display_name: part.folder.display_name.clone(),
+            is_disabled: part.folder.is_disabled,
+            matched_entry_key: Some(canonical_entry_key(&matched.name)),
+            matched_alias_name: Some(matched.name.clone()),
+            matched_confidence: Some(f64::from(part.result.confidence_score()) / 100.0),
+            matched_reason: Some(part.result.summary()),
+            object_type: Some(matched.object_type.clone()),
+            thumbnail_path: resolve_thumbnail(
+                &self.mods_path,
+                db_entry,
+                self.resource_dir.as_deref(),
+            ),
+            tags_json: entry_json.tags,
+            metadata_json: entry_json.metadata,
+            hash_db_json: entry_json.hash_db,
+            custom_skins_json: entry_json.custom_skins,
+            db_thumbnail: entry_json.thumbnail,
+            skip: false,
+            move_from_temp: true,
+            pack_parts: Vec::new(),
+        })
+    }
+}
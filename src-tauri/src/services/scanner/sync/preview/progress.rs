@@ -0,0 +1,51 @@
+//! Progress events for a running scan preview.
+
+use tauri::ipc::Channel;
+
+use crate::services::scanner::core::types::ScanEvent;
+use crate::services::scanner::sync::types::ScanPreviewItem;
+
+pub(super) struct PreviewProgress {
+    pub(super) channel: Option<Channel<ScanEvent>>,
+    pub(super) total: usize,
+    pub(super) started: std::time::Instant,
+}
+
+impl PreviewProgress {
+    pub(super) fn send_item(&self, preview_item: &ScanPreviewItem, current: usize) {
+        let Some(channel) = &self.channel else { return };
+        let elapsed_ms = self.started.elapsed().as_millis() as u64;
+        let _ = channel.send(ScanEvent::Progress {
+            current,
+            total: self.total,
+            folder_name: preview_item.display_name.clone(),
+            elapsed_ms,
+            eta_ms: elapsed_ms * (self.total - current) as u64 / current.max(1) as u64,
+        });
+        if let Some(object_name) = &preview_item.matched_alias_name {
+            let _ = channel.send(ScanEvent::Matched {
+                folder_name: preview_item.display_name.clone(),
+                object_name: object_name.clone(),
+                confidence: preview_item.confidence.clone(),
+            });
+        }
+    }
+
+    pub(super) fn finish(&self, game_id: &str, items: &[ScanPreviewItem]) {
+        let matched = items
+            .iter()
+            .filter(|preview_item| preview_item.matched_entry_key.is_some())
+            .count();
+        log::info!(
+            "scan_preview: done | game_id={game_id} folders={} matched={matched} unmatched={} elapsed_ms={}",
+            self.total,
+            self.total - matched,
+            self.started.elapsed().as_millis()
+        );
+        let Some(channel) = &self.channel else { return };
+        let _ = channel.send(ScanEvent::Finished {
+            matched,
+            unmatched: self.total - matched,
+        });
+    }
+}
@@ -0,0 +1,172 @@
+//! Matcher signals persisted across scans (`folder_signal_cache`).
+//!
+//! The in-memory `SignalCache` only lives for one folder's match. This store
+//! seeds it from the previous scan whenever the folder fingerprint is
+//! unchanged, so rescanning an unchanged library skips reading and tokenizing
+//! every INI. Like the hash index, it is a lookup cache: a failed read or write
+//! is logged and the scan carries on computing signals.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use sqlx::SqlitePool;
+
+use crate::domain::errors::AppError;
+use crate::repo::signal_cache_repo::{self, SignalCacheRow};
+use crate::services::keyviewer::hash_index::top_level_root;
+use crate::services::scanner::deep_matcher::analysis::content::FolderSignals;
+use crate::services::scanner::deep_matcher::state::signal_cache::SignalCache;
+
+/// Persisted signals of one game, loaded once per scan.
+#[derive(Debug, Default)]
+pub struct PersistedSignals {
+    /// `folder_path` → `(mode, fingerprint, signals)`.
+    by_folder: HashMap<String, Vec<(u8, String, FolderSignals)>>,
+}
+
+impl PersistedSignals {
+    /// Seeds `cache` with the persisted signals of `folder_path` taken at
+    /// `fingerprint`. Returns how many modes were seeded.
+    pub fn seed(&self, cache: &mut SignalCache, folder_path: &str, fingerprint: &str) -> usize {
+        let Some(entries) = self.by_folder.get(folder_path) else {
+            return 0;
+        };
+        let mut seeded = 0;
+        for (mode, stored, signals) in entries {
+            if stored == fingerprint {
+                cache.seed(folder_path.to_string(), *mode, signals.clone());
+                seeded += 1;
+            }
+        }
+        seeded
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_folder.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_folder.is_empty()
+    }
+}
+
+/// Signals a scan computed for one folder, to persist once it is done.
+#[derive(Debug, Clone)]
+pub struct ComputedSignals {
+    pub folder_path: String,
+    pub fingerprint: String,
+    pub mode: u8,
+    pub signals: FolderSignals,
+}
+
+impl ComputedSignals {
+    /// What `cache` computed for `folder_path` itself, fingerprinted at
+    /// `fingerprint`. Signals of other folders, such as the subfolders of a
+    /// pack, are dropped: the fingerprint does not cover them.
+    pub fn drain(cache: &mut SignalCache, folder_path: &str, fingerprint: &str) -> Vec<Self> {
+        cache
+            .take_computed()
+            .into_iter()
+            .filter(|(computed_path, _, _)| computed_path == folder_path)
+            .map(|(folder_path, mode, signals)| Self {
+                folder_path,
+                fingerprint: fingerprint.to_string(),
+                mode,
+                signals,
+            })
+            .collect()
+    }
+}
+
+/// Loads every persisted signal of a game. Unreadable rows are skipped; an
+/// unreadable table yields an empty store.
+pub async fn load_persisted_signals(pool: &SqlitePool, game_id: &str) -> PersistedSignals {
+    let rows = match signal_cache_repo::list_for_game(pool, game_id).await {
+        Ok(rows) => rows,
+        Err(error) => {
+            log::warn!("signal cache unavailable for game '{game_id}': {error}");
+            return PersistedSignals::default();
+        }
+    };
+
+    let mut by_folder: HashMap<String, Vec<(u8, String, FolderSignals)>> = HashMap::new();
+    for row in rows {
+        let (Ok(mode), Ok(signals)) = (
+            u8::try_from(row.mode),
+            serde_json::from_str::<FolderSignals>(&row.signals_json),
+        ) else {
+            continue;
+        };
+        by_folder
+            .entry(row.folder_path)
+            .or_default()
+            .push((mode, row.fingerprint, signals));
+    }
+    PersistedSignals { by_folder }
+}
+
+/// Persists signals a scan computed. Failures are logged, not returned.
+pub async fn store_signals(pool: &SqlitePool, game_id: &str, computed: &[ComputedSignals]) {
+    let rows: Vec<SignalCacheRow> = computed
+        .iter()
+        .filter_map(|entry| {
+            Some(SignalCacheRow {
+                folder_path: entry.folder_path.clone(),
+                mode: i64::from(entry.mode),
+                fingerprint: entry.fingerprint.clone(),
+                signals_json: serde_json::to_string(&entry.signals).ok()?,
+            })
+        })
+        .collect();
+    if rows.is_empty() {
+        return;
+    }
+    if let Err(error) = signal_cache_repo::upsert_rows(pool, game_id, &rows).await {
+        log::warn!("signal cache write failed for game '{game_id}': {error}");
+    }
+}
+
+/// Drops persisted signals disk reconcile has made suspect. Returns how many
+/// rows went.
+///
+/// `changed_roots` names the top-level folders a scoped pass touched; every
+/// row below them goes. `None` is a full pass, which prunes the rows of
+/// folders no longer on disk; a folder whose content moved is caught by its
+/// fingerprint on the next scan.
+pub async fn invalidate_signal_cache(
+    pool: &SqlitePool,
+    game_id: &str,
+    mods_root: &Path,
+    changed_roots: Option<&[String]>,
+) -> Result<u64, AppError> {
+    let folder_paths = signal_cache_repo::list_folder_paths(pool, game_id).await?;
+    let stale: Vec<String> = match changed_roots {
+        Some(roots) => folder_paths
+            .into_iter()
+            .filter(|folder_path| {
+                top_level_root(mods_root, Path::new(folder_path)).is_some_and(|root| {
+                    roots
+                        .iter()
+                        .any(|changed| changed.eq_ignore_ascii_case(&root))
+                })
+            })
+            .collect(),
+        None => {
+            tokio::task::spawn_blocking(move || {
+                folder_paths
+                    .into_iter()
+                    .filter(|folder_path| !PathBuf::from(folder_path).is_dir())
+                    .collect::<Vec<_>>()
+            })
+            .await?
+        }
+    };
+    if stale.is_empty() {
+        return Ok(0);
+    }
+    Ok(signal_cache_repo::delete_folders(pool, &stale).await?)
+}
+
+#[cfg(test)]
+#[path = "../tests/signal_store_tests.rs"]
+mod signal_store_tests;
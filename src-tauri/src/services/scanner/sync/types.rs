@@ -50,6 +50,10 @@ pub struct ScanPreviewItem {
     /// Optional custom_skins from MasterDB entry (JSON array string)
     pub custom_skins_json: Option<String>,
     pub db_thumbnail: Option<String>,
+    /// For a multi-object pack, one prefilled item per subfolder that matched
+    /// an object; confirming them splits the pack on commit.
+    #[serde(default)]
+    pub pack_parts: Vec<ConfirmedScanItem>,
 }
 
 /// A lightweight scored candidate for the frontend dropdown.
@@ -82,4 +86,9 @@ pub struct ConfirmedScanItem {
     pub skip: bool,
     #[serde(default)]
     pub move_from_temp: bool,
+    /// Subfolders of a multi-object pack, each filed under its own object on
+    /// commit instead of this item. Every part must be a direct child of
+    /// `folder_path`.
+    #[serde(default)]
+    pub pack_parts: Vec<ConfirmedScanItem>,
 }
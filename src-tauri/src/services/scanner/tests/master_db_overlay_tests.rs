@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use super::{
+    delete_overlay_entry, export_overlay, import_overlay, list_overlay, load_overlay,
+    merge_overlay_json, save_overlay_entry,
+};
+use crate::domain::errors::AppError;
+use crate::domain::models::GameType;
+use crate::services::scanner::deep_matcher::{CustomSkin, DbEntry, MasterDb};
+use crate::test_utils::init_test_db;
+
+const GIMI: i32 = GameType::GIMI as i32;
+
+fn entry(name: &str, object_type: &str) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: object_type.to_string(),
+        custom_skins: vec![],
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::new(),
+    }
+}
+
+fn skin(name: &str, aliases: &[&str]) -> CustomSkin {
+    CustomSkin {
+        name: name.to_string(),
+        aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+        thumbnail_skin_path: None,
+        rarity: None,
+    }
+}
+
+fn hashes(values: &[&str]) -> HashMap<String, Vec<String>> {
+    HashMap::from([(
+        "Default".to_string(),
+        values.iter().map(|value| value.to_string()).collect(),
+    )])
+}
+
+#[test]
+fn test_merge_overlay_extends_bundled_entries_and_adds_new_ones() {
+    let mut raiden = entry("Raiden Shogun", "Character");
+    raiden.tags = vec!["Electro".to_string()];
+    raiden.custom_skins = vec![skin("Default", &["Ei"])];
+    raiden.hash_db = hashes(&["d94c8962"]);
+    let mut entries = vec![raiden];
+
+    let mut addition = entry("raiden shogun", "Weapon");
+    addition.tags = vec!["electro".to_string(), "Inazuma".to_string()];
+    addition.custom_skins = vec![skin("Default", &["ei", "Shogun"]), skin("Summer", &[])];
+    addition.hash_db = hashes(&["D94C8962", "aa11bb22"]);
+    let mut npc = entry("Katheryne", "NPC");
+    npc.hash_db = hashes(&["0badf00d"]);
+
+    MasterDb::merge_overlay(&mut entries, &[addition, npc]);
+
+    assert_eq!(entries.len(), 2);
+    let raiden = &entries[0];
+    assert_eq!(raiden.name, "Raiden Shogun");
+    // What the bundled entry already has wins.
+    assert_eq!(raiden.object_type, "Character");
+    assert_eq!(raiden.tags, vec!["Electro", "Inazuma"]);
+    assert_eq!(raiden.custom_skins.len(), 2);
+    assert_eq!(raiden.custom_skins[0].aliases, vec!["Ei", "Shogun"]);
+    assert_eq!(raiden.hash_db["Default"], vec!["d94c8962", "aa11bb22"]);
+    assert_eq!(entries[1].name, "Katheryne");
+    assert_eq!(entries[1].object_type, "NPC");
+}
+
+#[test]
+fn test_merge_overlay_json_keeps_fields_entries_do_not_model() {
+    let mut entries = vec![json!({
+        "name": "Ayaka",
+        "object_type": "Character",
+        "tags": ["Cryo"],
+        "element": "Cryo",
+    })];
+    let mut addition = entry("Ayaka", "");
+    addition.tags = vec!["Inazuma".to_string()];
+
+    merge_overlay_json(&mut entries, &[addition, entry("Paimon", "NPC")]);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["element"], "Cryo");
+    assert_eq!(entries[0]["tags"], json!(["Cryo", "Inazuma"]));
+    assert_eq!(entries[1]["name"], "Paimon");
+}
+
+#[tokio::test]
+async fn test_overlay_entries_are_saved_replaced_and_deleted() {
+    let pool = init_test_db().await.pool;
+    let mut npc = entry("Katheryne", "NPC");
+    save_overlay_entry(&pool, GIMI, npc.clone())
+        .await
+        .expect("save");
+    npc.tags = vec!["Adventurers' Guild".to_string()];
+    save_overlay_entry(&pool, GIMI, npc).await.expect("replace");
+
+    let listed = list_overlay(&pool, GIMI).await.expect("list");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].tags, vec!["Adventurers' Guild"]);
+    assert_eq!(load_overlay(&pool, GIMI).await.len(), 1);
+
+    let nameless = save_overlay_entry(&pool, GIMI, entry("  ", "NPC")).await;
+    assert!(matches!(nameless, Err(AppError::Validation(_))));
+
+    delete_overlay_entry(&pool, GIMI, "katheryne")
+        .await
+        .expect("delete");
+    assert!(list_overlay(&pool, GIMI).await.expect("list").is_empty());
+    let missing = delete_overlay_entry(&pool, GIMI, "Katheryne").await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_overlay_export_uses_master_db_layout_and_imports_additively() {
+    let pool = init_test_db().await.pool;
+    let temp = TempDir::new().expect("temp dir");
+    let path = temp.path().join("overlay.json");
+    let mut npc = entry("Katheryne", "NPC");
+    npc.hash_db = hashes(&["0badf00d"]);
+    save_overlay_entry(&pool, GIMI, npc).await.expect("save");
+
+    assert_eq!(export_overlay(&pool, GIMI, &path).await.expect("export"), 1);
+    let exported: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("json");
+    assert_eq!(exported["hash_db"]["Katheryne"], json!(["0badf00d"]));
+    assert_eq!(exported["entries"][0]["hash_db"], json!({}));
+
+    // An upstream-style fragment adds to the stored record.
+    std::fs::write(
+        &path,
+        r#"{"entries": [{"name": "Katheryne", "tags": ["Guild"]}],
+            "hash_db": {"Katheryne": ["feedbeef"]}}"#,
+    )
+    .expect("write");
+    assert_eq!(import_overlay(&pool, GIMI, &path).await.expect("import"), 1);
+
+    let listed = list_overlay(&pool, GIMI).await.expect("list");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].object_type, "NPC");
+    assert_eq!(listed[0].tags, vec!["Guild"]);
+    assert_eq!(listed[0].hash_db["Default"], vec!["0badf00d", "feedbeef"]);
+    // Overlays are per game type.
+    assert!(list_overlay(&pool, GameType::SRMI as i32)
+        .await
+        .expect("list")
+        .is_empty());
+}
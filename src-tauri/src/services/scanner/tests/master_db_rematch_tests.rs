@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use tempfile::TempDir;
+
+use super::{
+    diff_fingerprints, load_baseline, plan_rematch, snapshot_outgoing_baselines, store_baseline,
+    EntryChange, MasterDbFingerprint, RematchCause, REMATCH_SOURCE,
+};
+use crate::domain::models::GameType;
+use crate::repo::object_repo;
+use crate::services::scanner::deep_matcher::analysis::content::IniTokenizationConfig;
+use crate::services::scanner::deep_matcher::{DbEntry, MasterDb};
+use crate::services::scanner::master_db::corrections::MANUAL_MATCH_SOURCE;
+use crate::test_utils::{
+    init_test_db, insert_test_game, insert_test_object, TestGameFixture, TestObjectFixture,
+};
+
+const RAIDEN_INI: &str = "[TextureOverrideRaidenBody]\nhash = d94c8962\n\
+                          [TextureOverrideRaidenDress]\nhash = aa11bb22\n";
+
+fn entry(name: &str, hashes: &[&str]) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins: vec![],
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::from([(
+            "Default".to_string(),
+            hashes.iter().map(|hash| hash.to_string()).collect(),
+        )]),
+    }
+}
+
+fn db_v1() -> MasterDb {
+    MasterDb::new(vec![
+        entry("Raiden Shogun", &["d94c8962", "aa11bb22"]),
+        entry("Ayaka", &["5eed0001"]),
+        entry("Old Character", &["0dd00001"]),
+    ])
+}
+
+/// v1 with "Old Character" dropped and a new hash on "Ayaka".
+fn db_v2() -> MasterDb {
+    MasterDb::new(vec![
+        entry("Raiden Shogun", &["d94c8962", "aa11bb22"]),
+        entry("Ayaka", &["5eed0001", "5eed0002"]),
+    ])
+}
+
+fn write_ini(folder: &Path, ini: &str) {
+    std::fs::create_dir_all(folder).expect("create folder");
+    std::fs::write(folder.join("mod.ini"), ini).expect("write ini");
+}
+
+fn write_bundled_db(resource_dir: &Path, db: &MasterDb) {
+    let databases = resource_dir.join("databases");
+    std::fs::create_dir_all(&databases).expect("create databases");
+    let json = serde_json::to_string(&db.entries).expect("serialize entries");
+    std::fs::write(databases.join("gimi.json"), json).expect("write database");
+}
+
+async fn insert_object(pool: &sqlx::SqlitePool, id: &str, name: &str) {
+    insert_test_object(
+        pool,
+        &TestObjectFixture {
+            id,
+            game_id: "g1",
+            name,
+            folder_path: name,
+            object_type: "Character",
+        },
+    )
+    .await
+    .expect("insert object");
+}
+
+async fn set_match(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+    entry_key: &str,
+    confidence: f64,
+    source: &str,
+) {
+    object_repo::apply_canonical_match(
+        pool,
+        id,
+        Some(entry_key),
+        Some(entry_key),
+        Some(confidence),
+        Some("test"),
+        Some(source),
+    )
+    .await
+    .expect("set match");
+}
+
+#[test]
+fn test_diff_reports_added_removed_and_changed_parts() {
+    let mut renamed = entry("Ayaka", &["5eed0001"]);
+    renamed.tags = vec!["Inazuma".to_string()];
+    let old = MasterDbFingerprint::of(&db_v1().entries);
+    let new = MasterDbFingerprint::of(&[
+        entry("Raiden Shogun", &["AA11BB22", "d94c8962"]),
+        renamed,
+        entry("Ganyu", &["feed0001"]),
+    ]);
+
+    let diff = diff_fingerprints(&old, &new);
+
+    // Hash order and case are not a change.
+    assert_eq!(
+        diff.into_iter().collect::<Vec<_>>(),
+        vec![
+            // Tags double as the entry's own aliases.
+            (
+                "ayaka".to_string(),
+                vec![EntryChange::AliasesChanged, EntryChange::TagsChanged]
+            ),
+            ("ganyu".to_string(), vec![EntryChange::Added]),
+            ("old character".to_string(), vec![EntryChange::Removed]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_plan_rematch_targets_changed_entries_and_weak_matches() {
+    let pool = init_test_db().await.pool;
+    let mods = TempDir::new().expect("temp dir");
+    let mods_path = mods.path().to_string_lossy().into_owned();
+    insert_test_game(
+        &pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Genshin",
+            game_type: GameType::GIMI,
+            path: "/games/genshin",
+            mods_path: Some(&mods_path),
+        },
+    )
+    .await
+    .expect("insert game");
+    write_ini(&mods.path().join("Lightning Mod"), RAIDEN_INI);
+    insert_object(&pool, "unmatched", "Lightning Mod").await;
+    insert_object(&pool, "manual", "My Ayaka").await;
+    set_match(&pool, "manual", "ayaka", 1.0, MANUAL_MATCH_SOURCE).await;
+    insert_object(&pool, "removed", "Old Character Skin").await;
+    set_match(&pool, "removed", "old character", 0.9, "scan").await;
+    insert_object(&pool, "settled", "Raiden Shogun").await;
+    set_match(&pool, "settled", "raiden shogun", 0.95, "scan").await;
+    let ini_filters = IniTokenizationConfig::default().prepare();
+
+    // No baseline yet, so only the unmatched object is tried.
+    let first = plan_rematch(&pool, "g1", Arc::new(db_v1()), ini_filters.clone())
+        .await
+        .expect("first plan");
+    assert!(!first.has_baseline);
+    assert_eq!(first.examined, 1);
+    assert_eq!(first.changes.len(), 1);
+    assert_eq!(first.changes[0].object_id, "unmatched");
+    assert_eq!(first.changes[0].cause, RematchCause::Unmatched);
+    assert_eq!(
+        first.changes[0].matched_entry_key.as_deref(),
+        Some("raiden shogun")
+    );
+    assert_eq!(first.changes[0].matched_source, REMATCH_SOURCE);
+    assert!(load_baseline(&pool, "g1").await.is_none());
+
+    // Metadata sync snapshots v1 before the update replaces it.
+    let resources = TempDir::new().expect("temp dir");
+    write_bundled_db(resources.path(), &db_v1());
+    snapshot_outgoing_baselines(&pool, resources.path())
+        .await
+        .expect("snapshot");
+
+    let second = plan_rematch(&pool, "g1", Arc::new(db_v2()), ini_filters)
+        .await
+        .expect("second plan");
+    assert!(second.has_baseline);
+    assert_eq!(second.changed_entries, 2);
+    // The manual match stays put even though its entry changed.
+    assert_eq!(second.examined, 2);
+    let removed = second
+        .changes
+        .iter()
+        .find(|change| change.object_id == "removed")
+        .expect("removed entry change");
+    assert_eq!(removed.cause, RematchCause::EntryRemoved);
+    assert_eq!(removed.previous_entry_key.as_deref(), Some("old character"));
+    assert!(removed.matched_entry_key.is_none());
+
+    // Planning writes nothing.
+    let states = object_repo::get_match_states_by_game(&pool, "g1")
+        .await
+        .expect("states");
+    let removed_state = states
+        .iter()
+        .find(|state| state.id == "removed")
+        .expect("removed object");
+    assert_eq!(
+        removed_state.matched_entry_key.as_deref(),
+        Some("old character")
+    );
+}
+
+#[tokio::test]
+async fn test_snapshot_keeps_the_baseline_of_a_reviewed_game() {
+    let pool = init_test_db().await.pool;
+    for id in ["g1", "g2"] {
+        insert_test_game(
+            &pool,
+            &TestGameFixture {
+                id,
+                name: id,
+                game_type: GameType::GIMI,
+                path: id,
+                mods_path: Some(id),
+            },
+        )
+        .await
+        .expect("insert game");
+    }
+    store_baseline(&pool, "g1", &db_v2())
+        .await
+        .expect("review g1");
+    let resources = TempDir::new().expect("temp dir");
+    write_bundled_db(resources.path(), &db_v1());
+
+    snapshot_outgoing_baselines(&pool, resources.path())
+        .await
+        .expect("snapshot");
+
+    assert_eq!(
+        load_baseline(&pool, "g1").await,
+        Some(MasterDbFingerprint::of(&db_v2().entries))
+    );
+    assert_eq!(
+        load_baseline(&pool, "g2").await,
+        Some(MasterDbFingerprint::of(&db_v1().entries))
+    );
+}
+
+#[tokio::test]
+async fn test_store_baseline_reports_a_failed_write() {
+    let pool = init_test_db().await.pool;
+    sqlx::query("DROP TABLE app_meta")
+        .execute(&pool)
+        .await
+        .expect("drop app_meta");
+
+    assert!(store_baseline(&pool, "g1", &db_v1()).await.is_err());
+}
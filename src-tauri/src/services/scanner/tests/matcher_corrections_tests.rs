@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use super::{
+    export_corrections, import_corrections, list_corrections, load_user_corrections,
+    manual_match_correction, record_corrections, scan_overrides, MANUAL_OVERRIDE_REASON,
+};
+use crate::domain::models::GameType;
+use crate::services::scanner::core::walker::{FolderContent, ModCandidate};
+use crate::services::scanner::deep_matcher::analysis::ai_rerank::AiRerankConfig;
+use crate::services::scanner::deep_matcher::analysis::content::IniTokenizationConfig;
+use crate::services::scanner::deep_matcher::{
+    match_folder_phased, DbEntry, MasterDb, MatchStatus, Reason, StagedMatchResult,
+};
+use crate::services::scanner::sync::ConfirmedScanItem;
+use crate::test_utils::{init_test_db, insert_test_game, TestGameFixture};
+
+const GAME: &str = "game-1";
+
+async fn seed_game(pool: &sqlx::SqlitePool, id: &str) {
+    let path = format!("E:/Games/{id}");
+    insert_test_game(
+        pool,
+        &TestGameFixture {
+            id,
+            name: id,
+            game_type: GameType::GIMI,
+            path: &path,
+            mods_path: Some(&path),
+        },
+    )
+    .await
+    .expect("insert game");
+}
+
+fn entry(name: &str) -> DbEntry {
+    DbEntry {
+        name: name.to_string(),
+        tags: vec![],
+        object_type: "Character".to_string(),
+        custom_skins: vec![],
+        thumbnail_path: None,
+        metadata: None,
+        hash_db: HashMap::new(),
+    }
+}
+
+fn entries() -> Vec<DbEntry> {
+    vec![entry("Raiden Shogun"), entry("Yae Miko")]
+}
+
+fn match_folder(db: &MasterDb, folder: &str) -> StagedMatchResult {
+    let candidate = ModCandidate {
+        path: format!("mods/{folder}").into(),
+        raw_name: folder.to_string(),
+        display_name: folder.to_string(),
+        is_disabled: false,
+    };
+    let content = FolderContent {
+        subfolder_names: vec![],
+        files: vec![],
+        ini_files: vec![],
+    };
+    match_folder_phased(
+        &candidate,
+        db,
+        &content,
+        &IniTokenizationConfig::default().prepare(),
+        &AiRerankConfig::default(),
+    )
+}
+
+fn scan_item(folder_path: &str, entry_key: &str, reason: &str) -> ConfirmedScanItem {
+    ConfirmedScanItem {
+        folder_path: folder_path.to_string(),
+        display_name: folder_path.to_string(),
+        is_disabled: false,
+        matched_entry_key: Some(entry_key.to_string()),
+        matched_alias_name: None,
+        matched_confidence: Some(1.0),
+        matched_reason: Some(reason.to_string()),
+        object_type: None,
+        thumbnail_path: None,
+        tags_json: None,
+        metadata_json: None,
+        hash_db_json: None,
+        custom_skins_json: None,
+        db_thumbnail: None,
+        skip: false,
+        move_from_temp: false,
+        pack_parts: Vec::new(),
+    }
+}
+
+fn has_correction(result: &StagedMatchResult, exact: bool) -> bool {
+    result.best.as_ref().is_some_and(|best| {
+        best.reasons
+            .iter()
+            .any(|reason| matches!(reason, Reason::UserCorrection { exact: e, .. } if *e == exact))
+    })
+}
+
+/// The whole loop: a review override is stored, loaded back, and decides the
+/// same folder on the next scan with a reason of its own.
+#[tokio::test]
+async fn scan_override_is_learned_and_matches_the_folder_next_time() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool, GAME).await;
+    let folder = "Purple Thunder Lady";
+    assert_eq!(
+        match_folder(&MasterDb::new(entries()), folder).status,
+        MatchStatus::NoMatch
+    );
+
+    let items = vec![
+        scan_item(
+            &format!("/mods/{folder}"),
+            "raiden shogun",
+            MANUAL_OVERRIDE_REASON,
+        ),
+        scan_item("/mods/Yae Fox", "yae miko", "Exact name match"),
+    ];
+    let overrides = scan_overrides(&items);
+    assert_eq!(
+        overrides.len(),
+        1,
+        "only the overridden row is a correction"
+    );
+    assert_eq!(
+        record_corrections(&ctx.pool, GAME, &overrides)
+            .await
+            .unwrap(),
+        1
+    );
+
+    let corrections = load_user_corrections(&ctx.pool, GAME, &entries()).await;
+    let db = MasterDb::new_with_corrections(entries(), &corrections);
+    let result = match_folder(&db, &format!("DISABLED {folder}"));
+
+    assert_eq!(result.status, MatchStatus::AutoMatched);
+    assert_eq!(result.best.as_ref().unwrap().name, "Raiden Shogun");
+    assert!(has_correction(&result, true));
+}
+
+#[tokio::test]
+async fn a_folder_carrying_every_corrected_word_matches_as_a_hint() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool, GAME).await;
+    record_corrections(
+        &ctx.pool,
+        GAME,
+        &[(
+            "Purple Thunder Lady".to_string(),
+            "raiden shogun".to_string(),
+        )],
+    )
+    .await
+    .unwrap();
+
+    let corrections = load_user_corrections(&ctx.pool, GAME, &entries()).await;
+    let db = MasterDb::new_with_corrections(entries(), &corrections);
+    let result = match_folder(&db, "Purple Thunder Lady Remake");
+
+    assert_eq!(result.best.as_ref().unwrap().name, "Raiden Shogun");
+    assert!(has_correction(&result, false));
+}
+
+#[tokio::test]
+async fn a_correction_only_steers_the_game_it_was_learned_in() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool, GAME).await;
+    seed_game(&ctx.pool, "game-2").await;
+    record_corrections(
+        &ctx.pool,
+        GAME,
+        &[(
+            "Purple Thunder Lady".to_string(),
+            "raiden shogun".to_string(),
+        )],
+    )
+    .await
+    .unwrap();
+
+    assert!(load_user_corrections(&ctx.pool, "game-2", &entries())
+        .await
+        .is_empty());
+    assert!(list_corrections(&ctx.pool, "game-2")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn a_later_correction_repoints_the_pattern_and_repeats_count_up() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool, GAME).await;
+    let folder = "Purple Thunder Lady".to_string();
+    for entry_key in ["yae miko", "raiden shogun", "raiden shogun"] {
+        record_corrections(&ctx.pool, GAME, &[(folder.clone(), entry_key.to_string())])
+            .await
+            .unwrap();
+    }
+
+    let stored = list_corrections(&ctx.pool, GAME).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].folder_pattern, "purple thunder lady");
+    assert_eq!(stored[0].entry_key, "raiden shogun");
+    assert_eq!(stored[0].hits, 2);
+}
+
+#[tokio::test]
+async fn corrections_round_trip_through_an_export_file() {
+    let source = init_test_db().await;
+    seed_game(&source.pool, GAME).await;
+    record_corrections(
+        &source.pool,
+        GAME,
+        &[
+            (
+                "Purple Thunder Lady".to_string(),
+                "raiden shogun".to_string(),
+            ),
+            ("Pink Fox Priestess".to_string(), "yae miko".to_string()),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("corrections.json");
+    assert_eq!(
+        export_corrections(&source.pool, GAME, &path).await.unwrap(),
+        2
+    );
+
+    let target = init_test_db().await;
+    seed_game(&target.pool, GAME).await;
+    assert_eq!(
+        import_corrections(&target.pool, GAME, &path).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        list_corrections(&target.pool, GAME).await.unwrap(),
+        list_corrections(&source.pool, GAME).await.unwrap()
+    );
+}
+
+#[test]
+fn reapplying_the_current_entry_teaches_nothing() {
+    assert_eq!(
+        manual_match_correction(None, "Raiden", Some("raiden shogun"), "raiden shogun"),
+        None
+    );
+    assert_eq!(
+        manual_match_correction(
+            Some("Raiden/Purple Thunder Lady"),
+            "Raiden",
+            Some("yae miko"),
+            "raiden shogun"
+        ),
+        Some((
+            "Purple Thunder Lady".to_string(),
+            "raiden shogun".to_string()
+        ))
+    );
+}
@@ -0,0 +1,190 @@
+use super::sync_tests::{scan_item, test_pool};
+use super::*;
+use crate::domain::errors::ScannerError;
+use crate::services::scanner::sync::helpers::canonical_entry_key;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_commit_splits_pack_parts_into_their_object_folders() {
+    let pool = test_pool().await;
+    let temp_dir = TempDir::new().unwrap();
+    let mods_root = temp_dir.path().join("Mods");
+    let pack_dir = mods_root.join(".emmm_temp").join("Summer Pack");
+    for part in ["Amber", "Lisa", "Readme Extras"] {
+        fs::create_dir_all(pack_dir.join(part)).unwrap();
+        fs::write(pack_dir.join(part).join("mod.ini"), "[Constants]\n").unwrap();
+    }
+    let mods_path = mods_root.to_string_lossy().to_string();
+    let part = |name: &str| ConfirmedScanItem {
+        matched_entry_key: Some(canonical_entry_key(name)),
+        matched_alias_name: Some(name.to_string()),
+        ..scan_item(pack_dir.join(name).to_string_lossy().to_string(), name)
+    };
+
+    let result = commit_scan_results(CommitScanRequest {
+        pool: &pool,
+        game_id: "g1",
+        game_name: "Game",
+        game_type: "gimi",
+        mods_path: &mods_path,
+        items: vec![ConfirmedScanItem {
+            move_from_temp: true,
+            pack_parts: vec![part("Amber"), part("Lisa")],
+            ..scan_item(pack_dir.to_string_lossy().to_string(), "Summer Pack")
+        }],
+        resource_dir: None,
+        safe_mode_keywords: &[],
+        preserve_existing_mappings: false,
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        result.total_scanned, 2,
+        "the pack folder itself is not a mod"
+    );
+    assert_eq!(result.new_objects, 2);
+    assert!(mods_root
+        .join("Amber")
+        .join("Amber")
+        .join("mod.ini")
+        .exists());
+    assert!(mods_root.join("Lisa").join("Lisa").join("mod.ini").exists());
+    // The unmatched subfolder stays behind in the pack.
+    assert!(pack_dir.join("Readme Extras").exists());
+    assert!(!pack_dir.join("Amber").exists());
+
+    let mods_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mods WHERE game_id = 'g1'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(mods_count, 2);
+}
+
+#[tokio::test]
+async fn test_commit_rejects_pack_part_outside_the_pack_before_moving_anything() {
+    let pool = test_pool().await;
+    let temp_dir = TempDir::new().unwrap();
+    let mods_root = temp_dir.path().join("Mods");
+    let pack_dir = mods_root.join(".emmm_temp").join("Summer Pack");
+    let inside = pack_dir.join("Amber");
+    let outside = temp_dir.path().join("Elsewhere").join("Lisa");
+    fs::create_dir_all(&inside).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    let mods_path = mods_root.to_string_lossy().to_string();
+
+    let result = commit_scan_results(CommitScanRequest {
+        pool: &pool,
+        game_id: "g1",
+        game_name: "Game",
+        game_type: "gimi",
+        mods_path: &mods_path,
+        items: vec![ConfirmedScanItem {
+            pack_parts: vec![
+                scan_item(inside.to_string_lossy().to_string(), "Amber"),
+                scan_item(outside.to_string_lossy().to_string(), "Lisa"),
+            ],
+            ..scan_item(pack_dir.to_string_lossy().to_string(), "Summer Pack")
+        }],
+        resource_dir: None,
+        safe_mode_keywords: &[],
+        preserve_existing_mappings: false,
+    })
+    .await;
+
+    assert!(matches!(result, Err(ScannerError::PathEscape { .. })));
+    assert!(inside.exists());
+    assert!(outside.exists());
+}
+
+/// Commits `pack_dir` split into `parts`, each matched to the object of the
+/// same name.
+async fn commit_pack_parts(
+    pool: &sqlx::SqlitePool,
+    mods_root: &Path,
+    pack_dir: &Path,
+    parts: &[&str],
+) -> Result<crate::services::scanner::sync::SyncResult, ScannerError> {
+    let part = |name: &str| ConfirmedScanItem {
+        matched_entry_key: Some(canonical_entry_key(name)),
+        matched_alias_name: Some(name.to_string()),
+        ..scan_item(pack_dir.join(name).to_string_lossy().to_string(), name)
+    };
+    commit_scan_results(CommitScanRequest {
+        pool,
+        game_id: "g1",
+        game_name: "Game",
+        game_type: "gimi",
+        mods_path: &mods_root.to_string_lossy(),
+        items: vec![ConfirmedScanItem {
+            move_from_temp: true,
+            pack_parts: parts.iter().map(|name| part(name)).collect(),
+            ..scan_item(pack_dir.to_string_lossy().to_string(), "Summer Pack")
+        }],
+        resource_dir: None,
+        safe_mode_keywords: &[],
+        preserve_existing_mappings: false,
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_commit_removes_pack_folder_left_empty() {
+    let pool = test_pool().await;
+    let temp_dir = TempDir::new().unwrap();
+    let mods_root = temp_dir.path().join("Mods");
+    let pack_dir = mods_root.join("Summer Pack");
+    for part in ["Amber", "Lisa"] {
+        fs::create_dir_all(pack_dir.join(part)).unwrap();
+    }
+
+    commit_pack_parts(&pool, &mods_root, &pack_dir, &["Amber", "Lisa"])
+        .await
+        .unwrap();
+
+    assert!(mods_root.join("Lisa").join("Lisa").exists());
+    assert!(!pack_dir.exists());
+}
+
+#[tokio::test]
+async fn test_commit_moves_pack_parts_back_when_a_later_move_fails() {
+    let pool = test_pool().await;
+    let temp_dir = TempDir::new().unwrap();
+    let mods_root = temp_dir.path().join("Mods");
+    let pack_dir = mods_root.join("Summer Pack");
+    for part in ["Amber", "Lisa"] {
+        fs::create_dir_all(pack_dir.join(part)).unwrap();
+    }
+    // Lisa already has an object whose folder is a file, so her move fails
+    // after Amber's went through.
+    crate::test_utils::insert_test_game(
+        &pool,
+        &crate::test_utils::TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: crate::domain::models::GameType::GIMI,
+            path: "/",
+            mods_path: Some("/Mods"),
+        },
+    )
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO objects (id, game_id, name, folder_path, object_type, matched_entry_key)
+         VALUES ('lisa_id', 'g1', 'Lisa', 'Lisa', 'Character', ?)",
+    )
+    .bind(canonical_entry_key("Lisa"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    fs::write(mods_root.join("Lisa"), "not a folder").unwrap();
+
+    let result = commit_pack_parts(&pool, &mods_root, &pack_dir, &["Amber", "Lisa"]).await;
+
+    assert!(result.is_err());
+    assert!(pack_dir.join("Amber").exists());
+    assert!(pack_dir.join("Lisa").exists());
+    assert!(!mods_root.join("Amber").join("Amber").exists());
+}
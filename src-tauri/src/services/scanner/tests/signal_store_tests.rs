@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use super::{invalidate_signal_cache, load_persisted_signals, store_signals, ComputedSignals};
+use crate::domain::models::GameType;
+use crate::repo::signal_cache_repo;
+use crate::services::scanner::core::walker::scan_folder_content;
+use crate::services::scanner::deep_matcher::analysis::content::{
+    IniTokenizationConfig, PreparedTokenFilters,
+};
+use crate::services::scanner::deep_matcher::state::signal_cache::{
+    folder_fingerprint, SignalCache,
+};
+use crate::services::scanner::deep_matcher::MatchMode;
+use crate::test_utils::{init_test_db, insert_test_game, TestGameFixture};
+
+async fn seed_game(pool: &sqlx::SqlitePool) {
+    insert_test_game(
+        pool,
+        &TestGameFixture {
+            id: "g1",
+            name: "Game",
+            game_type: GameType::GIMI,
+            path: "/game",
+            mods_path: Some("/mods"),
+        },
+    )
+    .await
+    .unwrap();
+}
+
+fn write_mod(mods: &Path, name: &str, ini: &str) -> std::path::PathBuf {
+    let folder = mods.join(name);
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(folder.join("mod.ini"), ini).unwrap();
+    folder
+}
+
+/// Computes Quick signals for `folder` the way the scan preview does, and
+/// returns the fingerprint with what the cache computed.
+fn compute(folder: &Path, filters: &PreparedTokenFilters) -> (String, Vec<ComputedSignals>) {
+    let content = scan_folder_content(folder, 3);
+    let fingerprint = folder_fingerprint(folder, &content, filters);
+    let mut cache = SignalCache::new();
+    cache.get_or_compute(folder, &content, MatchMode::Quick, filters);
+    let computed = ComputedSignals::drain(&mut cache, &folder.to_string_lossy(), &fingerprint);
+    (fingerprint, computed)
+}
+
+#[tokio::test]
+async fn unchanged_folder_reuses_persisted_signals_without_recomputing() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool).await;
+    let mods = tempfile::TempDir::new().unwrap();
+    let folder = write_mod(
+        mods.path(),
+        "Raiden",
+        "[TextureOverrideRaidenBody]\nhash = deadbeef\n",
+    );
+    let filters = IniTokenizationConfig::default().prepare();
+
+    let (_, computed) = compute(&folder, &filters);
+    assert_eq!(computed.len(), 1);
+    store_signals(&ctx.pool, "g1", &computed).await;
+
+    let persisted = load_persisted_signals(&ctx.pool, "g1").await;
+    assert_eq!(persisted.len(), 1);
+
+    let content = scan_folder_content(&folder, 3);
+    let fingerprint = folder_fingerprint(&folder, &content, &filters);
+    let folder_path = folder.to_string_lossy().to_string();
+    let mut cache = SignalCache::new();
+    assert_eq!(persisted.seed(&mut cache, &folder_path, &fingerprint), 1);
+
+    let signals = cache
+        .get_or_compute(&folder, &content, MatchMode::Quick, &filters)
+        .clone();
+    assert_eq!(signals, computed[0].signals);
+    assert!(
+        cache.take_computed().is_empty(),
+        "seeded signals must not be recomputed"
+    );
+}
+
+#[tokio::test]
+async fn edited_ini_moves_the_fingerprint_and_misses_the_cache() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool).await;
+    let mods = tempfile::TempDir::new().unwrap();
+    let folder = write_mod(
+        mods.path(),
+        "Raiden",
+        "[TextureOverrideRaidenBody]\nhash = deadbeef\n",
+    );
+    let filters = IniTokenizationConfig::default().prepare();
+
+    let (before, computed) = compute(&folder, &filters);
+    store_signals(&ctx.pool, "g1", &computed).await;
+
+    std::fs::write(
+        folder.join("mod.ini"),
+        "[TextureOverrideRaidenBody]\nhash = deadbeef\n\n[TextureOverrideRaidenHead]\nhash = d94c8962\n",
+    )
+    .unwrap();
+    let content = scan_folder_content(&folder, 3);
+    let after = folder_fingerprint(&folder, &content, &filters);
+    assert_ne!(before, after);
+
+    let persisted = load_persisted_signals(&ctx.pool, "g1").await;
+    let mut cache = SignalCache::new();
+    assert_eq!(
+        persisted.seed(&mut cache, &folder.to_string_lossy(), &after),
+        0
+    );
+}
+
+#[tokio::test]
+async fn reconcile_drops_touched_roots_and_prunes_vanished_folders() {
+    let ctx = init_test_db().await;
+    seed_game(&ctx.pool).await;
+    let mods = tempfile::TempDir::new().unwrap();
+    let filters = IniTokenizationConfig::default().prepare();
+    let mut computed = Vec::new();
+    for name in ["Alice", "Bob", "Carol"] {
+        let folder = write_mod(
+            mods.path(),
+            name,
+            "[TextureOverrideBody]\nhash = 0badf00d\n",
+        );
+        computed.extend(compute(&folder, &filters).1);
+    }
+    store_signals(&ctx.pool, "g1", &computed).await;
+
+    let dropped = invalidate_signal_cache(&ctx.pool, "g1", mods.path(), Some(&["bob".to_string()]))
+        .await
+        .unwrap();
+    assert_eq!(dropped, 1);
+
+    std::fs::remove_dir_all(mods.path().join("Carol")).unwrap();
+    let pruned = invalidate_signal_cache(&ctx.pool, "g1", mods.path(), None)
+        .await
+        .unwrap();
+    assert_eq!(pruned, 1);
+
+    let remaining = signal_cache_repo::list_folder_paths(&ctx.pool, "g1")
+        .await
+        .unwrap();
+    assert_eq!(
+        remaining,
+        vec![mods.path().join("Alice").to_string_lossy().to_string()]
+    );
+}
@@ -11,7 +11,7 @@ use sqlx::SqlitePool;
 use std::fs;
 use tempfile::TempDir;
 
-async fn test_pool() -> SqlitePool {
+pub(super) async fn test_pool() -> SqlitePool {
     crate::test_utils::init_test_db().await.pool
 }
 
@@ -59,7 +59,7 @@ fn needs_review_db() -> deep_matcher::MasterDb {
 ///
 /// Tests that do care override them with struct-update syntax, so each one
 /// shows only what it is actually about instead of sixteen fields of noise.
-fn scan_item(folder_path: impl Into<String>, display_name: &str) -> ConfirmedScanItem {
+pub(super) fn scan_item(folder_path: impl Into<String>, display_name: &str) -> ConfirmedScanItem {
     ConfirmedScanItem {
         folder_path: folder_path.into(),
         display_name: display_name.to_string(),
@@ -77,6 +77,7 @@ fn scan_item(folder_path: impl Into<String>, display_name: &str) -> ConfirmedSca
         db_thumbnail: None,
         skip: false,
         move_from_temp: false,
+        pack_parts: Vec::new(),
     }
 }
 
@@ -117,6 +118,7 @@ async fn test_scan_preview_needs_review_has_no_auto_assignment() {
         resource_dir: None,
         on_progress: None,
         specific_paths: None,
+        ai: Default::default(),
     })
     .await
     .unwrap();
@@ -162,6 +164,7 @@ async fn test_parallel_scan_preview_preserves_requested_order() {
         resource_dir: None,
         on_progress: None,
         specific_paths: Some(requested_paths),
+        ai: Default::default(),
     })
     .await
     .unwrap();
@@ -1,5 +1,6 @@
 use super::*;
 use crate::domain::task::{RecoveryAction, TaskStatus};
+use crate::services::fs_utils::operation_lock::OperationLock;
 use crate::services::scanner::watcher::WatcherState;
 use crate::test_utils::{init_test_db, insert_test_game, TestGameFixture};
 
@@ -26,12 +27,26 @@ async fn setup() -> (sqlx::SqlitePool, ConfigService, WatcherState) {
 #[tokio::test]
 async fn pending_startup_tasks_lists_only_open_work() {
     let (pool, _config, _watcher) = setup().await;
-    crate::repo::task_repo::create_task(&pool, "t-open", "g1", "apply_collection", Some("c1"))
-        .await
-        .expect("create open task");
-    crate::repo::task_repo::create_task(&pool, "t-done", "g1", "apply_collection", Some("c1"))
-        .await
-        .expect("create done task");
+    crate::repo::task_repo::create_task(
+        &pool,
+        "t-open",
+        "g1",
+        "apply_collection",
+        Some("c1"),
+        None,
+    )
+    .await
+    .expect("create open task");
+    crate::repo::task_repo::create_task(
+        &pool,
+        "t-done",
+        "g1",
+        "apply_collection",
+        Some("c1"),
+        None,
+    )
+    .await
+    .expect("create done task");
     crate::repo::task_repo::update_status(&pool, "t-done", TaskStatus::Completed)
         .await
         .expect("complete task");
@@ -47,10 +62,19 @@ async fn pending_startup_tasks_lists_only_open_work() {
 #[tokio::test]
 async fn missing_task_is_rejected() {
     let (pool, config, watcher) = setup().await;
-
-    let error = resolve_recovery_task(&pool, &config, &watcher, "nope", RecoveryAction::Ignore)
-        .await
-        .expect_err("unknown task id must fail");
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+
+    let error = resolve_recovery_task(
+        &op_guard,
+        &pool,
+        &config,
+        &watcher,
+        "nope",
+        RecoveryAction::Ignore,
+    )
+    .await
+    .expect_err("unknown task id must fail");
 
     assert!(format!("{error:?}").contains("nope"));
 }
@@ -58,13 +82,22 @@ async fn missing_task_is_rejected() {
 #[tokio::test]
 async fn ignore_marks_the_task_failed_without_touching_the_filesystem() {
     let (pool, config, watcher) = setup().await;
-    crate::repo::task_repo::create_task(&pool, "t1", "g1", "apply_collection", Some("c1"))
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    crate::repo::task_repo::create_task(&pool, "t1", "g1", "apply_collection", Some("c1"), None)
         .await
         .expect("create task");
 
-    resolve_recovery_task(&pool, &config, &watcher, "t1", RecoveryAction::Ignore)
-        .await
-        .expect("ignore should succeed");
+    resolve_recovery_task(
+        &op_guard,
+        &pool,
+        &config,
+        &watcher,
+        "t1",
+        RecoveryAction::Ignore,
+    )
+    .await
+    .expect("ignore should succeed");
 
     let task = crate::repo::task_repo::get_task_by_id(&pool, "t1")
         .await
@@ -81,13 +114,22 @@ async fn ignore_marks_the_task_failed_without_touching_the_filesystem() {
 #[tokio::test]
 async fn retry_rejects_an_unsupported_task_type() {
     let (pool, config, watcher) = setup().await;
-    crate::repo::task_repo::create_task(&pool, "t1", "g1", "something_else", None)
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    crate::repo::task_repo::create_task(&pool, "t1", "g1", "something_else", None, None)
         .await
         .expect("create task");
 
-    let error = resolve_recovery_task(&pool, &config, &watcher, "t1", RecoveryAction::Retry)
-        .await
-        .expect_err("unsupported task type must fail");
+    let error = resolve_recovery_task(
+        &op_guard,
+        &pool,
+        &config,
+        &watcher,
+        "t1",
+        RecoveryAction::Retry,
+    )
+    .await
+    .expect_err("unsupported task type must fail");
 
     assert!(format!("{error:?}").contains("something_else"));
 
@@ -102,13 +144,22 @@ async fn retry_rejects_an_unsupported_task_type() {
 #[tokio::test]
 async fn retry_requires_a_target_collection() {
     let (pool, config, watcher) = setup().await;
-    crate::repo::task_repo::create_task(&pool, "t1", "g1", "apply_collection", None)
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    crate::repo::task_repo::create_task(&pool, "t1", "g1", "apply_collection", None, None)
         .await
         .expect("create task");
 
-    let error = resolve_recovery_task(&pool, &config, &watcher, "t1", RecoveryAction::Retry)
-        .await
-        .expect_err("apply_collection without a target must fail");
+    let error = resolve_recovery_task(
+        &op_guard,
+        &pool,
+        &config,
+        &watcher,
+        "t1",
+        RecoveryAction::Retry,
+    )
+    .await
+    .expect_err("apply_collection without a target must fail");
 
     assert!(format!("{error:?}").contains("target collection"));
 }
@@ -116,13 +167,22 @@ async fn retry_requires_a_target_collection() {
 #[tokio::test]
 async fn legacy_switch_corridor_tasks_are_retired_not_replayed() {
     let (pool, config, watcher) = setup().await;
-    crate::repo::task_repo::create_task(&pool, "t1", "g1", "switch_corridor", None)
+    let op_lock = OperationLock::new();
+    let op_guard = op_lock.acquire().await.expect("op lock");
+    crate::repo::task_repo::create_task(&pool, "t1", "g1", "switch_corridor", None, None)
         .await
         .expect("create task");
 
-    resolve_recovery_task(&pool, &config, &watcher, "t1", RecoveryAction::Retry)
-        .await
-        .expect("legacy task should be retired quietly");
+    resolve_recovery_task(
+        &op_guard,
+        &pool,
+        &config,
+        &watcher,
+        "t1",
+        RecoveryAction::Retry,
+    )
+    .await
+    .expect("legacy task should be retired quietly");
 
     let task = crate::repo::task_repo::get_task_by_id(&pool, "t1")
         .await
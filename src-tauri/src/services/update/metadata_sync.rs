@@ -2,6 +2,7 @@ use log::{info, warn};
 use reqwest::Client;
 use serde::Deserialize;
 use sqlx::SqlitePool;
+use std::path::Path;
 use std::time::Duration;
 
 /// Remote manifest structure
@@ -29,9 +30,13 @@ const RETRY_BACKOFF: Duration = Duration::from_millis(500);
 /// Check remote manifest and sync metadata if a newer version is available.
 ///
 /// This function is designed to be called at startup and fail silently on any
-/// network error so it never blocks the app from launching.
-pub async fn check_and_sync_metadata(pool: &SqlitePool) -> MetadataSyncResult {
-    match try_sync(pool).await {
+/// network error so it never blocks the app from launching. `resource_dir`
+/// holds the bundled MasterDB, snapshotted for re-matching before an update.
+pub async fn check_and_sync_metadata(
+    pool: &SqlitePool,
+    resource_dir: Option<&Path>,
+) -> MetadataSyncResult {
+    match try_sync(pool, resource_dir).await {
         Ok(result) => result,
         Err(e) => {
             warn!("Metadata sync skipped: {e}");
@@ -43,7 +48,10 @@ pub async fn check_and_sync_metadata(pool: &SqlitePool) -> MetadataSyncResult {
     }
 }
 
-async fn try_sync(pool: &SqlitePool) -> Result<MetadataSyncResult, anyhow::Error> {
+async fn try_sync(
+    pool: &SqlitePool,
+    resource_dir: Option<&Path>,
+) -> Result<MetadataSyncResult, anyhow::Error> {
     let client = super::http_client(super::MANIFEST_TIMEOUT)?;
 
     // Read cached ETag / Last-Modified from DB
@@ -114,6 +122,20 @@ async fn try_sync(pool: &SqlitePool) -> Result<MetadataSyncResult, anyhow::Error
         local_version, manifest.db_version
     );
 
+    // Fingerprint the database about to be replaced, so the first re-match
+    // after the update diffs against what objects were matched with.
+    if let Some(resource_dir) = resource_dir {
+        if let Err(error) =
+            crate::services::scanner::master_db::rematch::snapshot_outgoing_baselines(
+                pool,
+                resource_dir,
+            )
+            .await
+        {
+            warn!("Metadata: re-match baseline snapshot failed: {error}");
+        }
+    }
+
     // Download the character DB payload if URL is provided
     if let Some(db_url) = &manifest.db_url {
         let db_response = request_with_retry(&client, db_url, "", "").await?;
@@ -39,6 +39,7 @@ import type {
   CollectionPreview,
   ApplyPreview,
   ApplyResult,
+  ApplyScope,
   ApplyProgressSnapshot,
 } from '../../../types/collection';
 import type { CollectionSaveMode } from '../types';
@@ -84,11 +85,15 @@ export function useCollectionPreview(collectionId: string | null, gameId: string
   });
 }
 
-/** Get before/after preview for applying a collection. */
-export function useApplyCollectionPreview(gameId: string | null, collectionId: string | null) {
+/** Get before/after preview for applying a collection, optionally scoped to some objects. */
+export function useApplyCollectionPreview(
+  gameId: string | null,
+  collectionId: string | null,
+  scope: ApplyScope | null = null,
+) {
   return useQuery<ApplyPreview>({
-    queryKey: [...collectionKeys.previewApply(collectionId ?? ''), gameId ?? ''],
-    queryFn: () => commands.previewApplyCollection(gameId ?? '', collectionId ?? ''),
+    queryKey: [...collectionKeys.previewApply(collectionId ?? ''), gameId ?? '', scope],
+    queryFn: () => commands.previewApplyCollection(gameId ?? '', collectionId ?? '', scope),
     enabled: !!gameId && !!collectionId,
     // Don't cache this long, we want fresh disk state when viewing the modal
     staleTime: 0,
@@ -221,11 +226,13 @@ export function useApplyCollection() {
       gameId,
       collectionId,
       ignoreMissing,
+      scope,
     }: {
       gameId: string;
       collectionId: string;
       ignoreMissing?: boolean;
-    }) => commands.applyCollection(gameId, collectionId, ignoreMissing ?? false),
+      scope?: ApplyScope;
+    }) => commands.applyCollection(gameId, collectionId, ignoreMissing ?? false, scope ?? null),
 
     onSuccess: async (result: ApplyResult) => {
       await publishCollectionMutation(
@@ -1,5 +1,5 @@
 import { useDialogSync } from '../../hooks/useDialogSync';
-import { AlertTriangle, Folder, X } from 'lucide-react';
+import { AlertTriangle, FileCode, Folder, X } from 'lucide-react';
 import { useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import type { ConflictInfo } from '../../types/scanner';
@@ -80,6 +80,23 @@ export default function ConflictModal({ open, onClose, conflicts }: ConflictModa
                         </div>
                       );
                     })}
+                    {conflict.evidence
+                      .filter((item) => item.owner === 'game_shader_fixes')
+                      .map((item) => (
+                        <div
+                          key={item.source_path}
+                          className="text-sm truncate text-base-content/70 cursor-default"
+                          title={item.source_path}
+                        >
+                          <span className="inline-flex items-center gap-1">
+                            <FileCode size={14} className="shrink-0" />
+                            <span className="truncate">{item.section_name}</span>
+                            <span className="badge badge-xs badge-ghost">
+                              {t('scanner:conflict_modal.game_shader_fixes')}
+                            </span>
+                          </span>
+                        </div>
+                      ))}
                   </div>
                 </div>
               ))}
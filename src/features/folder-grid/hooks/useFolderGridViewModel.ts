@@ -26,9 +26,6 @@ export function useFolderGridViewModel({
   const conflictPathSet = useMemo(() => {
     const paths = new Set<string>();
     for (const conflict of conflicts) {
-      if (conflict.mod_paths.length <= 1) {
-        continue;
-      }
       for (const path of conflict.mod_paths) {
         paths.add(normalizeWorkspacePath(path));
       }
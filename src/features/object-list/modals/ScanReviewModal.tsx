@@ -106,6 +106,8 @@ export default function ScanReviewModal({
         dbThumbnail: item.dbThumbnail,
         skip: isSkipped,
         moveFromTemp: item.moveFromTemp,
+        // A manual override files the whole folder under one object.
+        packParts: ov ? [] : item.packParts,
       };
     });
     onConfirm(confirmed);
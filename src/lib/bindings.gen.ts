@@ -45,6 +45,19 @@ async getActiveKeybindings(gameId: string) : Promise<Result<ActiveKeyBinding[],
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Keys that fire in more than one enabled mod, or in a mod and one of the
+ * app's global hotkeys. Reads every enabled mod's INI files, like
+ * `get_active_keybindings`.
+ */
+async getKeybindCollisions(gameId: string) : Promise<Result<KeybindCollision[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_keybind_collisions", { gameId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getWorkspaceViewModel(input: WorkspaceViewModelInput) : Promise<Result<WorkspaceViewModel, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_workspace_view_model", { input }) };
@@ -213,6 +226,7 @@ async getObject(id: string) : Promise<Result<GameObject | null, AppError>> {
  * Loads from `resources/databases/{game_type}.json`.
  * Returns array JSON for frontend compatibility (even if file uses new object format).
  * When hash_db is present in source, merges hashes into matching entries.
+ * The user's MasterDB overlay is merged in too.
  */
 async getMasterDb(gameType: number) : Promise<Result<string, AppError>> {
     try {
@@ -259,6 +273,91 @@ async pinObject(id: string, pin: boolean) : Promise<Result<null, AppError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Re-match a game's objects against the current MasterDB.
+ * 
+ * Only objects whose entry changed since the last reviewed run, plus
+ * unmatched and low-confidence ones, are tried. Nothing is written; accepted
+ * changes go through `apply_object_match_cmd`.
+ */
+async planMasterDbRematchCmd(gameId: string, gameType: number) : Promise<Result<RematchPlan, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plan_master_db_rematch_cmd", { gameId, gameType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mark the current MasterDB as reviewed, so the next re-match only diffs
+ * against later updates.
+ */
+async markMasterDbRematchReviewedCmd(gameId: string, gameType: number) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_master_db_rematch_reviewed_cmd", { gameId, gameType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The user's MasterDB overlay for a game type.
+ */
+async listMasterDbOverlayCmd(gameType: number) : Promise<Result<DbEntry[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_master_db_overlay_cmd", { gameType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Adds or replaces one overlay entry. An entry named like a bundled one adds
+ * its tags, skins, aliases, and hashes to it; any other is a new entry.
+ */
+async saveMasterDbOverlayEntryCmd(gameType: number, entry: DbEntry) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_master_db_overlay_entry_cmd", { gameType, entry }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Drops one overlay entry.
+ */
+async deleteMasterDbOverlayEntryCmd(gameType: number, entryName: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_master_db_overlay_entry_cmd", { gameType, entryName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes the overlay to `path` in the MasterDB file layout. Returns how many
+ * entries were written.
+ */
+async exportMasterDbOverlayCmd(gameType: number, path: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_master_db_overlay_cmd", { gameType, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Merges a MasterDB-layout file into the overlay. Returns how many entries it
+ * carried.
+ */
+async importMasterDbOverlayCmd(gameType: number, path: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_master_db_overlay_cmd", { gameType, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async cancelScanCmd() : Promise<Result<null, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("cancel_scan_cmd") };
@@ -359,6 +458,17 @@ async detectConflictsInFolderCmd(modsPath: string) : Promise<Result<ConflictInfo
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Mods whose INIs carry a resource hash, from the persisted hash index.
+ */
+async findModsByHash(gameId: string, hash: string) : Promise<Result<HashIndexHit[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("find_mods_by_hash", { gameId, hash }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Manually set watcher suppression state (e.g. for bulk operations).
  * 
@@ -449,6 +559,30 @@ async listIgnoredObjectConflicts(gameId: string) : Promise<Result<IgnoredConflic
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Settles the hash conflict on `hash` in favour of `winner_path`, disabling
+ * the other mods or demoting their override sections, and stores the choice.
+ */
+async resolveHashConflict(gameId: string, hash: string, kind: ConflictKind, winnerPath: string, strategy: ConflictResolutionStrategy) : Promise<Result<ConflictResolution, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_hash_conflict", { gameId, hash, kind, winnerPath, strategy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forgets the stored winner for `hash`. Disabled mods and demoted sections
+ * are left as they are.
+ */
+async clearHashConflictResolution(gameId: string, hash: string, kind: ConflictKind) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_hash_conflict_resolution", { gameId, hash, kind }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async renameModFolder(folderPath: string, newName: string, gameId: string) : Promise<Result<RenameResult, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("rename_mod_folder", { folderPath, newName, gameId }) };
@@ -680,6 +814,69 @@ async writeModIni(gameId: string, folderPath: string, fileName: string, expected
     else return { status: "error", error: e  as any };
 }
 },
+async editModIni(input: EditModIniInput) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("edit_mod_ini", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async lintModIni(gameId: string, folderPath: string) : Promise<Result<IniDiagnostic[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("lint_mod_ini", { gameId, folderPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Moves the mod's keybinds that collide with other enabled mods or app
+ * hotkeys to free keys from the KeyViewer remap pool.
+ */
+async remapModKeybinds(gameId: string, folderPath: string) : Promise<Result<KeybindRemapReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remap_mod_keybinds", { gameId, folderPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Restores the bindings a previous `remap_mod_keybinds` changed.
+ */
+async revertModKeybindRemap(gameId: string, folderPath: string) : Promise<Result<KeybindRemapReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revert_mod_keybind_remap", { gameId, folderPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Learns resource hashes for confidently matched objects from the mods under
+ * them, for the KeyViewer to use where the bundled `hash_db` falls short.
+ * Opt-in: nothing is learned until this runs, and each run replaces the last.
+ */
+async learnObjectHashesCmd(gameId: string) : Promise<Result<LearnedHashSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("learn_object_hashes_cmd", { gameId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forgets every hash learned for a game. Returns how many there were.
+ */
+async resetLearnedHashesCmd(gameId: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_learned_hashes_cmd", { gameId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async listModPreviewImages(gameId: string, folderPath: string) : Promise<Result<string[], AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("list_mod_preview_images", { gameId, folderPath }) };
@@ -872,17 +1069,17 @@ async listCollections(gameId: string) : Promise<Result<CollectionSummary[], AppE
     else return { status: "error", error: e  as any };
 }
 },
-async createCollection(gameId: string, name: string, saveMode: CreateCollectionMode | null, sourceCollectionId: string | null) : Promise<Result<CollectionSummary, AppError>> {
+async createCollection(gameId: string, name: string, saveMode: CreateCollectionMode | null, sourceCollectionId: string | null, modIds: string[] | null) : Promise<Result<CollectionSummary, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_collection", { gameId, name, saveMode, sourceCollectionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_collection", { gameId, name, saveMode, sourceCollectionId, modIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async applyCollection(gameId: string, collectionId: string, ignoreMissing: boolean | null) : Promise<Result<ApplyResult, AppError>> {
+async applyCollection(gameId: string, collectionId: string, ignoreMissing: boolean | null, scope: ApplyScope | null) : Promise<Result<ApplyResult, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_collection", { gameId, collectionId, ignoreMissing }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_collection", { gameId, collectionId, ignoreMissing, scope }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -904,6 +1101,30 @@ async replaceCollectionWithCurrentState(gameId: string, collectionId: string) :
     else return { status: "error", error: e  as any };
 }
 },
+async captureCollectionUserVars(gameId: string, collectionId: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("capture_collection_user_vars", { gameId, collectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCollectionLayers(gameId: string, collectionId: string) : Promise<Result<CollectionLayers, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_collection_layers", { gameId, collectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setCollectionLayers(gameId: string, id: string, parentCollectionId: string | null, overlays: CollectionOverlay[]) : Promise<Result<CollectionSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_collection_layers", { gameId, id, parentCollectionId, overlays }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async deleteCollection(id: string) : Promise<Result<null, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("delete_collection", { id }) };
@@ -936,9 +1157,49 @@ async getCollectionPreview(collectionId: string, gameId: string) : Promise<Resul
     else return { status: "error", error: e  as any };
 }
 },
-async previewApplyCollection(gameId: string, collectionId: string) : Promise<Result<ApplyPreview, AppError>> {
+async previewApplyCollection(gameId: string, collectionId: string, scope: ApplyScope | null) : Promise<Result<ApplyPreview, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_apply_collection", { gameId, collectionId, scope }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffCollections(gameId: string, base: CollectionDiffSource, target: CollectionDiffSource) : Promise<Result<CollectionDiff, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_collections", { gameId, base, target }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async compareCollectionsThreeWay(gameId: string, base: CollectionDiffSource, ours: CollectionDiffSource, theirs: CollectionDiffSource) : Promise<Result<ThreeWayCollectionDiff, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_collections_three_way", { gameId, base, ours, theirs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportCollectionBundle(gameId: string, collectionId: string, path: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_collection_bundle", { gameId, collectionId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resolveCollectionBundle(gameId: string, path: string) : Promise<Result<BundleResolution, AppError>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("preview_apply_collection", { gameId, collectionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("resolve_collection_bundle", { gameId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importCollectionBundle(gameId: string, path: string, name: string | null) : Promise<Result<BundleImportResult, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_collection_bundle", { gameId, path, name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -1039,6 +1300,21 @@ async scoreCandidatesBatchCmd(folderPath: string, candidateNames: string[], game
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Explain how the matcher scores one folder: extracted tokens, each stage's
+ * contribution, the acceptance decisions, and mechanical rerank points.
+ * 
+ * Uses the same per-game MasterDB as scans, corrections included, so the
+ * explanation describes the match a scan would make.
+ */
+async explainMatchCmd(folderPath: string, gameId: string, gameType: number) : Promise<Result<MatchExplanation, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("explain_match_cmd", { folderPath, gameId, gameType }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async listFolderEntriesCmd(folderPath: string, gameId: string) : Promise<Result<FolderEntry[], AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("list_folder_entries_cmd", { folderPath, gameId }) };
@@ -1047,6 +1323,50 @@ async listFolderEntriesCmd(folderPath: string, gameId: string) : Promise<Result<
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Every correction the matcher has learned for a game.
+ */
+async listMatcherCorrectionsCmd(gameId: string) : Promise<Result<MatcherCorrection[], AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_matcher_corrections_cmd", { gameId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forgets one correction so the folder pattern is matched from scratch.
+ */
+async deleteMatcherCorrectionCmd(gameId: string, folderPattern: string) : Promise<Result<null, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_matcher_correction_cmd", { gameId, folderPattern }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Writes a game's corrections to `path`. Returns how many were written.
+ */
+async exportMatcherCorrectionsCmd(gameId: string, path: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_matcher_corrections_cmd", { gameId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Merges a shared corrections file into a game's. Returns how many were kept.
+ */
+async importMatcherCorrectionsCmd(gameId: string, path: string) : Promise<Result<number, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_matcher_corrections_cmd", { gameId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async reconcileDiskStateCmd(gameId: string, reason: DiskReconcileReason, changedPaths: string[] | null, forceFull: boolean | null) : Promise<Result<DiskReconcileResult, AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("reconcile_disk_state_cmd", { gameId, reason, changedPaths, forceFull }) };
@@ -1119,6 +1439,29 @@ async dupResolveBatch(requests: ResolutionRequest[], gameId: string) : Promise<R
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Identical payload files across every configured game and the bytes
+ * hardlinking them would free.
+ */
+async dupBlobReport() : Promise<Result<BlobDedupReport, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dup_blob_report") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Collapses every identical payload file, across games, into hardlinks.
+ */
+async dupHardlinkAll() : Promise<Result<HardlinkAllSummary, AppError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dup_hardlink_all") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getIgnoredPairs(gameId: string) : Promise<Result<WhitelistEntry[], AppError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_ignored_pairs", { gameId }) };
@@ -1396,11 +1739,61 @@ async browserCancelImport(jobId: string) : Promise<Result<null, AppError>> {
 
 /** user-defined types **/
 
+/**
+ * Why an acceptance check accepted, deferred, or stopped.
+ */
+export type AcceptanceOutcome = "no_candidates" | "threshold_not_met" | "no_primary_evidence" | "margin_insufficient" | "margin_conflict_review" | "ambiguity_forced_review" | "auto_matched" | 
+/**
+ * Final check: the best candidate is worth a review.
+ */
+"review" | 
+/**
+ * Final check: nothing scored high enough to show.
+ */
+"no_match" | 
+/**
+ * Final check: hashes unique to several entries, so the folder is a pack.
+ */
+"multi_object_pack"
+export type AcceptanceTrace = { outcome: AcceptanceOutcome; 
+/**
+ * The stage's bar; `None` for the final review check.
+ */
+threshold: number | null; margin: number | null; best: string | null; best_score: number; second_score: number; best_has_primary_evidence: boolean; pack_multi_entity: boolean }
 /**
  * A keybinding entry extracted from an enabled mod's INI file.
  */
 export type ActiveKeyBinding = { mod_name: string; section_name: string; key: string | null; back: string | null }
-export type AiConfig = { enabled: boolean; api_key: string | null; base_url: string | null }
+export type AiConfig = { enabled: boolean; api_key: string | null; base_url: string | null; provider?: AiProviderKind; 
+/**
+ * Model name sent with each request. `None` uses the provider's default.
+ */
+model?: string | null; 
+/**
+ * Prompt for local providers, with `{folder_tokens}`, `{deep_tokens}`,
+ * `{section_tokens}`, `{content_tokens}` and `{candidates}` placeholders.
+ */
+prompt_template?: string | null; timeout_secs?: number | null; 
+/**
+ * Most candidates sent per folder; the rest keep their mechanical scores.
+ */
+max_candidates?: number | null }
+/**
+ * The protocol the AI rerank endpoint speaks.
+ */
+export type AiProviderKind = 
+/**
+ * OpenAI-style chat completions.
+ */
+"openai" | 
+/**
+ * Ollama's `/api/generate`.
+ */
+"ollama" | 
+/**
+ * llama.cpp server's `/completion`.
+ */
+"llama_cpp"
 /**
  * Unified error type for Tauri command boundaries.
  * Each domain error converts into this for consistent frontend handling.
@@ -1425,6 +1818,19 @@ export type ApplyProgressSnapshot = { game_id: string; is_safe: boolean; phase:
  * Result of applying a collection.
  */
 export type ApplyResult = { mods_enabled: number; mods_disabled: number; warnings: string[]; final_state_name: string | null; final_mode: string | null; partial_apply: boolean; skipped_missing_paths: string[]; runtime_path_rewrites: WorkspacePathRewrite[] }
+/**
+ * Which part of the runtime an apply converges to the collection.
+ */
+export type ApplyScope = 
+/**
+ * The whole corridor: mods the collection does not have are disabled.
+ */
+{ type: "Full" } | 
+/**
+ * Only the listed objects and every object of the listed types, e.g.
+ * `Weapon`. Mods of other objects are left as they are.
+ */
+{ type: "Objects"; object_ids?: string[]; object_types?: string[] }
 /**
  * Result of analyzing an archive before extraction.
  */
@@ -1465,6 +1871,29 @@ is_encrypted: boolean;
  * Whether the archive contains other archives (e.g. .zip, .rar, .7z).
  */
 contains_nested_archives: boolean }
+export type BlobDedupReport = { indexedFiles: number; rehashedFiles: number; groups: BlobDuplicateGroup[]; reclaimableBytes: number }
+export type BlobDuplicateGroup = { contentHash: string; sizeBytes: number; files: BlobFile[]; 
+/**
+ * Bytes freed by collapsing every physical copy but one.
+ */
+reclaimableBytes: number }
+export type BlobFile = { gameId: string; filePath: string; 
+/**
+ * Files sharing a `copy_index` are already hardlinks of one another.
+ */
+copyIndex: number }
+/**
+ * One `key =`/`back =` line, or one app hotkey.
+ */
+export type BoundKey = { owner: KeybindOwner; object_name: string | null; section_name: string | null; 
+/**
+ * The value as written.
+ */
+raw: string; 
+/**
+ * Normalized form, e.g. `Ctrl+F5`.
+ */
+chord: string }
 /**
  * DTO for the frontend download list.
  */
@@ -1476,6 +1905,56 @@ export type BrowserDownloadDto = { id: string; session_id: string | null; filena
 export type BrowserError = "WindowUnavailable" | { WebviewNotFound: { label: string } } | { InvalidUrl: string } | { Download: string } | { JobIncomplete: { job_id: string; field: string } } | { Import: string } | "QueueClosed" | { Io: string } | { Db: string }
 export type BulkActionError = { path: string; error: AppError }
 export type BulkResult = { success: string[]; failures: BulkActionError[]; collection_impact: CollectionReferenceImpact; path_rewrites: WorkspacePathRewrite[] }
+/**
+ * A collection created from a bundle, and what of it could not be found.
+ */
+export type BundleImportResult = { collection: CollectionSummary; resolution: BundleResolution }
+/**
+ * How a bundle member was found in the local library.
+ */
+export type BundleMatch = "path" | "fingerprint" | 
+/**
+ * Same object and display name.
+ */
+"name"
+/**
+ * One mod of a collection bundle, described without machine-specific paths.
+ */
+export type BundleMember = { 
+/**
+ * MasterDB entry key of the mod's object, when it was matched.
+ */
+object_entry_key?: string | null; object_name?: string | null; display_name: string; 
+/**
+ * Path under the mods root, `/`-separated, without `DISABLED` prefixes.
+ */
+relative_path: string; 
+/**
+ * Hash over the resource hashes the mod's INIs override. Survives a
+ * rename or a move to another object folder.
+ */
+content_fingerprint?: string | null; source?: BundleSource | null }
+/**
+ * A bundle's members resolved against the local library.
+ */
+export type BundleResolution = { name: string; resolved: ResolvedBundleMember[]; 
+/**
+ * Members no local mod stands in for.
+ */
+missing: BundleMember[]; 
+/**
+ * Members whose content matches several local mods, left out rather
+ * than guessed.
+ */
+ambiguous: BundleMember[] }
+/**
+ * Where a bundled mod was published.
+ */
+export type BundleSource = { 
+/**
+ * GameBanana item type, e.g. `Mod`.
+ */
+item_type: string; item_id: number }
 export type CategoryCount = { object_type: string; count: number }
 export type CategoryDef = { name: string; 
 /**
@@ -1490,10 +1969,30 @@ filters?: FilterDef[] | null }
  * A single slice of the category distribution pie chart.
  */
 export type CategorySlice = { category: string; count: number }
+/**
+ * What switching from `base` to `target` would change, per object.
+ */
+export type CollectionDiff = { base_name: string; target_name: string; 
+/**
+ * Only objects that differ, ordered by name.
+ */
+objects: ObjectDiff[]; base_tree_nodes: PreviewTreeNode[]; target_tree_nodes: PreviewTreeNode[] }
+/**
+ * One side of a collection comparison.
+ */
+export type CollectionDiffSource = 
+/**
+ * The live runtime state, as `CorridorSnapshot.current_mods` shows it.
+ */
+{ type: "Current" } | { type: "Collection"; collection_id: string }
 /**
  * Errors specific to collection operations.
  */
 export type CollectionError = { NotFound: { id: string } } | { DuplicateName: { name: string } } | { MissingMods: { count: number; paths: string[] } } | { Validation: string } | { Db: string } | { Corridor: CorridorError } | { Io: string } | { FileInUse: { path: string; processes: string[] } } | { PathBusy: { path: string } }
+/**
+ * A collection's parent and overlays. No parent means a plain collection.
+ */
+export type CollectionLayers = { parent_collection_id: string | null; overlays: CollectionOverlay[] }
 /**
  * A single mod member of a collection (from `collection_mods`).
  */
@@ -1502,6 +2001,14 @@ export type CollectionMod = { kind: MemberKind; collection_id: string; mod_id: s
  * A single object member of a collection (from `collection_objects`).
  */
 export type CollectionObject = { kind: MemberKind; collection_id: string; object_id: string; is_enabled: boolean; display_name: string | null; path_key: string | null }
+/**
+ * One change a layered collection makes to its parent's members.
+ */
+export type CollectionOverlay = { target_kind: OverlayTargetKind; 
+/**
+ * Object id or mod id, per `target_kind`.
+ */
+target_key: string; is_enabled: boolean }
 export type CollectionPathRewrite = { from: string; to: string }
 /**
  * Preview data for a collection.
@@ -1511,7 +2018,7 @@ export type CollectionReferenceImpact = { affected_collection_count: number; aff
 /**
  * Summary returned in list views.
  */
-export type CollectionSummary = { id: string; name: string; is_safe: boolean; is_unsaved: boolean; is_active: boolean; signature: string | null; updated_at: string; mod_count: number }
+export type CollectionSummary = { id: string; name: string; is_safe: boolean; is_unsaved: boolean; is_active: boolean; signature: string | null; updated_at: string; mod_count: number; parent_collection_id: string | null }
 /**
  * Represents a folder naming collision discovered during sync or organize.
  */
@@ -1543,10 +2050,25 @@ export type ConfigStatus = "FreshInstall" | "HasConfig"
 /**
  * User-confirmed item sent back from the review modal.
  */
-export type ConfirmedScanItem = { folderPath: string; displayName: string; isDisabled: boolean; matchedEntryKey: string | null; matchedAliasName: string | null; matchedConfidence: number | null; matchedReason: string | null; objectType: string | null; thumbnailPath: string | null; tagsJson: string | null; metadataJson: string | null; hashDbJson: string | null; customSkinsJson: string | null; dbThumbnail: string | null; skip: boolean; moveFromTemp?: boolean }
+export type ConfirmedScanItem = { folderPath: string; displayName: string; isDisabled: boolean; matchedEntryKey: string | null; matchedAliasName: string | null; matchedConfidence: number | null; matchedReason: string | null; objectType: string | null; thumbnailPath: string | null; tagsJson: string | null; metadataJson: string | null; hashDbJson: string | null; customSkinsJson: string | null; dbThumbnail: string | null; skip: boolean; moveFromTemp?: boolean; 
+/**
+ * Subfolders of a multi-object pack, each filed under its own object on
+ * commit instead of this item. Every part must be a direct child of
+ * `folder_path`.
+ */
+packParts?: ConfirmedScanItem[] }
 export type ConflictCertainty = "definite" | "potential"
 export type ConflictDetails = { enabled: FolderDetail; disabled: FolderDetail }
-export type ConflictEvidence = { mod_path: string; source_path: string; section_name: string; namespace: string | null; condition: string | null; priority: number | null; match_first_index: number | null; shader_stage: string | null }
+export type ConflictEvidence = { 
+/**
+ * The owning folder: a mod, or the game's `ShaderFixes/` (see `owner`).
+ */
+mod_path: string; owner?: ConflictOwner; source_path: string; section_name: string; namespace: string | null; condition: string | null; priority: number | null; match_first_index: number | null; shader_stage: string | null; 
+/**
+ * BLAKE3 of a replacement shader's body, whitespace-normalized for HLSL,
+ * so two mods shipping the same fix are not reported against each other.
+ */
+content_hash?: string | null }
 /**
  * A group of folders sharing the same base name in the same parent directory.
  * Created when both "X" and "DISABLED X" exist on disk.
@@ -1567,7 +2089,16 @@ members: ConflictMember[] }
 /**
  * A potential collision plus the source facts needed to judge it.
  */
-export type ConflictInfo = { hash: string; section_name: string; mod_paths: string[]; is_active: boolean; kind: ConflictKind; certainty: ConflictCertainty; has_conditional_evidence: boolean; evidence: ConflictEvidence[] }
+export type ConflictInfo = { hash: string; section_name: string; 
+/**
+ * The mods involved; a loose `ShaderFixes/` file is only in `evidence`.
+ */
+mod_paths: string[]; is_active: boolean; kind: ConflictKind; certainty: ConflictCertainty; has_conditional_evidence: boolean; evidence: ConflictEvidence[]; 
+/**
+ * The stored winner decision for this hash, filled in by the services
+ * that report conflicts; detection itself never sets it.
+ */
+resolution?: ConflictResolution | null }
 export type ConflictKind = "resource_hash" | "shader_hash" | "shader_replacement"
 /**
  * A single member of a conflict group (for the Resolve dialog).
@@ -1593,6 +2124,36 @@ modified_at: number;
  * Total size in bytes
  */
 size_bytes: number }
+/**
+ * What a piece of conflict evidence belongs to.
+ */
+export type ConflictOwner = 
+/**
+ * A mod folder, which the user can disable or demote.
+ */
+"mod" | 
+/**
+ * A loose file in the game's own `ShaderFixes/`, which 3DMigoto always
+ * loads and no resolution touches.
+ */
+"game_shader_fixes"
+/**
+ * A stored winner decision for one conflicting hash. Mods are referenced by
+ * their stable ids, so the decision outlives rescans and toggles.
+ */
+export type ConflictResolution = { game_id: string; hash: string; kind: ConflictKind; strategy: ConflictResolutionStrategy; winner_mod_id: string; loser_mod_ids: string[]; created_at: string }
+/**
+ * How a hash conflict was settled in favour of one mod.
+ */
+export type ConflictResolutionStrategy = 
+/**
+ * The losing mods were disabled.
+ */
+"disable_losers" | 
+/**
+ * The losing override sections got a lower `match_priority`.
+ */
+"match_priority"
 /**
  * How to break a duplicate pair apart.
  * 
@@ -1623,7 +2184,11 @@ export type CorridorSnapshot = { game_id: string; is_safe: boolean; active_colle
 /**
  * Input for creating a new collection.
  */
-export type CreateCollectionMode = "save_current_state" | "clone_snapshot"
+export type CreateCollectionMode = "save_current_state" | "clone_snapshot" | 
+/**
+ * The mods named by `mod_ids`, whatever their current state.
+ */
+"selected_mods"
 export type CreateObjectInput = { game_id: string; name: string; folder_path: string | null; object_type: string; sub_category: string | null; status: number | null; metadata: JsonValue | null; thumbnail_url: string | null; hash_db: HashDbPayload | null; custom_skins: CustomSkinsPayload | null }
 /**
  * A named skin/outfit with aliases.
@@ -1707,6 +2272,15 @@ export type DupScanSignal = { key: string; detail: string; score: number }
  * Info about an enabled duplicate/conflicting mod for a given object.
  */
 export type DuplicateModInfo = { mod_id: string; object_id: string; folder_path: string; actual_name: string; is_variant: boolean; parent_path: string }
+/**
+ * Structural edits to one INI of a mod, checked against the hash it was read
+ * with.
+ */
+export type EditModIniInput = { game_id: string; folder_path: string; file_name: string; expected_source_hash: string; edits: IniEdit[] }
+/**
+ * How one entry differs between two databases.
+ */
+export type EntryChange = "added" | "removed" | "aliases_changed" | "skins_changed" | "hashes_changed" | "tags_changed"
 /**
  * Progress events streamed to frontend during archive extraction via `Channel<ExtractionEvent>`.
  */
@@ -1773,10 +2347,24 @@ ini_key_whitelist?: string[] }
  */
 export type GameSlice = { game_id: string; game_name: string; count: number }
 export type GetObjectsResult = { objects: ObjectSummary[]; lost_objects: string[] }
+export type HardlinkAllSummary = { total: number; linked: number; failed: number; reclaimedBytes: number; errors: HardlinkError[] }
+export type HardlinkError = { filePath: string; message: string }
 /**
  * Strongly-typed payload for an object's known hashed files
  */
 export type HashDbPayload = Partial<{ [key in string]: string[] }>
+/**
+ * One mod INI section carrying a resource hash, read from `mod_hash_index`.
+ */
+export type HashIndexHit = { mod_id: string; actual_name: string; folder_path: string; enabled: boolean; section_name: string; 
+/**
+ * INI path relative to the mod folder, `/`-separated.
+ */
+file_path: string; 
+/**
+ * Other mods in the game whose INIs carry the same hash.
+ */
+collision_count: number }
 /**
  * Hotkey configuration — persisted in AppSettings.
  */
@@ -1798,10 +2386,59 @@ export type IgnoredConflict = { id: string; game_id: string; object_id: string;
  * DTO returned to the frontend for import queue display.
  */
 export type ImportJobDto = { id: string; download_id: string | null; game_id: string | null; archive_path: string; status: string; match_category: string | null; match_entry_key: string | null; match_alias_name: string | null; match_confidence: number | null; match_reason: string | null; placed_path: string | null; error_msg: string | null; is_duplicate: boolean; created_at: string; updated_at: string }
+export type IniDiagnostic = { 
+/**
+ * Path relative to the mod root, `/`-separated like `IniFileEntry`.
+ */
+file_name: string; 
+/**
+ * `None` for findings about the whole file.
+ */
+line_idx: number; severity: IniLintSeverity; code: IniLintCode; message: string }
 export type IniDocument = { file_path: string; raw_lines: string[]; variables: IniVariable[]; key_bindings: KeyBinding[]; had_bom: boolean; encoding: IniEncoding; newline_style: NewlineStyle; line_terminators: LineTerminator[]; source_hash: string; mode: IniReadMode }
+/**
+ * One structural change. Section and key names match case-insensitively,
+ * like 3DMigoto itself; when a section name is duplicated in the file, the
+ * first occurrence is the one edited.
+ */
+export type IniEdit = 
+/**
+ * Appends `[name]` followed by `lines` at the end of the file.
+ */
+{ type: "InsertSection"; name: string; lines: string[] } | 
+/**
+ * Removes the header and every line up to the next section header.
+ */
+{ type: "RemoveSection"; name: string } | 
+/**
+ * Rewrites the header only; `run =` references are left to the caller.
+ */
+{ type: "RenameSection"; from: string; to: string } | 
+/**
+ * Replaces the value of `key` in `section`, or appends `key = value`
+ * when the section does not have it yet.
+ */
+{ type: "SetKey"; section: string; key: string; value: string } | 
+/**
+ * Removes every `key = …` line in `section`.
+ */
+{ type: "DeleteKey"; section: string; key: string } | 
+/**
+ * Appends a raw line to the end of `section`, or to the end of the file
+ * when no section is given.
+ */
+{ type: "AppendLine"; section: string | null; line: string } | 
+/**
+ * Rewrites the line at `line_idx` in place. The index counts lines as
+ * the buffer holds them, so it refers to the document as read only
+ * while no earlier edit inserted or removed lines.
+ */
+{ type: "ReplaceLine"; line_idx: number; line: string }
 export type IniEncoding = "Utf8" | "ShiftJis" | "LossyUtf8"
 export type IniFileEntry = { filename: string; path: string }
 export type IniLineUpdate = { line_idx: number; content: string }
+export type IniLintCode = "MalformedHeader" | "UnbalancedConditional" | "UndefinedCommandList" | "UndefinedResource" | "MissingFile" | "DuplicateSection" | "DuplicateKeybind" | "LossyDecoding" | "Unreadable"
+export type IniLintSeverity = "Error" | "Warning" | "Info"
 export type IniReadMode = "Structured" | "RawFallback"
 export type IniVariable = { qualifier: string | null; name: string; value: string; line_idx: number }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
@@ -1813,13 +2450,71 @@ export type KeyViewerConfig = {
 /**
  * Whether KeyViewer generation is enabled.
  */
-enabled: boolean }
+enabled: boolean; 
+/**
+ * Keys that keybind remapping may move a colliding binding to, tried in
+ * order. 3DMigoto spelling (`VK_NUMPAD1`).
+ */
+remap_pool?: string[] }
+export type KeybindCollision = { 
+/**
+ * Key name shared by every binding below, without modifiers.
+ */
+key: string; 
+/**
+ * Sorted by object, then owner, so one object's bindings sit together.
+ */
+bindings: BoundKey[] }
+export type KeybindField = "Key" | "Back"
+export type KeybindOwner = { type: "Mod"; mod_name: string; folder_path: string } | 
+/**
+ * One of the app's own global hotkeys, named by its `HotkeyConfig` field.
+ */
+{ type: "Hotkey"; action: string }
+export type KeybindRemapReport = { changed: RemappedKeybind[]; 
+/**
+ * Bindings left alone: no free key in the pool, or (on revert) a line
+ * edited since the remap.
+ */
+skipped: string[] }
+/**
+ * What a learning pass kept.
+ */
+export type LearnedHashSummary = { 
+/**
+ * Objects that learned at least one hash.
+ */
+objects: number; hashes: number }
 export type LineTerminator = "None" | "Lf" | "CrLf" | "Cr"
 export type MatchCheckResult = { matchedName: string | null; matchScorePct: number; targetScorePct: number; isMatch: boolean; confidence: string }
+/**
+ * A full-scoring match of one folder, with the trace of how it was reached.
+ */
+export type MatchExplanation = { matched_name: string | null; match_level: string; match_confidence: string; match_detail: string; trace: MatchTrace }
+/**
+ * Everything the tracer saw, in pipeline order.
+ */
+export type MatchTrace = { signals: SignalTrace; stages: StageTrace[]; 
+/**
+ * Empty when the mechanical reranker did not run.
+ */
+mechanical_rerank: RerankPointsTrace[] }
 /**
  * Matched DB entry returned to frontend with resolved absolute thumbnail path.
  */
 export type MatchedDbEntry = { name: string; matched_entry_key: string | null; matched_alias_name: string | null; object_type: string; tags: string[]; metadata: JsonValue | null; thumbnail_path: string | null; match_level: string; match_confidence: string; match_detail: string }
+/**
+ * One learned correction, as listed and exported.
+ */
+export type MatcherCorrection = { 
+/**
+ * Normalized folder name the correction keys on. Recomputed on import.
+ */
+folder_pattern?: string; folder_name: string; entry_key: string; 
+/**
+ * How often the user made this same correction.
+ */
+hits?: number }
 /**
  * The kind of member in a collection.
  */
@@ -1842,9 +2537,17 @@ export type ModInfo = { actual_name?: string; author?: string; description?: str
 /**
  * Partial update struct — only fields that are `Some` will be updated.
  */
-export type ModInfoUpdate = { actual_name: string | null; author: string | null; description: string | null; version: string | null; tags: string[] | null; tags_add: string[] | null; tags_remove: string[] | null; is_safe: boolean | null; is_favorite: boolean | null; is_pinned: boolean | null; is_auto_sync: boolean | null; preset_name_add: string[] | null; preset_name_remove: string[] | null; metadata: Partial<{ [key in string]: string }> | null }
+export type ModInfoUpdate = { actual_name: string | null; author: string | null; description: string | null; version: string | null; tags: string[] | null; tags_add: string[] | null; tags_remove: string[] | null; is_safe: boolean | null; is_favorite: boolean | null; is_pinned: boolean | null; is_auto_sync: boolean | null; preset_name_add: string[] | null; preset_name_remove: string[] | null; metadata: Partial<{ [key in string]: string }> | null; metadata_remove: string[] | null }
 export type MoveModsToObjectInput = { game_id: string; folder_paths: string[]; target_object_id: string; target_subpath: string | null; status: string | null }
 export type NewlineStyle = "Lf" | "CrLf"
+/**
+ * How one object differs between two projected states.
+ */
+export type ObjectDiff = { object_id: string; display_name: string; 
+/**
+ * `None` when the object is not part of that side.
+ */
+base_enabled: boolean | null; target_enabled: boolean | null; added: ProjectedActiveRoot[]; removed: ProjectedActiveRoot[]; changed: RootVariantChange[] }
 /**
  * `Default` is the unfiltered, safe-mode-off query. Callers spell out only
  * the axes they actually constrain — the full seven-field literal was written
@@ -1852,6 +2555,7 @@ export type NewlineStyle = "Lf" | "CrLf"
  */
 export type ObjectFilter = { game_id: string; search_query: string | null; object_type: string | null; meta_filters: Partial<{ [key in string]: string[] }> | null; sort_by: string | null; status_filter: number | null }
 export type ObjectSummary = { id: string; name: string; folder_path: string; matched_entry_key: string | null; matched_alias_name: string | null; matched_confidence: number | null; matched_reason: string | null; matched_source: string | null; object_type: string; sub_category: string | null; status: number; metadata: string; tags: string; hash_db: HashDbPayload | null; custom_skins: CustomSkinsPayload | null; is_pinned: boolean; is_auto_sync: boolean; thumbnail_path: string | null; created_at: string | null; mod_count: number; enabled_count: number; is_object_disabled: boolean; has_naming_conflict: boolean; active_mod_paths: string | null }
+export type OverlayTargetKind = "object" | "mod"
 /**
  * Errors specific to Pin operations.
  */
@@ -1860,7 +2564,11 @@ export type PinError = { Db: string }
  * Frontend-facing PIN status (no hashes leaked).
  */
 export type PinStatus = { has_pin: boolean; is_locked: boolean; attempts_remaining: number; lockout_seconds_remaining: number }
-export type PipelineTask = { id: string; game_id: string; task_type: string; status: TaskStatus; target_id: string | null; created_at: string; updated_at: string }
+export type PipelineTask = { id: string; game_id: string; task_type: string; status: TaskStatus; target_id: string | null; 
+/**
+ * JSON the task is resumed with, e.g. an apply's `ApplyScope`.
+ */
+payload: string | null; created_at: string; updated_at: string }
 export type PreviewTreeNode = { kind: PreviewTreeNodeKind; id: string; name: string; path: string | null; object_id: string | null; node_type: string | null; is_enabled: boolean; is_effectively_active: boolean; inactive_reason: string | null; show_inactive_chip: boolean; status_kind: string | null; collapse_children: boolean; warnings: string[]; mod_count: number | null; children: PreviewTreeNode[] }
 export type PreviewTreeNodeKind = "object" | "folder" | "mod"
 export type ProjectedActiveRoot = { object_id: string; root_key: string; display_name: string; root_type: string; source_path: string; thumbnail_hint: string | null; warnings: string[]; is_missing: boolean }
@@ -1873,11 +2581,72 @@ export type RandomModProposal = { object_id: string; object_name: string; mod_id
  */
 export type RecentMod = { id: string; name: string; game_name: string; object_name: string | null; indexed_at: string | null }
 export type RecoveryAction = "RETRY" | "ROLLBACK" | "IGNORE"
+export type RemappedKeybind = { 
+/**
+ * INI path relative to the mod root, `/`-separated.
+ */
+file_name: string; section_name: string; field: KeybindField; line_idx: number; original: string; remapped: string }
+/**
+ * Why an object was re-matched.
+ */
+export type RematchCause = 
+/**
+ * Its entry gained or lost aliases, skins, hashes, or tags.
+ */
+"entry_changed" | 
+/**
+ * Its entry is gone from the database.
+ */
+"entry_removed" | "unmatched" | "low_confidence"
+/**
+ * One proposed match update, shaped for `apply_object_match_cmd`.
+ */
+export type RematchChange = { object_id: string; object_name: string; cause: RematchCause; 
+/**
+ * What changed in the previous entry; empty unless `cause` is
+ * `EntryChanged`.
+ */
+entry_changes: EntryChange[]; previous_entry_key: string | null; previous_confidence: number | null; 
+/**
+ * `None` clears the match: the previous entry was removed and nothing
+ * else matched.
+ */
+matched_entry_key: string | null; matched_alias_name: string | null; matched_confidence: number | null; matched_reason: string | null; matched_source: string }
+/**
+ * The reviewable outcome of a re-match run.
+ */
+export type RematchPlan = { 
+/**
+ * False on the first run for a game: there was no earlier database to
+ * diff against, so only unmatched and low-confidence objects were tried.
+ */
+has_baseline: boolean; 
+/**
+ * Entries added, removed, or changed since the last review.
+ */
+changed_entries: number; 
+/**
+ * Objects the matcher was re-run on.
+ */
+examined: number; changes: RematchChange[] }
 export type RenameResult = { old_path: string; new_path: string; new_name: string; collection_impact: CollectionReferenceImpact }
+/**
+ * Mechanical reranker points for one candidate.
+ */
+export type RerankPointsTrace = { entry_id: number; name: string; points: number; 
+/**
+ * Sum of the penalties, zero or negative.
+ */
+penalties: number }
 export type ResolutionAction = "keepA" | "keepB" | "ignore" | "hardlink"
 export type ResolutionError = { groupId: string; action: ResolutionAction; message: string }
 export type ResolutionRequest = { groupId: string; action: ResolutionAction; folderA: string; folderB: string }
 export type ResolutionSummary = { total: number; successful: number; failed: number; errors: ResolutionError[] }
+export type ResolvedBundleMember = { member: BundleMember; mod_id: string; matched_by: BundleMatch }
+/**
+ * An object whose active root changed from one mod to another.
+ */
+export type RootVariantChange = { from: ProjectedActiveRoot; to: ProjectedActiveRoot }
 export type SafeModeConfig = { enabled: boolean; pin_hash: string | null; recovery_code_hash: string | null; keywords: string[]; force_exclusive_mode: boolean; failed_attempts: number | null; lockout_until_ts: number | null }
 /**
  * Progress events streamed to frontend via `Channel<ScanEvent>`.
@@ -1930,7 +2699,12 @@ hashDbJson: string | null;
 /**
  * Optional custom_skins from MasterDB entry (JSON array string)
  */
-customSkinsJson: string | null; dbThumbnail: string | null }
+customSkinsJson: string | null; dbThumbnail: string | null; 
+/**
+ * For a multi-object pack, one prefilled item per subfolder that matched
+ * an object; confirming them splits the pack on commit.
+ */
+packParts?: ConfirmedScanItem[] }
 /**
  * Errors from scanning the mods tree: walking folders, matching against the
  * MasterDB, duplicate detection, and committing a scan into the index.
@@ -1941,11 +2715,55 @@ export type ScannerError = { PathNotFound: { path: string } } | { NotADirectory:
  */
 export type ScoredCandidate = { name: string; objectType: string; scorePct: number }
 export type SearchResultEntry = { item: DbEntry; score: number }
+/**
+ * The tokens `signal_collector` extracted from the folder.
+ */
+export type SignalTrace = { folder_name_normalized: string; folder_tokens: string[]; deep_name_tokens: string[]; ini_section_tokens: string[]; ini_content_tokens: string[]; ini_hashes: string[]; override_hashes: string[]; scanned_ini_files: number; scanned_name_items: number }
+export type StageContribution = { entry_id: number; name: string; delta: number; score: number }
+export type StageTrace = { stage: string; 
+/**
+ * Entries whose score this stage changed, highest resulting score first.
+ */
+contributions: StageContribution[]; 
+/**
+ * The acceptance check run after the stage; `None` when the stage ended
+ * without one.
+ */
+decision: AcceptanceTrace | null }
 export type SyncResult = { totalScanned: number; newMods: number; updatedMods: number; deletedMods: number; newObjects: number; skipped: number; collisions: CollisionInfo[] }
 export type TAURI_CHANNEL<TSend> = null
 export type TaskStatus = "PENDING" | "COMPLETED" | "FAILED"
 export type ThemeConfig = { colors: Partial<{ [key in string]: string }>; glass: Partial<{ [key in string]: string }> }
 export type ThemeMetadata = { id: string; label: string }
+/**
+ * Two loadouts compared against the one they both started from.
+ */
+export type ThreeWayCollectionDiff = { base_name: string; ours_name: string; theirs_name: string; 
+/**
+ * Only objects either side changed, ordered by name.
+ */
+objects: ThreeWayObjectDiff[] }
+export type ThreeWayObjectDiff = { object_id: string; display_name: string; status: ThreeWayStatus; 
+/**
+ * Base → ours, when ours changed the object.
+ */
+ours: ObjectDiff | null; 
+/**
+ * Base → theirs, when theirs changed the object.
+ */
+theirs: ObjectDiff | null }
+/**
+ * Which side of a three-way compare changed an object relative to the base.
+ */
+export type ThreeWayStatus = "ours" | "theirs" | 
+/**
+ * Both sides made the same change.
+ */
+"both" | 
+/**
+ * Both sides changed the object, differently.
+ */
+"conflict"
 /**
  * Metadata stored alongside each trashed item for restore.
  */
@@ -2,6 +2,7 @@ export type {
   ApplyPreview,
   ApplyProgressSnapshot,
   ApplyResult,
+  ApplyScope,
   CollectionMod,
   CollectionObject,
   CollectionPathRewrite,